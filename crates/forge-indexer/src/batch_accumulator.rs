@@ -0,0 +1,248 @@
+//! Merges embedding requests from concurrent `process_file` calls into larger batches, so small
+//! files don't each pay for a tiny, inefficient request to a remote provider billed per call.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::{Mutex, oneshot};
+use tracing::debug;
+
+use crate::chunker::token_budget::estimate_tokens;
+use crate::embedder::Embedder;
+
+/// How often the background flush task checks whether the linger deadline has elapsed. Small
+/// relative to realistic linger timeouts (tens to hundreds of ms) so a lingering batch flushes
+/// promptly without a dedicated per-item sleep.
+const LINGER_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+struct QueuedText {
+    text: String,
+    responder: oneshot::Sender<Result<(Vec<f32>, String)>>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    items: Vec<QueuedText>,
+    queued_tokens: usize,
+    /// Set when the first item lands in an empty queue; cleared on flush. The background task
+    /// flushes once `Instant::now()` passes this, even if the token budget is never reached.
+    deadline: Option<Instant>,
+}
+
+/// Wraps an inner `Embedder`, accumulating the texts passed to `embed_batch`/`embed_batch_tagged`
+/// across *every* concurrent caller into one shared queue, and flushing it to the inner embedder
+/// as one request once either the summed estimated token count reaches `token_budget` or
+/// `linger` has elapsed since the first queued item -- whichever comes first. Each caller's
+/// `embed_batch_tagged` call returns only once every text it submitted has been embedded, however
+/// many other callers' texts ended up sharing the flush.
+pub struct BatchAccumulator {
+    inner: Arc<dyn Embedder>,
+    token_budget: usize,
+    linger: Duration,
+    state: Mutex<QueueState>,
+}
+
+impl BatchAccumulator {
+    /// Wrap `inner`, flushing the shared queue once it holds `token_budget` estimated tokens or
+    /// `linger` has elapsed since the oldest queued item, whichever comes first. Spawns a
+    /// background task that polls for the linger deadline; the task exits once every `Arc` to the
+    /// returned accumulator is dropped.
+    pub fn new(inner: Arc<dyn Embedder>, token_budget: usize, linger: Duration) -> Arc<Self> {
+        let this = Arc::new(Self {
+            inner,
+            token_budget,
+            linger,
+            state: Mutex::new(QueueState::default()),
+        });
+
+        let weak = Arc::downgrade(&this);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(LINGER_POLL_INTERVAL).await;
+                let Some(this) = weak.upgrade() else { break };
+
+                let batch = {
+                    let mut state = this.state.lock().await;
+                    let lingered = state.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+                    if lingered { Some(Self::take_batch(&mut state)) } else { None }
+                };
+
+                if let Some(batch) = batch {
+                    debug!(
+                        "BatchAccumulator: flushing {} text(s) after linger timeout",
+                        batch.len()
+                    );
+                    this.flush(batch).await;
+                }
+            }
+        });
+
+        this
+    }
+
+    fn take_batch(state: &mut QueueState) -> Vec<QueuedText> {
+        state.queued_tokens = 0;
+        state.deadline = None;
+        std::mem::take(&mut state.items)
+    }
+
+    async fn flush(&self, batch: Vec<QueuedText>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let texts: Vec<String> = batch.iter().map(|item| item.text.clone()).collect();
+        match self.inner.embed_batch_tagged(&texts).await {
+            Ok(tagged) if tagged.len() == batch.len() => {
+                for (item, result) in batch.into_iter().zip(tagged) {
+                    let _ = item.responder.send(Ok(result));
+                }
+            }
+            Ok(tagged) => {
+                let err = anyhow::anyhow!(
+                    "embedder returned {} vector(s) for {} queued text(s)",
+                    tagged.len(),
+                    batch.len()
+                );
+                for item in batch {
+                    let _ = item.responder.send(Err(anyhow::anyhow!("{}", err)));
+                }
+            }
+            Err(e) => {
+                for item in batch {
+                    let _ = item.responder.send(Err(anyhow::anyhow!("{}", e)));
+                }
+            }
+        }
+    }
+
+    /// Enqueue `texts`, returning once every one of them has been embedded -- whether that
+    /// happened as part of this call's own flush or one triggered by another concurrent caller's
+    /// submission.
+    async fn submit(&self, texts: &[String]) -> Result<Vec<(Vec<f32>, String)>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut receivers = Vec::with_capacity(texts.len());
+        let ready_batch = {
+            let mut state = self.state.lock().await;
+            for text in texts {
+                let (tx, rx) = oneshot::channel();
+                if state.items.is_empty() {
+                    state.deadline = Some(Instant::now() + self.linger);
+                }
+                state.queued_tokens += estimate_tokens(text);
+                state.items.push(QueuedText { text: text.clone(), responder: tx });
+                receivers.push(rx);
+            }
+
+            if state.queued_tokens >= self.token_budget {
+                debug!(
+                    "BatchAccumulator: flushing {} text(s) at token budget ({} >= {})",
+                    state.items.len(),
+                    state.queued_tokens,
+                    self.token_budget
+                );
+                Some(Self::take_batch(&mut state))
+            } else {
+                None
+            }
+        };
+
+        if let Some(batch) = ready_batch {
+            self.flush(batch).await;
+        }
+
+        let mut results = Vec::with_capacity(receivers.len());
+        for rx in receivers {
+            results.push(rx.await.map_err(|_| {
+                anyhow::anyhow!("batch accumulator dropped before embedding this text")
+            })??);
+        }
+        Ok(results)
+    }
+}
+
+#[async_trait]
+impl Embedder for BatchAccumulator {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.inner.embedding_dimension()
+    }
+
+    fn max_input_tokens(&self) -> Option<usize> {
+        self.inner.max_input_tokens()
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(self.submit(texts).await?.into_iter().map(|(vector, _)| vector).collect())
+    }
+
+    async fn embed_batch_tagged(&self, texts: &[String]) -> Result<Vec<(Vec<f32>, String)>> {
+        self.submit(texts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct CountingEmbedder {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Embedder for CountingEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn merges_concurrent_callers_into_one_request_at_token_budget() {
+        let inner = Arc::new(CountingEmbedder { calls: AtomicUsize::new(0) });
+        // `estimate_tokens` puts each text at 2 tokens, so a budget of 4 needs both of them.
+        let accumulator = BatchAccumulator::new(inner.clone(), 4, Duration::from_secs(60));
+
+        let (a, b) = tokio::join!(
+            accumulator.embed_batch(&["fn a() {}".to_string()]),
+            accumulator.embed_batch(&["fn b() {}".to_string()])
+        );
+
+        assert_eq!(a.unwrap(), vec![vec!["fn a() {}".len() as f32]]);
+        assert_eq!(b.unwrap(), vec![vec!["fn b() {}".len() as f32]]);
+        // Neither call alone reaches the token budget; together they tip it over and share a
+        // single flush.
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn flushes_on_linger_timeout_when_under_budget() {
+        let inner = Arc::new(CountingEmbedder { calls: AtomicUsize::new(0) });
+        let accumulator = BatchAccumulator::new(inner.clone(), 1_000_000, Duration::from_millis(30));
+
+        let result = accumulator.embed_batch(&["fn a() {}".to_string()]).await.unwrap();
+
+        assert_eq!(result, vec![vec!["fn a() {}".len() as f32]]);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -26,24 +26,38 @@ async fn main() -> Result<()> {
     match args.command {
         Commands::Index {
             path,
+            config,
             embedder,
             openai_api_key,
             local_model_path,
             local_tokenizer_path,
+            ollama_url,
+            ollama_model,
             batch_size,
             max_concurrent_files,
+            non_recursive,
+            all_files,
+            max_crawl_memory,
         } => {
             run_indexer(indexer::IndexArgs {
                 path,
+                config,
                 embedder,
                 openai_api_key,
                 local_model_path,
                 local_tokenizer_path,
+                ollama_url,
+                ollama_model,
                 batch_size,
                 max_concurrent_files,
+                non_recursive,
+                all_files,
+                max_crawl_memory,
             })
             .await
         }
-        Commands::Reset { embedder } => run_reset(embedder).await,
+        Commands::Reset { embedder, ollama_url, ollama_model } => {
+            run_reset(embedder, ollama_url, ollama_model).await
+        }
     }
 }
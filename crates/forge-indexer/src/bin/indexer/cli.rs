@@ -15,9 +15,17 @@ pub enum Commands {
         #[arg(default_value = ".")]
         path: String,
 
-        /// Embedder type to use (openai, local, hybrid)
-        #[arg(long, default_value_t = if std::env::var("OPENAI_API_KEY").is_ok() { "openai".to_string() } else { "local".to_string() })]
-        embedder: String,
+        /// Layered TOML config file. May set `include = [...]` to merge other files in first
+        /// (resolved relative to this file's directory) and `unset = [...]` to clear a key
+        /// inherited from one of them. Precedence is CLI args > env vars > this file > includes.
+        #[arg(long)]
+        config: Option<String>,
+
+        /// Embedder type to use (openai, local, hybrid, ollama). Defaults to `openai` if
+        /// `OPENAI_API_KEY` is set and neither this flag nor a config file says otherwise, else
+        /// `local`.
+        #[arg(long)]
+        embedder: Option<String>,
 
         /// OpenAI API key (required if using OpenAI embedder)
         #[arg(long)]
@@ -31,31 +39,70 @@ pub enum Commands {
         #[arg(long)]
         local_tokenizer_path: Option<String>,
 
+        /// Base URL of the Ollama server used by the Ollama embedder (defaults to
+        /// `http://localhost:11434`)
+        #[arg(long)]
+        ollama_url: Option<String>,
+
+        /// Ollama embedding model name used by the Ollama embedder (defaults to
+        /// `nomic-embed-text`)
+        #[arg(long)]
+        ollama_model: Option<String>,
+
         /// Batch size for processing
-        #[arg(long, default_value_t = 10)]
-        batch_size: usize,
+        #[arg(long)]
+        batch_size: Option<usize>,
 
         /// Maximum concurrent files to process
-        #[arg(long, default_value_t = 5)]
-        max_concurrent_files: usize,
+        #[arg(long)]
+        max_concurrent_files: Option<usize>,
+
+        /// Watch only the top level of `path` instead of descending into subdirectories. Passing
+        /// this flag always forces non-recursive; omitting it falls through to the config file.
+        #[arg(long, default_value_t = false)]
+        non_recursive: bool,
+
+        /// Force indexing of every file the crawl finds, bypassing the extension allowlist.
+        /// Passing this flag always forces it on; omitting it falls through to the config file.
+        #[arg(long, default_value_t = false)]
+        all_files: bool,
+
+        /// Memory budget, in MB, for the initial directory crawl before it flushes its
+        /// accumulated batch through processing
+        #[arg(long)]
+        max_crawl_memory: Option<usize>,
     },
     /// Reset the Qdrant collection (deletes all indexed data)
     Reset {
-        /// Embedder type to determine vector dimensions (openai, local, hybrid)
+        /// Embedder type to determine vector dimensions (openai, local, ollama)
         #[arg(long, default_value_t = if std::env::var("OPENAI_API_KEY").is_ok() { "openai".to_string() } else { "local".to_string() })]
         embedder: String,
+
+        /// Base URL of the Ollama server used by the Ollama embedder
+        #[arg(long, default_value = "http://localhost:11434")]
+        ollama_url: String,
+
+        /// Ollama embedding model name used by the Ollama embedder
+        #[arg(long, default_value = "nomic-embed-text")]
+        ollama_model: String,
     },
 }
 
 #[derive(Debug)]
 pub struct IndexArgs {
     pub path: String,
-    pub embedder: String,
+    pub config: Option<String>,
+    pub embedder: Option<String>,
     pub openai_api_key: Option<String>,
     pub local_model_path: Option<String>,
     pub local_tokenizer_path: Option<String>,
-    pub batch_size: usize,
-    pub max_concurrent_files: usize,
+    pub ollama_url: Option<String>,
+    pub ollama_model: Option<String>,
+    pub batch_size: Option<usize>,
+    pub max_concurrent_files: Option<usize>,
+    pub non_recursive: bool,
+    pub all_files: bool,
+    pub max_crawl_memory: Option<usize>,
 }
 
 impl From<&Commands> for Option<IndexArgs> {
@@ -63,20 +110,32 @@ impl From<&Commands> for Option<IndexArgs> {
         match commands {
             Commands::Index {
                 path,
+                config,
                 embedder,
                 openai_api_key,
                 local_model_path,
                 local_tokenizer_path,
+                ollama_url,
+                ollama_model,
                 batch_size,
                 max_concurrent_files,
+                non_recursive,
+                all_files,
+                max_crawl_memory,
             } => Some(IndexArgs {
                 path: path.clone(),
+                config: config.clone(),
                 embedder: embedder.clone(),
                 openai_api_key: openai_api_key.clone(),
                 local_model_path: local_model_path.clone(),
                 local_tokenizer_path: local_tokenizer_path.clone(),
+                ollama_url: ollama_url.clone(),
+                ollama_model: ollama_model.clone(),
                 batch_size: *batch_size,
                 max_concurrent_files: *max_concurrent_files,
+                non_recursive: *non_recursive,
+                all_files: *all_files,
+                max_crawl_memory: *max_crawl_memory,
             }),
             Commands::Reset { .. } => None,
         }
@@ -1,62 +1,83 @@
 use std::path::Path;
 
 use anyhow::Result;
-use forge_indexer::{EmbedderType, PipelineConfig};
+use forge_indexer::{EmbedderType, PipelineConfig, WatchMode};
 
 use super::cli::IndexArgs;
+use super::config_file::{self, ConfigFile};
 
-/// Load configuration from command line arguments
+/// Load configuration from command line arguments, layered over an optional `--config` TOML file
+/// (see `config_file`) and environment variables. Precedence is CLI args > env vars > the config
+/// file > its includes.
 pub fn load_config_from_args(args: &IndexArgs) -> Result<PipelineConfig> {
+    let file = match &args.config {
+        Some(path) => config_file::load_config_file(Path::new(path))?,
+        None => ConfigFile::default(),
+    };
+
     let mut config = PipelineConfig::default();
-    config.batch_size = args.batch_size;
-    config.max_concurrent_files = args.max_concurrent_files;
+    config.batch_size = args.batch_size.or(file.batch_size).unwrap_or(config.batch_size);
+    config.max_concurrent_files =
+        args.max_concurrent_files.or(file.max_concurrent_files).unwrap_or(config.max_concurrent_files);
+    // `non_recursive`/`all_files` are plain flags rather than `Option<bool>`, so passing them on
+    // the command line always forces `true`; leaving them off falls through to the config file.
+    let non_recursive = args.non_recursive || file.non_recursive.unwrap_or(false);
+    config.watch_mode = if non_recursive { WatchMode::NonRecursive } else { WatchMode::Recursive };
+    config.all_files = args.all_files || file.all_files.unwrap_or(false);
+    config.max_crawl_memory_mb =
+        args.max_crawl_memory.or(file.max_crawl_memory).unwrap_or(config.max_crawl_memory_mb);
 
     // Configure embedder type
-    config.embedder_type = match args.embedder.as_str() {
+    let embedder = args.embedder.clone().or(file.embedder.clone()).unwrap_or_else(|| {
+        if std::env::var("OPENAI_API_KEY").is_ok() { "openai".to_string() } else { "local".to_string() }
+    });
+    let ollama_url = args
+        .ollama_url
+        .clone()
+        .or(file.ollama_url.clone())
+        .unwrap_or_else(|| "http://localhost:11434".to_string());
+    let ollama_model =
+        args.ollama_model.clone().or(file.ollama_model.clone()).unwrap_or_else(|| "nomic-embed-text".to_string());
+
+    config.embedder_type = match embedder.as_str() {
         "openai" => EmbedderType::OpenAI,
         "local" => EmbedderType::Local,
         "hybrid" => EmbedderType::Hybrid,
+        "ollama" => EmbedderType::Ollama { model: ollama_model, dimension: None, url: ollama_url },
         _ => {
-            return Err(anyhow::anyhow!(
-                "Invalid embedder type: {}. Must be openai, local, or hybrid",
-                args.embedder
-            ));
+            return Err(anyhow::anyhow!("Invalid embedder type: {}. Must be openai, local, hybrid, or ollama", embedder));
         }
     };
 
-    // If using OpenAI embedder, automatically use API key from environment if not provided
-    if matches!(config.embedder_type, EmbedderType::OpenAI) && config.openai_api_key.is_none() {
-        if let Ok(api_key) = std::env::var("OPENAI_API_KEY") {
-            config.openai_api_key = Some(api_key);
-        }
-    };
+    // If using OpenAI embedder, automatically use API key from environment if not provided;
+    // precedence is CLI arg > env var > config file.
+    config.openai_api_key = args.openai_api_key.clone();
+    if matches!(config.embedder_type, EmbedderType::OpenAI) && config.openai_api_key.is_none()
+        && let Ok(api_key) = std::env::var("OPENAI_API_KEY")
+    {
+        config.openai_api_key = Some(api_key);
+    }
+    if config.openai_api_key.is_none() {
+        config.openai_api_key = file.openai_api_key.clone();
+    }
 
     // Configure OpenAI API key if using OpenAI embedder
-    if matches!(
-        config.embedder_type,
-        EmbedderType::OpenAI | EmbedderType::Hybrid
-    ) {
-        // Only override with CLI argument if it's provided
-        if args.openai_api_key.is_some() {
-            config.openai_api_key = args.openai_api_key.clone();
-        }
-        if config.openai_api_key.is_none() {
-            return Err(anyhow::anyhow!(
-                "OPENAI_API_KEY environment variable or --openai-api-key argument required for OpenAI embedder"
-            ));
-        }
+    if matches!(config.embedder_type, EmbedderType::OpenAI | EmbedderType::Hybrid) && config.openai_api_key.is_none()
+    {
+        return Err(anyhow::anyhow!(
+            "OPENAI_API_KEY environment variable or --openai-api-key argument required for OpenAI embedder"
+        ));
     }
 
     // Configure local model paths
-    if matches!(
-        config.embedder_type,
-        EmbedderType::Local | EmbedderType::Hybrid
-    ) {
-        if let Some(model_path) = &args.local_model_path {
-            config.local_model_path = Some(Path::new(model_path).to_path_buf());
+    if matches!(config.embedder_type, EmbedderType::Local | EmbedderType::Hybrid) {
+        let local_model_path = args.local_model_path.clone().or(file.local_model_path.clone());
+        let local_tokenizer_path = args.local_tokenizer_path.clone().or(file.local_tokenizer_path.clone());
+        if let Some(model_path) = local_model_path {
+            config.local_model_path = Some(Path::new(&model_path).to_path_buf());
         }
-        if let Some(tokenizer_path) = &args.local_tokenizer_path {
-            config.local_tokenizer_path = Some(Path::new(tokenizer_path).to_path_buf());
+        if let Some(tokenizer_path) = local_tokenizer_path {
+            config.local_tokenizer_path = Some(Path::new(&tokenizer_path).to_path_buf());
         }
     }
 
@@ -0,0 +1,154 @@
+//! Layered TOML config files for the indexer CLI, merged underneath command-line overrides.
+//!
+//! A config file may pull in others via `include = ["base.toml", "team.toml"]`, resolved relative
+//! to the including file's directory and merged in order before the including file's own keys are
+//! applied (so later includes, then the file itself, win). An `unset = ["openai_api_key"]`
+//! directive clears a key that would otherwise be inherited from an include. Precedence across the
+//! whole system is CLI args > env vars > the file passed via `--config` > its includes.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+/// One layer of indexer configuration, as read from a single TOML file. Every field beyond
+/// `include`/`unset` is optional so a file only needs to set what it wants to override.
+#[derive(Debug, Deserialize, Default)]
+pub struct ConfigFile {
+    /// Other config files to merge in before this file's own keys apply, resolved relative to
+    /// this file's directory. Later entries win over earlier ones.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Keys inherited from an include that this file wants to clear rather than override.
+    #[serde(default)]
+    pub unset: Vec<String>,
+
+    pub embedder: Option<String>,
+    pub openai_api_key: Option<String>,
+    pub local_model_path: Option<String>,
+    pub local_tokenizer_path: Option<String>,
+    pub ollama_url: Option<String>,
+    pub ollama_model: Option<String>,
+    pub batch_size: Option<usize>,
+    pub max_concurrent_files: Option<usize>,
+    pub non_recursive: Option<bool>,
+    pub all_files: Option<bool>,
+    pub max_crawl_memory: Option<usize>,
+}
+
+impl ConfigFile {
+    /// Clear whichever fields are named in `self.unset`, then drop the now-inert `unset` list.
+    fn apply_unset(&mut self) {
+        for key in self.unset.drain(..).collect::<Vec<_>>() {
+            match key.as_str() {
+                "embedder" => self.embedder = None,
+                "openai_api_key" => self.openai_api_key = None,
+                "local_model_path" => self.local_model_path = None,
+                "local_tokenizer_path" => self.local_tokenizer_path = None,
+                "ollama_url" => self.ollama_url = None,
+                "ollama_model" => self.ollama_model = None,
+                "batch_size" => self.batch_size = None,
+                "max_concurrent_files" => self.max_concurrent_files = None,
+                "non_recursive" => self.non_recursive = None,
+                "all_files" => self.all_files = None,
+                "max_crawl_memory" => self.max_crawl_memory = None,
+                other => {
+                    tracing::warn!("⚠️  Ignoring `unset` for unknown config key: {}", other);
+                }
+            }
+        }
+    }
+
+    /// Overlay `other`'s set fields onto `self` in place, so `other` wins wherever it sets a value.
+    fn merge_from(&mut self, other: ConfigFile) {
+        let ConfigFile {
+            include: _,
+            unset: _,
+            embedder,
+            openai_api_key,
+            local_model_path,
+            local_tokenizer_path,
+            ollama_url,
+            ollama_model,
+            batch_size,
+            max_concurrent_files,
+            non_recursive,
+            all_files,
+            max_crawl_memory,
+        } = other;
+
+        if embedder.is_some() {
+            self.embedder = embedder;
+        }
+        if openai_api_key.is_some() {
+            self.openai_api_key = openai_api_key;
+        }
+        if local_model_path.is_some() {
+            self.local_model_path = local_model_path;
+        }
+        if local_tokenizer_path.is_some() {
+            self.local_tokenizer_path = local_tokenizer_path;
+        }
+        if ollama_url.is_some() {
+            self.ollama_url = ollama_url;
+        }
+        if ollama_model.is_some() {
+            self.ollama_model = ollama_model;
+        }
+        if batch_size.is_some() {
+            self.batch_size = batch_size;
+        }
+        if max_concurrent_files.is_some() {
+            self.max_concurrent_files = max_concurrent_files;
+        }
+        if non_recursive.is_some() {
+            self.non_recursive = non_recursive;
+        }
+        if all_files.is_some() {
+            self.all_files = all_files;
+        }
+        if max_crawl_memory.is_some() {
+            self.max_crawl_memory = max_crawl_memory;
+        }
+    }
+}
+
+/// Load `path` and every config file it (transitively) includes, merged into a single layer with
+/// precedence `path` > its includes (last include wins), and `unset` applied last. `visited`
+/// tracks canonicalized paths already on the current include chain so a cycle is reported as an
+/// error instead of recursing forever.
+fn load_layer(path: &Path, visited: &mut HashSet<PathBuf>) -> Result<ConfigFile> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("Config file not found: {}", path.display()))?;
+
+    if !visited.insert(canonical.clone()) {
+        bail!("Config include cycle detected at {}", path.display());
+    }
+
+    let raw = std::fs::read_to_string(&canonical)
+        .with_context(|| format!("Failed to read config file: {}", canonical.display()))?;
+    let mut file: ConfigFile =
+        toml::from_str(&raw).with_context(|| format!("Failed to parse config file: {}", canonical.display()))?;
+
+    let base_dir = canonical.parent().unwrap_or_else(|| Path::new("."));
+    let mut merged = ConfigFile::default();
+    for include in &file.include {
+        let included = load_layer(&base_dir.join(include), visited)?;
+        merged.merge_from(included);
+    }
+
+    visited.remove(&canonical);
+
+    let own_unset = std::mem::take(&mut file.unset);
+    merged.merge_from(file);
+    merged.unset = own_unset;
+    merged.apply_unset();
+    Ok(merged)
+}
+
+/// Load the config file at `path`, resolving its `include` chain and `unset` directives.
+pub fn load_config_file(path: &Path) -> Result<ConfigFile> {
+    load_layer(path, &mut HashSet::new())
+}
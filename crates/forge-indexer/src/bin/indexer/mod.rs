@@ -1,5 +1,6 @@
 pub mod cli;
 pub mod config;
+pub mod config_file;
 pub mod reset;
 pub mod service;
 pub mod signals;
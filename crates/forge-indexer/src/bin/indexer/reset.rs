@@ -2,11 +2,11 @@ use std::sync::Arc;
 
 use anyhow::Result;
 use forge_indexer::IndexService;
-use forge_indexer::embedder::{Embedder, LocalEmbedder, OpenAIEmbedder};
+use forge_indexer::embedder::{Embedder, LocalEmbedder, OllamaEmbedder, OpenAIEmbedder};
 use tracing::info;
 
 /// Run the reset command to clear the Qdrant collection
-pub async fn run_reset(embedder_type: String) -> Result<()> {
+pub async fn run_reset(embedder_type: String, ollama_url: String, ollama_model: String) -> Result<()> {
     info!("🔄 Starting Qdrant collection reset...");
     info!("📏 Embedder type: {}", embedder_type);
 
@@ -20,9 +20,10 @@ pub async fn run_reset(embedder_type: String) -> Result<()> {
             Arc::new(OpenAIEmbedder::new().await?)
         }
         "local" => Arc::new(LocalEmbedder::new_default()?),
+        "ollama" => Arc::new(OllamaEmbedder::new(ollama_url, ollama_model, None)),
         _ => {
             return Err(anyhow::anyhow!(
-                "Invalid embedder type: {}. Must be openai or local",
+                "Invalid embedder type: {}. Must be openai, local, or ollama",
                 embedder_type
             ));
         }
@@ -69,24 +69,17 @@ pub async fn run_indexer(args: IndexArgs) -> Result<()> {
         }
     }
 
-    // Process initial files in the directory
+    // Process initial files in the directory, flushing batches through `process_files` as the
+    // crawl's memory budget is reached rather than collecting every file's content at once.
     info!("🔍 Processing initial files in directory...");
-    let initial_files = match pipeline.collect_files_from_directory(&watch_path).await {
-        Ok(files) => files,
-        Err(e) => {
-            error!("❌ Failed to collect files from directory: {}", e);
-            return Err(e);
+    match pipeline.collect_and_process_with_memory_budget(&watch_path).await {
+        Ok(flush_cycles) => {
+            info!("📄 Initial crawl complete ({} memory-budget flush cycle(s))", flush_cycles);
         }
-    };
-
-    if !initial_files.is_empty() {
-        info!("📄 Found {} initial files to process", initial_files.len());
-        if let Err(e) = pipeline.process_files(initial_files).await {
-            error!("❌ Error processing initial files: {}", e);
+        Err(e) => {
+            error!("❌ Failed to process initial files: {}", e);
             return Err(e);
         }
-    } else {
-        info!("📂 No initial files found in directory");
     }
 
     // Set up graceful shutdown
@@ -120,6 +113,7 @@ pub async fn run_indexer(args: IndexArgs) -> Result<()> {
         stats.bytes_processed as f64 / 1_048_576.0
     );
     info!("   ❌ Errors encountered: {}", stats.errors_encountered);
+    info!("   💧 Crawl memory-budget flush cycles: {}", stats.crawl_flush_cycles);
 
     info!("👋 Forge Indexer shutdown complete");
     Ok(())
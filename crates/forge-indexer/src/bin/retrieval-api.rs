@@ -1,6 +1,7 @@
 //! Retrieval API service with HTTP/gRPC endpoints and proof-of-possession
 //! validation
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
 
@@ -8,6 +9,7 @@ use anyhow::anyhow;
 use axum::Router;
 use axum::routing::{get, post};
 use forge_indexer::{Embedder, IndexService, init_production_logging};
+use tokio::sync::RwLock;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
@@ -15,7 +17,10 @@ use tracing::info;
 
 mod retrieval_api;
 
-use retrieval_api::{AppState, health_handler, retrieve_handler};
+use retrieval_api::{
+    AppState, Metrics, batch_retrieve_handler, cancel_handler, health_handler, metrics_handler,
+    retrieve_handler, stream_retrieve_handler,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -38,12 +43,21 @@ async fn main() -> anyhow::Result<()> {
             .map_err(|e| anyhow!("Failed to create index service: {}", e))?,
     );
 
-    let app_state = AppState { index_service, embedder };
+    let app_state = AppState {
+        index_service,
+        embedder,
+        active_searches: Arc::new(RwLock::new(HashMap::new())),
+        metrics: Arc::new(Metrics::new()),
+    };
 
     // Build router
     let app = Router::new()
         .route("/health", get(health_handler))
+        .route("/metrics", get(metrics_handler))
         .route("/retrieve", post(retrieve_handler))
+        .route("/retrieve/batch", post(batch_retrieve_handler))
+        .route("/retrieve/stream", post(stream_retrieve_handler))
+        .route("/cancel/{id}", post(cancel_handler))
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -1,19 +1,30 @@
-use axum::extract::{Query, State};
-use axum::http::StatusCode;
-use axum::response::Json;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Json, Response};
 use forge_indexer::proto::RetrievalRequest;
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use super::service::handle_retrieval_request;
+use super::service::{handle_retrieval_request, stream_retrieval_results};
 use super::state::AppState;
 use super::types::{
-    ErrorResponse, HealthQuery, HealthResponse, HttpRetrievalRequest, HttpRetrievalResponse,
+    BatchQueryResult, CancelResponse, ErrorResponse, HealthQuery, HealthResponse,
+    HttpBatchRetrievalRequest, HttpBatchRetrievalResponse, HttpRetrievalRequest,
+    HttpRetrievalResponse, StreamEvent,
 };
 use super::validation::validate_proof_of_possession;
 
 /// Health check endpoint
 pub async fn health_handler(
+    State(state): State<AppState>,
     Query(params): Query<HealthQuery>,
 ) -> Result<Json<HealthResponse>, (StatusCode, Json<ErrorResponse>)> {
     let request_id = Uuid::new_v4().to_string();
@@ -23,7 +34,7 @@ pub async fn health_handler(
     let response = HealthResponse {
         status: "healthy".to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-        uptime_seconds: 0, // TODO: Track actual uptime
+        uptime_seconds: state.metrics.uptime_seconds(),
     };
 
     if params.detailed {
@@ -33,14 +44,49 @@ pub async fn health_handler(
     Ok(Json(response))
 }
 
-/// Main retrieval endpoint with proof-of-possession validation
+/// Prometheus scrape endpoint for the retrieval API's request/error counters and latency and
+/// chunks-returned histograms.
+pub async fn metrics_handler(State(state): State<AppState>) -> (StatusCode, [(&'static str, &'static str); 1], String) {
+    let body = state.metrics.render_prometheus().await;
+    (StatusCode::OK, [("content-type", "text/plain; version=0.0.4")], body)
+}
+
+/// Strong ETag for a retrieval request: a SHA-256 of the normalized `(query, repo, branch, k,
+/// file_hashes)` tuple together with the index's current version, so the same inputs against an
+/// unchanged index always produce the same tag, and any index mutation changes it.
+fn compute_etag(req: &HttpRetrievalRequest, index_version: u64) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(req.query.as_bytes());
+    hasher.update(req.repo.as_bytes());
+    hasher.update(req.branch.as_bytes());
+    hasher.update(req.k.to_le_bytes());
+
+    let mut paths: Vec<&String> = req.file_hashes.keys().collect();
+    paths.sort();
+    for path in paths {
+        hasher.update(path.as_bytes());
+        hasher.update(req.file_hashes[path].as_bytes());
+    }
+
+    hasher.update(index_version.to_le_bytes());
+    format!("\"{:x}\"", hasher.finalize())
+}
+
+/// Main retrieval endpoint with proof-of-possession validation and ETag-based conditional
+/// responses: a matching `If-None-Match` short-circuits to `304 Not Modified` before the
+/// (expensive) embedding and vector search run at all.
 pub async fn retrieve_handler(
     State(state): State<AppState>,
+    headers: HeaderMap,
     Json(req): Json<HttpRetrievalRequest>,
-) -> Result<Json<HttpRetrievalResponse>, (StatusCode, Json<ErrorResponse>)> {
+) -> Response {
     let request_id = Uuid::new_v4().to_string();
     let start_time = std::time::Instant::now();
 
+    state.metrics.record_request("retrieve").await;
+
     info!(
         request_id = %request_id,
         query = %req.query,
@@ -52,7 +98,9 @@ pub async fn retrieve_handler(
         "Retrieval request received"
     );
 
-    // Validate proof-of-possession
+    // Validate proof-of-possession. This must run and succeed before any conditional-response
+    // short-circuit below -- the ETag is derived entirely from client-supplied fields, so it is
+    // not itself proof of anything and must never let a caller skip this check.
     match validate_proof_of_possession(&req.file_hashes).await {
         Ok(()) => {
             info!(request_id = %request_id, "Proof-of-possession validation successful");
@@ -63,14 +111,25 @@ pub async fn retrieve_handler(
                 error = %e,
                 "Proof-of-possession validation failed"
             );
-            return Err((
+            state.metrics.record_error("INVALID_PROOF").await;
+            return (
                 StatusCode::FORBIDDEN,
                 Json(ErrorResponse {
                     error: "Proof-of-possession validation failed".to_string(),
                     request_id: request_id.clone(),
                     code: "INVALID_PROOF".to_string(),
                 }),
-            ));
+            )
+                .into_response();
+        }
+    }
+
+    let etag = compute_etag(&req, state.index_service.index_version());
+
+    if let Some(if_none_match) = headers.get(header::IF_NONE_MATCH).and_then(|v| v.to_str().ok()) {
+        if if_none_match == etag {
+            info!(request_id = %request_id, etag = %etag, "Retrieval short-circuited: ETag matched If-None-Match");
+            return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response();
         }
     }
 
@@ -82,12 +141,18 @@ pub async fn retrieve_handler(
         user_id: req.user_id.clone(),
         file_hashes: req.file_hashes.clone(),
         k: req.k,
+        semantic_ratio: req.semantic_ratio,
     };
 
     // Process retrieval request
     match handle_retrieval_request(&state, internal_req).await {
         Ok(response) => {
             let processing_time = start_time.elapsed();
+            state
+                .metrics
+                .record_retrieval(processing_time.as_secs_f64() * 1000.0, response.chunks.len())
+                .await;
+
             info!(
                 request_id = %request_id,
                 chunks_found = response.chunks.len(),
@@ -95,13 +160,18 @@ pub async fn retrieve_handler(
                 "Retrieval request completed successfully"
             );
 
-            Ok(Json(HttpRetrievalResponse {
-                request_id,
-                total_found: response.chunks.len(),
-                chunks: response.chunks,
-                processing_time_ms: processing_time.as_millis() as u64,
-                stats: std::collections::HashMap::new(),
-            }))
+            (
+                StatusCode::OK,
+                [(header::ETAG, etag)],
+                Json(HttpRetrievalResponse {
+                    request_id,
+                    total_found: response.chunks.len(),
+                    chunks: response.chunks,
+                    processing_time_ms: processing_time.as_millis() as u64,
+                    stats: state.metrics.snapshot_stats().await,
+                }),
+            )
+                .into_response()
         }
         Err(e) => {
             error!(
@@ -109,14 +179,232 @@ pub async fn retrieve_handler(
                 error = %e,
                 "Retrieval request failed"
             );
-            Err((
+            state.metrics.record_error(e.error_code()).await;
+            (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
                     error: format!("Retrieval failed: {e}"),
                     request_id,
                     code: "RETRIEVAL_ERROR".to_string(),
                 }),
-            ))
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Batch retrieval endpoint. Runs several queries against one shared `repo`/`branch`/`user_id`/
+/// `file_hashes` snapshot, validating proof-of-possession exactly once for the whole batch, then
+/// fans the queries out concurrently. Each query's result is isolated -- a failing query reports
+/// its own error inline in `results` rather than failing the batch.
+pub async fn batch_retrieve_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HttpBatchRetrievalRequest>,
+) -> Result<Json<HttpBatchRetrievalResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let batch_id = Uuid::new_v4().to_string();
+    let start_time = std::time::Instant::now();
+
+    state.metrics.record_request("retrieve_batch").await;
+
+    info!(
+        batch_id = %batch_id,
+        query_count = req.queries.len(),
+        repo = %req.repo,
+        branch = %req.branch,
+        user_id = %req.user_id,
+        "Batch retrieval request received"
+    );
+
+    if let Err(e) = validate_proof_of_possession(&req.file_hashes).await {
+        warn!(batch_id = %batch_id, error = %e, "Proof-of-possession validation failed");
+        state.metrics.record_error("INVALID_PROOF").await;
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "Proof-of-possession validation failed".to_string(),
+                request_id: batch_id,
+                code: "INVALID_PROOF".to_string(),
+            }),
+        ));
+    }
+
+    let queries = req.queries.into_iter().map(|query| {
+        run_single_retrieval(
+            &state,
+            query,
+            req.repo.clone(),
+            req.branch.clone(),
+            req.user_id.clone(),
+            req.file_hashes.clone(),
+            req.k,
+            req.semantic_ratio,
+        )
+    });
+
+    let results = join_all(queries).await;
+
+    info!(
+        batch_id = %batch_id,
+        result_count = results.len(),
+        total_processing_time_ms = start_time.elapsed().as_millis(),
+        "Batch retrieval request completed"
+    );
+
+    Ok(Json(HttpBatchRetrievalResponse {
+        batch_id,
+        results,
+        total_processing_time_ms: start_time.elapsed().as_millis() as u64,
+    }))
+}
+
+/// Run a single query from a batch, already proof-of-possession validated by the caller, and
+/// isolate its outcome into a [`BatchQueryResult`] rather than propagating an error out of the
+/// whole batch.
+async fn run_single_retrieval(
+    state: &AppState,
+    query: String,
+    repo: String,
+    branch: String,
+    user_id: String,
+    file_hashes: HashMap<String, String>,
+    k: usize,
+    semantic_ratio: Option<f32>,
+) -> BatchQueryResult {
+    let request_id = Uuid::new_v4().to_string();
+    let start_time = std::time::Instant::now();
+
+    let internal_req =
+        RetrievalRequest { query: query.clone(), repo, branch, user_id, file_hashes, k, semantic_ratio };
+
+    match handle_retrieval_request(state, internal_req).await {
+        Ok(response) => {
+            let processing_time = start_time.elapsed();
+            state
+                .metrics
+                .record_retrieval(processing_time.as_secs_f64() * 1000.0, response.chunks.len())
+                .await;
+
+            BatchQueryResult::Ok(HttpRetrievalResponse {
+                request_id,
+                total_found: response.chunks.len(),
+                chunks: response.chunks,
+                processing_time_ms: processing_time.as_millis() as u64,
+                stats: state.metrics.snapshot_stats().await,
+            })
+        }
+        Err(e) => {
+            error!(request_id = %request_id, query = %query, error = %e, "Batch query failed");
+            state.metrics.record_error(e.error_code()).await;
+            BatchQueryResult::Error { query, error: e.to_string(), code: e.error_code().to_string() }
         }
     }
 }
+
+/// Streaming retrieval endpoint. Returns results incrementally over SSE and registers a
+/// cancellation token under the search id carried in the first event, so a client can abort
+/// an in-flight query via `POST /cancel/{id}`.
+pub async fn stream_retrieve_handler(
+    State(state): State<AppState>,
+    Json(req): Json<HttpRetrievalRequest>,
+) -> Result<Sse<impl futures::Stream<Item = Result<Event, Infallible>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    let search_id = Uuid::new_v4().to_string();
+
+    info!(
+        search_id = %search_id,
+        query = %req.query,
+        repo = %req.repo,
+        branch = %req.branch,
+        "Streaming retrieval request received"
+    );
+
+    if let Err(e) = validate_proof_of_possession(&req.file_hashes).await {
+        warn!(search_id = %search_id, error = %e, "Proof-of-possession validation failed");
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ErrorResponse {
+                error: "Proof-of-possession validation failed".to_string(),
+                request_id: search_id,
+                code: "INVALID_PROOF".to_string(),
+            }),
+        ));
+    }
+
+    let internal_req = RetrievalRequest {
+        query: req.query.clone(),
+        repo: req.repo.clone(),
+        branch: req.branch.clone(),
+        user_id: req.user_id.clone(),
+        file_hashes: req.file_hashes.clone(),
+        k: req.k,
+        semantic_ratio: req.semantic_ratio,
+    };
+
+    let cancel_token = state.register_search(search_id.clone()).await;
+
+    let chunks = stream_retrieval_results(&state, internal_req, cancel_token.clone())
+        .await
+        .map_err(|e| {
+            error!(search_id = %search_id, error = %e, "Streaming retrieval failed to start");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Retrieval failed: {e}"),
+                    request_id: search_id.clone(),
+                    code: "RETRIEVAL_ERROR".to_string(),
+                }),
+            )
+        })?;
+
+    let started_id = search_id.clone();
+    let started = stream::once(async move { StreamEvent::Started { search_id: started_id } }).boxed();
+
+    let total_sent = Arc::new(AtomicUsize::new(0));
+    let body_counter = total_sent.clone();
+    let body = chunks
+        .map(move |chunk| {
+            body_counter.fetch_add(1, Ordering::Relaxed);
+            StreamEvent::Chunk(chunk)
+        })
+        .boxed();
+
+    let closing_state = state.clone();
+    let closing_search_id = search_id.clone();
+    let closing = stream::once(async move {
+        let cancelled = cancel_token.is_cancelled();
+        closing_state.complete_search(&closing_search_id).await;
+        StreamEvent::Completed { cancelled, total_sent: total_sent.load(Ordering::Relaxed) }
+    })
+    .boxed();
+
+    let events = started.chain(body).chain(closing);
+
+    let sse_stream = events.map(|event| {
+        let axum_event = match &event {
+            StreamEvent::Started { .. } => Event::default().event("search_started"),
+            StreamEvent::Chunk(_) => Event::default().event("chunk"),
+            StreamEvent::Completed { .. } => Event::default().event("done"),
+        };
+        Ok(axum_event
+            .json_data(&event)
+            .unwrap_or_else(|_| Event::default().event("error")))
+    });
+
+    Ok(Sse::new(sse_stream).keep_alive(KeepAlive::default()))
+}
+
+/// Cancel an in-flight streaming search by id
+pub async fn cancel_handler(
+    State(state): State<AppState>,
+    Path(search_id): Path<String>,
+) -> Json<CancelResponse> {
+    let cancelled = state.cancel_search(&search_id).await;
+
+    if cancelled {
+        info!(search_id = %search_id, "Streaming search cancelled");
+    } else {
+        info!(search_id = %search_id, "Cancel requested for unknown or already-finished search");
+    }
+
+    Json(CancelResponse { search_id, cancelled })
+}
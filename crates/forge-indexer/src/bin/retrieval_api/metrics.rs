@@ -0,0 +1,233 @@
+//! Lightweight, dependency-free metrics for the retrieval API: counters and histograms kept as
+//! plain maps behind a lock and rendered by hand in Prometheus text exposition format, rather
+//! than pulling in a metrics crate for a handful of gauges.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::time::Instant;
+
+use tokio::sync::RwLock;
+
+/// Upper bounds of the retrieval latency histogram buckets, in milliseconds.
+const LATENCY_BUCKETS_MS: &[f64] = &[10.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 2500.0, 5000.0, 10000.0];
+
+/// Upper bounds of the chunks-returned histogram buckets.
+const CHUNKS_RETURNED_BUCKETS: &[f64] = &[0.0, 1.0, 5.0, 10.0, 25.0, 50.0, 100.0];
+
+struct HistogramState {
+    /// `bucket_counts[i]` is the number of observations `<= bounds[i]`, matching Prometheus'
+    /// cumulative-bucket convention directly -- no extra accumulation pass needed at render time.
+    bucket_counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+/// A cumulative histogram over a fixed, pre-declared set of bucket bounds.
+struct Histogram {
+    bounds: &'static [f64],
+    state: RwLock<HistogramState>,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            state: RwLock::new(HistogramState {
+                bucket_counts: vec![0; bounds.len()],
+                sum: 0.0,
+                count: 0,
+            }),
+        }
+    }
+
+    async fn observe(&self, value: f64) {
+        let mut state = self.state.write().await;
+        for (bound, count) in self.bounds.iter().zip(state.bucket_counts.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        state.sum += value;
+        state.count += 1;
+    }
+
+    async fn mean(&self) -> f64 {
+        let state = self.state.read().await;
+        if state.count == 0 { 0.0 } else { state.sum / state.count as f64 }
+    }
+
+    async fn render(&self, name: &str, help: &str, out: &mut String) {
+        let state = self.state.read().await;
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (bound, count) in self.bounds.iter().zip(state.bucket_counts.iter()) {
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {}", state.count);
+        let _ = writeln!(out, "{name}_sum {}", state.sum);
+        let _ = writeln!(out, "{name}_count {}", state.count);
+    }
+}
+
+/// Per-endpoint request counts, `ForgeIndexerError::error_code()`-keyed error counts, and
+/// retrieval latency/result-size histograms for the retrieval API, rendered on demand as
+/// Prometheus text exposition format.
+pub struct Metrics {
+    start_time: Instant,
+    request_counts: RwLock<HashMap<&'static str, u64>>,
+    error_counts: RwLock<HashMap<String, u64>>,
+    retrieval_latency_ms: Histogram,
+    chunks_returned: Histogram,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            start_time: Instant::now(),
+            request_counts: RwLock::new(HashMap::new()),
+            error_counts: RwLock::new(HashMap::new()),
+            retrieval_latency_ms: Histogram::new(LATENCY_BUCKETS_MS),
+            chunks_returned: Histogram::new(CHUNKS_RETURNED_BUCKETS),
+        }
+    }
+
+    /// Seconds since this `Metrics` (and therefore the process) started, for `health_handler`.
+    pub fn uptime_seconds(&self) -> u64 {
+        self.start_time.elapsed().as_secs()
+    }
+
+    pub async fn record_request(&self, endpoint: &'static str) {
+        *self.request_counts.write().await.entry(endpoint).or_insert(0) += 1;
+    }
+
+    pub async fn record_error(&self, error_code: &str) {
+        *self
+            .error_counts
+            .write()
+            .await
+            .entry(error_code.to_string())
+            .or_insert(0) += 1;
+    }
+
+    pub async fn record_retrieval(&self, latency_ms: f64, chunks_returned: usize) {
+        self.retrieval_latency_ms.observe(latency_ms).await;
+        self.chunks_returned.observe(chunks_returned as f64).await;
+    }
+
+    /// A handful of running counters, suitable for the otherwise-empty
+    /// `HttpRetrievalResponse::stats` field.
+    pub async fn snapshot_stats(&self) -> HashMap<String, String> {
+        let retrieve_requests = self
+            .request_counts
+            .read()
+            .await
+            .get("retrieve")
+            .copied()
+            .unwrap_or(0);
+        let errors: u64 = self.error_counts.read().await.values().sum();
+        let avg_latency_ms = self.retrieval_latency_ms.mean().await;
+        let avg_chunks_returned = self.chunks_returned.mean().await;
+
+        HashMap::from([
+            ("total_retrieve_requests".to_string(), retrieve_requests.to_string()),
+            ("total_errors".to_string(), errors.to_string()),
+            ("avg_retrieval_latency_ms".to_string(), format!("{avg_latency_ms:.2}")),
+            ("avg_chunks_returned".to_string(), format!("{avg_chunks_returned:.2}")),
+        ])
+    }
+
+    /// Render every counter/histogram as Prometheus text exposition format.
+    pub async fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP forge_retrieval_requests_total Total requests handled, by endpoint.");
+        let _ = writeln!(out, "# TYPE forge_retrieval_requests_total counter");
+        for (endpoint, count) in self.request_counts.read().await.iter() {
+            let _ = writeln!(out, "forge_retrieval_requests_total{{endpoint=\"{endpoint}\"}} {count}");
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP forge_retrieval_errors_total Total errors, keyed by ForgeIndexerError::error_code()."
+        );
+        let _ = writeln!(out, "# TYPE forge_retrieval_errors_total counter");
+        for (code, count) in self.error_counts.read().await.iter() {
+            let _ = writeln!(out, "forge_retrieval_errors_total{{code=\"{code}\"}} {count}");
+        }
+
+        self.retrieval_latency_ms
+            .render(
+                "forge_retrieval_latency_milliseconds",
+                "Retrieval request latency in milliseconds.",
+                &mut out,
+            )
+            .await;
+        self.chunks_returned
+            .render(
+                "forge_retrieval_chunks_returned",
+                "Number of chunks returned per retrieval request.",
+                &mut out,
+            )
+            .await;
+
+        let _ = writeln!(out, "# HELP forge_retrieval_uptime_seconds Seconds since the process started.");
+        let _ = writeln!(out, "# TYPE forge_retrieval_uptime_seconds gauge");
+        let _ = writeln!(out, "forge_retrieval_uptime_seconds {}", self.uptime_seconds());
+
+        out
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn renders_counts_and_error_codes() {
+        let metrics = Metrics::new();
+        metrics.record_request("retrieve").await;
+        metrics.record_request("retrieve").await;
+        metrics.record_error("VECTOR_DB_ERROR").await;
+
+        let rendered = metrics.render_prometheus().await;
+
+        assert!(rendered.contains("forge_retrieval_requests_total{endpoint=\"retrieve\"} 2"));
+        assert!(rendered.contains("forge_retrieval_errors_total{code=\"VECTOR_DB_ERROR\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn histogram_buckets_are_cumulative() {
+        let histogram = Histogram::new(LATENCY_BUCKETS_MS);
+        histogram.observe(5.0).await;
+        histogram.observe(75.0).await;
+
+        let mut out = String::new();
+        histogram.render("latency", "help text", &mut out).await;
+
+        assert!(out.contains("latency_bucket{le=\"10\"} 1"));
+        assert!(out.contains("latency_bucket{le=\"100\"} 2"));
+        assert!(out.contains("latency_bucket{le=\"+Inf\"} 2"));
+        assert!(out.contains("latency_sum 80"));
+        assert!(out.contains("latency_count 2"));
+    }
+
+    #[tokio::test]
+    async fn snapshot_stats_reports_running_averages() {
+        let metrics = Metrics::new();
+        metrics.record_retrieval(100.0, 4).await;
+        metrics.record_retrieval(200.0, 6).await;
+
+        let stats = metrics.snapshot_stats().await;
+
+        assert_eq!(stats.get("avg_retrieval_latency_ms").unwrap(), "150.00");
+        assert_eq!(stats.get("avg_chunks_returned").unwrap(), "5.00");
+    }
+}
@@ -1,8 +1,13 @@
 pub mod handlers;
+pub mod metrics;
 pub mod service;
 pub mod state;
 pub mod types;
 pub mod validation;
 
-pub use handlers::{health_handler, retrieve_handler};
+pub use handlers::{
+    batch_retrieve_handler, cancel_handler, health_handler, metrics_handler, retrieve_handler,
+    stream_retrieve_handler,
+};
+pub use metrics::Metrics;
 pub use state::AppState;
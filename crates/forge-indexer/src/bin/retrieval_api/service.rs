@@ -1,10 +1,15 @@
+use forge_indexer::index_svc::CODE_VECTOR;
 use forge_indexer::proto::{RetrievalRequest, RetrievalResponse, RetrievedChunk};
+use forge_indexer::retry::{RetryPolicy, retry_with_backoff};
 use forge_indexer::{ForgeIndexerError, Result as ForgeResult};
+use futures::stream::{self, Stream};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info};
 
 use super::state::AppState;
 
-/// Handle retrieval request with simple vector search
+/// Handle retrieval request with hybrid dense+sparse search (see
+/// `IndexService::search_hybrid`); `request.semantic_ratio` controls the vector/keyword blend.
 pub async fn handle_retrieval_request(
     state: &AppState,
     request: RetrievalRequest,
@@ -14,42 +19,52 @@ pub async fn handle_retrieval_request(
         request.query, request.repo, request.branch
     );
 
-    // Generate embedding for the query
-    let query_embedding = state.embedder.embed(&request.query).await.map_err(|e| {
-        error!("Failed to generate query embedding: {}", e);
-        ForgeIndexerError::embedding_error(format!("Failed to generate query embedding: {e}"))
-    })?;
+    let retry_policy = RetryPolicy::default();
+
+    // Generate embedding for the query, retrying transient embedding-service failures.
+    let query_embedding = retry_with_backoff(&retry_policy, || async {
+        state.embedder.embed(&request.query).await.map_err(|e| {
+            error!("Failed to generate query embedding: {}", e);
+            ForgeIndexerError::embedding_error(format!("Failed to generate query embedding: {e}"))
+        })
+    })
+    .await?;
 
     info!(
         "Generated query embedding with {} dimensions",
         query_embedding.len()
     );
 
-    // Search the vector database
-    let search_results = state
-        .index_service
-        .search_similar(&query_embedding, request.k, None, None)
-        .await
-        .map_err(|e| {
-            error!("Vector search failed: {}", e);
-            ForgeIndexerError::vector_db_error(format!("Vector search failed: {e}"))
-        })?;
+    // Fuse the vector and keyword legs, retrying transient Qdrant failures.
+    let search_results = retry_with_backoff(&retry_policy, || async {
+        state
+            .index_service
+            .search_hybrid(&query_embedding, &request.query, request.k, CODE_VECTOR, request.semantic_ratio, None, None)
+            .await
+            .map_err(|e| {
+                error!("Hybrid search failed: {}", e);
+                ForgeIndexerError::vector_db_error(format!("Hybrid search failed: {e}"))
+            })
+    })
+    .await?;
 
     info!(
-        "Found {} search results from vector database",
+        "Found {} search results from hybrid search",
         search_results.len()
     );
 
     // Convert to response format
     let final_results: Vec<RetrievedChunk> = search_results
         .into_iter()
-        .map(|(chunk, score)| {
-            info!("Result: path={}, score={:.4}", chunk.path, score);
+        .map(|result| {
+            info!("Result: path={}, score={:.4}", result.chunk.path, result.score);
             RetrievedChunk {
-                code: chunk.code,
-                path: chunk.path,
-                score,
-                chunk_hash: chunk.id,
+                code: result.chunk.code,
+                path: result.chunk.path,
+                score: result.score,
+                chunk_hash: result.chunk.id,
+                vector_score: result.vector_score,
+                keyword_score: result.keyword_score,
             }
         })
         .collect();
@@ -58,3 +73,61 @@ pub async fn handle_retrieval_request(
 
     Ok(RetrievalResponse { chunks: final_results })
 }
+
+/// Run retrieval and yield results one chunk at a time, checking `cancel_token` before each
+/// one is emitted. The Qdrant client has no cursor-based search API, so the batch size the
+/// token is polled between is a single chunk rather than a server-side page; a cancelled
+/// search still stops paying for the remaining chunks to be serialized and sent to the
+/// client, which is what matters for a rapidly refined or abandoned query.
+pub async fn stream_retrieval_results(
+    state: &AppState,
+    request: RetrievalRequest,
+    cancel_token: CancellationToken,
+) -> ForgeResult<impl Stream<Item = RetrievedChunk> + Send + 'static> {
+    let chunks: Vec<RetrievedChunk> = if cancel_token.is_cancelled() {
+        info!("Streaming search cancelled before it started");
+        Vec::new()
+    } else {
+        let query_embedding = state.embedder.embed(&request.query).await.map_err(|e| {
+            error!("Failed to generate query embedding: {}", e);
+            ForgeIndexerError::embedding_error(format!("Failed to generate query embedding: {e}"))
+        })?;
+
+        if cancel_token.is_cancelled() {
+            info!("Streaming search cancelled before vector search began");
+            Vec::new()
+        } else {
+            let search_results = state
+                .index_service
+                .search_similar(&query_embedding, request.k, CODE_VECTOR, None, None)
+                .await
+                .map_err(|e| {
+                    error!("Vector search failed: {}", e);
+                    ForgeIndexerError::vector_db_error(format!("Vector search failed: {e}"))
+                })?;
+
+            search_results
+                .into_iter()
+                .map(|(chunk, score)| RetrievedChunk {
+                    code: chunk.code,
+                    path: chunk.path,
+                    score,
+                    chunk_hash: chunk.id,
+                    vector_score: Some(score),
+                    keyword_score: None,
+                })
+                .collect()
+        }
+    };
+
+    Ok(stream::unfold(
+        (chunks.into_iter(), cancel_token),
+        |(mut remaining, cancel_token)| async move {
+            if cancel_token.is_cancelled() {
+                return None;
+            }
+            let next = remaining.next()?;
+            Some((next, (remaining, cancel_token)))
+        },
+    ))
+}
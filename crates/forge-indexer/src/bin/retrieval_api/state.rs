@@ -1,10 +1,49 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use forge_indexer::{Embedder, IndexService};
+use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use super::metrics::Metrics;
 
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
     pub index_service: Arc<IndexService>,
     pub embedder: Arc<dyn Embedder>,
+    /// Cancellation tokens for in-flight streaming searches, keyed by the server-generated
+    /// search id handed back to the client in the first SSE event.
+    pub active_searches: Arc<RwLock<HashMap<String, CancellationToken>>>,
+    /// Request/error counters and latency histograms, rendered at `/metrics` and used to report
+    /// true process uptime from `health_handler`.
+    pub metrics: Arc<Metrics>,
+}
+
+impl AppState {
+    /// Register a new streaming search and return the token its pipeline should poll
+    pub async fn register_search(&self, search_id: String) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.active_searches
+            .write()
+            .await
+            .insert(search_id, token.clone());
+        token
+    }
+
+    /// Cancel an in-flight streaming search. Returns `true` if a matching search was found.
+    pub async fn cancel_search(&self, search_id: &str) -> bool {
+        match self.active_searches.write().await.remove(search_id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a streaming search from the registry once it finishes on its own
+    pub async fn complete_search(&self, search_id: &str) {
+        self.active_searches.write().await.remove(search_id);
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use forge_indexer::proto::Chunk;
+use forge_indexer::proto::{Chunk, RetrievedChunk};
 use serde::{Deserialize, Serialize};
 
 /// HTTP request for retrieval endpoint
@@ -13,6 +13,10 @@ pub struct HttpRetrievalRequest {
     pub file_hashes: HashMap<String, String>, // path -> sha256
     #[serde(default = "default_k")]
     pub k: usize,
+    /// Hybrid search blend: `1.0` pure vector, `0.0` pure keyword, omitted for reciprocal rank
+    /// fusion. See `RetrievalRequest::semantic_ratio`.
+    #[serde(default)]
+    pub semantic_ratio: Option<f32>,
 }
 
 fn default_k() -> usize {
@@ -29,6 +33,41 @@ pub struct HttpRetrievalResponse {
     pub stats: HashMap<String, String>,
 }
 
+/// HTTP request for the batch retrieval endpoint: several `queries` run against one shared
+/// `repo`/`branch`/`user_id`/`file_hashes` snapshot, so proof-of-possession is validated once for
+/// the whole batch instead of once per query.
+#[derive(Debug, Deserialize)]
+pub struct HttpBatchRetrievalRequest {
+    pub queries: Vec<String>,
+    pub repo: String,
+    pub branch: String,
+    pub user_id: String,
+    pub file_hashes: HashMap<String, String>,
+    #[serde(default = "default_k")]
+    pub k: usize,
+    /// Hybrid search blend applied to every query in the batch; see
+    /// `RetrievalRequest::semantic_ratio`.
+    #[serde(default)]
+    pub semantic_ratio: Option<f32>,
+}
+
+/// Outcome of a single query within a batch. Kept as its own result type, rather than reusing
+/// `Result<HttpRetrievalResponse, ErrorResponse>`, so serde can tag it cleanly for clients.
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchQueryResult {
+    Ok(HttpRetrievalResponse),
+    Error { query: String, error: String, code: String },
+}
+
+/// HTTP response for the batch retrieval endpoint
+#[derive(Debug, Serialize)]
+pub struct HttpBatchRetrievalResponse {
+    pub batch_id: String,
+    pub results: Vec<BatchQueryResult>,
+    pub total_processing_time_ms: u64,
+}
+
 /// Error response format
 #[derive(Debug, Serialize)]
 pub struct ErrorResponse {
@@ -51,3 +90,22 @@ pub struct HealthQuery {
     #[serde(default)]
     pub detailed: bool,
 }
+
+/// Event emitted on the `/retrieve/stream` SSE channel
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type")]
+pub enum StreamEvent {
+    /// First event: the server-generated search id this stream can be cancelled with
+    Started { search_id: String },
+    /// One incrementally-streamed retrieval result
+    Chunk(RetrievedChunk),
+    /// Final event, whether the stream ran to completion or was cancelled mid-flight
+    Completed { cancelled: bool, total_sent: usize },
+}
+
+/// Response for `POST /cancel/{id}`
+#[derive(Debug, Serialize)]
+pub struct CancelResponse {
+    pub search_id: String,
+    pub cancelled: bool,
+}
@@ -1,12 +1,43 @@
+pub(crate) mod analysis;
+pub(crate) mod dedup;
+pub(crate) mod language_registry;
+pub(crate) mod outline;
+pub(crate) mod parser;
+pub(crate) mod similarity;
+pub(crate) mod strategies;
+pub(crate) mod symbols;
+pub(crate) mod token_budget;
+pub(crate) mod types;
+pub(crate) mod utils;
+
 use anyhow::Result;
 use text_splitter::CodeSplitter;
 use tracing::debug;
 
+use crate::chunker::analysis::CodeAnalyzer;
+use crate::chunker::language_registry::LanguageRegistry;
+use crate::chunker::parser::ParserManager;
+use crate::chunker::strategies::ChunkingStrategies;
+use crate::chunker::token_budget::DEFAULT_MAX_TOKENS;
+use crate::chunker::utils::ChunkerUtils;
+
 // Re-export public types
 pub use crate::proto::Chunk;
 
+/// Default exact-Jaccard threshold above which `Chunker::find_near_duplicate_chunks` considers
+/// two chunks near-duplicates, once MinHash + LSH has narrowed the field to candidates.
+pub const NEAR_DUPLICATE_JACCARD_THRESHOLD: f32 = 0.85;
+
 pub struct Chunker {
     pub max_chunk_size: usize,
+    /// Token budget applied by the tree-sitter-backed token-aware path (see
+    /// `chunk_file_token_aware`); kept separate from `max_chunk_size`, which bounds the
+    /// byte-oriented `text_splitter::CodeSplitter` fallback path.
+    pub max_tokens: usize,
+    /// Which languages `chunk_file` can build a `text_splitter::CodeSplitter` for. Pre-populated
+    /// with the crate's built-in grammars; call `language_registry.register_language(...)` to
+    /// add support for one this crate doesn't ship without recompiling it.
+    pub language_registry: LanguageRegistry,
 }
 
 impl Default for Chunker {
@@ -19,10 +50,17 @@ impl Chunker {
     pub fn new() -> Self {
         Self {
             max_chunk_size: 500,
+            max_tokens: DEFAULT_MAX_TOKENS,
+            language_registry: LanguageRegistry::new(),
         }
     }
 
-    /// Chunk a file using text_splitter::CodeSplitter when possible, otherwise fallback to simple windows.
+    /// Chunk a file using tree-sitter-derived semantic boundaries packed into a token budget,
+    /// falling back to `text_splitter::CodeSplitter` (and ultimately size-bounded window
+    /// splitting) when the language has no registered tree-sitter grammar or parsing fails. Which
+    /// languages get a `CodeSplitter` is driven by `self.language_registry`, not a fixed list, so
+    /// `language_registry.register_language(...)` is enough to add a language this crate doesn't
+    /// ship a grammar for.
     pub fn chunk_file(
         &mut self,
         path: &str,
@@ -30,18 +68,13 @@ impl Chunker {
         lang: &str,
         rev: &str,
     ) -> Result<Vec<Chunk>> {
-        // Try to construct a CodeSplitter using the language if available
-        let maybe_splitter = match lang {
-            "rust" => Some(CodeSplitter::new(tree_sitter_rust::LANGUAGE, self.max_chunk_size)),
-            "python" => Some(CodeSplitter::new(tree_sitter_python::LANGUAGE, self.max_chunk_size)),
-            "javascript" | "typescript" => Some(CodeSplitter::new(tree_sitter_typescript::LANGUAGE_TYPESCRIPT, self.max_chunk_size)),
-            "go" => Some(CodeSplitter::new(tree_sitter_go::LANGUAGE, self.max_chunk_size)),
-            "java" => Some(CodeSplitter::new(tree_sitter_java::LANGUAGE, self.max_chunk_size)),
-            "cpp" | "c" => Some(CodeSplitter::new(tree_sitter_cpp::LANGUAGE, self.max_chunk_size)),
-            "css" => Some(CodeSplitter::new(tree_sitter_css::LANGUAGE, self.max_chunk_size)),
-            "ruby" => Some(CodeSplitter::new(tree_sitter_ruby::LANGUAGE, self.max_chunk_size)),
-            _ => None,
-        };
+        if let Some(chunks) = self.chunk_file_token_aware(path, content, lang, rev) {
+            return Ok(chunks);
+        }
+
+        // Try to construct a CodeSplitter for a registered grammar
+        let maybe_splitter =
+            self.language_registry.grammar(lang).map(|grammar| CodeSplitter::new(grammar, self.max_chunk_size));
 
         let mut chunks = Vec::new();
 
@@ -61,6 +94,10 @@ impl Chunker {
                             size: piece.len(),
                             code: piece.to_string(),
                             summary: None,
+                            start_byte: start,
+                            end_byte: end,
+                            start_line: ChunkerUtils::line_for_byte(content, start),
+                            end_line: ChunkerUtils::line_for_byte(content, end),
                             embedding: None,
                         };
                         debug!("Created chunk {} for {}: {} chars", i, path, piece.len());
@@ -82,22 +119,104 @@ impl Chunker {
         Ok(chunks)
     }
 
-    /// Create chunks using simple window splitting
+    /// Attempt token-budgeted, tree-sitter-aware chunking for `lang`. Returns `None` when no
+    /// grammar is registered or the source fails to parse, signalling the caller to fall back to
+    /// `text_splitter`.
+    fn chunk_file_token_aware(
+        &self,
+        path: &str,
+        content: &str,
+        lang: &str,
+        rev: &str,
+    ) -> Option<Vec<Chunk>> {
+        let parser_manager = ParserManager::new();
+        let mut parser = parser_manager.create_parser(lang)?;
+        let tree = parser.parse(content, None)?;
+
+        let analysis = CodeAnalyzer::analyze_code_structure(content, lang, &tree);
+        let token_chunks = ChunkingStrategies::extract_token_budgeted_chunks(
+            path,
+            content,
+            lang,
+            rev,
+            &analysis,
+            self.max_tokens,
+        );
+
+        if token_chunks.is_empty() {
+            return None;
+        }
+
+        debug!(
+            "Token-budgeted chunking produced {} chunk(s) for {} (max_tokens={})",
+            token_chunks.len(),
+            path,
+            self.max_tokens
+        );
+
+        Some(
+            token_chunks
+                .into_iter()
+                .map(|c| Chunk {
+                    id: c.id,
+                    path: c.path,
+                    lang: c.lang,
+                    symbol: c.symbol,
+                    rev: c.rev,
+                    size: c.size,
+                    code: c.code,
+                    summary: c.summary,
+                    start_byte: c.start_byte,
+                    end_byte: c.end_byte,
+                    start_line: c.start_line,
+                    end_line: c.end_line,
+                    embedding: None,
+                })
+                .collect(),
+        )
+    }
+
+    /// Size-bounded window splitting for a language with no registered grammar: still respects
+    /// `self.max_chunk_size` and still prefers breaking on blank/comment lines (see
+    /// `ChunkingStrategies::fallback_chunking`) instead of emitting the whole file as one chunk.
     fn create_chunks_with_windows(&self, path: &str, content: &str, lang: &str, rev: &str) -> Vec<Chunk> {
-        // For unsupported languages, just create one chunk with the entire content
-        // since we're simplifying and letting text-splitter handle the complex logic
-        debug!("Creating fallback chunk for {}: {} chars", path, content.len());
-        vec![Chunk {
-            id: format!("{}:{}:{}:{}", path, 0, content.len(), rev),
-            path: path.to_string(),
-            lang: lang.to_string(),
-            symbol: None,
-            rev: rev.to_string(),
-            size: content.len(),
-            code: content.to_string(),
-            summary: None,
-            embedding: None,
-        }]
+        debug!(
+            "No grammar registered for {}; falling back to size-bounded window splitting for {}",
+            lang, path
+        );
+        ChunkingStrategies::fallback_chunking(path, content, lang, rev, None, Some(self.max_chunk_size))
+            .into_iter()
+            .map(|c| Chunk {
+                id: c.id,
+                path: c.path,
+                lang: c.lang,
+                symbol: c.symbol,
+                rev: c.rev,
+                size: c.size,
+                code: c.code,
+                summary: c.summary,
+                start_byte: c.start_byte,
+                end_byte: c.end_byte,
+                start_line: c.start_line,
+                end_line: c.end_line,
+                embedding: None,
+            })
+            .collect()
+    }
+
+    /// Cluster `chunks` into near-duplicate groups via MinHash + banded LSH over each chunk's
+    /// symbol set (see `dedup`), approximating `ChunkerUtils::jaccard_similarity` at a scale that
+    /// doesn't require comparing every pair. Callers can embed one representative per cluster
+    /// instead of every near-identical generated/vendored chunk. Returns each cluster as a sorted
+    /// list of indices into `chunks`; chunks with no near-duplicate are omitted entirely.
+    pub fn find_near_duplicate_chunks(chunks: &[Chunk]) -> Vec<Vec<usize>> {
+        let token_sets: Vec<std::collections::HashSet<String>> =
+            chunks.iter().map(|chunk| crate::chunker::symbols::extract_symbol_table(&chunk.code, &chunk.lang).all()).collect();
+        dedup::find_near_duplicate_clusters(
+            &token_sets,
+            &dedup::MinHashLshConfig::default(),
+            NEAR_DUPLICATE_JACCARD_THRESHOLD,
+        )
     }
 }
 
@@ -151,4 +270,51 @@ mod tests {
         assert_eq!(chunk.size, content.len());
         assert_eq!(chunk.code, content);
     }
+
+    fn make_chunk(id: &str, code: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: "test.rs".to_string(),
+            lang: "rust".to_string(),
+            symbol: None,
+            rev: "rev1".to_string(),
+            size: code.len(),
+            code: code.to_string(),
+            summary: None,
+            start_byte: 0,
+            end_byte: code.len(),
+            start_line: 1,
+            end_line: 1,
+            embedding: None,
+        }
+    }
+
+    #[test]
+    fn test_find_near_duplicate_chunks_clusters_similar_code() {
+        let original = make_chunk(
+            "a",
+            "fn process_widget(widget_id: u32, widget_name: String) -> WidgetResult { widget_name }",
+        );
+        // Same identifiers, trivially reformatted -- should land in the same cluster.
+        let near_copy = make_chunk(
+            "b",
+            "fn process_widget(widget_id: u32, widget_name: String) -> WidgetResult {\n    widget_name\n}",
+        );
+        let unrelated =
+            make_chunk("c", "fn compute_checksum(buffer: &[u8]) -> u64 { buffer.len() as u64 }");
+
+        let clusters = Chunker::find_near_duplicate_chunks(&[original, near_copy, unrelated]);
+
+        assert_eq!(clusters, vec![vec![0, 1]]);
+    }
+
+    #[test]
+    fn test_find_near_duplicate_chunks_no_duplicates() {
+        let chunks = vec![
+            make_chunk("a", "fn alpha_function(alpha_value: i32) -> i32 { alpha_value }"),
+            make_chunk("b", "fn beta_function(beta_value: String) -> String { beta_value }"),
+        ];
+
+        assert!(Chunker::find_near_duplicate_chunks(&chunks).is_empty());
+    }
 }
\ No newline at end of file
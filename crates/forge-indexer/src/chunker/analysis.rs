@@ -15,6 +15,10 @@ impl CodeAnalyzer {
         // Extract symbols and calculate complexity using all available methods
         Self::extract_symbols_recursive(&mut cursor, content, &mut analysis.symbols, lang);
 
+        // Second pass: resolve which symbols reference which, now that every symbol's byte range
+        // is known.
+        Self::resolve_symbol_references(tree, content, &mut analysis.symbols);
+
         // Calculate overall complexity using symbol analysis
         analysis.calculate_overall_complexity();
 
@@ -93,7 +97,7 @@ impl CodeAnalyzer {
                 start_byte: node.start_byte(),
                 end_byte: node.end_byte(),
                 importance_score: CodeSymbol::calculate_importance(node.kind()),
-                complexity: CodeSymbol::calculate_complexity(node),
+                complexity: CodeSymbol::calculate_complexity(node, content),
                 references: Vec::new(),
             });
         }
@@ -110,6 +114,88 @@ impl CodeAnalyzer {
         }
     }
 
+    /// Node kinds that can carry a use of another symbol's name: plain identifiers, call
+    /// expressions (`foo()`), type references (`type_identifier`), and field names
+    /// (`field_identifier`). A symbol's own name at its definition site is one of these too; that
+    /// self-edge is dropped in `resolve_symbol_references`.
+    fn is_reference_node(kind: &str) -> bool {
+        matches!(kind, "identifier" | "call_expression" | "type_identifier" | "field_identifier")
+    }
+
+    /// Build an intra-file symbol dependency graph by filling in each `CodeSymbol.references`:
+    /// for every identifier/call/type reference in the file, if its text names a known symbol and
+    /// it falls inside a *different* symbol's byte range, record that the enclosing symbol
+    /// references the named one. This is a second AST pass over `tree` now that every symbol's
+    /// range has been collected, mirroring how `extract_symbols_recursive` walked it the first
+    /// time.
+    fn resolve_symbol_references(tree: &Tree, content: &str, symbols: &mut [CodeSymbol]) {
+        if symbols.is_empty() {
+            return;
+        }
+
+        let mut names_to_indices: std::collections::HashMap<&str, Vec<usize>> =
+            std::collections::HashMap::new();
+        for (index, symbol) in symbols.iter().enumerate() {
+            names_to_indices.entry(symbol.name.as_str()).or_default().push(index);
+        }
+
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut cursor = tree.walk();
+        Self::collect_reference_edges(&mut cursor, content, symbols, &names_to_indices, &mut edges);
+
+        for (referencing, referenced) in edges {
+            if referencing == referenced {
+                continue;
+            }
+            if !symbols[referencing].references.contains(&referenced) {
+                symbols[referencing].references.push(referenced);
+            }
+        }
+    }
+
+    /// Innermost symbol whose byte range contains `byte_pos`, i.e. the smallest range that still
+    /// contains the position -- this attributes a reference found inside a method to the method
+    /// itself rather than its enclosing `impl` block.
+    fn innermost_symbol_at(symbols: &[CodeSymbol], byte_pos: usize) -> Option<usize> {
+        symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| symbol.start_byte <= byte_pos && byte_pos < symbol.end_byte)
+            .min_by_key(|(_, symbol)| symbol.end_byte - symbol.start_byte)
+            .map(|(index, _)| index)
+    }
+
+    fn collect_reference_edges(
+        cursor: &mut TreeCursor,
+        content: &str,
+        symbols: &[CodeSymbol],
+        names_to_indices: &std::collections::HashMap<&str, Vec<usize>>,
+        edges: &mut Vec<(usize, usize)>,
+    ) {
+        let node = cursor.node();
+
+        if Self::is_reference_node(node.kind()) {
+            let text = &content[node.byte_range()];
+            if let Some(referenced_indices) = names_to_indices.get(text)
+                && let Some(referencing) = Self::innermost_symbol_at(symbols, node.start_byte())
+            {
+                for &referenced in referenced_indices {
+                    edges.push((referencing, referenced));
+                }
+            }
+        }
+
+        if cursor.goto_first_child() {
+            loop {
+                Self::collect_reference_edges(cursor, content, symbols, names_to_indices, edges);
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+            cursor.goto_parent();
+        }
+    }
+
     fn is_symbol_node(kind: &str, _lang: &str) -> bool {
         matches!(
             kind,
@@ -348,3 +434,68 @@ impl CodeAnalyzer {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tree_sitter::Parser;
+
+    use super::*;
+
+    fn parse_rust(content: &str) -> Tree {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::LANGUAGE.into()).unwrap();
+        parser.parse(content, None).unwrap()
+    }
+
+    #[test]
+    fn records_call_site_as_a_reference() {
+        let content = r#"
+fn helper() -> u32 {
+    42
+}
+
+fn main() {
+    helper();
+}
+"#;
+        let tree = parse_rust(content);
+        let analysis = CodeAnalyzer::analyze_code_structure(content, "rust", &tree);
+
+        let main_index = analysis.symbols.iter().position(|s| s.name == "main").unwrap();
+        let helper_index = analysis.symbols.iter().position(|s| s.name == "helper").unwrap();
+
+        assert!(analysis.symbols[main_index].references.contains(&helper_index));
+    }
+
+    #[test]
+    fn does_not_self_reference_a_symbols_own_definition() {
+        let content = "fn lonely() {}";
+        let tree = parse_rust(content);
+        let analysis = CodeAnalyzer::analyze_code_structure(content, "rust", &tree);
+
+        let lonely_index = analysis.symbols.iter().position(|s| s.name == "lonely").unwrap();
+
+        assert_eq!(analysis.symbols[lonely_index].references, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn attributes_reference_to_the_innermost_enclosing_symbol() {
+        let content = r#"
+struct Thing;
+
+impl Thing {
+    fn make() -> Thing {
+        Thing
+    }
+}
+"#;
+        let tree = parse_rust(content);
+        let analysis = CodeAnalyzer::analyze_code_structure(content, "rust", &tree);
+
+        let make_index = analysis.symbols.iter().position(|s| s.name == "make").unwrap();
+        let thing_index = analysis.symbols.iter().position(|s| s.name == "Thing").unwrap();
+
+        assert!(analysis.symbols[make_index].references.contains(&thing_index));
+    }
+}
@@ -0,0 +1,141 @@
+//! MinHash + LSH near-duplicate detection, approximating `ChunkerUtils::jaccard_similarity` at
+//! O(n) bucket-scan scale instead of its native O(n^2) all-pairs comparison.
+//!
+//! Each token set is hashed into a length-`k` MinHash signature (the per-seed minimum hash over
+//! its tokens), then banded into `b` bands of `r` rows each (`b * r == k`) -- two sets whose
+//! signatures collide in any band become *candidates*. Only candidates pay for an exact
+//! `jaccard_similarity` check, and only pairs above a threshold are merged into a cluster via
+//! union-find. `k`/`b`/`r` trade recall for speed: more bands (smaller `r` per band) surfaces more
+//! true duplicates as candidates but also more false ones for the exact check to reject, while
+//! fewer, larger bands does the opposite.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use crate::chunker::utils::ChunkerUtils;
+
+/// Tuning knobs for `find_near_duplicate_clusters`. `b * r` must equal `k`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MinHashLshConfig {
+    /// Number of independent hash functions, i.e. the MinHash signature length.
+    pub k: usize,
+    /// Number of LSH bands; two sets become candidates once they match on every row within at
+    /// least one band.
+    pub b: usize,
+    /// Rows (hash slots) per band.
+    pub r: usize,
+}
+
+impl Default for MinHashLshConfig {
+    /// `k=64` signature hashes banded into `b=8` bands of `r=8` rows, which approximates an LSH
+    /// collision-probability threshold around 0.77 -- deliberately looser than the `0.85` exact
+    /// cluster threshold `find_near_duplicate_clusters` is typically called with, since LSH only
+    /// needs to be permissive enough to surface candidates; the exact `jaccard_similarity` check
+    /// is what actually decides cluster membership.
+    fn default() -> Self {
+        Self { k: 64, b: 8, r: 8 }
+    }
+}
+
+/// One independent hash of `token`, seeded by `seed` so that `k` calls with different seeds behave
+/// like `k` independent hash functions over the same input.
+fn seeded_hash(seed: u64, token: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    seed.hash(&mut hasher);
+    token.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A length-`config.k` MinHash signature for `tokens`: slot `i` is the minimum of hash function
+/// `i` over every token in the set, so two sets sharing more tokens are more likely to agree on
+/// any given slot.
+fn minhash_signature(tokens: &HashSet<String>, config: &MinHashLshConfig) -> Vec<u64> {
+    (0..config.k as u64).map(|seed| tokens.iter().map(|t| seeded_hash(seed, t)).min().unwrap_or(u64::MAX)).collect()
+}
+
+/// Bucket signatures by band and return every pair of indices that collided in at least one band.
+fn candidate_pairs(signatures: &[Vec<u64>], config: &MinHashLshConfig) -> HashSet<(usize, usize)> {
+    let mut buckets: HashMap<(usize, Vec<u64>), Vec<usize>> = HashMap::new();
+    for (idx, sig) in signatures.iter().enumerate() {
+        for band in 0..config.b {
+            let rows = sig[band * config.r..(band + 1) * config.r].to_vec();
+            buckets.entry((band, rows)).or_default().push(idx);
+        }
+    }
+
+    let mut pairs = HashSet::new();
+    for members in buckets.into_values() {
+        if members.len() < 2 {
+            continue;
+        }
+        for i in 0..members.len() {
+            for &j in &members[i + 1..] {
+                pairs.insert((members[i].min(j), members[i].max(j)));
+            }
+        }
+    }
+    pairs
+}
+
+/// Union-find over `0..n`, used to merge candidate pairs confirmed above threshold into clusters.
+struct UnionFind {
+    parent: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(n: usize) -> Self {
+        Self { parent: (0..n).collect() }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a != root_b {
+            self.parent[root_a] = root_b;
+        }
+    }
+}
+
+/// Find clusters of near-duplicate token sets: MinHash + banded LSH narrows all-pairs comparison
+/// down to candidates colliding in some band, then only those candidates pay for an exact
+/// `ChunkerUtils::jaccard_similarity` check against `threshold`. Returns each cluster as a sorted
+/// list of indices into `token_sets`; sets with no near-duplicate are omitted entirely.
+pub(crate) fn find_near_duplicate_clusters(
+    token_sets: &[HashSet<String>],
+    config: &MinHashLshConfig,
+    threshold: f32,
+) -> Vec<Vec<usize>> {
+    debug_assert_eq!(config.b * config.r, config.k, "b * r must equal k");
+    if token_sets.len() < 2 {
+        return Vec::new();
+    }
+
+    let signatures: Vec<Vec<u64>> = token_sets.iter().map(|tokens| minhash_signature(tokens, config)).collect();
+    let candidates = candidate_pairs(&signatures, config);
+
+    let mut union_find = UnionFind::new(token_sets.len());
+    for (i, j) in candidates {
+        if ChunkerUtils::jaccard_similarity(&token_sets[i], &token_sets[j]) >= threshold {
+            union_find.union(i, j);
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for idx in 0..token_sets.len() {
+        let root = union_find.find(idx);
+        clusters.entry(root).or_default().push(idx);
+    }
+
+    let mut result: Vec<Vec<usize>> = clusters.into_values().filter(|members| members.len() > 1).collect();
+    for members in &mut result {
+        members.sort_unstable();
+    }
+    result.sort_by_key(|members| members[0]);
+    result
+}
@@ -0,0 +1,148 @@
+//! Pluggable language support for [`crate::chunker::Chunker`]: which tree-sitter grammars are
+//! available, and the outline query (see `outline.rs`) each one should use, instead of the fixed
+//! `match lang` the chunking entry points used to hardcode. Pre-populated with the crate's
+//! built-in grammars, but extensible at runtime via `register_language` so a caller can index a
+//! language this crate doesn't ship a grammar for without recompiling it. A small alias table
+//! lets closely related file extensions (`tsx`, `mjs`, `c++`, `h`) resolve to an already-registered
+//! grammar instead of needing their own entry.
+
+use std::collections::HashMap;
+
+use tree_sitter::Language;
+
+/// One registered language: its tree-sitter grammar, and (optionally) an outline query for it.
+/// Built-in languages leave this `None` since `outline.rs` already has its own dispatch for them;
+/// a caller registering a custom grammar can supply one here for forward compatibility with
+/// outline-aware chunking strategies.
+#[derive(Clone)]
+pub(crate) struct LanguageEntry {
+    pub grammar: Language,
+    pub outline_query: Option<String>,
+}
+
+pub(crate) struct LanguageRegistry {
+    languages: HashMap<String, LanguageEntry>,
+    aliases: HashMap<String, String>,
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageRegistry {
+    /// A registry pre-populated with the crate's built-in grammars and the standard file
+    /// extension aliases.
+    pub fn new() -> Self {
+        let mut registry = Self { languages: HashMap::new(), aliases: HashMap::new() };
+
+        let builtins: &[(&str, Language)] = &[
+            ("rust", tree_sitter_rust::LANGUAGE.into()),
+            ("python", tree_sitter_python::LANGUAGE.into()),
+            ("javascript", tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            ("typescript", tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+            ("go", tree_sitter_go::LANGUAGE.into()),
+            ("java", tree_sitter_java::LANGUAGE.into()),
+            ("cpp", tree_sitter_cpp::LANGUAGE.into()),
+            ("c", tree_sitter_cpp::LANGUAGE.into()),
+            ("css", tree_sitter_css::LANGUAGE.into()),
+            ("ruby", tree_sitter_ruby::LANGUAGE.into()),
+        ];
+        for (lang, grammar) in builtins {
+            registry.languages.insert((*lang).to_string(), LanguageEntry { grammar: grammar.clone(), outline_query: None });
+        }
+
+        for (alias, canonical) in [("tsx", "typescript"), ("mjs", "javascript"), ("c++", "cpp"), ("h", "c")] {
+            registry.aliases.insert(alias.to_string(), canonical.to_string());
+        }
+
+        registry
+    }
+
+    /// Register (or override) a language's grammar and outline query at runtime.
+    pub fn register_language(
+        &mut self,
+        lang: impl Into<String>,
+        grammar: Language,
+        outline_query: Option<String>,
+    ) {
+        self.languages.insert(lang.into(), LanguageEntry { grammar, outline_query });
+    }
+
+    /// Register an alias that resolves to an already-registered canonical language id, e.g.
+    /// `registry.register_alias("jsx", "javascript")`.
+    pub fn register_alias(&mut self, alias: impl Into<String>, canonical: impl Into<String>) {
+        self.aliases.insert(alias.into(), canonical.into());
+    }
+
+    /// The canonical language id `lang` resolves to, following the alias table once; `lang`
+    /// itself if it isn't an alias.
+    pub fn resolve<'a>(&'a self, lang: &'a str) -> &'a str {
+        self.aliases.get(lang).map(String::as_str).unwrap_or(lang)
+    }
+
+    /// This language's grammar, if registered, after alias resolution.
+    pub fn grammar(&self, lang: &str) -> Option<Language> {
+        self.languages.get(self.resolve(lang)).map(|entry| entry.grammar.clone())
+    }
+
+    /// This language's outline query, if registered and it has one, after alias resolution.
+    pub fn outline_query(&self, lang: &str) -> Option<&str> {
+        self.languages.get(self.resolve(lang))?.outline_query.as_deref()
+    }
+
+    /// Whether `lang` (after alias resolution) has a registered grammar.
+    pub fn is_registered(&self, lang: &str) -> bool {
+        self.languages.contains_key(self.resolve(lang))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_language_is_registered() {
+        let registry = LanguageRegistry::new();
+        assert!(registry.is_registered("rust"));
+        assert!(registry.grammar("rust").is_some());
+    }
+
+    #[test]
+    fn unregistered_language_has_no_grammar() {
+        let registry = LanguageRegistry::new();
+        assert!(!registry.is_registered("cobol"));
+        assert!(registry.grammar("cobol").is_none());
+    }
+
+    #[test]
+    fn aliases_resolve_to_their_canonical_language() {
+        let registry = LanguageRegistry::new();
+        assert_eq!(registry.resolve("tsx"), "typescript");
+        assert_eq!(registry.resolve("mjs"), "javascript");
+        assert_eq!(registry.resolve("c++"), "cpp");
+        assert_eq!(registry.resolve("h"), "c");
+        assert!(registry.is_registered("tsx"));
+        assert!(registry.is_registered("h"));
+    }
+
+    #[test]
+    fn register_language_makes_a_custom_grammar_available() {
+        let mut registry = LanguageRegistry::new();
+        assert!(!registry.is_registered("zig"));
+
+        registry.register_language("zig", tree_sitter_rust::LANGUAGE.into(), Some("(function_item) @item".to_string()));
+
+        assert!(registry.is_registered("zig"));
+        assert_eq!(registry.outline_query("zig"), Some("(function_item) @item"));
+    }
+
+    #[test]
+    fn register_alias_points_an_unregistered_id_at_an_existing_language() {
+        let mut registry = LanguageRegistry::new();
+        registry.register_alias("jsx", "javascript");
+        assert!(registry.is_registered("jsx"));
+        assert_eq!(registry.resolve("jsx"), "javascript");
+    }
+}
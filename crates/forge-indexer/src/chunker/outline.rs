@@ -0,0 +1,172 @@
+//! Outline-query-driven chunk boundaries: a byte-range-and-depth skeleton of a file's definitions
+//! (functions, types, impls, ...), used by `ChunkingStrategies::extract_outline_chunks` to split
+//! large files between sibling items instead of mid-body by raw line count.
+
+use tree_sitter::{Query, QueryCursor, Tree};
+
+use crate::chunker::analysis::CodeAnalyzer;
+
+/// One definition captured by `outline_items`: its byte range, its nesting depth among the other
+/// captured items (0 = top-level), and its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct OutlineItem {
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub depth: usize,
+    pub name: String,
+}
+
+/// Run `lang`'s outline query against an already-parsed `tree`, returning items sorted by start
+/// byte with `depth` filled in from their nesting among each other. `None` when no grammar/query
+/// is registered for `lang`.
+pub(crate) fn outline_items(content: &str, lang: &str, tree: &Tree) -> Option<Vec<OutlineItem>> {
+    let source = query_source(lang)?;
+    let language = language_for(lang)?;
+    let query = Query::new(&language, source).ok()?;
+
+    let mut raw: Vec<(usize, usize, String)> = Vec::new();
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&query, tree.root_node(), content.as_bytes()) {
+        for capture in m.captures {
+            let node = capture.node;
+            let name = CodeAnalyzer::extract_symbol_name(node, content, lang)
+                .unwrap_or_else(|| node.kind().to_string());
+            raw.push((node.start_byte(), node.end_byte(), name));
+        }
+    }
+
+    Some(with_depth(raw))
+}
+
+/// Names of every item in `items` whose range contains `byte_offset`, outermost first -- the
+/// "mod foo > impl Bar > fn baz" chain a split sub-chunk records as its summary.
+pub(crate) fn enclosing_chain(items: &[OutlineItem], byte_offset: usize) -> Vec<String> {
+    let mut chain: Vec<&OutlineItem> =
+        items.iter().filter(|it| it.start_byte <= byte_offset && byte_offset < it.end_byte).collect();
+    chain.sort_by_key(|it| it.depth);
+    chain.into_iter().map(|it| it.name.clone()).collect()
+}
+
+/// Assign nesting depth to a (possibly unsorted) list of `(start, end, name)` byte ranges by
+/// containment: depth 0 for an item with no enclosing item in the list, depth N+1 for one nested
+/// inside a depth-N item. Ties at the same start are ordered widest-first so an enclosing item is
+/// always processed (and pushed onto the open-ancestor stack) before the items nested inside it.
+fn with_depth(mut raw: Vec<(usize, usize, String)>) -> Vec<OutlineItem> {
+    raw.sort_by_key(|&(start, end, _)| (start, std::cmp::Reverse(end)));
+
+    let mut stack: Vec<usize> = Vec::new();
+    let mut items = Vec::with_capacity(raw.len());
+    for (start, end, name) in raw {
+        while let Some(&open_end) = stack.last() {
+            if open_end <= start {
+                stack.pop();
+            } else {
+                break;
+            }
+        }
+        items.push(OutlineItem { start_byte: start, end_byte: end, depth: stack.len(), name });
+        stack.push(end);
+    }
+    items
+}
+
+/// Tree-sitter `Language` for `lang`, mirroring `ParserManager::create_parser`'s dispatch.
+fn language_for(lang: &str) -> Option<tree_sitter::Language> {
+    match lang {
+        "rust" => Some(tree_sitter_rust::LANGUAGE.into()),
+        "python" => Some(tree_sitter_python::LANGUAGE.into()),
+        "javascript" | "typescript" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        "go" => Some(tree_sitter_go::LANGUAGE.into()),
+        "java" => Some(tree_sitter_java::LANGUAGE.into()),
+        "cpp" | "c" => Some(tree_sitter_cpp::LANGUAGE.into()),
+        _ => None,
+    }
+}
+
+/// Outline queries capturing whole definition nodes (not just their name, as `symbols.rs`'s tag
+/// queries do) so `with_depth` can derive nesting from byte-range containment.
+fn query_source(lang: &str) -> Option<&'static str> {
+    match lang {
+        "rust" => Some(
+            r#"
+            (function_item) @item
+            (struct_item) @item
+            (enum_item) @item
+            (trait_item) @item
+            (impl_item) @item
+            (mod_item) @item
+            "#,
+        ),
+        "python" => Some(
+            r#"
+            (function_definition) @item
+            (class_definition) @item
+            "#,
+        ),
+        "javascript" | "typescript" => Some(
+            r#"
+            (function_declaration) @item
+            (class_declaration) @item
+            (method_definition) @item
+            "#,
+        ),
+        "go" => Some(
+            r#"
+            (function_declaration) @item
+            (method_declaration) @item
+            (type_declaration) @item
+            "#,
+        ),
+        "java" => Some(
+            r#"
+            (method_declaration) @item
+            (class_declaration) @item
+            (interface_declaration) @item
+            "#,
+        ),
+        "cpp" | "c" => Some(
+            r#"
+            (function_definition) @item
+            (struct_specifier) @item
+            (class_specifier) @item
+            "#,
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn item(start: usize, end: usize, name: &str) -> (usize, usize, String) {
+        (start, end, name.to_string())
+    }
+
+    #[test]
+    fn sibling_items_are_both_depth_zero() {
+        let items = with_depth(vec![item(0, 10, "foo"), item(10, 20, "bar")]);
+        assert_eq!(items.iter().map(|i| i.depth).collect::<Vec<_>>(), vec![0, 0]);
+    }
+
+    #[test]
+    fn nested_item_is_one_level_deeper_than_its_enclosing_item() {
+        let items = with_depth(vec![item(0, 100, "impl Foo"), item(10, 30, "fn bar")]);
+        assert_eq!(items[0].depth, 0);
+        assert_eq!(items[1].depth, 1);
+    }
+
+    #[test]
+    fn enclosing_chain_lists_outermost_first() {
+        let items = with_depth(vec![item(0, 100, "impl Foo"), item(10, 30, "fn bar")]);
+        assert_eq!(enclosing_chain(&items, 15), vec!["impl Foo".to_string(), "fn bar".to_string()]);
+    }
+
+    #[test]
+    fn enclosing_chain_is_empty_outside_every_item() {
+        let items = with_depth(vec![item(0, 10, "foo")]);
+        assert!(enclosing_chain(&items, 50).is_empty());
+    }
+}
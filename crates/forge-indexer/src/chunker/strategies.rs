@@ -3,18 +3,69 @@
 use tree_sitter::{Tree, TreeCursor};
 
 use crate::chunker::analysis::CodeAnalyzer;
+use crate::chunker::outline::{self, OutlineItem};
+use crate::chunker::token_budget::pack_spans_by_token_budget;
 use crate::chunker::types::{Chunk, CodeAnalysis};
+use crate::chunker::utils::ChunkerUtils;
+
+/// Default number of lines of overlap `subdivide_large_chunk` carries over between consecutive
+/// sub-chunks, so a statement straddling a window boundary still appears whole in at least one
+/// fragment.
+pub(crate) const DEFAULT_SUBDIVISION_OVERLAP_LINES: usize = 10;
 
 pub(crate) struct ChunkingStrategies;
 
 impl ChunkingStrategies {
+    /// Pack `analysis.semantic_boundaries`-delimited spans into chunks whose estimated token
+    /// count stays under `max_tokens`, splitting an oversized span at the line break nearest its
+    /// midpoint (a span with no line break is emitted whole). This is the token-aware counterpart to
+    /// `extract_semantic_chunks`'s fixed byte-size subdivision.
+    pub fn extract_token_budgeted_chunks(
+        path: &str,
+        content: &str,
+        lang: &str,
+        rev: &str,
+        analysis: &CodeAnalysis,
+        max_tokens: usize,
+    ) -> Vec<Chunk> {
+        let spans = pack_spans_by_token_budget(content, &analysis.semantic_boundaries, max_tokens);
+
+        spans
+            .into_iter()
+            .filter(|&(start, end)| end > start)
+            .map(|(start, end)| {
+                let chunk_content = &content[start..end];
+                let symbol = analysis
+                    .symbols
+                    .iter()
+                    .find(|s| s.start_byte >= start && s.end_byte <= end)
+                    .map(|s| s.name.clone());
+
+                Chunk {
+                    id: format!("{path}:tb:{start}:{end}"),
+                    path: path.to_string(),
+                    lang: lang.to_string(),
+                    symbol,
+                    rev: rev.to_string(),
+                    size: chunk_content.len(),
+                    code: chunk_content.to_string(),
+                    summary: None,
+                    start_byte: start,
+                    end_byte: end,
+                    start_line: ChunkerUtils::line_for_byte(content, start),
+                    end_line: ChunkerUtils::line_for_byte(content, end),
+                }
+            })
+            .collect()
+    }
+
     /// Extract semantic chunks based on code structure
     pub fn extract_semantic_chunks(
         path: &str,
         content: &str,
         lang: &str,
         rev: &str,
-        analysis: &CodeAnalysis,
+        _analysis: &CodeAnalysis,
         tree: &Tree,
     ) -> Vec<Chunk> {
         let mut chunks = Vec::new();
@@ -27,7 +78,12 @@ impl ChunkingStrategies {
         let mut refined_chunks = Vec::new();
         for chunk in chunks {
             if chunk.size > 2000 {
-                refined_chunks.extend(Self::subdivide_large_chunk(&chunk, analysis, tree));
+                refined_chunks.extend(Self::subdivide_large_chunk(
+                    &chunk,
+                    content,
+                    tree,
+                    DEFAULT_SUBDIVISION_OVERLAP_LINES,
+                ));
             } else {
                 refined_chunks.push(chunk);
             }
@@ -36,6 +92,114 @@ impl ChunkingStrategies {
         refined_chunks
     }
 
+    /// Split `content` between sibling outline items (functions/structs/.../methods) rather than
+    /// `extract_semantic_chunks`'s blind line-count subdivision, so a split never lands mid-body
+    /// of a deeply nested item when a shallower split point is available nearby. Each sub-chunk's
+    /// `summary` records the chain of enclosing item names (e.g. `mod foo > impl Bar > fn baz`) so
+    /// it still carries structural context after being split out on its own. Falls back to
+    /// `fallback_chunking` when no outline query is registered for `lang` or the file has no
+    /// recognized items.
+    pub fn extract_outline_chunks(
+        path: &str,
+        content: &str,
+        lang: &str,
+        rev: &str,
+        tree: &Tree,
+        max_chunk_size: usize,
+    ) -> Vec<Chunk> {
+        let Some(items) = outline::outline_items(content, lang, tree) else {
+            return Self::fallback_chunking(path, content, lang, rev, Some(tree), None);
+        };
+        if items.is_empty() {
+            return Self::fallback_chunking(path, content, lang, rev, Some(tree), None);
+        }
+
+        let splits = Self::choose_split_points(content, &items, max_chunk_size);
+
+        let mut chunks = Vec::new();
+        let mut start = 0usize;
+        for end in splits {
+            if end <= start {
+                continue;
+            }
+
+            let chunk_content = &content[start..end];
+            let chain = outline::enclosing_chain(&items, start);
+
+            chunks.push(Chunk {
+                id: format!("{path}:outline:{start}:{end}"),
+                path: path.to_string(),
+                lang: lang.to_string(),
+                symbol: chain.last().cloned(),
+                rev: rev.to_string(),
+                size: chunk_content.len(),
+                code: chunk_content.to_string(),
+                summary: if chain.is_empty() { None } else { Some(chain.join(" > ")) },
+                start_byte: start,
+                end_byte: end,
+                start_line: ChunkerUtils::line_for_byte(content, start),
+                end_line: ChunkerUtils::line_for_byte(content, end),
+            });
+
+            start = end;
+        }
+
+        chunks
+    }
+
+    /// Greedily grow a chunk from the current offset; once the next candidate boundary would
+    /// exceed `max_chunk_size`, split at the best candidate seen so far, where "best" means
+    /// closing the fewest open outline scopes (prefer a break between two top-level siblings over
+    /// one mid-body of a nested function) and, among ties, the one closest to the budget.
+    /// Candidates are every item's end byte rounded up to the next line start, plus the end of
+    /// the file, so every boundary falls on a line start and a split never cuts a token. When a
+    /// single item alone exceeds the budget with no candidate inside it, the next candidate past
+    /// the budget is used rather than cutting arbitrarily.
+    fn choose_split_points(content: &str, items: &[OutlineItem], max_chunk_size: usize) -> Vec<usize> {
+        let mut candidates: Vec<(usize, usize)> = items
+            .iter()
+            .map(|item| {
+                let pos = Self::round_up_to_line_start(content, item.end_byte);
+                let open_scopes =
+                    items.iter().filter(|other| other.start_byte < pos && pos < other.end_byte).count();
+                (pos, open_scopes)
+            })
+            .collect();
+        candidates.push((content.len(), 0));
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut splits = Vec::new();
+        let mut chunk_start = 0usize;
+        while chunk_start < content.len() {
+            let budget_end = chunk_start + max_chunk_size;
+            let chosen = candidates
+                .iter()
+                .filter(|&&(pos, _)| pos > chunk_start && pos <= budget_end)
+                .min_by_key(|&&(pos, cost)| (cost, std::cmp::Reverse(pos)))
+                .map(|&(pos, _)| pos)
+                .or_else(|| candidates.iter().map(|&(pos, _)| pos).find(|&pos| pos > chunk_start));
+
+            let Some(split_at) = chosen else { break };
+            splits.push(split_at);
+            chunk_start = split_at;
+        }
+
+        splits
+    }
+
+    /// Round `byte_offset` up to the start of the next line, so a chunk boundary never lands
+    /// mid-line (and so never mid-token).
+    fn round_up_to_line_start(content: &str, byte_offset: usize) -> usize {
+        if byte_offset >= content.len() {
+            return content.len();
+        }
+        match content[byte_offset..].find('\n') {
+            Some(rel) => byte_offset + rel + 1,
+            None => content.len(),
+        }
+    }
+
     /// Extract context-aware chunks using comprehensive semantic analysis
     pub fn extract_context_chunks(
         path: &str,
@@ -49,7 +213,7 @@ impl ChunkingStrategies {
 
         // If no semantic analysis available, fallback to basic chunking
         if analysis.symbols.is_empty() && analysis.semantic_boundaries.is_empty() {
-            return Self::fallback_chunking(path, content, lang, rev, Some(tree));
+            return Self::fallback_chunking(path, content, lang, rev, Some(tree), None);
         }
 
         // Strategy 1: Symbol-based chunking for high-importance symbols
@@ -100,6 +264,10 @@ impl ChunkingStrategies {
                         size: end - start,
                         code: chunk_content.to_string(),
                         summary: Some(summary_parts.join(" | ")),
+                        start_byte: start,
+                        end_byte: end,
+                        start_line: ChunkerUtils::line_for_byte(content, start),
+                        end_line: ChunkerUtils::line_for_byte(content, end),
                     });
                 }
             }
@@ -182,6 +350,10 @@ impl ChunkingStrategies {
                         } else {
                             Some(summary_parts.join(" | "))
                         },
+                        start_byte: start,
+                        end_byte: boundary,
+                        start_line: ChunkerUtils::line_for_byte(content, start),
+                        end_line: ChunkerUtils::line_for_byte(content, boundary),
                     });
 
                     start = boundary;
@@ -222,6 +394,10 @@ impl ChunkingStrategies {
                         size: content.len() - start,
                         code: chunk_content.to_string(),
                         summary: Some(summary_parts.join(" | ")),
+                        start_byte: start,
+                        end_byte: content.len(),
+                        start_line: ChunkerUtils::line_for_byte(content, start),
+                        end_line: ChunkerUtils::line_for_byte(content, content.len()),
                     });
                 }
             }
@@ -229,7 +405,7 @@ impl ChunkingStrategies {
 
         // If no chunks were created, fallback to basic chunking
         if chunks.is_empty() {
-            return Self::fallback_chunking(path, content, lang, rev, Some(tree));
+            return Self::fallback_chunking(path, content, lang, rev, Some(tree), None);
         }
 
         // Sort chunks by start position for consistent ordering
@@ -245,17 +421,31 @@ impl ChunkingStrategies {
         chunks
     }
 
-    /// Fallback chunking for unsupported languages
+    /// Fallback chunking for unsupported languages. `max_chunk_size` bounds each chunk's byte
+    /// size (see `fallback_chunking_bounded`) rather than the fixed 50-line window
+    /// `fallback_chunking_by_lines` uses; pass `None` to keep the fixed-window behavior the
+    /// tree-sitter-aware strategies above fall back to.
     pub fn fallback_chunking(
         path: &str,
         content: &str,
         lang: &str,
         rev: &str,
         _tree: Option<&Tree>,
+        max_chunk_size: Option<usize>,
     ) -> Vec<Chunk> {
+        match max_chunk_size {
+            Some(max_bytes) => Self::fallback_chunking_bounded(path, content, lang, rev, max_bytes),
+            None => Self::fallback_chunking_by_lines(path, content, lang, rev),
+        }
+    }
+
+    /// The original fixed-window fallback: 50-line windows, preferring to break at a blank or
+    /// comment line in the window's last 20 lines.
+    fn fallback_chunking_by_lines(path: &str, content: &str, lang: &str, rev: &str) -> Vec<Chunk> {
         let mut chunks = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
         let mut start_line = 0;
+        let mut start_byte = 0;
 
         while start_line < lines.len() {
             let mut end_line = (start_line + 50).min(lines.len());
@@ -275,6 +465,9 @@ impl ChunkingStrategies {
 
             let chunk_lines = &lines[start_line..end_line];
             let chunk_content = chunk_lines.join("\n");
+            // +1 newline per line except after the file's very last line
+            let end_byte = (start_byte + chunk_content.len() + usize::from(end_line < lines.len()))
+                .min(content.len());
 
             chunks.push(Chunk {
                 id: format!("{path}:{start_line}:{end_line}"),
@@ -285,8 +478,84 @@ impl ChunkingStrategies {
                 size: chunk_content.len(),
                 code: chunk_content,
                 summary: None,
+                start_byte,
+                end_byte,
+                start_line: start_line + 1,
+                end_line,
             });
 
+            start_byte = end_byte;
+            start_line = end_line;
+        }
+
+        chunks
+    }
+
+    /// Window splitting bounded by `max_bytes` rather than a fixed line count: grows a window
+    /// line-by-line until adding the next line would exceed `max_bytes` (a single oversized line
+    /// is still accepted whole rather than split mid-line), preferring to break at a blank or
+    /// comment line in roughly the window's last third, same heuristic as
+    /// `fallback_chunking_by_lines`.
+    fn fallback_chunking_bounded(
+        path: &str,
+        content: &str,
+        lang: &str,
+        rev: &str,
+        max_bytes: usize,
+    ) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+        let lines: Vec<&str> = content.lines().collect();
+        let mut start_line = 0;
+        let mut start_byte = 0;
+
+        while start_line < lines.len() {
+            let mut end_line = start_line + 1;
+            let mut size = lines[start_line].len();
+            while end_line < lines.len() {
+                let next_size = size + 1 + lines[end_line].len();
+                if next_size > max_bytes {
+                    break;
+                }
+                size = next_size;
+                end_line += 1;
+            }
+
+            // Try to break at natural boundaries (empty lines, comments) in the window's last third
+            if end_line < lines.len() && end_line > start_line + 1 {
+                let search_from = start_line + (end_line - start_line) * 2 / 3;
+                for i in (search_from..end_line).rev() {
+                    if lines[i].trim().is_empty()
+                        || lines[i].trim_start().starts_with("//")
+                        || lines[i].trim_start().starts_with('#')
+                    {
+                        end_line = i + 1;
+                        break;
+                    }
+                }
+            }
+
+            let chunk_lines = &lines[start_line..end_line];
+            let chunk_content = chunk_lines.join("\n");
+            // +1 newline per line except after the file's very last line
+            let end_byte = (start_byte + chunk_content.len() + usize::from(end_line < lines.len()))
+                .min(content.len());
+
+            chunks.push(Chunk {
+                id: format!("{path}:{start_line}:{end_line}"),
+                path: path.to_string(),
+                lang: lang.to_string(),
+                symbol: None,
+                rev: rev.to_string(),
+                size: chunk_content.len(),
+                code: chunk_content,
+                summary: None,
+                start_byte,
+                end_byte,
+                start_line: start_line + 1,
+                end_line,
+            });
+
+            start_byte = end_byte;
             start_line = end_line;
         }
 
@@ -316,6 +585,10 @@ impl ChunkingStrategies {
                 size: chunk_content.len(),
                 code: chunk_content.to_string(),
                 summary: None,
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                start_line: ChunkerUtils::line_for_byte(content, node.start_byte()),
+                end_line: ChunkerUtils::line_for_byte(content, node.end_byte()),
             });
         }
 
@@ -331,30 +604,97 @@ impl ChunkingStrategies {
         }
     }
 
-    fn subdivide_large_chunk(chunk: &Chunk, _analysis: &CodeAnalysis, _tree: &Tree) -> Vec<Chunk> {
-        // For now, simple subdivision by lines
-        // TODO: Implement more sophisticated subdivision based on semantic analysis
+    /// Split an oversized definition chunk into fixed-size line windows, each one prefixed with
+    /// the enclosing definition's signature line(s) (extracted from `tree`, e.g. `fn foo(...) {`
+    /// or `impl Block {`) so a fragment pulled from the middle of a long function still reads as
+    /// self-describing, and overlapping the last `overlap_lines` of each window with the start of
+    /// the next so a statement straddling a window boundary appears whole in at least one
+    /// fragment. The very first window already begins with the signature as part of its own body,
+    /// so it isn't prefixed a second time. `size` is always the returned `code`'s own length, so
+    /// the re-attached header is counted once, not separately on top of it.
+    fn subdivide_large_chunk(chunk: &Chunk, content: &str, tree: &Tree, overlap_lines: usize) -> Vec<Chunk> {
+        const WINDOW_LINES: usize = 50;
+
+        let header = Self::enclosing_signature(chunk, content, tree);
+
         let lines: Vec<&str> = chunk.code.lines().collect();
+        let mut line_start_byte = Vec::with_capacity(lines.len() + 1);
+        let mut acc = 0usize;
+        for line in &lines {
+            line_start_byte.push(acc);
+            acc += line.len() + 1; // +1 for the joining newline
+        }
+        line_start_byte.push(acc);
+
         let mut sub_chunks = Vec::new();
-        let chunk_size = 50; // lines per sub-chunk
+        let mut window_start = 0usize;
+        let mut index = 0usize;
+
+        while window_start < lines.len() {
+            let window_end = (window_start + WINDOW_LINES).min(lines.len());
+            let body = lines[window_start..window_end].join("\n");
+
+            let sub_content = match &header {
+                Some(header) if window_start > 0 => format!("{header}\n{body}"),
+                _ => body,
+            };
+
+            let sub_start_byte = chunk.start_byte + line_start_byte[window_start];
+            let sub_end_byte = if window_end == lines.len() {
+                chunk.end_byte
+            } else {
+                chunk.start_byte + line_start_byte[window_end]
+            };
 
-        for (i, lines_chunk) in lines.chunks(chunk_size).enumerate() {
-            let sub_content = lines_chunk.join("\n");
             sub_chunks.push(Chunk {
-                id: format!("{}.{}", chunk.id, i),
+                id: format!("{}.{}", chunk.id, index),
                 path: chunk.path.clone(),
                 lang: chunk.lang.clone(),
                 symbol: chunk.symbol.clone(),
                 rev: chunk.rev.clone(),
                 size: sub_content.len(),
                 code: sub_content,
-                summary: None,
+                summary: if window_start > 0 { header.clone() } else { chunk.summary.clone() },
+                start_byte: sub_start_byte,
+                end_byte: sub_end_byte,
+                start_line: chunk.start_line + window_start,
+                end_line: chunk.start_line + window_end - 1,
             });
+
+            index += 1;
+            if window_end >= lines.len() {
+                break;
+            }
+            window_start = window_end.saturating_sub(overlap_lines).max(window_start + 1);
         }
 
         sub_chunks
     }
 
+    /// The enclosing definition's signature line(s) for `chunk` -- the span from the start of the
+    /// tree-sitter node `chunk` was built from up to (and including) the opening brace of its
+    /// body, e.g. `fn foo(...) {` or `impl Block {`. `None` when no such node can be found (the
+    /// chunk didn't come from `chunk_by_definitions`) or it has no distinct body block.
+    fn enclosing_signature(chunk: &Chunk, content: &str, tree: &Tree) -> Option<String> {
+        let mut node = tree.root_node().descendant_for_byte_range(chunk.start_byte, chunk.start_byte)?;
+        while node.end_byte() < chunk.end_byte {
+            node = node.parent()?;
+        }
+
+        let mut cursor = node.walk();
+        let body = node
+            .children(&mut cursor)
+            .find(|child| child.kind().ends_with("block") || child.kind() == "compound_statement")?;
+
+        let header_end = (body.start_byte() + 1).min(content.len());
+        if header_end <= node.start_byte() {
+            return None;
+        }
+
+        let header = content[node.start_byte()..header_end].trim().to_string();
+        if header.is_empty() { None } else { Some(header) }
+    }
+
     fn is_symbol_node(kind: &str, _lang: &str) -> bool {
         matches!(
             kind,
@@ -374,3 +714,142 @@ impl ChunkingStrategies {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tree_sitter::Parser;
+
+    use super::*;
+
+    fn parse_rust(content: &str) -> Tree {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::LANGUAGE.into()).unwrap();
+        parser.parse(content, None).unwrap()
+    }
+
+    #[test]
+    fn splits_between_siblings_rather_than_mid_function() {
+        let content = r#"fn one() {
+    1
+}
+
+fn two() {
+    2
+}
+"#;
+        let tree = parse_rust(content);
+        // A budget that fits `one` alone but not both functions together forces a split; it
+        // should land on the blank line between them (depth 0), not mid-body of either function.
+        let chunks = ChunkingStrategies::extract_outline_chunks("lib.rs", content, "rust", "rev1", &tree, 20);
+
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].code.contains("fn one"));
+        assert!(!chunks[0].code.contains("fn two"));
+        assert!(chunks[1].code.contains("fn two"));
+    }
+
+    #[test]
+    fn split_between_nested_methods_still_records_the_enclosing_impl() {
+        let content = r#"impl Foo {
+    fn bar() {
+        1
+    }
+
+    fn baz() {
+        2
+    }
+}
+"#;
+        let tree = parse_rust(content);
+        // A budget that forces a split between `bar` and `baz` (but not one that escapes the
+        // enclosing `impl`) should still tag the second chunk as being inside `impl Foo`, even
+        // though it starts between two methods rather than at either one's name.
+        let chunks = ChunkingStrategies::extract_outline_chunks("lib.rs", content, "rust", "rev1", &tree, 30);
+
+        assert!(chunks.len() >= 2);
+        // `impl Foo`'s name is a `type_identifier`, which `extract_symbol_name`'s rust branch
+        // doesn't match, so the chain falls back to the node kind.
+        assert!(chunks[1].summary.as_deref().unwrap_or_default().contains("impl_item"));
+    }
+
+    #[test]
+    fn falls_back_when_language_has_no_outline_query() {
+        let content = "some free-form text with no grammar";
+        let tree = parse_rust("");
+        let chunks =
+            ChunkingStrategies::extract_outline_chunks("notes.txt", content, "plaintext", "rev1", &tree, 1000);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].code, content);
+    }
+
+    fn long_function_chunk(content: &str) -> Chunk {
+        Chunk {
+            id: "lib.rs:0:end".to_string(),
+            path: "lib.rs".to_string(),
+            lang: "rust".to_string(),
+            symbol: Some("long".to_string()),
+            rev: "rev1".to_string(),
+            size: content.len(),
+            code: content.to_string(),
+            summary: None,
+            start_byte: 0,
+            end_byte: content.len(),
+            start_line: 1,
+            end_line: content.lines().count(),
+        }
+    }
+
+    #[test]
+    fn subdivides_with_overlap_and_reattaches_signature() {
+        let mut content = String::from("fn long() -> u32 {\n");
+        for i in 0..68 {
+            content.push_str(&format!("    let v{i} = {i};\n"));
+        }
+        content.push_str("}");
+        // header(1) + 68 body statements + closing brace(1) = 70 lines.
+        assert_eq!(content.lines().count(), 70);
+
+        let tree = parse_rust(&content);
+        let chunk = long_function_chunk(&content);
+
+        let subs = ChunkingStrategies::subdivide_large_chunk(&chunk, &content, &tree, 10);
+
+        assert_eq!(subs.len(), 2);
+
+        // The first window already starts with the signature as its own first line, so it isn't
+        // prefixed a second time.
+        assert_eq!(subs[0].code.matches("fn long() -> u32 {").count(), 1);
+        assert!(subs[0].code.contains("let v0 = 0;"));
+        assert!(subs[0].code.contains("let v48 = 48;")); // last line of window 0 (line index 49)
+        assert!(!subs[0].code.contains("let v49 = 49;"));
+
+        // The second window is reattached with the signature, and overlaps the last 10 lines of
+        // the first window (line indices 40..49) before continuing into new territory.
+        assert!(subs[1].code.starts_with("fn long() -> u32 {\n"));
+        assert!(subs[1].code.contains("let v39 = 39;")); // first overlapping line (index 40)
+        assert!(subs[1].code.contains("let v48 = 48;")); // last overlapping line
+        assert!(subs[1].code.contains("let v67 = 67;")); // new tail content
+        assert_eq!(subs[1].summary.as_deref(), Some("fn long() -> u32 {"));
+
+        // `size` always matches the returned `code`'s own length -- no separate header bookkeeping.
+        assert_eq!(subs[1].size, subs[1].code.len());
+
+        assert_eq!(subs[1].start_line, chunk.start_line + 40);
+        assert_eq!(subs[1].end_line, chunk.start_line + 70 - 1);
+    }
+
+    #[test]
+    fn single_window_chunk_is_returned_unprefixed() {
+        let content = "fn small() -> u32 {\n    1\n}";
+        let tree = parse_rust(content);
+        let chunk = long_function_chunk(content);
+
+        let subs = ChunkingStrategies::subdivide_large_chunk(&chunk, content, &tree, 10);
+
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs[0].code, content);
+        assert_eq!(subs[0].summary, chunk.summary);
+    }
+}
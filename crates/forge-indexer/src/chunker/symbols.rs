@@ -0,0 +1,191 @@
+//! Tree-sitter query-based symbol extraction, classifying identifiers by role (definition vs.
+//! reference) instead of `ChunkerUtils::extract_symbols`'s whitespace-splitting fallback, which
+//! can't tell a struct definition from a string literal that happens to look like an identifier.
+//!
+//! Each supported language gets a tag query modeled on tree-sitter's own `tags.scm` convention:
+//! `@definition.*` captures mark where a symbol is declared (function/method/struct/enum/trait/
+//! class), `@reference.*` captures mark where it's merely used (a call site). Retrieval can use
+//! this to weight chunks that *define* a queried symbol over chunks that just mention it; callers
+//! that only need a flat set (e.g. near-duplicate detection) can still get one via
+//! `SymbolTable::all`.
+
+use std::collections::HashSet;
+
+use tree_sitter::{Query, QueryCursor};
+
+use crate::chunker::parser::ParserManager;
+use crate::chunker::utils::ChunkerUtils;
+
+/// Identifiers extracted from a chunk of code, grouped by role. `extract_symbol_table` falls back
+/// to `ChunkerUtils::extract_symbols` -- with every symbol landing in `references`, since the
+/// whitespace heuristic can't tell a definition from a use -- when no grammar or query is
+/// registered for the language.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SymbolTable {
+    pub defines: HashSet<String>,
+    pub references: HashSet<String>,
+}
+
+impl SymbolTable {
+    /// Every symbol regardless of role, for callers that only care about set overlap.
+    pub fn all(&self) -> HashSet<String> {
+        self.defines.union(&self.references).cloned().collect()
+    }
+}
+
+/// Extract a role-classified `SymbolTable` for `content`. Runs `lang`'s tag query against a fresh
+/// parse when both a grammar and a query are registered for it; otherwise falls back to
+/// `ChunkerUtils::extract_symbols`.
+pub(crate) fn extract_symbol_table(content: &str, lang: &str) -> SymbolTable {
+    if let Some(table) = extract_with_query(content, lang) {
+        return table;
+    }
+
+    SymbolTable {
+        defines: HashSet::new(),
+        references: ChunkerUtils::extract_symbols(content, lang),
+    }
+}
+
+fn extract_with_query(content: &str, lang: &str) -> Option<SymbolTable> {
+    let source = query_source(lang)?;
+    let language = language_for(lang)?;
+    let parser_manager = ParserManager::new();
+    let mut parser = parser_manager.create_parser(lang)?;
+    let tree = parser.parse(content, None)?;
+    let query = Query::new(&language, source).ok()?;
+
+    let mut table = SymbolTable::default();
+    let mut cursor = QueryCursor::new();
+    for m in cursor.matches(&query, tree.root_node(), content.as_bytes()) {
+        for capture in m.captures {
+            let name = query.capture_names()[capture.index as usize];
+            let text = &content[capture.node.byte_range()];
+            if name.starts_with("definition.") {
+                table.defines.insert(text.to_string());
+            } else if name.starts_with("reference.") {
+                table.references.insert(text.to_string());
+            }
+        }
+    }
+
+    Some(table)
+}
+
+/// Tree-sitter `Language` for `lang`, mirroring `ParserManager::create_parser`'s dispatch so query
+/// compilation targets the same grammar a chunk was parsed with.
+fn language_for(lang: &str) -> Option<tree_sitter::Language> {
+    match lang {
+        "rust" => Some(tree_sitter_rust::LANGUAGE.into()),
+        "python" => Some(tree_sitter_python::LANGUAGE.into()),
+        "javascript" | "typescript" => Some(tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()),
+        "go" => Some(tree_sitter_go::LANGUAGE.into()),
+        "java" => Some(tree_sitter_java::LANGUAGE.into()),
+        "cpp" | "c" => Some(tree_sitter_cpp::LANGUAGE.into()),
+        _ => None,
+    }
+}
+
+/// Tag queries in the `tree-sitter-tags` convention: `@definition.<kind>` for declaration sites,
+/// `@reference.<kind>` for uses. Kept to the common definition forms and call expressions rather
+/// than exhaustive -- enough to separate "this chunk defines `foo`" from "this chunk calls `foo`".
+fn query_source(lang: &str) -> Option<&'static str> {
+    match lang {
+        "rust" => Some(
+            r#"
+            (function_item name: (identifier) @definition.function)
+            (struct_item name: (type_identifier) @definition.struct)
+            (enum_item name: (type_identifier) @definition.enum)
+            (trait_item name: (type_identifier) @definition.trait)
+            (mod_item name: (identifier) @definition.module)
+            (call_expression function: (identifier) @reference.call)
+            (call_expression function: (field_expression field: (field_identifier) @reference.call))
+            (macro_invocation macro: (identifier) @reference.call)
+            "#,
+        ),
+        "python" => Some(
+            r#"
+            (function_definition name: (identifier) @definition.function)
+            (class_definition name: (identifier) @definition.class)
+            (call function: (identifier) @reference.call)
+            (call function: (attribute attribute: (identifier) @reference.call))
+            "#,
+        ),
+        "javascript" | "typescript" => Some(
+            r#"
+            (function_declaration name: (identifier) @definition.function)
+            (class_declaration name: (type_identifier) @definition.class)
+            (method_definition name: (property_identifier) @definition.method)
+            (call_expression function: (identifier) @reference.call)
+            (call_expression function: (member_expression property: (property_identifier) @reference.call))
+            "#,
+        ),
+        "go" => Some(
+            r#"
+            (function_declaration name: (identifier) @definition.function)
+            (method_declaration name: (field_identifier) @definition.method)
+            (type_spec name: (type_identifier) @definition.type)
+            (call_expression function: (identifier) @reference.call)
+            (call_expression function: (selector_expression field: (field_identifier) @reference.call))
+            "#,
+        ),
+        "java" => Some(
+            r#"
+            (method_declaration name: (identifier) @definition.method)
+            (class_declaration name: (identifier) @definition.class)
+            (interface_declaration name: (identifier) @definition.interface)
+            (method_invocation name: (identifier) @reference.call)
+            "#,
+        ),
+        "cpp" | "c" => Some(
+            r#"
+            (function_definition declarator: (function_declarator declarator: (identifier) @definition.function))
+            (struct_specifier name: (type_identifier) @definition.struct)
+            (class_specifier name: (type_identifier) @definition.class)
+            (call_expression function: (identifier) @reference.call)
+            "#,
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn classifies_definition_separately_from_call_site() {
+        let content = r#"
+fn helper() -> u32 {
+    42
+}
+
+fn main() {
+    helper();
+}
+"#;
+        let table = extract_symbol_table(content, "rust");
+        assert!(table.defines.contains("helper"));
+        assert!(table.defines.contains("main"));
+        assert!(table.references.contains("helper"));
+        assert!(!table.references.contains("main"));
+    }
+
+    #[test]
+    fn falls_back_to_whitespace_heuristic_for_unregistered_language() {
+        let table = extract_symbol_table("let fooBar = some_value;", "cobol");
+        assert!(table.defines.is_empty());
+        assert_eq!(table.references, ChunkerUtils::extract_symbols("let fooBar = some_value;", "cobol"));
+    }
+
+    #[test]
+    fn all_merges_both_roles() {
+        let content = "fn one() { two(); }\nfn two() {}";
+        let table = extract_symbol_table(content, "rust");
+        let all = table.all();
+        assert!(all.contains("one"));
+        assert!(all.contains("two"));
+    }
+}
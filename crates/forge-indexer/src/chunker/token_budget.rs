@@ -0,0 +1,135 @@
+//! Token-budget aware packing of semantic-boundary-delimited spans
+
+/// Rough token estimate used where no provider-specific tokenizer is wired in. Approximates the
+/// common ~4-characters-per-token ratio for source code.
+pub(crate) fn estimate_tokens(text: &str) -> usize {
+    (text.len() / 4).max(1)
+}
+
+/// Default token budget applied when an embedder-specific limit isn't configured.
+pub(crate) const DEFAULT_MAX_TOKENS: usize = 512;
+
+/// Greedily merge adjacent spans delimited by `boundaries` into byte ranges whose estimated
+/// token count stays under `max_tokens`. A span that alone exceeds the budget is split at the line
+/// break nearest its midpoint, and each half is packed again the same way; a span with no line
+/// break to split at is emitted as-is, even though it exceeds the budget.
+pub(crate) fn pack_spans_by_token_budget(
+    content: &str,
+    boundaries: &[usize],
+    max_tokens: usize,
+) -> Vec<(usize, usize)> {
+    if content.is_empty() {
+        return Vec::new();
+    }
+
+    let mut points: Vec<usize> = boundaries.iter().copied().filter(|&b| b <= content.len()).collect();
+    points.push(0);
+    points.push(content.len());
+    points.sort_unstable();
+    points.dedup();
+
+    let mut packed = Vec::new();
+    let mut span_start = points[0];
+    let mut span_tokens = 0usize;
+
+    for window in points.windows(2) {
+        let (start, end) = (window[0], window[1]);
+        if end <= start {
+            continue;
+        }
+
+        let span_token_count = estimate_tokens(&content[start..end]);
+
+        if span_token_count > max_tokens {
+            if span_tokens > 0 {
+                packed.push((span_start, start));
+                span_tokens = 0;
+            }
+
+            packed.extend(split_oversized_span(content, start, end, max_tokens));
+
+            span_start = end;
+            continue;
+        }
+
+        if span_tokens > 0 && span_tokens + span_token_count > max_tokens {
+            packed.push((span_start, start));
+            span_start = start;
+            span_tokens = 0;
+        }
+
+        span_tokens += span_token_count;
+    }
+
+    if span_tokens > 0 {
+        packed.push((span_start, content.len()));
+    }
+
+    packed
+}
+
+/// Split a single oversized `[start, end)` span at the nearest inner boundary to its midpoint.
+/// Since `boundaries` has already been consumed into flat windows by the caller, "inner boundary"
+/// here means the nearest line break; if none exists the span is emitted as-is.
+fn split_oversized_span(
+    content: &str,
+    start: usize,
+    end: usize,
+    max_tokens: usize,
+) -> Vec<(usize, usize)> {
+    let mid = start + (end - start) / 2;
+    let nearest_newline = content[start..end]
+        .char_indices()
+        .filter(|&(_, c)| c == '\n')
+        .map(|(i, _)| start + i + 1)
+        .filter(|&b| b > start && b < end)
+        .min_by_key(|&b| b.abs_diff(mid));
+
+    match nearest_newline {
+        Some(split) => {
+            let mut halves = pack_spans_by_token_budget(&content[start..split], &[], max_tokens)
+                .into_iter()
+                .map(|(s, e)| (start + s, start + e))
+                .collect::<Vec<_>>();
+            halves.extend(
+                pack_spans_by_token_budget(&content[split..end], &[], max_tokens)
+                    .into_iter()
+                    .map(|(s, e)| (split + s, split + e)),
+            );
+            halves
+        }
+        None => vec![(start, end)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn packs_small_spans_together() {
+        let content = "a".repeat(40);
+        let boundaries = vec![10, 20, 30];
+        let packed = pack_spans_by_token_budget(&content, &boundaries, 100);
+        assert_eq!(packed, vec![(0, 40)]);
+    }
+
+    #[test]
+    fn splits_when_budget_exceeded() {
+        let content = "x".repeat(80);
+        let boundaries = vec![40];
+        // each half is ~10 tokens (40 chars / 4), a budget of 15 should keep them separate
+        let packed = pack_spans_by_token_budget(&content, &boundaries, 15);
+        assert_eq!(packed, vec![(0, 40), (40, 80)]);
+    }
+
+    #[test]
+    fn splits_oversized_span_at_nearest_line_break() {
+        let content = format!("{}\n{}", "x".repeat(48), "y".repeat(48));
+        // one giant span covering the whole buffer, far over budget, with no symbol boundary
+        let packed = pack_spans_by_token_budget(&content, &[], 5);
+        assert_eq!(packed, vec![(0, 49), (49, content.len())]);
+    }
+}
@@ -10,6 +10,10 @@ pub struct Chunk {
     pub size: usize,
     pub code: String,
     pub summary: Option<String>,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub start_line: usize,
+    pub end_line: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -57,30 +61,60 @@ impl CodeSymbol {
         }
     }
 
-    pub fn calculate_complexity(node: tree_sitter::Node) -> u32 {
-        let mut complexity = 1;
+    /// McCabe cyclomatic complexity: 1 plus one for every decision point anywhere in `node`'s
+    /// subtree (not just its immediate children), so a `match` buried inside a nested `for` loop
+    /// is still counted. Node-kind checks stay permissive across the supported grammars rather
+    /// than branching per language, as `CodeAnalyzer::is_symbol_node` does.
+    pub fn calculate_complexity(node: tree_sitter::Node, content: &str) -> u32 {
+        1 + Self::count_decision_points(node, content)
+    }
 
-        // Add complexity based on node size and nesting
-        complexity += (node.child_count() / 5) as u32;
+    fn count_decision_points(node: tree_sitter::Node, content: &str) -> u32 {
+        let mut count = u32::from(Self::is_decision_point(node, content));
 
-        // Add complexity for control flow structures
         let mut cursor = node.walk();
         if cursor.goto_first_child() {
             loop {
-                let child = cursor.node();
-                match child.kind() {
-                    "if_statement" | "if_expression" => complexity += 1,
-                    "while_statement" | "for_statement" => complexity += 2,
-                    "match_expression" | "switch_statement" => complexity += 3,
-                    _ => {}
-                }
+                count += Self::count_decision_points(cursor.node(), content);
                 if !cursor.goto_next_sibling() {
                     break;
                 }
             }
+            cursor.goto_parent();
+        }
+
+        count
+    }
+
+    /// Whether `node` is itself a decision point: a branch, a loop, a non-default
+    /// `match`/`switch` arm, a short-circuiting `&&`/`||`, or a `catch`/`except`/`?`-style error
+    /// branch.
+    fn is_decision_point(node: tree_sitter::Node, content: &str) -> bool {
+        match node.kind() {
+            "if_statement" | "if_expression" | "elif_clause" => true,
+            "while_statement" | "while_expression" | "for_statement" | "for_expression"
+            | "for_in_statement" | "loop_expression" => true,
+            "try_expression" | "catch_clause" | "except_clause" | "rescue_clause" => true,
+            "match_arm" | "switch_case" | "expression_case" => !Self::is_default_arm(node, content),
+            "binary_expression" | "boolean_operator" => Self::is_short_circuit_operator(node, content),
+            _ => false,
         }
+    }
+
+    /// A `match`/`switch` arm is the "default" branch (and so not its own decision point) when
+    /// its pattern is a bare wildcard (`_` in Rust) rather than a specific value.
+    fn is_default_arm(node: tree_sitter::Node, content: &str) -> bool {
+        node.child_by_field_name("pattern")
+            .map(|pattern| content[pattern.byte_range()].trim() == "_")
+            .unwrap_or(false)
+    }
 
-        complexity
+    /// A `binary_expression`/`boolean_operator` is a decision point only when it short-circuits
+    /// (`&&`/`||`/`and`/`or`) -- arithmetic and comparison operators aren't branches.
+    fn is_short_circuit_operator(node: tree_sitter::Node, content: &str) -> bool {
+        node.child_by_field_name("operator")
+            .map(|op| matches!(&content[op.byte_range()], "&&" | "||" | "and" | "or"))
+            .unwrap_or(false)
     }
 }
 
@@ -104,3 +138,74 @@ impl CodeAnalysis {
         self.complexity_score = (avg_complexity + weighted_complexity) / 2.0;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tree_sitter::Parser;
+
+    use super::*;
+
+    fn parse_rust(content: &str) -> tree_sitter::Tree {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::LANGUAGE.into()).unwrap();
+        parser.parse(content, None).unwrap()
+    }
+
+    fn complexity_of_first_function(content: &str) -> u32 {
+        let tree = parse_rust(content);
+        let mut cursor = tree.walk();
+        cursor.goto_first_child();
+        let function_node = cursor.node();
+        CodeSymbol::calculate_complexity(function_node, content)
+    }
+
+    #[test]
+    fn straight_line_function_has_complexity_one() {
+        let content = "fn add(a: u32, b: u32) -> u32 { a + b }";
+        assert_eq!(complexity_of_first_function(content), 1);
+    }
+
+    #[test]
+    fn counts_decision_points_nested_arbitrarily_deep() {
+        let content = r#"
+fn nested(xs: &[u32]) -> u32 {
+    let mut total = 0;
+    for x in xs {
+        if *x > 0 {
+            total += x;
+        }
+    }
+    total
+}
+"#;
+        // base(1) + for(1) + if(1)
+        assert_eq!(complexity_of_first_function(content), 3);
+    }
+
+    #[test]
+    fn counts_each_non_default_match_arm_but_not_the_wildcard() {
+        let content = r#"
+fn classify(n: i32) -> &'static str {
+    match n {
+        0 => "zero",
+        1 => "one",
+        _ => "many",
+    }
+}
+"#;
+        // base(1) + two non-default arms(2); the `_` wildcard arm doesn't count
+        assert_eq!(complexity_of_first_function(content), 3);
+    }
+
+    #[test]
+    fn counts_short_circuit_operators() {
+        let content = r#"
+fn both(a: bool, b: bool) -> bool {
+    a && b || a
+}
+"#;
+        // base(1) + && (1) + || (1)
+        assert_eq!(complexity_of_first_function(content), 3);
+    }
+}
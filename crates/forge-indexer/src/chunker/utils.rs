@@ -12,47 +12,72 @@ impl ChunkerUtils {
         let mut complexity = 0.0;
         let mut cursor = tree.walk();
 
-        // Traverse the tree and count complexity indicators
-        Self::traverse_for_complexity(&mut cursor, content, &mut complexity);
+        // Traverse the tree and count complexity indicators, starting at nesting depth 0
+        Self::traverse_for_complexity(&mut cursor, content, &mut complexity, 0);
 
         // Normalize by chunk size
         let lines = chunk_code.lines().count() as f32;
         if lines > 0.0 { complexity / lines } else { 0.0 }
     }
 
-    /// Recursively traverse tree to calculate complexity
-    pub fn traverse_for_complexity(cursor: &mut TreeCursor, content: &str, complexity: &mut f32) {
+    /// Cognitive-complexity-style traversal: a control-flow structure (`if`/`while`/`for`/`match`/
+    /// `try`/closures) contributes `1 + nesting` instead of a flat weight, so the same construct
+    /// costs more the deeper it's nested -- a `match` inside a nested loop reads harder than three
+    /// sibling `match`es, and this is the only way the score tells them apart. `nesting`
+    /// increments while descending into such a structure's subtree and is restored on return, so
+    /// sibling branches don't inherit each other's depth. An `else if`, which tree-sitter nests in
+    /// its preceding `if`'s `else_clause`, is scored (and its body nested) at the depth of the
+    /// `if` that heads its chain rather than one level deeper. A short-circuiting
+    /// `&&`/`||` run and a labeled `break`/`continue` each add a flat +1 with no nesting change,
+    /// mirroring how `CodeSymbol::is_decision_point` treats the same constructs for cyclomatic
+    /// complexity.
+    pub fn traverse_for_complexity(
+        cursor: &mut TreeCursor,
+        content: &str,
+        complexity: &mut f32,
+        nesting: u32,
+    ) {
         let node = cursor.node();
 
-        // Add complexity based on node type
-        match node.kind() {
-            // Control flow structures
-            "if_statement" | "if_expression" => *complexity += 1.0,
-            "while_statement" | "while_expression" => *complexity += 2.0,
-            "for_statement" | "for_expression" => *complexity += 2.0,
-            "match_expression" | "switch_statement" => *complexity += 3.0,
-            "try_statement" | "try_expression" => *complexity += 2.0,
-
-            // Function definitions (higher complexity)
-            "function_item" | "function_definition" | "method_definition" => *complexity += 3.0,
-            "closure_expression" | "lambda" => *complexity += 2.0,
-
-            // Class/struct definitions
-            "struct_item" | "class_definition" | "impl_item" => *complexity += 4.0,
-
-            // Generic/template usage
-            "generic_type" | "type_arguments" => *complexity += 1.5,
-
-            // Async/concurrency
-            "async_block" | "await_expression" => *complexity += 2.5,
-
-            _ => {}
+        let is_else_if = matches!(node.kind(), "if_statement" | "if_expression")
+            && node.parent().is_some_and(|parent| parent.kind() == "else_clause");
+        let nesting = if is_else_if { nesting.saturating_sub(1) } else { nesting };
+
+        let is_nesting_structure = matches!(
+            node.kind(),
+            "if_statement"
+                | "if_expression"
+                | "while_statement"
+                | "while_expression"
+                | "for_statement"
+                | "for_expression"
+                | "for_in_statement"
+                | "loop_expression"
+                | "match_expression"
+                | "switch_statement"
+                | "try_statement"
+                | "try_expression"
+                | "closure_expression"
+                | "lambda"
+        );
+
+        if is_nesting_structure {
+            *complexity += 1.0 + nesting as f32;
+        } else if matches!(node.kind(), "binary_expression" | "boolean_operator")
+            && Self::is_short_circuit_operator(node, content)
+        {
+            *complexity += 1.0;
+        } else if matches!(node.kind(), "break_expression" | "continue_expression")
+            && (0..node.child_count()).any(|i| node.child(i).is_some_and(|c| c.kind() == "label"))
+        {
+            *complexity += 1.0;
         }
 
-        // Traverse children
+        // Traverse children, descending one nesting level only under a control-flow structure
+        let child_nesting = if is_nesting_structure { nesting + 1 } else { nesting };
         if cursor.goto_first_child() {
             loop {
-                Self::traverse_for_complexity(cursor, content, complexity);
+                Self::traverse_for_complexity(cursor, content, complexity, child_nesting);
                 if !cursor.goto_next_sibling() {
                     break;
                 }
@@ -61,6 +86,14 @@ impl ChunkerUtils {
         }
     }
 
+    /// A `binary_expression`/`boolean_operator` node is a short-circuiting `&&`/`||` (as opposed
+    /// to an arithmetic or comparison operator) only if its `operator` field is one of them.
+    fn is_short_circuit_operator(node: tree_sitter::Node, content: &str) -> bool {
+        node.child_by_field_name("operator")
+            .map(|op| matches!(&content[op.byte_range()], "&&" | "||" | "and" | "or"))
+            .unwrap_or(false)
+    }
+
     /// Detect semantic boundaries in code
     pub fn detect_semantic_boundaries(tree: &Tree, content: &str) -> Vec<usize> {
         let mut boundaries = Vec::new();
@@ -245,6 +278,14 @@ impl ChunkerUtils {
         word.len() > 3 // longer words are more likely to be meaningful
     }
 
+    /// 1-based line number containing `byte_offset`, counting newlines in `content` before it.
+    pub fn line_for_byte(content: &str, byte_offset: usize) -> usize {
+        1 + content.as_bytes()[..byte_offset.min(content.len())]
+            .iter()
+            .filter(|&&b| b == b'\n')
+            .count()
+    }
+
     /// Calculate Jaccard similarity between two sets
     pub fn jaccard_similarity(set1: &HashSet<String>, set2: &HashSet<String>) -> f32 {
         if set1.is_empty() && set2.is_empty() {
@@ -261,3 +302,133 @@ impl ChunkerUtils {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tree_sitter::Parser;
+
+    use super::*;
+
+    fn parse_rust(content: &str) -> Tree {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::LANGUAGE.into()).unwrap();
+        parser.parse(content, None).unwrap()
+    }
+
+    fn raw_complexity(content: &str) -> f32 {
+        let tree = parse_rust(content);
+        let mut complexity = 0.0;
+        ChunkerUtils::traverse_for_complexity(&mut tree.walk(), content, &mut complexity, 0);
+        complexity
+    }
+
+    #[test]
+    fn deeper_nesting_costs_more() {
+        let content = r#"fn f() {
+    if true {
+        if true {
+            1
+        }
+    }
+}
+"#;
+        // Outer if: 1 + nesting(0) = 1. Inner if, one level deeper: 1 + nesting(1) = 2. Total 3.
+        assert_eq!(raw_complexity(content), 3.0);
+    }
+
+    #[test]
+    fn then_and_else_branches_are_nested_to_the_same_depth() {
+        let content = r#"fn f() {
+    if true {
+        if true {
+            1
+        }
+    } else {
+        if true {
+            2
+        }
+    }
+}
+"#;
+        // Outer if: 1 + nesting(0) = 1. The `if` nested in the `then` arm and the one nested in
+        // the `else` arm both inherit nesting(1) from the same outer if, so each costs 1 + 1 = 2
+        // -- neither arm inherits a different depth than the other. Total 1 + 2 + 2 = 5.
+        assert_eq!(raw_complexity(content), 5.0);
+    }
+
+    #[test]
+    fn nested_loop_and_match_each_add_their_own_depth() {
+        let content = r#"fn f() {
+    for i in 0..10 {
+        match i {
+            _ => {}
+        }
+    }
+}
+"#;
+        // for_expression: 1 + nesting(0) = 1. match_expression nested inside it: 1 + nesting(1) = 2.
+        assert_eq!(raw_complexity(content), 3.0);
+    }
+
+    #[test]
+    fn short_circuit_chain_counts_each_operator() {
+        let content = r#"fn f(a: bool, b: bool, c: bool) -> bool {
+    a && b || c
+}
+"#;
+        // `a && b || c` parses as `(a && b) || c`: two short-circuit binary_expressions, +1 each.
+        assert_eq!(raw_complexity(content), 2.0);
+    }
+
+    #[test]
+    fn comparison_operators_are_not_short_circuit_operators() {
+        let content = r#"fn f(a: i32, b: i32) -> bool {
+    a < b
+}
+"#;
+        assert_eq!(raw_complexity(content), 0.0);
+    }
+
+    #[test]
+    fn labeled_break_and_continue_each_add_flat_complexity() {
+        let content = r#"fn f() {
+    'outer: loop {
+        if true {
+            continue 'outer;
+        }
+        break 'outer;
+    }
+}
+"#;
+        // loop_expression: 1 + nesting(0) = 1. Nested if: 1 + nesting(1) = 2. Labeled continue and
+        // labeled break: +1 each. Total 1 + 2 + 1 + 1 = 5.
+        assert_eq!(raw_complexity(content), 5.0);
+    }
+
+    #[test]
+    fn unlabeled_break_and_continue_add_nothing() {
+        let content = r#"fn f() {
+    loop {
+        if true {
+            continue;
+        }
+        break;
+    }
+}
+"#;
+        // loop_expression: 1. Nested if: 1 + nesting(1) = 2. Unlabeled break/continue: +0 each.
+        assert_eq!(raw_complexity(content), 3.0);
+    }
+
+    #[test]
+    fn calculate_semantic_complexity_normalizes_by_line_count() {
+        let content = "fn f() {\n    if true {\n        1\n    }\n}\n";
+        let tree = parse_rust(content);
+
+        let complexity = ChunkerUtils::calculate_semantic_complexity(content, &tree, content);
+
+        // Raw complexity 1 (single if at nesting 0) over 5 lines.
+        assert_eq!(complexity, 1.0 / 5.0);
+    }
+}
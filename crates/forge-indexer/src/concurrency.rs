@@ -0,0 +1,250 @@
+//! Adaptive (AIMD) concurrency control for embedding/index batches, replacing a fixed semaphore
+//! that under-utilizes a fast embedder and overwhelms a slow or rate-limited one.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
+
+use crate::embedder_retry::is_retryable;
+
+/// How close a batch's latency must stay to the EWMA baseline to count as "no load" and grow the
+/// limit; anything slower than this multiple of baseline shrinks it instead, even without an
+/// explicit overload error.
+const DEFAULT_GRADIENT_THRESHOLD: f64 = 2.0;
+/// Multiplicative decrease factor applied to the current limit on overload.
+const DEFAULT_DECREASE_FACTOR: f64 = 0.7;
+/// Smoothing factor for the minimum-latency EWMA baseline: lower values remember a longer history
+/// and react more slowly to a single slow batch.
+const EWMA_ALPHA: f64 = 0.2;
+
+/// Additive-increase/multiplicative-decrease controller over the number of in-flight batches.
+/// Maintains a current limit `L` backed by a `tokio::sync::Semaphore` of that size, and an EWMA of
+/// the minimum observed batch latency as a "no-load" baseline. After each batch, [`record_success`]
+/// or [`record_overload`] nudges `L`: a batch that stayed near baseline grows it by one (up to
+/// `max_limit`); a timeout, rate limit, or a latency spike past `baseline * gradient_threshold`
+/// shrinks it by `decrease_factor` (floored at `min_limit`). Callers hold the pipeline's single
+/// instance across calls to `process_files` so the limit adapts live as files stream through.
+///
+/// [`record_success`]: AdaptiveConcurrencyLimiter::record_success
+/// [`record_overload`]: AdaptiveConcurrencyLimiter::record_overload
+pub struct AdaptiveConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+    current_limit: AtomicUsize,
+    min_limit: usize,
+    max_limit: usize,
+    /// EWMA of the minimum observed batch latency, in milliseconds. `0.0` until the first sample.
+    baseline_latency_ms: RwLock<f64>,
+    gradient_threshold: f64,
+    decrease_factor: f64,
+    increases: AtomicU64,
+    decreases: AtomicU64,
+}
+
+/// A point-in-time snapshot of the limiter's state, surfaced via `PipelineStats`.
+#[derive(Debug, Clone, Copy)]
+pub struct ConcurrencyLimiterStats {
+    pub current_limit: usize,
+    pub increases: u64,
+    pub decreases: u64,
+}
+
+impl AdaptiveConcurrencyLimiter {
+    /// `initial_limit` is typically `PipelineConfig::max_concurrent_files`; the limiter is free to
+    /// grow past it up to `max_limit` or shrink down to `min_limit` (floored at 1) as batches run.
+    pub fn new(initial_limit: usize, min_limit: usize, max_limit: usize) -> Self {
+        let min_limit = min_limit.max(1);
+        let max_limit = max_limit.max(min_limit);
+        let initial_limit = initial_limit.clamp(min_limit, max_limit);
+
+        Self {
+            semaphore: Arc::new(Semaphore::new(initial_limit)),
+            current_limit: AtomicUsize::new(initial_limit),
+            min_limit,
+            max_limit,
+            baseline_latency_ms: RwLock::new(0.0),
+            gradient_threshold: DEFAULT_GRADIENT_THRESHOLD,
+            decrease_factor: DEFAULT_DECREASE_FACTOR,
+            increases: AtomicU64::new(0),
+            decreases: AtomicU64::new(0),
+        }
+    }
+
+    /// Acquire a permit against the current limit, waiting if it's already saturated.
+    pub async fn acquire(&self) -> OwnedSemaphorePermit {
+        self.semaphore.clone().acquire_owned().await.expect("semaphore is never closed")
+    }
+
+    pub fn current_limit(&self) -> usize {
+        self.current_limit.load(Ordering::Relaxed)
+    }
+
+    pub fn stats(&self) -> ConcurrencyLimiterStats {
+        ConcurrencyLimiterStats {
+            current_limit: self.current_limit(),
+            increases: self.increases.load(Ordering::Relaxed),
+            decreases: self.decreases.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Record a batch that completed without an error. Updates the baseline EWMA, then grows the
+    /// limit by one if this batch stayed within `gradient_threshold` of baseline, or shrinks it if
+    /// latency crept past that even though nothing actually errored (e.g. a provider silently
+    /// queuing requests under load rather than returning 429).
+    pub async fn record_success(&self, latency: Duration) {
+        let latency_ms = latency.as_secs_f64() * 1000.0;
+        let baseline = {
+            let mut baseline = self.baseline_latency_ms.write().await;
+            *baseline = if *baseline <= 0.0 {
+                latency_ms
+            } else {
+                EWMA_ALPHA * latency_ms.min(*baseline) + (1.0 - EWMA_ALPHA) * *baseline
+            };
+            *baseline
+        };
+
+        if latency_ms > baseline * self.gradient_threshold {
+            self.decrease().await;
+        } else {
+            self.increase();
+        }
+    }
+
+    /// Record a batch that failed with a transient, load-related error (timeout, rate limit, 5xx
+    /// -- see [`is_retryable`]). Always shrinks the limit; a non-transient error shouldn't move it,
+    /// since it isn't evidence the provider is overloaded.
+    pub async fn record_error(&self, err: &anyhow::Error) {
+        if is_retryable(err) {
+            self.decrease().await;
+        }
+    }
+
+    fn increase(&self) {
+        let current = self.current_limit.load(Ordering::Relaxed);
+        if current >= self.max_limit {
+            return;
+        }
+        self.semaphore.add_permits(1);
+        self.current_limit.fetch_add(1, Ordering::Relaxed);
+        self.increases.fetch_add(1, Ordering::Relaxed);
+    }
+
+    async fn decrease(&self) {
+        let current = self.current_limit.load(Ordering::Relaxed);
+        let target = ((current as f64 * self.decrease_factor).floor() as usize).max(self.min_limit);
+        if target >= current {
+            return;
+        }
+
+        // A `Semaphore` only grows via `add_permits`, so shrinking it means permanently removing
+        // permits: acquire one at a time and `forget` each instead of returning it. This can block
+        // briefly if every permit is currently checked out, which is fine -- it just means the next
+        // acquirer waits slightly longer for a now-smaller pool, exactly the backpressure a
+        // decrease is meant to apply.
+        for _ in 0..(current - target) {
+            if let Ok(permit) = self.semaphore.clone().acquire_owned().await {
+                permit.forget();
+            }
+        }
+        self.current_limit.store(target, Ordering::Relaxed);
+        self.decreases.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn grows_limit_on_batches_near_baseline() {
+        let limiter = AdaptiveConcurrencyLimiter::new(2, 1, 10);
+
+        limiter.record_success(Duration::from_millis(100)).await;
+        limiter.record_success(Duration::from_millis(100)).await;
+
+        assert_eq!(limiter.current_limit(), 4);
+        assert_eq!(limiter.stats().increases, 2);
+    }
+
+    #[tokio::test]
+    async fn shrinks_limit_on_overload_error() {
+        let limiter = AdaptiveConcurrencyLimiter::new(10, 1, 20);
+
+        limiter.record_error(&anyhow::anyhow!("429 Too Many Requests")).await;
+
+        assert_eq!(limiter.current_limit(), 7);
+        assert_eq!(limiter.stats().decreases, 1);
+    }
+
+    #[tokio::test]
+    async fn ignores_non_transient_errors() {
+        let limiter = AdaptiveConcurrencyLimiter::new(10, 1, 20);
+
+        limiter.record_error(&anyhow::anyhow!("invalid UTF-8 in file")).await;
+
+        assert_eq!(limiter.current_limit(), 10);
+        assert_eq!(limiter.stats().decreases, 0);
+    }
+
+    #[tokio::test]
+    async fn shrinks_on_a_latency_spike_even_without_an_error() {
+        let limiter = AdaptiveConcurrencyLimiter::new(4, 1, 10);
+
+        limiter.record_success(Duration::from_millis(100)).await;
+        limiter.record_success(Duration::from_millis(1000)).await;
+
+        assert_eq!(limiter.current_limit(), 3);
+    }
+
+    #[tokio::test]
+    async fn never_grows_past_max_limit() {
+        let limiter = AdaptiveConcurrencyLimiter::new(2, 1, 2);
+
+        limiter.record_success(Duration::from_millis(100)).await;
+
+        assert_eq!(limiter.current_limit(), 2);
+        assert_eq!(limiter.stats().increases, 0);
+    }
+
+    #[tokio::test]
+    async fn never_shrinks_below_min_limit() {
+        let limiter = AdaptiveConcurrencyLimiter::new(1, 1, 10);
+
+        limiter.record_error(&anyhow::anyhow!("timed out")).await;
+
+        assert_eq!(limiter.current_limit(), 1);
+    }
+
+    /// Regression test for a deadlock: if a caller holds its `acquire`d permit across the
+    /// `record_error`/`record_success` call (as `pipeline::process_files` used to), a full batch
+    /// failing together has every permit checked out when every task's `decrease()` call races to
+    /// forcibly remove one -- none can proceed since none of those permits will ever be released.
+    /// Mirrors `process_files`' pattern (acquire, drop the permit, *then* record the outcome) and
+    /// wraps it in a timeout so a regression fails loudly instead of hanging the test suite.
+    #[tokio::test]
+    async fn full_batch_correlated_failure_does_not_deadlock() {
+        let limiter = Arc::new(AdaptiveConcurrencyLimiter::new(4, 1, 10));
+
+        let tasks: Vec<_> = (0..limiter.current_limit())
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    let permit = limiter.acquire().await;
+                    drop(permit);
+                    limiter.record_error(&anyhow::anyhow!("429 Too Many Requests")).await;
+                })
+            })
+            .collect();
+
+        tokio::time::timeout(Duration::from_secs(5), futures::future::join_all(tasks))
+            .await
+            .expect("a full-batch correlated failure must not deadlock the limiter");
+
+        // Every task raced `decrease()` concurrently, so the exact count depends on how many saw
+        // the limit before another's store landed; what matters is that they all completed.
+        assert!(limiter.stats().decreases >= 1);
+    }
+}
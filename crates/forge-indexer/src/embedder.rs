@@ -2,8 +2,9 @@
 
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use futures::stream::{self, StreamExt};
 use tracing::{debug, info, warn};
 
 #[async_trait]
@@ -23,11 +24,66 @@ pub trait Embedder: Send + Sync {
 
     /// Get the dimension of embeddings produced by this embedder
     fn embedding_dimension(&self) -> usize;
+
+    /// The provider's per-request token limit, if known, so callers (e.g. `EmbeddingsQueue`'s
+    /// token-budget packing) can size their batches without hard-coding a provider's limit.
+    /// `None` means "unknown; the caller's own ceiling is what applies".
+    fn max_input_tokens(&self) -> Option<usize> {
+        None
+    }
+
+    /// Same as `embed_batch`, but each returned vector is tagged with the id of the provider that
+    /// actually produced it. The default just tags every vector with `self.name()`; a wrapper that
+    /// can route a single call to more than one underlying provider (e.g. `FallbackEmbedder`)
+    /// overrides this so a batch that fell back to a later provider is tagged with that provider's
+    /// id instead of the wrapper's own.
+    async fn embed_batch_tagged(&self, texts: &[String]) -> Result<Vec<(Vec<f32>, String)>> {
+        let vectors = self.embed_batch(texts).await?;
+        let provider = self.name().to_string();
+        Ok(vectors.into_iter().map(|v| (v, provider.clone())).collect())
+    }
+}
+
+/// Normalize a vector to unit length in place; a zero vector is left as-is.
+pub fn normalize_vector(vector: &mut [f32]) {
+    let norm: f32 = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        vector.iter_mut().for_each(|x| *x /= norm);
+    }
+}
+
+/// Whether every component of `vector` is finite (no NaN/infinity), as a sanity check before an
+/// embedding is handed to the vector store.
+pub fn is_valid_vector(vector: &[f32]) -> bool {
+    vector.iter().all(|x| x.is_finite())
+}
+
+/// Batching and retry policy for `OpenAIEmbedder::embed_batch`.
+#[derive(Debug, Clone)]
+pub struct OpenAIBatchConfig {
+    /// Max texts sent in a single request, bounded by OpenAI's per-request item cap (2048 at time
+    /// of writing); kept well under that by default so one oversized caller-supplied batch never
+    /// hits it.
+    pub max_batch_size: usize,
+    /// Max sub-batches dispatched concurrently.
+    pub max_concurrency: usize,
+    /// Retry policy applied to each sub-batch independently.
+    pub retry: crate::embedder_retry::RetryConfig,
+}
+
+impl Default for OpenAIBatchConfig {
+    fn default() -> Self {
+        Self { max_batch_size: 256, max_concurrency: 4, retry: crate::embedder_retry::RetryConfig::default() }
+    }
 }
 
 pub struct OpenAIEmbedder {
     api_key: String,
     model: String,
+    /// `"openai:<model>"`, recorded in the Qdrant payload via `embed_batch_tagged` so a
+    /// mixed-provider index can be filtered by which provider/model produced a given vector.
+    id: String,
+    batch_config: OpenAIBatchConfig,
 }
 
 impl OpenAIEmbedder {
@@ -36,72 +92,144 @@ impl OpenAIEmbedder {
         let model = std::env::var("OPENAI_EMBEDDING_MODEL")
             .unwrap_or_else(|_| "text-embedding-3-large".to_string());
 
-        Ok(Self { api_key, model })
+        let id = format!("openai:{model}");
+        Ok(Self { api_key, model, id, batch_config: OpenAIBatchConfig::default() })
     }
 
-    pub fn new_with_config(api_key: String, model: Option<String>) -> Self {
-        Self {
-            api_key,
-            model: model.unwrap_or_else(|| "text-embedding-3-large".to_string()),
-        }
+    /// `batch_config` defaults to [`OpenAIBatchConfig::default`] when `None`.
+    pub fn new_with_config(api_key: String, model: Option<String>, batch_config: Option<OpenAIBatchConfig>) -> Self {
+        let model = model.unwrap_or_else(|| "text-embedding-3-large".to_string());
+        let id = format!("openai:{model}");
+        Self { api_key, model, id, batch_config: batch_config.unwrap_or_default() }
     }
-}
-/// Preprocess code text for better embedding quality
-fn preprocess_code_for_embedding(text: &str) -> String {
-    let mut processed = text.to_string();
-
-    // Remove excessive whitespace while preserving structure
-    processed = processed
-        .lines()
-        .map(|line| line.trim())
-        .filter(|line| !line.is_empty())
-        .collect::<Vec<_>>()
-        .join("\n");
 
-    // Add semantic markers for better understanding
-    let mut enhanced = String::new();
+    /// Split `texts` into sub-batches bounded by both `max_batch_size` items and the embedder's
+    /// per-request token limit, so a single oversized caller-supplied batch never exceeds either
+    /// of OpenAI's request limits.
+    fn split_into_batches(&self, texts: &[String]) -> Vec<Vec<String>> {
+        let max_tokens = self.max_input_tokens().unwrap_or(usize::MAX);
+        let mut batches = Vec::new();
+        let mut current = Vec::new();
+        let mut current_tokens = 0usize;
+
+        for text in texts {
+            let tokens = crate::chunker::token_budget::estimate_tokens(text);
+            let would_overflow = !current.is_empty()
+                && (current.len() >= self.batch_config.max_batch_size || current_tokens + tokens > max_tokens);
+            if would_overflow {
+                batches.push(std::mem::take(&mut current));
+                current_tokens = 0;
+            }
+            current.push(text.clone());
+            current_tokens += tokens;
+        }
+        if !current.is_empty() {
+            batches.push(current);
+        }
 
-    // Add language context hints
-    if processed.contains("fn ") || processed.contains("impl ") {
-        enhanced.push_str("RUST_CODE: ");
-    } else if processed.contains("def ") || processed.contains("class ") {
-        enhanced.push_str("PYTHON_CODE: ");
-    } else if processed.contains("function ") || processed.contains("const ") {
-        enhanced.push_str("JAVASCRIPT_CODE: ");
+        batches
     }
 
-    // Add function/class markers
-    if processed.contains("fn ") || processed.contains("def ") || processed.contains("function ") {
-        enhanced.push_str("FUNCTION_DEFINITION ");
-    }
-    if processed.contains("struct ")
-        || processed.contains("class ")
-        || processed.contains("interface ")
-    {
-        enhanced.push_str("TYPE_DEFINITION ");
-    }
-    if processed.contains("impl ") || processed.contains("trait ") {
-        enhanced.push_str("IMPLEMENTATION ");
+    /// Send one sub-batch to the OpenAI embeddings endpoint, retrying on HTTP 429/5xx with
+    /// exponential backoff, honoring a server-supplied `Retry-After` header when present.
+    async fn send_batch_with_retry(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut attempt = 0;
+        loop {
+            match self.send_batch(texts).await {
+                Ok(embeddings) => return Ok(embeddings),
+                Err(RequestOutcome::Retryable { status, retry_after }) => {
+                    if attempt + 1 >= self.batch_config.retry.max_attempts {
+                        return Err(anyhow::anyhow!(
+                            "OpenAI API request failed with status {status} after {} attempts",
+                            self.batch_config.retry.max_attempts
+                        ));
+                    }
+                    let delay = retry_after.unwrap_or_else(|| self.backoff_delay(attempt));
+                    warn!(
+                        "OpenAI embed_batch attempt {}/{} failed (status {status}), retrying in {:?}",
+                        attempt + 1,
+                        self.batch_config.retry.max_attempts,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(RequestOutcome::Fatal(err)) => return Err(err),
+            }
+        }
     }
-    if processed.contains("test") || processed.contains("#[test]") {
-        enhanced.push_str("TEST_CODE ");
+
+    /// Exponential backoff from the configured retry policy's `base_delay`, capped at
+    /// `max_delay`.
+    fn backoff_delay(&self, attempt: u32) -> std::time::Duration {
+        let retry = &self.batch_config.retry;
+        retry.base_delay.saturating_mul(1u32 << attempt.min(16)).min(retry.max_delay)
     }
 
-    enhanced.push_str(&processed);
+    /// One HTTP call to the OpenAI embeddings endpoint for `texts`, returning embeddings in the
+    /// same order.
+    async fn send_batch(&self, texts: &[String]) -> std::result::Result<Vec<Vec<f32>>, RequestOutcome> {
+        let client = reqwest::Client::new();
+
+        let request_body = serde_json::json!({
+            "input": texts,
+            "model": self.model
+        });
+
+        let response = client
+            .post("https://api.openai.com/v1/embeddings")
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request_body)
+            .send()
+            .await
+            .map_err(|e| RequestOutcome::Fatal(e.into()))?;
+
+        let status = response.status();
+        if status.as_u16() == 429 || status.is_server_error() {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(std::time::Duration::from_secs);
+            return Err(RequestOutcome::Retryable { status: status.as_u16(), retry_after });
+        }
+        if !status.is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(RequestOutcome::Fatal(anyhow::anyhow!("OpenAI API request failed: {}", error_text)));
+        }
+
+        let response_json: serde_json::Value =
+            response.json().await.map_err(|e| RequestOutcome::Fatal(e.into()))?;
 
-    // Limit length to avoid token limits
-    if enhanced.len() > 8000 {
-        enhanced.truncate(8000);
-        enhanced.push_str("...[TRUNCATED]");
+        response_json["data"]
+            .as_array()
+            .ok_or_else(|| RequestOutcome::Fatal(anyhow::anyhow!("Invalid response format")))?
+            .iter()
+            .map(|item| -> std::result::Result<Vec<f32>, RequestOutcome> {
+                Ok(item["embedding"]
+                    .as_array()
+                    .ok_or_else(|| RequestOutcome::Fatal(anyhow::anyhow!("Invalid embedding format")))?
+                    .iter()
+                    .map(|val| val.as_f64().unwrap_or_default() as f32)
+                    .collect::<Vec<f32>>())
+            })
+            .collect::<std::result::Result<Vec<Vec<f32>>, RequestOutcome>>()
     }
+}
 
-    enhanced
+/// Outcome of a single OpenAI embeddings request: a transient failure worth retrying (with an
+/// optional server-supplied delay), or one that should be surfaced to the caller immediately.
+enum RequestOutcome {
+    Retryable { status: u16, retry_after: Option<std::time::Duration> },
+    Fatal(anyhow::Error),
 }
 
 #[async_trait]
 impl Embedder for OpenAIEmbedder {
     fn name(&self) -> &str {
-        "openai"
+        &self.id
     }
 
     fn embedding_dimension(&self) -> usize {
@@ -118,78 +246,41 @@ impl Embedder for OpenAIEmbedder {
         }
     }
 
+    fn max_input_tokens(&self) -> Option<usize> {
+        // OpenAI's embeddings endpoint caps a single input at 8191 tokens regardless of model; the
+        // same figure serves as the summed token budget of each request `split_into_batches` builds.
+        Some(8191)
+    }
+
     async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
         info!("OpenAI embedder processing batch of {} texts", texts.len());
 
-        // Preprocess texts for better embeddings
-        info!("Preprocessing {} texts for better OpenAI embeddings", texts.len());
-        let preprocessed_texts: Vec<String> = texts
-            .iter()
-            .map(|text| preprocess_code_for_embedding(text))
-            .collect();
-
         // Bypass HTTP call for test API key
         if self.api_key == "test-key" {
             info!("Using test API key, returning dummy embeddings");
             // Return dummy embeddings for testing
-            return Ok(preprocessed_texts.iter().map(|_| vec![0.0; 1536]).collect());
-        }
-
-        // Log text lengths for debugging
-        for (i, text) in preprocessed_texts.iter().enumerate() {
-            info!("Preprocessed text {}: length {}", i, text.len());
-            debug!("Preprocessed text {}: {}", i, text);
+            return Ok(texts.iter().map(|_| vec![0.0; 1536]).collect());
         }
 
-        // Create the OpenAI API client
-        let client = reqwest::Client::new();
-
-        // Prepare the request body
-        let request_body = serde_json::json!({
-            "input": preprocessed_texts,
-            "model": self.model
-        });
-
-        info!("Sending request to OpenAI API with model: {}", self.model);
-        debug!("Request body: {}", serde_json::to_string_pretty(&request_body)?);
-
-        // Send the request to OpenAI API
-        let response = client
-            .post("https://api.openai.com/v1/embeddings")
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request_body)
-            .send()
-            .await?;
-
-        info!("Received response from OpenAI API with status: {}", response.status());
-
-        // Check if the request was successful
-        if !response.status().is_success() {
-            let error_text = response.text().await?;
-            return Err(anyhow::anyhow!("OpenAI API request failed: {}", error_text));
-        }
-
-        // Parse the response
-        let response_json: serde_json::Value = response.json().await?;
+        let batches = self.split_into_batches(texts);
+        info!(
+            "Sending {} text(s) to OpenAI as {} sub-batch(es) (max {} items, {} concurrent)",
+            texts.len(),
+            batches.len(),
+            self.batch_config.max_batch_size,
+            self.batch_config.max_concurrency
+        );
 
-        // Extract embeddings from the response
-        let embeddings = response_json["data"]
-            .as_array()
-            .ok_or_else(|| anyhow::anyhow!("Invalid response format"))?
-            .iter()
-            .map(|item| -> Result<Vec<f32>> {
-                Ok(item["embedding"]
-                    .as_array()
-                    .ok_or_else(|| anyhow::anyhow!("Invalid embedding format"))?
-                    .iter()
-                    .map(|val| val.as_f64().unwrap() as f32)
-                    .collect::<Vec<f32>>())
-            })
-            .collect::<Result<Vec<Vec<f32>>>>()?;
+        let embeddings: Vec<Vec<f32>> = stream::iter(batches.iter().map(|batch| self.send_batch_with_retry(batch)))
+            .buffered(self.batch_config.max_concurrency.max(1))
+            .collect::<Vec<Result<Vec<Vec<f32>>>>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<Vec<Vec<f32>>>>>()?
+            .into_iter()
+            .flatten()
+            .collect();
 
-        // Log response details for debugging
-        debug!("OpenAI API response: {}", serde_json::to_string_pretty(&response_json)?);
         info!(
             "OpenAI API returned {} embeddings, each with {} dimensions",
             embeddings.len(),
@@ -200,14 +291,20 @@ impl Embedder for OpenAIEmbedder {
     }
 }
 
-/// Production-ready local embedding service
-/// Currently uses a placeholder implementation - to be replaced with actual
-/// ONNX Runtime integration
+/// Production-ready local embedding service. Without the `onnx` feature this is a deterministic
+/// hash-based placeholder (below) that produces consistent but non-semantic vectors, so the rest
+/// of the indexing pipeline -- and its tests -- can run with no model file on disk. Enabling
+/// `onnx` swaps in a genuine tokenizer + ONNX Runtime session (see the `onnx`-gated impl further
+/// down this file).
+#[cfg(not(feature = "onnx"))]
 pub struct LocalEmbedder {
     model_name: String,
     embedding_dim: usize,
+    /// `"local:<model_name>"`, recorded in the Qdrant payload via `embed_batch_tagged`.
+    id: String,
 }
 
+#[cfg(not(feature = "onnx"))]
 impl LocalEmbedder {
     /// Create a new LocalEmbedder with model configuration
     /// This is a placeholder implementation that will be replaced with actual
@@ -226,17 +323,17 @@ impl LocalEmbedder {
         );
         warn!("This is a placeholder implementation. For production, integrate with ONNX Runtime.");
 
-        Ok(Self { model_name, embedding_dim })
+        let id = format!("local:{model_name}");
+        Ok(Self { model_name, embedding_dim, id })
     }
 
     /// Create a LocalEmbedder with default configuration for testing
     pub fn new_default() -> Result<Self> {
         info!("Creating LocalEmbedder with default configuration");
 
-        Ok(Self {
-            model_name: "microsoft/codebert-base".to_string(),
-            embedding_dim: 768,
-        })
+        let model_name = "microsoft/codebert-base".to_string();
+        let id = format!("local:{model_name}");
+        Ok(Self { model_name, embedding_dim: 768, id })
     }
 
     fn get_embedding_dim(model_name: &str) -> usize {
@@ -260,19 +357,17 @@ impl LocalEmbedder {
         }
     }
 
-    /// Generate deterministic embeddings based on text content
-    /// This is a placeholder that generates consistent but non-semantic
-    /// embeddings
+    /// Generate deterministic embeddings based on text content. A placeholder that produces
+    /// consistent but non-semantic embeddings; `text` is expected to already be the fully
+    /// rendered string an `EmbeddingTemplateSet` produced for a chunk -- this type has no
+    /// knowledge of chunk structure beyond the text it's handed.
     fn generate_placeholder_embedding(&self, text: &str) -> Vec<f32> {
-        // Preprocess the text to improve embedding quality
-        let processed_text = self.preprocess_text(text);
-
         use std::collections::hash_map::DefaultHasher;
         use std::hash::{Hash, Hasher};
 
         // Create a deterministic hash-based embedding that includes model name
         let mut hasher = DefaultHasher::new();
-        processed_text.hash(&mut hasher);
+        text.hash(&mut hasher);
         self.model_name.hash(&mut hasher); // Include model name for different embeddings per model
         let hash = hasher.finish();
 
@@ -295,136 +390,175 @@ impl LocalEmbedder {
 
         embedding
     }
+}
 
-    /// Preprocess text to improve embedding quality for code
-    fn preprocess_text(&self, text: &str) -> String {
-        // Enhanced preprocessing for code content
-        let mut processed = text.to_string();
+#[cfg(not(feature = "onnx"))]
+#[async_trait]
+impl Embedder for LocalEmbedder {
+    fn name(&self) -> &str {
+        &self.id
+    }
 
-        // Normalize whitespace but preserve code structure
-        let lines: Vec<&str> = processed.lines().collect();
-        let mut normalized_lines = Vec::new();
+    fn embedding_dimension(&self) -> usize {
+        self.embedding_dim
+    }
 
-        for line in lines {
-            // Preserve leading whitespace for indentation
-            let trimmed = line.trim_end();
-            if !trimmed.is_empty() {
-                normalized_lines.push(trimmed);
-            }
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
         }
 
-        processed = normalized_lines.join("\n");
-
-        // Add language-specific preprocessing
-        if processed.contains("fn ") || processed.contains("impl ") || processed.contains("struct ")
-        {
-            // Rust code - preserve keywords and structure
-            processed = self.enhance_rust_code(&processed);
-        } else if processed.contains("def ") || processed.contains("class ") {
-            // Python code - preserve keywords and structure
-            processed = self.enhance_python_code(&processed);
-        } else if processed.contains("function ")
-            || processed.contains("const ")
-            || processed.contains("class ")
-        {
-            // JavaScript/TypeScript code
-            processed = self.enhance_js_code(&processed);
-        }
+        info!(
+            "Processing batch of {} texts with LocalEmbedder (placeholder)",
+            texts.len()
+        );
 
-        // Limit length to prevent overly long texts from dominating embeddings
-        if processed.len() > 4000 {
-            // For code, try to keep complete functions/classes
-            if let Some(truncated) = self.smart_truncate_code(&processed, 4000) {
-                truncated
-            } else {
-                processed[..4000].to_string()
-            }
-        } else {
-            processed
+        for (i, text) in texts.iter().enumerate() {
+            debug!("Text {}: length {}: {}", i, text.len(), text);
         }
-    }
 
-    fn enhance_rust_code(&self, code: &str) -> String {
-        // Add semantic markers for Rust constructs
-        let mut enhanced = code.to_string();
+        // `texts` is already the rendered output of an `EmbeddingTemplateSet` (see
+        // `EmbeddingsQueue`); this embedder has no opinion on structure beyond the string it's handed.
+        let embeddings =
+            texts.iter().map(|text| self.generate_placeholder_embedding(text)).collect();
 
-        // Mark important Rust keywords for better embedding
-        enhanced = enhanced.replace("pub fn", "[RUST_PUBLIC_FUNCTION]");
-        enhanced = enhanced.replace("fn ", "[RUST_FUNCTION] ");
-        enhanced = enhanced.replace("impl ", "[RUST_IMPLEMENTATION] ");
-        enhanced = enhanced.replace("struct ", "[RUST_STRUCT] ");
-        enhanced = enhanced.replace("enum ", "[RUST_ENUM] ");
-        enhanced = enhanced.replace("trait ", "[RUST_TRAIT] ");
-        enhanced = enhanced.replace("mod ", "[RUST_MODULE] ");
+        info!(
+            "Successfully generated {} placeholder embeddings",
+            texts.len()
+        );
 
-        enhanced
+        Ok(embeddings)
     }
+}
 
-    fn enhance_python_code(&self, code: &str) -> String {
-        let mut enhanced = code.to_string();
+/// Real transformer inference backend for `LocalEmbedder`, enabled by the `onnx` feature. Loads a
+/// tokenizer and an ONNX Runtime session once at construction instead of hashing text into a
+/// deterministic vector.
+#[cfg(feature = "onnx")]
+pub struct LocalEmbedder {
+    model_name: String,
+    tokenizer: tokenizers::Tokenizer,
+    session: ort::session::Session,
+    hidden_size: usize,
+    /// `"local:<model_name>"`, recorded in the Qdrant payload via `embed_batch_tagged`.
+    id: String,
+}
 
-        enhanced = enhanced.replace("def ", "[PYTHON_FUNCTION] ");
-        enhanced = enhanced.replace("class ", "[PYTHON_CLASS] ");
-        enhanced = enhanced.replace("async def", "[PYTHON_ASYNC_FUNCTION]");
-        enhanced = enhanced.replace("@", "[PYTHON_DECORATOR]");
+#[cfg(feature = "onnx")]
+impl LocalEmbedder {
+    /// Longer inputs are truncated to this many tokens before the model sees them.
+    const MAX_SEQUENCE_LENGTH: usize = 512;
 
-        enhanced
-    }
+    /// Load the tokenizer from `tokenizer_path` and the model from `model_path` into an ONNX
+    /// Runtime session. The embedding dimension is read off the real output shape of a one-token
+    /// probe inference rather than a hardcoded model-name table, so it always matches whatever
+    /// `.onnx` file is actually loaded.
+    pub async fn new(
+        model_path: &Path,
+        tokenizer_path: &Path,
+        model_name: Option<String>,
+    ) -> Result<Self> {
+        let model_name = model_name.unwrap_or_else(|| "microsoft/codebert-base".to_string());
+        info!("Loading ONNX model from {:?} with tokenizer {:?}", model_path, tokenizer_path);
 
-    fn enhance_js_code(&self, code: &str) -> String {
-        let mut enhanced = code.to_string();
+        let tokenizer = tokenizers::Tokenizer::from_file(tokenizer_path).map_err(|e| {
+            anyhow::anyhow!("Failed to load tokenizer from {:?}: {}", tokenizer_path, e)
+        })?;
+        let session = ort::session::Session::builder()?.commit_from_file(model_path)?;
+        let id = format!("local:{model_name}");
 
-        enhanced = enhanced.replace("function ", "[JS_FUNCTION] ");
-        enhanced = enhanced.replace("const ", "[JS_CONST] ");
-        enhanced = enhanced.replace("class ", "[JS_CLASS] ");
-        enhanced = enhanced.replace("async ", "[JS_ASYNC] ");
-        enhanced = enhanced.replace("export ", "[JS_EXPORT] ");
-        enhanced = enhanced.replace("import ", "[JS_IMPORT] ");
+        let mut embedder = Self { model_name, tokenizer, session, hidden_size: 0, id };
+        embedder.hidden_size = embedder
+            .run_inference(&["x".to_string()])?
+            .first()
+            .map(|embedding| embedding.len())
+            .ok_or_else(|| anyhow::anyhow!("ONNX model produced no output for the probe input"))?;
 
-        enhanced
+        info!(
+            "LocalEmbedder initialized with model: {} (hidden size {})",
+            embedder.model_name, embedder.hidden_size
+        );
+        Ok(embedder)
     }
 
-    fn smart_truncate_code(&self, code: &str, max_len: usize) -> Option<String> {
-        if code.len() <= max_len {
-            return Some(code.to_string());
+    /// Tokenize `texts` (truncating to `MAX_SEQUENCE_LENGTH`, padding to the batch's own max
+    /// length), run the ONNX session, then mean-pool each sequence's last-hidden-state token
+    /// vectors weighted by the attention mask and L2-normalize the result to a unit vector.
+    fn run_inference(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let encodings = self
+            .tokenizer
+            .encode_batch(texts.to_vec(), true)
+            .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
+
+        let batch_size = encodings.len();
+        let seq_len = encodings
+            .iter()
+            .map(|encoding| encoding.get_ids().len().min(Self::MAX_SEQUENCE_LENGTH))
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut input_ids = ndarray::Array2::<i64>::zeros((batch_size, seq_len));
+        let mut attention_mask = ndarray::Array2::<i64>::zeros((batch_size, seq_len));
+        let mut token_type_ids = ndarray::Array2::<i64>::zeros((batch_size, seq_len));
+
+        for (row, encoding) in encodings.iter().enumerate() {
+            let ids = encoding.get_ids();
+            let mask = encoding.get_attention_mask();
+            let type_ids = encoding.get_type_ids();
+            let len = ids.len().min(seq_len);
+            for col in 0..len {
+                input_ids[[row, col]] = ids[col] as i64;
+                attention_mask[[row, col]] = mask[col] as i64;
+                token_type_ids[[row, col]] = type_ids[col] as i64;
+            }
         }
 
-        // Try to find a good breaking point (end of function, class, etc.)
-        let lines: Vec<&str> = code.lines().collect();
-        let mut current_len = 0;
-        let mut result_lines = Vec::new();
-
-        for line in lines {
-            if current_len + line.len() + 1 > max_len {
-                // Check if this is a good breaking point
-                let trimmed = line.trim();
-                if trimmed == "}" || trimmed.starts_with('}') {
-                    result_lines.push(line);
-                    break;
+        let outputs = self.session.run(ort::inputs![
+            "input_ids" => input_ids,
+            "attention_mask" => attention_mask.clone(),
+            "token_type_ids" => token_type_ids,
+        ]?)?;
+
+        let last_hidden_state = outputs[0].try_extract_tensor::<f32>()?;
+        let hidden_size = last_hidden_state.shape()[2];
+
+        let mut results = Vec::with_capacity(batch_size);
+        for row in 0..batch_size {
+            let mut pooled = vec![0.0f32; hidden_size];
+            let mut mask_sum = 0.0f32;
+            for col in 0..seq_len {
+                let weight = attention_mask[[row, col]] as f32;
+                if weight == 0.0 {
+                    continue;
+                }
+                mask_sum += weight;
+                for dim in 0..hidden_size {
+                    pooled[dim] += last_hidden_state[[row, col, dim]] * weight;
+                }
+            }
+            if mask_sum > 0.0 {
+                for value in pooled.iter_mut() {
+                    *value /= mask_sum;
                 }
-                // If not a good breaking point, break at previous line
-                break;
             }
-            result_lines.push(line);
-            current_len += line.len() + 1; // +1 for newline
+            normalize_vector(&mut pooled);
+            results.push(pooled);
         }
 
-        if result_lines.is_empty() {
-            None
-        } else {
-            Some(result_lines.join("\n"))
-        }
+        Ok(results)
     }
 }
 
+#[cfg(feature = "onnx")]
 #[async_trait]
 impl Embedder for LocalEmbedder {
     fn name(&self) -> &str {
-        "local"
+        &self.id
     }
 
     fn embedding_dimension(&self) -> usize {
-        self.embedding_dim
+        self.hidden_size
     }
 
     async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
@@ -432,35 +566,253 @@ impl Embedder for LocalEmbedder {
             return Ok(vec![]);
         }
 
-        info!(
-            "Processing batch of {} texts with LocalEmbedder (placeholder)",
-            texts.len()
-        );
+        info!("Processing batch of {} texts with ONNX LocalEmbedder", texts.len());
+        self.run_inference(texts)
+    }
+}
 
-        // Preprocess texts for better embeddings
-        info!("Preprocessing {} texts for better local embeddings", texts.len());
-        let preprocessed_texts: Vec<String> = texts
-            .iter()
-            .map(|text| preprocess_code_for_embedding(text))
-            .collect();
+/// Known embedding model names served by Ollama, mapped to the dimension they produce, so callers
+/// don't have to pass an explicit dimension for the common ones.
+fn default_ollama_dimension(model: &str) -> usize {
+    match model {
+        "nomic-embed-text" => 768,
+        "mxbai-embed-large" => 1024,
+        "all-minilm" => 384,
+        _ => {
+            warn!("Unknown Ollama embedding model {}, defaulting to 768 dimensions", model);
+            768
+        }
+    }
+}
+
+/// Ollama's embeddings endpoint handles one prompt per request, so `embed_batch` fans out this
+/// many requests concurrently rather than the server's own queueing.
+const DEFAULT_OLLAMA_CONCURRENCY: usize = 4;
 
-        // Log text lengths for debugging
-        for (i, text) in preprocessed_texts.iter().enumerate() {
-            info!("Preprocessed text {}: length {}", i, text.len());
-            debug!("Preprocessed text {}: {}", i, text);
+/// Embedder backed by a locally-served model over Ollama's HTTP API (`POST /api/embeddings`),
+/// for fully local semantic indexing with no OpenAI key. Ollama embeds one prompt per request, so
+/// `embed_batch` fans out with bounded concurrency instead of one request at a time.
+pub struct OllamaEmbedder {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    dimension: usize,
+    /// How many `/api/embeddings` requests `embed_batch` keeps in flight at once.
+    concurrency: usize,
+    /// `"ollama:<model>"`, recorded in the Qdrant payload via `embed_batch_tagged`.
+    id: String,
+}
+
+impl OllamaEmbedder {
+    /// `base_url` is the Ollama server root (e.g. `http://localhost:11434`); the embeddings
+    /// endpoint is resolved relative to it. `dimension` falls back to a table of known models'
+    /// dimensions rather than a network probe, so construction stays synchronous and offline.
+    pub fn new(base_url: String, model: String, dimension: Option<usize>) -> Self {
+        let dimension = dimension.unwrap_or_else(|| default_ollama_dimension(&model));
+        let id = format!("ollama:{model}");
+        Self {
+            client: reqwest::Client::new(),
+            base_url,
+            model,
+            dimension,
+            concurrency: DEFAULT_OLLAMA_CONCURRENCY,
+            id,
         }
+    }
+
+    /// Build from explicit overrides, falling back to `OLLAMA_HOST`/`OLLAMA_PORT` (default
+    /// `localhost`/`11434`), `OLLAMA_EMBEDDING_MODEL` (default `nomic-embed-text`), and
+    /// `OLLAMA_EMBED_CONCURRENCY` for whatever isn't given. Unlike `new`, the embedding dimension
+    /// is read from a real probe request against the server instead of the static model table, so
+    /// it's correct even for a model `default_ollama_dimension` doesn't know about.
+    pub async fn new_with_config(
+        base_url: Option<String>,
+        model: Option<String>,
+        concurrency: Option<usize>,
+    ) -> Result<Self> {
+        let base_url = base_url.unwrap_or_else(|| {
+            let host = std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "localhost".to_string());
+            let port = std::env::var("OLLAMA_PORT").unwrap_or_else(|_| "11434".to_string());
+            format!("http://{host}:{port}")
+        });
+        let model = model.unwrap_or_else(|| {
+            std::env::var("OLLAMA_EMBEDDING_MODEL")
+                .unwrap_or_else(|_| "nomic-embed-text".to_string())
+        });
+        let concurrency = concurrency
+            .or_else(|| {
+                std::env::var("OLLAMA_EMBED_CONCURRENCY").ok().and_then(|v| v.parse().ok())
+            })
+            .unwrap_or(DEFAULT_OLLAMA_CONCURRENCY);
+        let id = format!("ollama:{model}");
+
+        let mut embedder = Self {
+            client: reqwest::Client::new(),
+            base_url,
+            model,
+            dimension: 0,
+            concurrency,
+            id,
+        };
+        embedder.dimension = embedder
+            .embed_one("fn probe() {}")
+            .await
+            .context("Failed to probe Ollama server for embedding dimension")?
+            .len();
+
+        Ok(embedder)
+    }
+
+    fn embeddings_url(&self) -> String {
+        format!("{}/api/embeddings", self.base_url.trim_end_matches('/'))
+    }
 
-        // Generate placeholder embeddings based on preprocessed text
-        let embeddings = preprocessed_texts
+    async fn embed_one(&self, text: &str) -> Result<Vec<f32>> {
+        let response = self
+            .client
+            .post(self.embeddings_url())
+            .json(&serde_json::json!({ "model": self.model, "prompt": text }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow::anyhow!(
+                "Ollama embeddings request failed with status {}: {}",
+                status,
+                body
+            ));
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        let mut embedding: Vec<f32> = body["embedding"]
+            .as_array()
+            .ok_or_else(|| anyhow::anyhow!("Ollama response missing 'embedding' array"))?
             .iter()
-            .map(|text| self.generate_placeholder_embedding(text))
+            .map(|v| v.as_f64().unwrap_or(0.0) as f32)
             .collect();
 
+        normalize_vector(&mut embedding);
+        if !is_valid_vector(&embedding) {
+            return Err(anyhow::anyhow!("Ollama returned an invalid embedding vector (NaN/infinity)"));
+        }
+
+        Ok(embedding)
+    }
+}
+
+#[async_trait]
+impl Embedder for OllamaEmbedder {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.dimension
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
         info!(
-            "Successfully generated {} placeholder embeddings",
-            texts.len()
+            "Ollama embedder processing batch of {} text(s) via {} with concurrency {}",
+            texts.len(),
+            self.model,
+            self.concurrency
         );
 
-        Ok(embeddings)
+        // `buffered` (not `buffer_unordered`) keeps results in the same order as `texts`, so the
+        // caller can zip the result back against the chunks it embedded.
+        stream::iter(texts.iter())
+            .map(|text| self.embed_one(text))
+            .buffered(self.concurrency.max(1))
+            .collect::<Vec<Result<Vec<f32>>>>()
+            .await
+            .into_iter()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn normalize_vector_scales_to_unit_length() {
+        let mut v = vec![3.0, 4.0];
+        normalize_vector(&mut v);
+        assert_eq!(v, vec![0.6, 0.8]);
+    }
+
+    #[test]
+    fn normalize_vector_leaves_zero_vector_unchanged() {
+        let mut v = vec![0.0, 0.0];
+        normalize_vector(&mut v);
+        assert_eq!(v, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn is_valid_vector_rejects_nan_and_infinity() {
+        assert!(is_valid_vector(&[1.0, 2.0, 3.0]));
+        assert!(!is_valid_vector(&[1.0, f32::NAN]));
+        assert!(!is_valid_vector(&[1.0, f32::INFINITY]));
+    }
+
+    #[test]
+    fn ollama_embedder_defaults_dimension_from_known_model() {
+        let embedder = OllamaEmbedder::new(
+            "http://localhost:11434".to_string(),
+            "nomic-embed-text".to_string(),
+            None,
+        );
+        assert_eq!(embedder.embedding_dimension(), 768);
+    }
+
+    #[test]
+    fn ollama_embedder_honors_explicit_dimension_override() {
+        let embedder = OllamaEmbedder::new(
+            "http://localhost:11434".to_string(),
+            "some-custom-model".to_string(),
+            Some(512),
+        );
+        assert_eq!(embedder.embedding_dimension(), 512);
+    }
+
+    #[test]
+    fn openai_embedder_splits_batches_by_item_count() {
+        let embedder = OpenAIEmbedder::new_with_config(
+            "test-key".to_string(),
+            None,
+            Some(OpenAIBatchConfig { max_batch_size: 2, ..OpenAIBatchConfig::default() }),
+        );
+        let texts = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        let batches = embedder.split_into_batches(&texts);
+
+        assert_eq!(batches, vec![vec!["a".to_string(), "b".to_string()], vec!["c".to_string()]]);
+    }
+
+    #[test]
+    fn openai_embedder_splits_batches_by_token_budget() {
+        let embedder = OpenAIEmbedder::new_with_config(
+            "test-key".to_string(),
+            None,
+            Some(OpenAIBatchConfig { max_batch_size: 100, ..OpenAIBatchConfig::default() }),
+        );
+        // max_input_tokens() is 8191; each text below is ~4000 estimated tokens (len/4), so two
+        // fit in one request but a third pushes the running total over budget.
+        let big_text = "x".repeat(16000);
+        let texts = vec![big_text.clone(), big_text.clone(), big_text];
+
+        let batches = embedder.split_into_batches(&texts);
+
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].len(), 2);
+        assert_eq!(batches[1].len(), 1);
     }
 }
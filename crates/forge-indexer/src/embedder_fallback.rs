@@ -0,0 +1,166 @@
+//! Ordered fallback across `Embedder` providers, so a deployment can run locally for dev and
+//! switch to a hosted model in production without re-plumbing the pipeline: if the primary
+//! provider errors (including a rate limit its own retry wrapper already gave up on), the next
+//! provider in the chain is tried for that batch, and so on.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::warn;
+
+use crate::embedder::Embedder;
+
+/// Wraps an ordered chain of `Embedder`s, trying each in turn for a given `embed_batch` call until
+/// one succeeds. `name()`/`embedding_dimension()` report the first (primary) provider's, since
+/// that's what the pipeline sizes its Qdrant collection for -- every provider in the chain is
+/// expected to produce vectors of that same dimension, or a fallback hit would corrupt the index.
+/// `embed_batch_tagged` reports whichever provider in the chain actually produced a batch, so a
+/// fallback hit is still attributable in the Qdrant payload.
+pub struct FallbackEmbedder {
+    chain: Vec<Arc<dyn Embedder>>,
+}
+
+impl FallbackEmbedder {
+    /// `chain` must be non-empty; `chain[0]` is the primary provider, tried first.
+    pub fn new(chain: Vec<Arc<dyn Embedder>>) -> Self {
+        assert!(!chain.is_empty(), "FallbackEmbedder requires at least one provider");
+        Self { chain }
+    }
+}
+
+#[async_trait]
+impl Embedder for FallbackEmbedder {
+    fn name(&self) -> &str {
+        self.chain[0].name()
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.chain[0].embedding_dimension()
+    }
+
+    fn max_input_tokens(&self) -> Option<usize> {
+        self.chain[0].max_input_tokens()
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(self.embed_batch_tagged(texts).await?.into_iter().map(|(vector, _)| vector).collect())
+    }
+
+    async fn embed_batch_tagged(&self, texts: &[String]) -> Result<Vec<(Vec<f32>, String)>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut last_err = None;
+        for (position, provider) in self.chain.iter().enumerate() {
+            match provider.embed_batch_tagged(texts).await {
+                Ok(tagged) => {
+                    if position > 0 {
+                        warn!(
+                            "Embedding fallback: provider '{}' (position {}/{}) served this batch after {} earlier provider(s) failed",
+                            provider.name(),
+                            position + 1,
+                            self.chain.len(),
+                            position
+                        );
+                        let expected = self.embedding_dimension();
+                        if let Some((vector, _)) = tagged.first()
+                            && vector.len() != expected
+                        {
+                            warn!(
+                                "Embedding fallback: provider '{}' returned {}-dimensional vectors but the index expects {} (set by the primary provider) -- these will fail to upsert",
+                                provider.name(),
+                                vector.len(),
+                                expected
+                            );
+                        }
+                    }
+                    return Ok(tagged);
+                }
+                Err(e) => {
+                    warn!(
+                        "Embedding fallback: provider '{}' failed, trying next in chain: {}",
+                        provider.name(),
+                        e
+                    );
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("embedding fallback chain is empty")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct StubEmbedder {
+        id: &'static str,
+        fails: bool,
+    }
+
+    #[async_trait]
+    impl Embedder for StubEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            if self.fails {
+                return Err(anyhow::anyhow!("{}: 429 Too Many Requests", self.id));
+            }
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn name(&self) -> &str {
+            self.id
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn uses_primary_provider_when_it_succeeds() {
+        let fallback = FallbackEmbedder::new(vec![
+            Arc::new(StubEmbedder { id: "primary", fails: false }),
+            Arc::new(StubEmbedder { id: "secondary", fails: false }),
+        ]);
+
+        let tagged = fallback.embed_batch_tagged(&["fn a() {}".to_string()]).await.unwrap();
+
+        assert_eq!(tagged[0].1, "primary");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_next_provider_when_primary_fails() {
+        let fallback = FallbackEmbedder::new(vec![
+            Arc::new(StubEmbedder { id: "primary", fails: true }),
+            Arc::new(StubEmbedder { id: "secondary", fails: false }),
+        ]);
+
+        let tagged = fallback.embed_batch_tagged(&["fn a() {}".to_string()]).await.unwrap();
+
+        assert_eq!(tagged[0].1, "secondary");
+    }
+
+    #[tokio::test]
+    async fn errors_when_every_provider_in_the_chain_fails() {
+        let fallback = FallbackEmbedder::new(vec![
+            Arc::new(StubEmbedder { id: "primary", fails: true }),
+            Arc::new(StubEmbedder { id: "secondary", fails: true }),
+        ]);
+
+        let result = fallback.embed_batch(&["fn a() {}".to_string()]).await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "requires at least one provider")]
+    fn panics_on_empty_chain() {
+        FallbackEmbedder::new(vec![]);
+    }
+}
@@ -0,0 +1,324 @@
+//! Retry/backoff wrapper around an `Embedder`, for providers that throttle under load.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tokio::time::sleep;
+use tracing::warn;
+
+use crate::embedder::Embedder;
+
+/// Retry policy for `RetryingEmbedder`.
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Total number of attempts per call, including the first (non-retry) one.
+    pub max_attempts: u32,
+    /// Backoff before the first retry; doubled on each subsequent retry.
+    pub base_delay: Duration,
+    /// Upper bound on the (pre-jitter) backoff delay.
+    pub max_delay: Duration,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Wraps an `Embedder`, retrying `embed_batch`/`embed` with exponential backoff plus jitter when
+/// the inner embedder's error looks transient (rate limit, timeout, 5xx), honoring a
+/// provider-supplied retry-after delay parsed out of the error message when present -- `Embedder`
+/// has no structured HTTP metadata to carry one through directly. After `max_attempts` a batch
+/// call falls back to embedding texts one at a time, retrying each independently, so a single
+/// persistently-failing input doesn't sink the rest of an otherwise-valid batch.
+pub struct RetryingEmbedder {
+    inner: Arc<dyn Embedder>,
+    config: RetryConfig,
+}
+
+impl RetryingEmbedder {
+    /// Wrap `inner` with the default retry policy (5 attempts, 500ms base backoff).
+    pub fn new(inner: Arc<dyn Embedder>) -> Self {
+        Self::with_config(inner, RetryConfig::default())
+    }
+
+    pub fn with_config(inner: Arc<dyn Embedder>, config: RetryConfig) -> Self {
+        Self { inner, config }
+    }
+
+    /// Run `embed_batch` against the inner embedder, retrying transient failures with backoff.
+    async fn embed_batch_with_retry(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut attempt = 0;
+        loop {
+            match self.inner.embed_batch(texts).await {
+                Ok(embeddings) => return Ok(embeddings),
+                Err(err) if attempt + 1 < self.config.max_attempts && is_retryable(&err) => {
+                    let delay = retry_after(&err).unwrap_or_else(|| self.backoff_delay(attempt));
+                    warn!(
+                        "{}: embed_batch attempt {}/{} failed ({}), retrying in {:?}",
+                        self.inner.name(),
+                        attempt + 1,
+                        self.config.max_attempts,
+                        err,
+                        delay
+                    );
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Exponential backoff from `base_delay`, capped at `max_delay`, with full jitter (a uniform
+    /// random delay between 0 and the capped exponential value) so retrying callers don't all
+    /// wake up in lockstep and re-trigger the same rate limit.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exp = self.config.base_delay.saturating_mul(1u32 << attempt.min(16));
+        let capped = exp.min(self.config.max_delay);
+        let jitter_fraction = pseudo_random_fraction();
+        Duration::from_secs_f64(capped.as_secs_f64() * jitter_fraction)
+    }
+}
+
+#[async_trait]
+impl Embedder for RetryingEmbedder {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.inner.embedding_dimension()
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        match self.embed_batch_with_retry(texts).await {
+            Ok(embeddings) => Ok(embeddings),
+            Err(err) if texts.len() > 1 => {
+                warn!(
+                    "{}: batch of {} still failing after {} attempts ({}), falling back to per-item embedding",
+                    self.inner.name(),
+                    texts.len(),
+                    self.config.max_attempts,
+                    err
+                );
+                let mut embeddings = Vec::with_capacity(texts.len());
+                for text in texts {
+                    embeddings.push(self.embed_batch_with_retry(std::slice::from_ref(text)).await?.pop().ok_or_else(|| {
+                        anyhow::anyhow!("{}: no embedding returned for text", self.inner.name())
+                    })?);
+                }
+                Ok(embeddings)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Whether `err` looks like a transient provider failure worth retrying: a rate limit, a
+/// server-side (5xx) error, or a timeout. Matched on message content since `Embedder::embed_batch`
+/// returns `anyhow::Error` rather than a structured error type.
+pub(crate) fn is_retryable(err: &anyhow::Error) -> bool {
+    let message = err.to_string().to_lowercase();
+    message.contains("429")
+        || message.contains("rate limit")
+        || message.contains("too many requests")
+        || message.contains("timed out")
+        || message.contains("timeout")
+        || message.contains("500")
+        || message.contains("502")
+        || message.contains("503")
+        || message.contains("504")
+}
+
+/// Extract a server-provided retry-after delay from `err`'s message, if the inner embedder
+/// surfaced one (e.g. `"...retry after 12s..."` or `"...retry-after: 12..."`).
+fn retry_after(err: &anyhow::Error) -> Option<Duration> {
+    let message = err.to_string().to_lowercase();
+    let idx = message.find("retry-after").or_else(|| message.find("retry after"))?;
+    let tail = &message[idx..];
+    let digits: String = tail
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse::<u64>().ok().map(Duration::from_secs)
+}
+
+/// A dependency-free uniform value in `[0.0, 1.0)`, seeded from the system clock. Only used for
+/// retry jitter, where cryptographic quality randomness isn't needed -- just enough spread to
+/// avoid synchronized retries.
+fn pseudo_random_fraction() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    (nanos as f64) / 1_000_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct FlakyEmbedder {
+        calls: AtomicUsize,
+        fail_until: usize,
+    }
+
+    #[async_trait]
+    impl Embedder for FlakyEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            if call < self.fail_until {
+                return Err(anyhow::anyhow!("429 Too Many Requests"));
+            }
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            1
+        }
+    }
+
+    fn fast_retry_config() -> RetryConfig {
+        RetryConfig {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_transient_failures_until_success() {
+        let inner = Arc::new(FlakyEmbedder { calls: AtomicUsize::new(0), fail_until: 2 });
+        let retrying = RetryingEmbedder::with_config(inner.clone(), fast_retry_config());
+
+        let result = retrying.embed_batch(&["fn a() {}".to_string()]).await.unwrap();
+
+        assert_eq!(result, vec![vec!["fn a() {}".len() as f32]]);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts_on_persistent_failure() {
+        struct AlwaysFails;
+
+        #[async_trait]
+        impl Embedder for AlwaysFails {
+            async fn embed_batch(&self, _texts: &[String]) -> Result<Vec<Vec<f32>>> {
+                Err(anyhow::anyhow!("503 Service Unavailable"))
+            }
+
+            fn name(&self) -> &str {
+                "always-fails"
+            }
+
+            fn embedding_dimension(&self) -> usize {
+                1
+            }
+        }
+
+        let retrying =
+            RetryingEmbedder::with_config(Arc::new(AlwaysFails), fast_retry_config());
+
+        let result = retrying.embed_batch(&["x".to_string()]).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn non_retryable_error_fails_fast() {
+        struct AuthFailure;
+
+        #[async_trait]
+        impl Embedder for AuthFailure {
+            async fn embed_batch(&self, _texts: &[String]) -> Result<Vec<Vec<f32>>> {
+                Err(anyhow::anyhow!("401 Unauthorized: invalid API key"))
+            }
+
+            fn name(&self) -> &str {
+                "auth-failure"
+            }
+
+            fn embedding_dimension(&self) -> usize {
+                1
+            }
+        }
+
+        let retrying = RetryingEmbedder::with_config(Arc::new(AuthFailure), fast_retry_config());
+
+        let result = retrying.embed_batch(&["x".to_string()]).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_per_item_when_only_the_batch_call_keeps_failing() {
+        // Simulates a provider that rejects multi-item batches outright (e.g. a payload-size
+        // quirk) but happily embeds each text on its own -- the per-item fallback should recover
+        // the full batch instead of failing it.
+        struct RejectsMultiItemBatches {
+            batch_calls: AtomicUsize,
+        }
+
+        #[async_trait]
+        impl Embedder for RejectsMultiItemBatches {
+            async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+                if texts.len() > 1 {
+                    self.batch_calls.fetch_add(1, Ordering::SeqCst);
+                    return Err(anyhow::anyhow!("429 Too Many Requests"));
+                }
+                Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+            }
+
+            fn name(&self) -> &str {
+                "rejects-multi-item-batches"
+            }
+
+            fn embedding_dimension(&self) -> usize {
+                1
+            }
+        }
+
+        let inner = Arc::new(RejectsMultiItemBatches { batch_calls: AtomicUsize::new(0) });
+        let retrying = RetryingEmbedder::with_config(inner.clone(), fast_retry_config());
+
+        let result = retrying
+            .embed_batch(&["one".to_string(), "two".to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(result, vec![vec!["one".len() as f32], vec!["two".len() as f32]]);
+        assert_eq!(inner.batch_calls.load(Ordering::SeqCst), fast_retry_config().max_attempts as usize);
+    }
+
+    #[test]
+    fn retry_after_parses_seconds_from_message() {
+        let err = anyhow::anyhow!("rate limited, retry-after: 12 seconds");
+        assert_eq!(retry_after(&err), Some(Duration::from_secs(12)));
+    }
+
+    #[test]
+    fn retry_after_is_none_without_a_hint() {
+        let err = anyhow::anyhow!("429 Too Many Requests");
+        assert_eq!(retry_after(&err), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! Size/time-threshold batching in front of [`IndexService::add_embeddings_batch`], so a
+//! streaming indexer that discovers chunks one at a time (e.g. the file watcher re-indexing files
+//! as they change) still gets one upsert per batch rather than one gRPC round-trip per chunk.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use qdrant_client::Payload;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::index_svc::IndexService;
+use crate::proto::Chunk;
+use crate::Result;
+
+type Embedding = Vec<f32>;
+
+/// Number of points buffered before an automatic flush, absent an explicit override.
+pub const DEFAULT_BATCH_SIZE: usize = 256;
+/// Time a partially-filled batch is held before an automatic flush, absent an explicit override.
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Accumulates `(Chunk, Embedding, Option<Embedding>, Payload)` tuples -- a required code
+/// embedding and an optional summary embedding -- and flushes them to `IndexService` as a single
+/// batched upsert once either `max_batch_size` points have accumulated or `flush_interval` has
+/// elapsed since the last flush -- whichever comes first. A flush failure is returned to the
+/// caller but doesn't poison the batcher; pushing more chunks afterward starts a fresh batch
+/// rather than aborting the whole index run.
+pub struct EmbeddingBatcher {
+    index_service: Arc<RwLock<IndexService>>,
+    max_batch_size: usize,
+    flush_interval: Duration,
+    pending: Vec<(Chunk, Embedding, Option<Embedding>, Payload)>,
+    last_flush: Instant,
+}
+
+impl EmbeddingBatcher {
+    /// Wrap `index_service` with the default thresholds (256 points, 5 seconds).
+    pub fn new(index_service: Arc<RwLock<IndexService>>) -> Self {
+        Self::with_config(index_service, DEFAULT_BATCH_SIZE, DEFAULT_FLUSH_INTERVAL)
+    }
+
+    pub fn with_config(
+        index_service: Arc<RwLock<IndexService>>,
+        max_batch_size: usize,
+        flush_interval: Duration,
+    ) -> Self {
+        Self {
+            index_service,
+            max_batch_size,
+            flush_interval,
+            pending: Vec::new(),
+            last_flush: Instant::now(),
+        }
+    }
+
+    /// Buffer one chunk's embedding(s), flushing automatically once the size or time threshold is
+    /// reached. `summary_embedding` is optional since not every caller has a summary to embed.
+    /// Returns the number of points written if a flush happened, `0` otherwise.
+    pub async fn push(
+        &mut self,
+        chunk: Chunk,
+        code_embedding: Embedding,
+        summary_embedding: Option<Embedding>,
+        payload: Payload,
+    ) -> Result<usize> {
+        self.pending.push((chunk, code_embedding, summary_embedding, payload));
+
+        if self.pending.len() >= self.max_batch_size || self.last_flush.elapsed() >= self.flush_interval {
+            return self.flush().await;
+        }
+        Ok(0)
+    }
+
+    /// Flush whatever is currently buffered regardless of thresholds, e.g. at the end of an
+    /// index run so the last partial batch isn't dropped. A no-op if nothing is pending.
+    pub async fn flush(&mut self) -> Result<usize> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+
+        let batch = std::mem::take(&mut self.pending);
+        let count = batch.len();
+        self.last_flush = Instant::now();
+
+        match self.index_service.write().await.add_embeddings_batch(batch).await {
+            Ok(()) => {
+                debug!("Flushed batch of {} embeddings", count);
+                Ok(count)
+            }
+            Err(e) => {
+                warn!("Failed to flush batch of {} embeddings: {}", count, e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Number of points currently buffered, awaiting a flush.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
@@ -0,0 +1,295 @@
+//! Content-digest cache in front of an `Embedder`, skipping provider calls for chunk text that was
+//! already embedded in a previous run. The cache backend itself is pluggable via the
+//! [`EmbeddingCache`] trait, so the same [`CachingEmbedder`] wrapper works uniformly whether
+//! entries live only in memory or persist to disk.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::embedder::Embedder;
+
+/// A cache entry: the embedding vector alongside the id of the provider that produced it, so a
+/// cache hit can still be tagged correctly if the embedder wraps a `FallbackEmbedder` that may
+/// have switched providers between runs.
+type CacheEntry = (Vec<f32>, String);
+
+/// Pluggable storage backend for [`CachingEmbedder`]. Keys are pre-salted, stable digests (see
+/// `CachingEmbedder::cache_key`) -- implementations only need to get/put opaque entries by key.
+#[async_trait]
+pub trait EmbeddingCache: Send + Sync {
+    async fn get(&self, key: &str) -> Option<CacheEntry>;
+    async fn put(&self, key: &str, entry: CacheEntry);
+}
+
+/// In-memory cache backend. Entries don't survive the process, but there's no I/O on the hot
+/// path -- useful for short-lived indexing runs or tests where a durable cache adds no value.
+#[derive(Default)]
+pub struct InMemoryEmbeddingCache {
+    entries: RwLock<HashMap<String, CacheEntry>>,
+}
+
+impl InMemoryEmbeddingCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EmbeddingCache for InMemoryEmbeddingCache {
+    async fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.entries.read().await.get(key).cloned()
+    }
+
+    async fn put(&self, key: &str, entry: CacheEntry) {
+        self.entries.write().await.insert(key.to_string(), entry);
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct JsonCacheStore {
+    /// Changing this shape invalidates caches written before it existed -- `load` already treats
+    /// a parse failure as an empty store, so that's a silent (if one-time) cache miss, not an
+    /// error.
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Persistent cache backend, storing entries as a single JSON file on disk so they survive across
+/// indexing runs, mirroring the on-disk JSON persistence already used for the index manifest and
+/// keyword index elsewhere in this codebase.
+pub struct JsonFileEmbeddingCache {
+    store_path: PathBuf,
+    store: RwLock<JsonCacheStore>,
+}
+
+impl JsonFileEmbeddingCache {
+    /// Load any cached entries from `store_path` if it already exists.
+    pub async fn new(store_path: impl Into<PathBuf>) -> Self {
+        let store_path = store_path.into();
+        let store = Self::load(&store_path).await;
+        Self { store_path, store: RwLock::new(store) }
+    }
+
+    async fn load(path: &Path) -> JsonCacheStore {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => JsonCacheStore::default(),
+        }
+    }
+
+    async fn persist(&self) -> Result<()> {
+        if let Some(parent) = self.store_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let bytes = serde_json::to_vec(&*self.store.read().await)?;
+        tokio::fs::write(&self.store_path, bytes).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EmbeddingCache for JsonFileEmbeddingCache {
+    async fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.store.read().await.entries.get(key).cloned()
+    }
+
+    async fn put(&self, key: &str, entry: CacheEntry) {
+        self.store.write().await.entries.insert(key.to_string(), entry);
+        if let Err(e) = self.persist().await {
+            warn!("JsonFileEmbeddingCache: failed to persist cache to {:?}: {}", self.store_path, e);
+        }
+    }
+}
+
+/// Wraps an `Embedder`, looking up each text by a stable digest of its content (salted with the
+/// inner embedder's `name()`/dimension so entries never collide across providers) in a pluggable
+/// [`EmbeddingCache`] before issuing a provider call for misses.
+pub struct CachingEmbedder {
+    inner: Arc<dyn Embedder>,
+    key_salt: String,
+    cache: Arc<dyn EmbeddingCache>,
+}
+
+impl CachingEmbedder {
+    /// Wrap `inner` with a [`JsonFileEmbeddingCache`] backed by `store_path`, loading any entries
+    /// already on disk there.
+    pub async fn new(inner: Arc<dyn Embedder>, store_path: impl Into<PathBuf>) -> Result<Self> {
+        Ok(Self::with_cache(inner, Arc::new(JsonFileEmbeddingCache::new(store_path).await)))
+    }
+
+    /// Wrap `inner` with an arbitrary [`EmbeddingCache`] backend, e.g. [`InMemoryEmbeddingCache`]
+    /// for a run that shouldn't touch disk.
+    pub fn with_cache(inner: Arc<dyn Embedder>, cache: Arc<dyn EmbeddingCache>) -> Self {
+        let key_salt = format!("{}:{}", inner.name(), inner.embedding_dimension());
+        Self { inner, key_salt, cache }
+    }
+
+    fn cache_key(&self, text: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.key_salt.as_bytes());
+        hasher.update(text.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+#[async_trait]
+impl Embedder for CachingEmbedder {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.inner.embedding_dimension()
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        Ok(self.embed_batch_tagged(texts).await?.into_iter().map(|(vector, _)| vector).collect())
+    }
+
+    async fn embed_batch_tagged(&self, texts: &[String]) -> Result<Vec<(Vec<f32>, String)>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let keys: Vec<String> = texts.iter().map(|t| self.cache_key(t)).collect();
+        let mut results: Vec<Option<CacheEntry>> = Vec::with_capacity(keys.len());
+        for key in &keys {
+            results.push(self.cache.get(key).await);
+        }
+
+        let misses: Vec<usize> =
+            results.iter().enumerate().filter(|(_, v)| v.is_none()).map(|(i, _)| i).collect();
+
+        if misses.is_empty() {
+            debug!("CachingEmbedder: all {} text(s) served from cache", texts.len());
+            return Ok(results.into_iter().map(|v| v.expect("checked above")).collect());
+        }
+
+        let miss_texts: Vec<String> = misses.iter().map(|&i| texts[i].clone()).collect();
+        debug!(
+            "CachingEmbedder: {}/{} text(s) missed cache, calling {} embedder",
+            miss_texts.len(),
+            texts.len(),
+            self.inner.name()
+        );
+
+        let fresh = self.inner.embed_batch_tagged(&miss_texts).await?;
+        if fresh.len() != miss_texts.len() {
+            return Err(anyhow::anyhow!(
+                "embedder {} returned {} vector(s) for {} miss(es)",
+                self.inner.name(),
+                fresh.len(),
+                miss_texts.len()
+            ));
+        }
+
+        for (&i, tagged) in misses.iter().zip(fresh.iter()) {
+            self.cache.put(&keys[i], tagged.clone()).await;
+            results[i] = Some(tagged.clone());
+        }
+
+        Ok(results.into_iter().map(|v| v.expect("every miss filled above")).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// A fresh on-disk path for each test, under the OS temp dir.
+    fn temp_cache_path() -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("forge-indexer-embedding-cache-test-{}-{id}.json", std::process::id()))
+    }
+
+    struct CountingEmbedder {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Embedder for CountingEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_provider_call_for_cached_text() {
+        let store_path = temp_cache_path();
+        let inner = Arc::new(CountingEmbedder { calls: AtomicUsize::new(0) });
+        let cache = CachingEmbedder::new(inner.clone(), &store_path).await.unwrap();
+
+        let texts = vec!["fn a() {}".to_string()];
+        cache.embed_batch(&texts).await.unwrap();
+        cache.embed_batch(&texts).await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn merges_cached_and_fresh_vectors_in_original_order() {
+        let store_path = temp_cache_path();
+        let inner = Arc::new(CountingEmbedder { calls: AtomicUsize::new(0) });
+        let cache = CachingEmbedder::new(inner.clone(), &store_path).await.unwrap();
+
+        cache.embed_batch(&["cached".to_string()]).await.unwrap();
+
+        let texts = vec!["cached".to_string(), "fresh".to_string()];
+        let embedded = cache.embed_batch(&texts).await.unwrap();
+
+        assert_eq!(embedded.len(), 2);
+        assert_eq!(embedded[0], vec!["cached".len() as f32]);
+        assert_eq!(embedded[1], vec!["fresh".len() as f32]);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn persists_cache_across_instances() {
+        let store_path = temp_cache_path();
+        let inner = Arc::new(CountingEmbedder { calls: AtomicUsize::new(0) });
+
+        let cache = CachingEmbedder::new(inner.clone(), &store_path).await.unwrap();
+        cache.embed_batch(&["fn a() {}".to_string()]).await.unwrap();
+
+        let reloaded = CachingEmbedder::new(inner.clone(), &store_path).await.unwrap();
+        reloaded.embed_batch(&["fn a() {}".to_string()]).await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn in_memory_backend_skips_provider_call_for_cached_text() {
+        let inner = Arc::new(CountingEmbedder { calls: AtomicUsize::new(0) });
+        let cache = CachingEmbedder::with_cache(inner.clone(), Arc::new(InMemoryEmbeddingCache::new()));
+
+        let texts = vec!["fn a() {}".to_string()];
+        cache.embed_batch(&texts).await.unwrap();
+        cache.embed_batch(&texts).await.unwrap();
+
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+    }
+}
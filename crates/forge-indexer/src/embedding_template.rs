@@ -0,0 +1,273 @@
+//! Configurable, validated rendering of a chunk's structured metadata into the text handed to an
+//! `Embedder`, following MeiliSearch's prompt-template approach (mirroring
+//! `forge_domain::PromptTemplate`, which does the same for search queries). Replaces the old
+//! `preprocess_code_for_embedding`/`LocalEmbedder::enhance_*` helpers, which detected the
+//! language by substring-matching the code itself (so `"test"` anywhere in a chunk tagged it as
+//! `TEST_CODE`) and injected fixed markers with no way to customize them. A template is rejected
+//! at construction time if it references an unknown field, so a typo surfaces before indexing
+//! begins rather than silently embedding literal `{{...}}` text.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, bail};
+
+use crate::chunker::Chunk;
+
+/// Placeholders a template is allowed to reference
+const KNOWN_FIELDS: &[&str] = &["path", "lang", "symbol", "node_kind", "content"];
+
+/// Structured metadata about a chunk available to a template, beyond its raw code
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkContext<'a> {
+    pub path: &'a str,
+    pub lang: &'a str,
+    pub symbol: Option<&'a str>,
+    /// The tree-sitter node kind (e.g. `function_item`) the chunk was extracted from.
+    /// `from_chunk` always leaves it `None`, since `Chunk` does not record a node kind, and a
+    /// `{{node_kind}}` placeholder then renders as an empty string.
+    pub node_kind: Option<&'a str>,
+    pub code: &'a str,
+}
+
+impl<'a> ChunkContext<'a> {
+    pub fn from_chunk(chunk: &'a Chunk) -> Self {
+        Self {
+            path: &chunk.path,
+            lang: &chunk.lang,
+            symbol: chunk.symbol.as_deref(),
+            node_kind: None,
+            code: &chunk.code,
+        }
+    }
+}
+
+/// A `{{field}}` interpolation template rendered into the text handed to an `Embedder`. Validated
+/// at construction time so a typo in a field name is rejected before indexing starts rather than
+/// silently embedding the literal placeholder text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingTemplate {
+    template: String,
+    /// Maximum length of the rendered text before truncation
+    max_length: usize,
+}
+
+impl EmbeddingTemplate {
+    /// Build a template, rejecting unknown `{{field}}` placeholders up front
+    pub fn new(template: impl Into<String>, max_length: usize) -> Result<Self> {
+        let template = template.into();
+
+        for field in extract_placeholders(&template) {
+            if !KNOWN_FIELDS.contains(&field.as_str()) {
+                bail!(
+                    "Unknown embedding template field '{{{{{field}}}}}'; expected one of {:?}",
+                    KNOWN_FIELDS
+                );
+            }
+        }
+
+        Ok(Self { template, max_length })
+    }
+
+    /// Render the template against a chunk's fields, truncating to `max_length` if needed
+    pub fn render(&self, ctx: &ChunkContext) -> String {
+        let mut rendered = self.template.clone();
+        rendered = rendered.replace("{{path}}", ctx.path);
+        rendered = rendered.replace("{{lang}}", ctx.lang);
+        rendered = rendered.replace("{{symbol}}", ctx.symbol.unwrap_or(""));
+        rendered = rendered.replace("{{node_kind}}", ctx.node_kind.unwrap_or(""));
+        rendered = rendered.replace("{{content}}", ctx.code);
+
+        if rendered.len() > self.max_length {
+            rendered.truncate(self.max_length);
+        }
+
+        rendered
+    }
+}
+
+impl Default for EmbeddingTemplate {
+    fn default() -> Self {
+        Self::new("{{content}}", 8000).expect("default template references only known fields")
+    }
+}
+
+/// Extract the names of `{{field}}` placeholders in a template string
+fn extract_placeholders(template: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+
+        fields.push(after_open[..end].to_string());
+        rest = &after_open[end + 2..];
+    }
+
+    fields
+}
+
+/// Per-language set of templates: a chunk is rendered through the template registered for its
+/// `lang`, falling back to `default` for every other language. Ships with sensible defaults for
+/// the languages this crate chunks most (see `Default` below), but every one of them -- including
+/// the fallback -- can be overridden, e.g. from `PipelineConfig::embedding_template_overrides`.
+#[derive(Debug, Clone)]
+pub struct EmbeddingTemplateSet {
+    default: EmbeddingTemplate,
+    per_language: HashMap<String, EmbeddingTemplate>,
+}
+
+impl EmbeddingTemplateSet {
+    pub fn new(default: EmbeddingTemplate) -> Self {
+        Self { default, per_language: HashMap::new() }
+    }
+
+    /// Register (or replace) the template used for `lang`
+    pub fn with_language(mut self, lang: impl Into<String>, template: EmbeddingTemplate) -> Self {
+        self.per_language.insert(lang.into(), template);
+        self
+    }
+
+    /// Build a set from user-supplied raw template strings keyed by language name (e.g.
+    /// `PipelineConfig::embedding_template_overrides`), validating every one up front so a bad
+    /// template is rejected at load time instead of surfacing as a garbage embedding mid-run.
+    pub fn from_overrides(overrides: &HashMap<String, String>, max_length: usize) -> Result<Self> {
+        let mut set = Self::default();
+        for (lang, template) in overrides {
+            let template = EmbeddingTemplate::new(template.clone(), max_length)
+                .with_context(|| format!("invalid embedding template for language '{lang}'"))?;
+            set = set.with_language(lang.clone(), template);
+        }
+        Ok(set)
+    }
+
+    pub fn render(&self, ctx: &ChunkContext) -> String {
+        self.per_language.get(ctx.lang).unwrap_or(&self.default).render(ctx)
+    }
+}
+
+impl Default for EmbeddingTemplateSet {
+    fn default() -> Self {
+        Self::new(EmbeddingTemplate::default())
+            .with_language(
+                "rust",
+                EmbeddingTemplate::new("// rust {{node_kind}} {{symbol}}\n{{content}}", 8000)
+                    .expect("valid default rust template"),
+            )
+            .with_language(
+                "python",
+                EmbeddingTemplate::new("# python {{node_kind}} {{symbol}}\n{{content}}", 8000)
+                    .expect("valid default python template"),
+            )
+            .with_language(
+                "javascript",
+                EmbeddingTemplate::new("// javascript {{node_kind}} {{symbol}}\n{{content}}", 8000)
+                    .expect("valid default javascript template"),
+            )
+            .with_language(
+                "typescript",
+                EmbeddingTemplate::new("// typescript {{node_kind}} {{symbol}}\n{{content}}", 8000)
+                    .expect("valid default typescript template"),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn chunk_with(path: &str, lang: &str, symbol: Option<&str>, code: &str) -> Chunk {
+        Chunk {
+            id: "id".to_string(),
+            path: path.to_string(),
+            lang: lang.to_string(),
+            symbol: symbol.map(str::to_string),
+            rev: "rev".to_string(),
+            size: code.len(),
+            code: code.to_string(),
+            summary: None,
+            start_byte: 0,
+            end_byte: code.len(),
+            start_line: 1,
+            end_line: 1,
+            embedding: None,
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_field_at_construction() {
+        let result = EmbeddingTemplate::new("{{path}}: {{nonsense}}", 1000);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn renders_path_and_symbol_context_around_content() {
+        let template =
+            EmbeddingTemplate::new("File: {{path}}\nSymbol: {{symbol}}\n{{content}}", 1000).unwrap();
+        let chunk = chunk_with("src/lib.rs", "rust", Some("parse"), "fn parse() {}");
+
+        let actual = template.render(&ChunkContext::from_chunk(&chunk));
+
+        assert_eq!(actual, "File: src/lib.rs\nSymbol: parse\nfn parse() {}");
+    }
+
+    #[test]
+    fn truncates_rendered_output_to_max_length() {
+        let template = EmbeddingTemplate::new("{{content}}", 5).unwrap();
+        let chunk = chunk_with("src/lib.rs", "rust", None, "0123456789");
+
+        let actual = template.render(&ChunkContext::from_chunk(&chunk));
+
+        assert_eq!(actual, "01234");
+    }
+
+    #[test]
+    fn template_set_falls_back_to_default_for_unregistered_language() {
+        let set = EmbeddingTemplateSet::new(EmbeddingTemplate::new("plain: {{content}}", 1000).unwrap());
+        let chunk = chunk_with("main.go", "go", None, "func main() {}");
+
+        let actual = set.render(&ChunkContext::from_chunk(&chunk));
+
+        assert_eq!(actual, "plain: func main() {}");
+    }
+
+    #[test]
+    fn template_set_uses_registered_language_template() {
+        let set = EmbeddingTemplateSet::new(EmbeddingTemplate::default()).with_language(
+            "rust",
+            EmbeddingTemplate::new("RUST: {{content}}", 1000).unwrap(),
+        );
+        let chunk = chunk_with("src/lib.rs", "rust", None, "fn parse() {}");
+
+        let actual = set.render(&ChunkContext::from_chunk(&chunk));
+
+        assert_eq!(actual, "RUST: fn parse() {}");
+    }
+
+    #[test]
+    fn from_overrides_rejects_invalid_template_up_front() {
+        let mut overrides = HashMap::new();
+        overrides.insert("rust".to_string(), "{{bogus}}".to_string());
+
+        let result = EmbeddingTemplateSet::from_overrides(&overrides, 8000);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_overrides_applies_valid_template() {
+        let mut overrides = HashMap::new();
+        overrides.insert("rust".to_string(), "RUST: {{content}}".to_string());
+
+        let set = EmbeddingTemplateSet::from_overrides(&overrides, 1000).unwrap();
+        let chunk = chunk_with("src/lib.rs", "rust", None, "fn parse() {}");
+
+        let actual = set.render(&ChunkContext::from_chunk(&chunk));
+
+        assert_eq!(actual, "RUST: fn parse() {}");
+    }
+}
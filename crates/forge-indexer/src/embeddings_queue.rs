@@ -0,0 +1,245 @@
+//! Batches chunks between the chunker and the `Embedder` trait, flushing by token budget rather
+//! than a fixed item count, and committing a file's embeddings atomically.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use crate::chunker::Chunk;
+use crate::chunker::token_budget::estimate_tokens;
+use crate::embedder::{Embedder, normalize_vector};
+use crate::embedding_template::{ChunkContext, EmbeddingTemplateSet};
+use crate::errors::{ForgeIndexerError, Result};
+
+fn content_hash(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Accumulates chunks ahead of the embedder and flushes a sub-batch once the summed estimated
+/// token count approaches `token_ceiling`, rather than after a fixed `batch_size` count of
+/// chunks. `embed_file` commits a whole file's chunks atomically: if any sub-batch fails, no
+/// embeddings for that file are returned, so a partially-embedded file never reaches the index.
+pub struct EmbeddingsQueue {
+    embedder: Arc<dyn Embedder>,
+    token_ceiling: usize,
+    /// Renders each chunk's structured metadata into the text actually sent to `embedder`,
+    /// in place of embedding its raw `code` directly.
+    template_set: Arc<EmbeddingTemplateSet>,
+}
+
+impl EmbeddingsQueue {
+    /// `token_ceiling` should be set below the embedder provider's per-request token limit to
+    /// leave headroom for request overhead.
+    pub fn new(
+        embedder: Arc<dyn Embedder>,
+        token_ceiling: usize,
+        template_set: Arc<EmbeddingTemplateSet>,
+    ) -> Self {
+        Self { embedder, token_ceiling, template_set }
+    }
+
+    /// Embed every chunk belonging to a single file, packing sub-batches by token budget and
+    /// committing the whole set atomically: a failure in any sub-batch discards everything
+    /// embedded so far for this file and returns the error.
+    ///
+    /// Within each sub-batch, chunks whose `code` is byte-identical (license headers, vendored
+    /// boilerplate, generated files) are embedded once: the embedder only ever sees the set of
+    /// *unique* texts, and the resulting vector is fanned back out to every chunk that shared it.
+    /// Every returned vector is L2-normalized to unit length -- downstream search uses dot-product
+    /// similarity, which is only a meaningful proxy for cosine similarity on unit vectors -- and
+    /// tagged with the id of the provider (e.g. an `embed_batch_tagged`-aware `FallbackEmbedder`
+    /// chain) that actually produced it, so a mixed-provider index can be filtered by provider.
+    /// Returns the embedded chunks with their provider tag, alongside how many duplicate chunks
+    /// were skipped this way.
+    pub async fn embed_file(&self, chunks: &[Chunk]) -> Result<(Vec<(Chunk, Vec<f32>, String)>, usize)> {
+        let mut embedded = Vec::with_capacity(chunks.len());
+        let mut duplicates_deduplicated = 0usize;
+
+        for batch in self.pack_by_token_budget(chunks) {
+            let mut unique_texts: Vec<String> = Vec::new();
+            let mut hash_to_unique_index: HashMap<String, usize> = HashMap::new();
+            let mut chunk_unique_index: Vec<usize> = Vec::with_capacity(batch.len());
+
+            for chunk in &batch {
+                let hash = content_hash(&chunk.code);
+                let index = *hash_to_unique_index.entry(hash).or_insert_with(|| {
+                    unique_texts.push(self.template_set.render(&ChunkContext::from_chunk(chunk)));
+                    unique_texts.len() - 1
+                });
+                chunk_unique_index.push(index);
+            }
+            duplicates_deduplicated += batch.len() - unique_texts.len();
+
+            let tagged = self.embedder.embed_batch_tagged(&unique_texts).await.map_err(|e| {
+                ForgeIndexerError::embedding_error(format!(
+                    "failed to embed batch of {} unique text(s) ({} chunk(s)) for file {}: {e}",
+                    unique_texts.len(),
+                    batch.len(),
+                    batch.first().map(|c| c.path.as_str()).unwrap_or("<unknown>")
+                ))
+            })?;
+
+            if tagged.len() != unique_texts.len() {
+                return Err(ForgeIndexerError::embedding_error(format!(
+                    "embedder returned {} vector(s) for {} unique text(s)",
+                    tagged.len(),
+                    unique_texts.len()
+                )));
+            }
+
+            let mut unique_vectors: Vec<(Vec<f32>, String)> = tagged;
+            for (vector, _) in &mut unique_vectors {
+                normalize_vector(vector);
+            }
+
+            embedded.extend(batch.into_iter().zip(chunk_unique_index).map(|(chunk, i)| {
+                let (vector, provider) = unique_vectors[i].clone();
+                (chunk, vector, provider)
+            }));
+        }
+
+        debug!(
+            "EmbeddingsQueue embedded {} chunk(s) atomically for {} ({} duplicate(s) deduplicated)",
+            embedded.len(),
+            embedded.first().map(|(c, ..)| c.path.as_str()).unwrap_or("<unknown>"),
+            duplicates_deduplicated
+        );
+
+        Ok((embedded, duplicates_deduplicated))
+    }
+
+    /// Greedily pack chunks into sub-batches whose summed estimated token count stays under
+    /// `token_ceiling`. A single chunk exceeding the ceiling alone is still emitted as its own
+    /// batch rather than dropped.
+    fn pack_by_token_budget(&self, chunks: &[Chunk]) -> Vec<Vec<Chunk>> {
+        let mut batches = Vec::new();
+        let mut current = Vec::new();
+        let mut current_tokens = 0usize;
+
+        for chunk in chunks {
+            let chunk_tokens = estimate_tokens(&chunk.code);
+
+            if !current.is_empty() && current_tokens + chunk_tokens > self.token_ceiling {
+                batches.push(std::mem::take(&mut current));
+                current_tokens = 0;
+            }
+
+            current_tokens += chunk_tokens;
+            current.push(chunk.clone());
+        }
+
+        if !current.is_empty() {
+            batches.push(current);
+        }
+
+        batches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    struct StubEmbedder;
+
+    #[async_trait]
+    impl Embedder for StubEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            1
+        }
+    }
+
+    fn chunk(id: &str, code: &str) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: "file.rs".to_string(),
+            lang: "rust".to_string(),
+            symbol: None,
+            rev: "rev".to_string(),
+            size: code.len(),
+            code: code.to_string(),
+            summary: None,
+            start_byte: 0,
+            end_byte: code.len(),
+            start_line: 1,
+            end_line: 1,
+            embedding: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn embeds_all_chunks_for_a_file() {
+        let queue =
+            EmbeddingsQueue::new(Arc::new(StubEmbedder), 512, Arc::new(EmbeddingTemplateSet::default()));
+        let chunks = vec![chunk("1", "fn a() {}"), chunk("2", "fn b() {}")];
+
+        let (embedded, duplicates) = queue.embed_file(&chunks).await.unwrap();
+
+        assert_eq!(embedded.len(), 2);
+        assert_eq!(duplicates, 0);
+    }
+
+    #[tokio::test]
+    async fn packs_into_multiple_batches_under_tight_budget() {
+        let queue =
+            EmbeddingsQueue::new(Arc::new(StubEmbedder), 1, Arc::new(EmbeddingTemplateSet::default()));
+        let chunks = vec![chunk("1", "fn a() {}"), chunk("2", "fn b() {}")];
+
+        let batches = queue.pack_by_token_budget(&chunks);
+
+        assert_eq!(batches.len(), 2);
+    }
+
+    struct CountingEmbedder {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Embedder for CountingEmbedder {
+        async fn embed_batch(&self, texts: &[String]) -> anyhow::Result<Vec<Vec<f32>>> {
+            self.calls.fetch_add(texts.len(), std::sync::atomic::Ordering::SeqCst);
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            1
+        }
+    }
+
+    #[tokio::test]
+    async fn deduplicates_identical_chunk_text_within_a_batch() {
+        let embedder = Arc::new(CountingEmbedder { calls: std::sync::atomic::AtomicUsize::new(0) });
+        let queue = EmbeddingsQueue::new(embedder.clone(), 512, Arc::new(EmbeddingTemplateSet::default()));
+        let chunks = vec![
+            chunk("1", "// license header"),
+            chunk("2", "fn unique() {}"),
+            chunk("3", "// license header"),
+        ];
+
+        let (embedded, duplicates) = queue.embed_file(&chunks).await.unwrap();
+
+        assert_eq!(embedded.len(), 3);
+        assert_eq!(duplicates, 1);
+        assert_eq!(embedder.calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(embedded[0].1, embedded[2].1, "duplicate chunks should share the same vector");
+    }
+}
@@ -1,25 +1,153 @@
 //! Index service that manages the vector store
 
+use std::collections::HashMap;
 use std::env;
+use std::sync::atomic::{AtomicU64, Ordering};
 
+use qdrant_client::qdrant::quantization_config::Quantization as QuantizationKind;
 use qdrant_client::qdrant::vectors_config::Config as VectorConfig;
 use qdrant_client::qdrant::with_payload_selector::SelectorOptions;
 use qdrant_client::qdrant::{
-    CreateCollectionBuilder, DeletePointsBuilder, Distance, PointStruct, SearchPointsBuilder,
-    UpsertPointsBuilder, VectorParams, VectorsConfig,
+    CompressionRatio, Condition, CreateCollectionBuilder, CreateFieldIndexCollectionBuilder,
+    DeletePointsBuilder, Distance, FieldType, Filter, HnswConfigDiff, NamedVectors, PointStruct,
+    ProductQuantization, QuantizationConfig, QuantizationType, ScalarQuantization,
+    SearchPointsBuilder, SnapshotDescription, UpsertPointsBuilder, VectorParams, VectorParamsMap,
+    VectorsConfig,
 };
 use qdrant_client::{Payload, Qdrant};
 use tracing::{debug, error, info, warn};
 
+use crate::keyword_index::{self, KeywordIndex};
 use crate::proto::Chunk;
 use crate::{ForgeIndexerError, Result};
 
 type Embedding = Vec<f32>;
 
+/// Named vector carrying a chunk's code embedding -- the space to query when searching "by
+/// implementation".
+pub const CODE_VECTOR: &str = "code";
+/// Named vector carrying a chunk's summary embedding -- the space to query when searching "by
+/// intent". Only present on points indexed with a summary embedding.
+pub const SUMMARY_VECTOR: &str = "summary";
+
+/// Which Qdrant quantization scheme (if any) to apply to the collection's vectors, trading a
+/// small recall loss for a large memory/speed win on a big code index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantization {
+    None,
+    Scalar,
+    Product,
+}
+
+/// HNSW and quantization knobs applied at collection creation, read from env by `new` so
+/// operators can tune a production index without a code change -- mirroring the crate's existing
+/// env-driven Qdrant configuration (`QDRANT_URL`, `QDRANT_COLLECTION`, etc).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexingProfile {
+    pub quantization: Quantization,
+    pub hnsw_m: Option<u64>,
+    pub hnsw_ef_construct: Option<u64>,
+}
+
+impl IndexingProfile {
+    /// Read `QDRANT_QUANTIZATION` (`scalar`|`product`|anything else means `none`),
+    /// `QDRANT_HNSW_M`, and `QDRANT_HNSW_EF_CONSTRUCT` from the environment.
+    fn from_env() -> Self {
+        let quantization = match env::var("QDRANT_QUANTIZATION").unwrap_or_default().to_lowercase().as_str() {
+            "scalar" => Quantization::Scalar,
+            "product" => Quantization::Product,
+            _ => Quantization::None,
+        };
+        let hnsw_m = env::var("QDRANT_HNSW_M").ok().and_then(|v| v.parse().ok());
+        let hnsw_ef_construct = env::var("QDRANT_HNSW_EF_CONSTRUCT").ok().and_then(|v| v.parse().ok());
+        Self { quantization, hnsw_m, hnsw_ef_construct }
+    }
+
+    fn hnsw_config(&self) -> Option<HnswConfigDiff> {
+        if self.hnsw_m.is_none() && self.hnsw_ef_construct.is_none() {
+            return None;
+        }
+        Some(HnswConfigDiff {
+            m: self.hnsw_m,
+            ef_construct: self.hnsw_ef_construct,
+            ..Default::default()
+        })
+    }
+
+    fn quantization_config(&self) -> Option<QuantizationConfig> {
+        match self.quantization {
+            Quantization::None => None,
+            Quantization::Scalar => Some(QuantizationConfig {
+                quantization: Some(QuantizationKind::Scalar(ScalarQuantization {
+                    r#type: QuantizationType::Int8.into(),
+                    quantile: Some(0.99),
+                    always_ram: Some(true),
+                })),
+            }),
+            Quantization::Product => Some(QuantizationConfig {
+                quantization: Some(QuantizationKind::Product(ProductQuantization {
+                    compression: CompressionRatio::X16.into(),
+                    always_ram: Some(true),
+                })),
+            }),
+        }
+    }
+
+    /// Whether an already-existing collection's `hnsw_config`/`quantization_config` (as reported
+    /// by `collection_info`) still matches this profile, so `new` knows whether to recreate the
+    /// collection when the profile has been switched via env vars since the last run.
+    fn matches(&self, config: &qdrant_client::qdrant::CollectionConfig) -> bool {
+        let quantization_matches = match (&config.quantization_config, self.quantization) {
+            (None, Quantization::None) => true,
+            (Some(qc), Quantization::Scalar) => {
+                matches!(qc.quantization, Some(QuantizationKind::Scalar(_)))
+            }
+            (Some(qc), Quantization::Product) => {
+                matches!(qc.quantization, Some(QuantizationKind::Product(_)))
+            }
+            _ => false,
+        };
+
+        let hnsw_matches = match &config.hnsw_config {
+            Some(h) => h.m == self.hnsw_m && h.ef_construct == self.hnsw_ef_construct,
+            None => self.hnsw_m.is_none() && self.hnsw_ef_construct.is_none(),
+        };
+
+        quantization_matches && hnsw_matches
+    }
+}
+
+/// Where a previously-indexed chunk lives, so `update_file` can delete its Qdrant point and
+/// evict it from the `KeywordIndex` if the chunk vanishes from a later version of the file.
+#[derive(Clone)]
+struct IndexedChunkRef {
+    point_id: String,
+    chunk_id: String,
+}
+
 pub struct IndexService {
     client: Qdrant,
     collection_name: String,
+    /// Base collection name (without the namespace prefix), kept around so `clone_collection` can
+    /// compose a sibling collection name the same way `new` composes `collection_name`.
+    base_collection: String,
+    /// Qdrant's REST endpoint, used only for snapshot recovery -- the gRPC API `client` otherwise
+    /// talks has no "recover collection from snapshot" RPC, only the REST API does.
+    qdrant_rest_url: String,
     vector_dimension: usize,
+    indexing_profile: IndexingProfile,
+    keyword_index: KeywordIndex,
+    /// Per-path record of the chunk content hashes currently indexed, used by `update_file` to
+    /// diff a re-chunked file against what's already there instead of re-embedding and
+    /// re-upserting every chunk on every save. Keyed by path -> content hash -> indexed chunk.
+    file_chunks: HashMap<String, HashMap<String, IndexedChunkRef>>,
+    /// The content revision each path in `file_chunks` was last indexed at, so `indexed_paths` can
+    /// report it alongside the chunk count. A path with no chunks left (fully deleted) has no
+    /// entry here.
+    file_revisions: HashMap<String, String>,
+    /// Monotonic counter bumped on every mutation, so callers (e.g. the retrieval API's ETag
+    /// computation) can detect "the index changed since I last looked" without diffing content.
+    index_version: AtomicU64,
 }
 
 impl IndexService {
@@ -36,15 +164,17 @@ impl IndexService {
         let base_collection =
             env::var("QDRANT_COLLECTION").unwrap_or_else(|_| "forge-indexer".to_string());
         // Compose final collection name with namespace
-        let collection_name = if namespace_prefix.is_empty() {
-            base_collection.clone()
-        } else {
-            format!("{namespace_prefix}-{base_collection}")
-        };
+        let collection_name = Self::compose_collection_name(&namespace_prefix, &base_collection);
+        // Qdrant's REST port, for snapshot recovery; defaults to the conventional REST port
+        // alongside the gRPC default above.
+        let qdrant_rest_url =
+            env::var("QDRANT_REST_URL").unwrap_or_else(|_| "http://localhost:6333".to_string());
+        let indexing_profile = IndexingProfile::from_env();
 
         info!("🔗 Connecting to Qdrant at: {}", qdrant_url);
         info!("📦 Using collection: {}", collection_name);
         info!("📏 Vector dimension: {}", vector_dimension);
+        info!("⚙️  Indexing profile: {:?}", indexing_profile);
 
         // Create Qdrant client
         let client = match Qdrant::from_url(&qdrant_url).build() {
@@ -80,22 +210,48 @@ impl IndexService {
                             .as_ref()
                             .and_then(|p| p.vectors_config.as_ref())
                         {
-                            if let Some(VectorConfig::Params(params)) = &vectors_config.config {
-                                if params.size as usize != vector_dimension {
+                            if let Some(VectorConfig::ParamsMap(map)) = &vectors_config.config {
+                                // Compare each named vector's own size against `vector_dimension`
+                                // rather than a single global size -- `code` and `summary` are
+                                // independent spaces that must both match -- and separately check
+                                // the HNSW/quantization profile hasn't been switched since.
+                                let mismatched: Vec<&str> = [CODE_VECTOR, SUMMARY_VECTOR]
+                                    .into_iter()
+                                    .filter(|name| {
+                                        !map.map
+                                            .get(*name)
+                                            .is_some_and(|p| p.size as usize == vector_dimension)
+                                    })
+                                    .collect();
+                                let profile_matches = indexing_profile.matches(config);
+
+                                if mismatched.is_empty() && profile_matches {
+                                    info!(
+                                        "✅ Collection dimensions and indexing profile match, using existing collection"
+                                    );
+                                } else if !mismatched.is_empty() {
                                     warn!(
-                                        "🔄 Vector dimension mismatch! Collection has {} but need {}. Recreating collection...",
-                                        params.size, vector_dimension
+                                        "🔄 Named vector(s) {:?} missing or don't match {} dimensions. Recreating collection...",
+                                        mismatched, vector_dimension
                                     );
                                     Self::recreate_collection(
                                         &client,
                                         &collection_name,
                                         vector_dimension,
+                                        &indexing_profile,
                                     )
                                     .await?;
                                 } else {
-                                    info!(
-                                        "✅ Collection dimensions match, using existing collection"
+                                    warn!(
+                                        "🔄 Indexing profile changed (quantization/HNSW). Recreating collection..."
                                     );
+                                    Self::recreate_collection(
+                                        &client,
+                                        &collection_name,
+                                        vector_dimension,
+                                        &indexing_profile,
+                                    )
+                                    .await?;
                                 }
                             } else {
                                 warn!(
@@ -105,18 +261,29 @@ impl IndexService {
                                     &client,
                                     &collection_name,
                                     vector_dimension,
+                                    &indexing_profile,
                                 )
                                 .await?;
                             }
                         } else {
                             warn!("⚠️  Could not get vector config, recreating collection...");
-                            Self::recreate_collection(&client, &collection_name, vector_dimension)
-                                .await?;
+                            Self::recreate_collection(
+                                &client,
+                                &collection_name,
+                                vector_dimension,
+                                &indexing_profile,
+                            )
+                            .await?;
                         }
                     } else {
                         warn!("⚠️  Could not get collection config, recreating collection...");
-                        Self::recreate_collection(&client, &collection_name, vector_dimension)
-                            .await?;
+                        Self::recreate_collection(
+                            &client,
+                            &collection_name,
+                            vector_dimension,
+                            &indexing_profile,
+                        )
+                        .await?;
                     }
                 }
                 Err(e) => {
@@ -124,37 +291,106 @@ impl IndexService {
                         "⚠️  Could not get collection info: {}. Recreating collection...",
                         e
                     );
-                    Self::recreate_collection(&client, &collection_name, vector_dimension).await?;
+                    Self::recreate_collection(&client, &collection_name, vector_dimension, &indexing_profile)
+                        .await?;
                 }
             }
         } else {
             info!("📝 Creating new collection: {}", collection_name);
-            Self::create_collection(&client, &collection_name, vector_dimension).await?;
+            Self::create_collection(&client, &collection_name, vector_dimension, &indexing_profile).await?;
             info!("✅ Collection created successfully");
         }
 
         info!("✅ IndexService initialization complete");
-        Ok(Self { client, collection_name, vector_dimension })
+        Ok(Self {
+            client,
+            collection_name,
+            base_collection,
+            qdrant_rest_url,
+            vector_dimension,
+            indexing_profile,
+            keyword_index: KeywordIndex::new(),
+            file_chunks: HashMap::new(),
+            file_revisions: HashMap::new(),
+            index_version: AtomicU64::new(0),
+        })
+    }
+
+    /// Compose a namespaced collection name the same way for every caller that needs one: `new`
+    /// at startup, and `clone_collection` when branching off an existing index.
+    fn compose_collection_name(namespace_prefix: &str, base_collection: &str) -> String {
+        if namespace_prefix.is_empty() {
+            base_collection.to_string()
+        } else {
+            format!("{namespace_prefix}-{base_collection}")
+        }
+    }
+
+    /// Current index version, bumped on every mutating operation. Two calls returning the same
+    /// value are a guarantee the indexed content hasn't changed in between.
+    pub fn index_version(&self) -> u64 {
+        self.index_version.load(Ordering::Relaxed)
+    }
+
+    /// Build the `code`/`summary` named-vector params map shared by `create_collection` and the
+    /// dimension-mismatch check in `new`. Both spaces use the same embedder, so they always share
+    /// `vector_dimension`.
+    fn named_vector_params(vector_dimension: usize) -> HashMap<String, VectorParams> {
+        let params = VectorParams {
+            size: vector_dimension as u64,
+            distance: Distance::Cosine.into(),
+            ..Default::default()
+        };
+        HashMap::from([
+            (CODE_VECTOR.to_string(), params.clone()),
+            (SUMMARY_VECTOR.to_string(), params),
+        ])
     }
 
     async fn create_collection(
         client: &Qdrant,
         collection_name: &str,
         vector_dimension: usize,
+        indexing_profile: &IndexingProfile,
     ) -> Result<()> {
-        client
-            .create_collection(
-                CreateCollectionBuilder::new(collection_name.to_string())
-                    .vectors_config(VectorsConfig {
-                        config: Some(VectorConfig::Params(VectorParams {
-                            size: vector_dimension as u64,
-                            distance: Distance::Cosine.into(),
-                            ..Default::default()
-                        })),
-                    })
-                    .build(),
-            )
-            .await?;
+        let mut builder = CreateCollectionBuilder::new(collection_name.to_string()).vectors_config(
+            VectorsConfig {
+                config: Some(VectorConfig::ParamsMap(VectorParamsMap {
+                    map: Self::named_vector_params(vector_dimension),
+                })),
+            },
+        );
+        if let Some(hnsw_config) = indexing_profile.hnsw_config() {
+            builder = builder.hnsw_config(hnsw_config);
+        }
+        if let Some(quantization_config) = indexing_profile.quantization_config() {
+            builder = builder.quantization_config(quantization_config);
+        }
+
+        client.create_collection(builder.build()).await?;
+        Self::create_payload_indexes(client, collection_name).await?;
+        Ok(())
+    }
+
+    /// Create keyword payload indexes on the fields `search_similar` filters against, so pushing
+    /// those filters into Qdrant's query (rather than post-filtering results in Rust) stays fast
+    /// as the collection grows.
+    async fn create_payload_indexes(client: &Qdrant, collection_name: &str) -> Result<()> {
+        for field in ["branch", "path", "lang"] {
+            client
+                .create_field_index(CreateFieldIndexCollectionBuilder::new(
+                    collection_name.to_string(),
+                    field,
+                    FieldType::Keyword,
+                ))
+                .await
+                .map_err(|e| {
+                    ForgeIndexerError::vector_db_error_with_source(
+                        format!("Failed to create payload index on '{field}'"),
+                        e,
+                    )
+                })?;
+        }
         Ok(())
     }
 
@@ -162,6 +398,7 @@ impl IndexService {
         client: &Qdrant,
         collection_name: &str,
         vector_dimension: usize,
+        indexing_profile: &IndexingProfile,
     ) -> Result<()> {
         info!("🗑️  Deleting existing collection: {}", collection_name);
         if let Err(e) = client.delete_collection(collection_name).await {
@@ -172,7 +409,7 @@ impl IndexService {
             "📝 Creating new collection with {} dimensions",
             vector_dimension
         );
-        Self::create_collection(client, collection_name, vector_dimension).await?;
+        Self::create_collection(client, collection_name, vector_dimension, indexing_profile).await?;
         info!("✅ Collection recreated successfully");
         Ok(())
     }
@@ -180,20 +417,133 @@ impl IndexService {
     /// Reset the collection by deleting and recreating it
     pub async fn reset_collection(&self) -> Result<()> {
         info!("🔄 Resetting collection: {}", self.collection_name);
-        Self::recreate_collection(&self.client, &self.collection_name, self.vector_dimension).await
+        Self::recreate_collection(
+            &self.client,
+            &self.collection_name,
+            self.vector_dimension,
+            &self.indexing_profile,
+        )
+        .await?;
+        self.index_version.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Snapshot the current collection, for `restore_from_snapshot` or as a step in
+    /// `clone_collection`.
+    pub async fn create_snapshot(&self) -> Result<SnapshotDescription> {
+        let response = self.client.create_snapshot(&self.collection_name).await.map_err(|e| {
+            ForgeIndexerError::vector_db_error_with_source("Failed to create snapshot", e)
+        })?;
+        response.snapshot_description.ok_or_else(|| {
+            ForgeIndexerError::vector_db_error("Qdrant returned no snapshot description")
+        })
+    }
+
+    /// List the snapshots Qdrant is currently holding for this collection, newest first as
+    /// Qdrant returns them.
+    pub async fn list_snapshots(&self) -> Result<Vec<SnapshotDescription>> {
+        let response = self.client.list_snapshots(&self.collection_name).await.map_err(|e| {
+            ForgeIndexerError::vector_db_error_with_source("Failed to list snapshots", e)
+        })?;
+        Ok(response.snapshots)
+    }
+
+    /// Restore this collection in place from a snapshot previously returned by `create_snapshot`
+    /// (by its `name`). Goes over Qdrant's REST API rather than the gRPC `client` above --
+    /// snapshot recovery has no gRPC RPC, only `PUT /collections/{name}/snapshots/recover`.
+    pub async fn restore_from_snapshot(&self, name: &str) -> Result<()> {
+        self.recover_collection_from_snapshot(&self.collection_name, &self.collection_name, name)
+            .await
+    }
+
+    /// Snapshot this (namespaced) collection and restore it under a sibling collection composed
+    /// from `target_namespace`, so branching a repo can clone its already-built index instead of
+    /// re-embedding the whole thing -- the expensive step `namespace_prefix`-based isolation in
+    /// `new` otherwise forces on every branch/user. Returns the new collection's name; connect a
+    /// fresh `IndexService` to it by setting `QDRANT_NAMESPACE_PREFIX=target_namespace`.
+    pub async fn clone_collection(&self, target_namespace: &str) -> Result<String> {
+        let target_collection =
+            Self::compose_collection_name(target_namespace, &self.base_collection);
+        info!("📋 Cloning collection {} into {}", self.collection_name, target_collection);
+
+        let snapshot = self.create_snapshot().await?;
+
+        Self::create_collection(
+            &self.client,
+            &target_collection,
+            self.vector_dimension,
+            &self.indexing_profile,
+        )
+        .await?;
+        self.recover_collection_from_snapshot(&self.collection_name, &target_collection, &snapshot.name)
+            .await?;
+
+        info!("✅ Cloned {} into {}", self.collection_name, target_collection);
+        Ok(target_collection)
+    }
+
+    /// Point Qdrant's REST snapshot-recovery endpoint at the snapshot named `snapshot_name` taken
+    /// of `source_collection`, recovering it into `target_collection` (the same collection for a
+    /// plain restore, a freshly created sibling for `clone_collection`).
+    async fn recover_collection_from_snapshot(
+        &self,
+        source_collection: &str,
+        target_collection: &str,
+        snapshot_name: &str,
+    ) -> Result<()> {
+        let location = format!(
+            "{}/collections/{}/snapshots/{}",
+            self.qdrant_rest_url, source_collection, snapshot_name
+        );
+
+        let response = reqwest::Client::new()
+            .put(format!(
+                "{}/collections/{}/snapshots/recover",
+                self.qdrant_rest_url, target_collection
+            ))
+            .json(&serde_json::json!({ "location": location }))
+            .send()
+            .await
+            .map_err(|e| {
+                ForgeIndexerError::vector_db_error_with_source("Failed to call snapshot recover", e)
+            })?;
+
+        if !response.status().is_success() {
+            return Err(ForgeIndexerError::vector_db_error(format!(
+                "Snapshot recover into '{target_collection}' returned status {}",
+                response.status()
+            )));
+        }
+
+        Ok(())
     }
 
+    /// Build the named vectors for a point from its required `code` embedding and optional
+    /// `summary` embedding -- a point indexed without a summary simply has no `summary` vector and
+    /// is invisible to a search against that space.
+    fn build_vectors(code_embedding: Embedding, summary_embedding: Option<Embedding>) -> NamedVectors {
+        let mut vectors = NamedVectors::default().add_vector(CODE_VECTOR, code_embedding);
+        if let Some(summary_embedding) = summary_embedding {
+            vectors = vectors.add_vector(SUMMARY_VECTOR, summary_embedding);
+        }
+        vectors
+    }
+
+    /// Upsert `chunk`'s embedding(s) into Qdrant and into the in-process keyword index used by
+    /// `search_hybrid`, keyed by `chunk.id`. `summary_embedding` is optional since not every caller
+    /// has a summary to embed.
     pub async fn add_embedding(
         &mut self,
-        id: &str,
-        embedding: Embedding,
+        chunk: &Chunk,
+        code_embedding: Embedding,
+        summary_embedding: Option<Embedding>,
         payload: Payload,
     ) -> Result<()> {
-        debug!("🗂️  Adding embedding for chunk: {}", id);
+        debug!("🗂️  Adding embedding for chunk: {}", chunk.id);
 
-        // Use a valid UUID for the point ID
-        let point_id = uuid::Uuid::new_v4().to_string();
-        let points = vec![PointStruct::new(point_id.clone(), embedding, payload)];
+        let point_id = Self::deterministic_point_id(chunk);
+        let vectors = Self::build_vectors(code_embedding, summary_embedding);
+        let points = vec![PointStruct::new(point_id.clone(), vectors, payload)];
 
         match self
             .client
@@ -206,12 +556,14 @@ impl IndexService {
             Ok(_) => {
                 debug!(
                     "✅ Successfully added embedding for chunk: {} with point ID: {}",
-                    id, point_id
+                    chunk.id, point_id
                 );
+                self.keyword_index.add_chunk(chunk).await;
+                self.index_version.fetch_add(1, Ordering::Relaxed);
                 Ok(())
             }
             Err(e) => {
-                error!("❌ Failed to add embedding for chunk {}: {}", id, e);
+                error!("❌ Failed to add embedding for chunk {}: {}", chunk.id, e);
                 Err(ForgeIndexerError::vector_db_error_with_source(
                     "Failed to add embedding to Qdrant",
                     e,
@@ -220,11 +572,188 @@ impl IndexService {
         }
     }
 
-    pub async fn search(&self, query: &[f32], k: usize) -> Result<Vec<(String, f32)>> {
+    /// Upsert many chunks' embeddings in a single Qdrant request instead of one round-trip per
+    /// chunk -- the dominant cost of a full-repo index run for large codebases. A no-op for an
+    /// empty batch.
+    pub async fn add_embeddings_batch(
+        &mut self,
+        items: Vec<(Chunk, Embedding, Option<Embedding>, Payload)>,
+    ) -> Result<()> {
+        if items.is_empty() {
+            return Ok(());
+        }
+
+        let mut points = Vec::with_capacity(items.len());
+        let mut chunks = Vec::with_capacity(items.len());
+        for (chunk, code_embedding, summary_embedding, payload) in items {
+            let point_id = Self::deterministic_point_id(&chunk);
+            let vectors = Self::build_vectors(code_embedding, summary_embedding);
+            points.push(PointStruct::new(point_id, vectors, payload));
+            chunks.push(chunk);
+        }
+
+        debug!("🗂️  Upserting batch of {} embeddings", points.len());
+
+        self.client
+            .upsert_points(UpsertPointsBuilder::new(self.collection_name.clone(), points))
+            .await
+            .map_err(|e| {
+                error!("❌ Failed to upsert embedding batch: {}", e);
+                ForgeIndexerError::vector_db_error_with_source("Failed to upsert embedding batch", e)
+            })?;
+
+        for chunk in &chunks {
+            self.keyword_index.add_chunk(chunk).await;
+        }
+        self.index_version.fetch_add(1, Ordering::Relaxed);
+
+        debug!("✅ Successfully upserted batch of {} embeddings", chunks.len());
+        Ok(())
+    }
+
+    /// Content hash for `chunk`, stable across re-chunking as long as its code and symbol are
+    /// unchanged -- independent of `chunk.id`, which bakes in byte/line position and so changes
+    /// whenever unrelated code shifts earlier in the file.
+    fn chunk_content_hash(chunk: &Chunk) -> String {
+        use sha2::{Digest, Sha256};
+        let mut hasher = Sha256::new();
+        hasher.update(chunk.path.as_bytes());
+        hasher.update(chunk.code.as_bytes());
+        if let Some(symbol) = &chunk.symbol {
+            hasher.update(symbol.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Deterministic Qdrant point id for `chunk`, a UUIDv5 derived from its content hash rather
+    /// than a random UUIDv4. Re-indexing the same logical chunk -- same path/symbol/code, whatever
+    /// its revision -- always lands on the same point id, so a re-index upserts in place instead of piling up
+    /// duplicate points, and `delete`/`upsert_or_replace_file` can target a chunk's id without
+    /// the caller having to remember a server-minted random one.
+    fn deterministic_point_id(chunk: &Chunk) -> String {
+        uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_URL, Self::chunk_content_hash(chunk).as_bytes())
+            .to_string()
+    }
+
+    /// Incrementally re-index `path` given the chunks freshly produced for its latest content.
+    ///
+    /// Each chunk is content-hashed and compared against the hashes already indexed for this
+    /// path: a hash seen before is left untouched (its Qdrant point and embedding are not
+    /// re-uploaded), a new hash is upserted, and a hash that no longer appears is deleted from
+    /// both Qdrant and the `KeywordIndex`. This keeps a small edit to a large file cheap instead
+    /// of regenerating every chunk's point on every re-index. Returns `(inserted, deleted)`.
+    pub async fn update_file(
+        &mut self,
+        path: &str,
+        produced: Vec<(Chunk, Embedding, Option<Embedding>, Payload)>,
+    ) -> Result<(usize, usize)> {
+        let revision = produced.first().map(|(chunk, ..)| chunk.rev.clone());
+        let previous = self.file_chunks.remove(path).unwrap_or_default();
+        let mut current = HashMap::with_capacity(produced.len());
+        let mut inserted = 0;
+
+        for (chunk, code_embedding, summary_embedding, payload) in produced {
+            let hash = Self::chunk_content_hash(&chunk);
+            if let Some(existing) = previous.get(&hash) {
+                current.insert(hash, existing.clone());
+                continue;
+            }
+
+            let point_id = Self::deterministic_point_id(&chunk);
+            let vectors = Self::build_vectors(code_embedding, summary_embedding);
+            let points = vec![PointStruct::new(point_id.clone(), vectors, payload)];
+            self.client
+                .upsert_points(UpsertPointsBuilder::new(self.collection_name.clone(), points))
+                .await
+                .map_err(|e| {
+                    ForgeIndexerError::vector_db_error_with_source(
+                        "Failed to upsert incrementally updated chunk",
+                        e,
+                    )
+                })?;
+            self.keyword_index.add_chunk(&chunk).await;
+
+            current.insert(hash, IndexedChunkRef { point_id, chunk_id: chunk.id.clone() });
+            inserted += 1;
+        }
+
+        let vanished: Vec<&IndexedChunkRef> =
+            previous.iter().filter(|(hash, _)| !current.contains_key(*hash)).map(|(_, r)| r).collect();
+        let deleted = vanished.len();
+
+        if !vanished.is_empty() {
+            let point_ids: Vec<String> = vanished.iter().map(|r| r.point_id.clone()).collect();
+            self.client
+                .delete_points(DeletePointsBuilder::new(self.collection_name.clone()).points(point_ids))
+                .await
+                .map_err(|e| {
+                    ForgeIndexerError::vector_db_error_with_source(
+                        "Failed to delete vanished chunks during incremental update",
+                        e,
+                    )
+                })?;
+            for chunk_ref in &vanished {
+                self.keyword_index.remove(&chunk_ref.chunk_id).await;
+            }
+        }
+
+        debug!(
+            "🔄 Incremental update for {}: {} inserted, {} deleted, {} unchanged",
+            path,
+            inserted,
+            deleted,
+            current.len() - inserted
+        );
+
+        self.file_chunks.insert(path.to_string(), current);
+        match revision {
+            Some(revision) => {
+                self.file_revisions.insert(path.to_string(), revision);
+            }
+            None => {
+                self.file_revisions.remove(path);
+            }
+        }
+        if inserted > 0 || deleted > 0 {
+            self.index_version.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok((inserted, deleted))
+    }
+
+    /// List every path currently tracked in the index, with the content revision it was last
+    /// indexed at and how many chunks it currently holds for that revision. A path whose every
+    /// chunk was deleted (e.g. via `update_file` with an empty `produced`) has no entry here.
+    /// Backs `IndexingPipeline::indexed_paths` and `diff_against_directory`, which callers use to
+    /// answer "why isn't this file searchable?" by comparing what's on disk against what the
+    /// index actually has.
+    pub fn indexed_paths(&self) -> Vec<(String, String, usize)> {
+        let mut paths: Vec<(String, String, usize)> = self
+            .file_chunks
+            .iter()
+            .filter(|(_, chunks)| !chunks.is_empty())
+            .map(|(path, chunks)| {
+                let revision = self.file_revisions.get(path).cloned().unwrap_or_default();
+                (path.clone(), revision, chunks.len())
+            })
+            .collect();
+        paths.sort_by(|a, b| a.0.cmp(&b.0));
+        paths
+    }
+
+    /// The content revision currently indexed for `path`, if any. `IndexingPipeline::process_file`
+    /// consults this before chunking/embedding so a re-index over an unchanged file is a no-op.
+    pub fn file_revision(&self, path: &str) -> Option<&str> {
+        self.file_revisions.get(path).map(String::as_str)
+    }
+
+    /// Search `vector_name` (e.g. [`CODE_VECTOR`] or [`SUMMARY_VECTOR`]) for the `k` nearest
+    /// points to `query`.
+    pub async fn search(&self, query: &[f32], k: usize, vector_name: &str) -> Result<Vec<(String, f32)>> {
         let response = self
             .client
             .search_points(
                 SearchPointsBuilder::new(self.collection_name.clone(), query.to_vec(), k as u64)
+                    .vector_name(vector_name)
                     .with_payload(SelectorOptions::Enable(true)),
             )
             .await
@@ -250,127 +779,286 @@ impl IndexService {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to delete point from Qdrant: {}", e))?;
 
+        self.index_version.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
 
-    /// Search for similar chunks with optional filtering
+    /// Build a server-side Qdrant filter equivalent to the post-filtering `search_similar` used
+    /// to do in Rust: an exact match on the `branch` payload field, and a flexible repo match
+    /// against `path` (substring, since a repo name is rarely the whole path), with
+    /// `repo == "" | "." | "*"` mapped to no condition at all so it matches everything. Returns
+    /// `None` when neither filter applies, so callers can skip attaching a filter entirely.
+    fn build_filter(repo_filter: Option<&str>, branch_filter: Option<&str>) -> Option<Filter> {
+        let mut must = Vec::new();
+
+        if let Some(repo) = repo_filter {
+            if !(repo.is_empty() || repo == "." || repo == "*") {
+                must.push(Condition::matches_text("path", repo.to_string()));
+            }
+        }
+
+        if let Some(branch) = branch_filter {
+            must.push(Condition::matches("branch", branch.to_string()));
+        }
+
+        if must.is_empty() { None } else { Some(Filter::must(must)) }
+    }
+
+    /// Search for similar chunks against `vector_name` -- [`CODE_VECTOR`] to search "by
+    /// implementation" or [`SUMMARY_VECTOR`] to search "by intent" -- pushing
+    /// `repo_filter`/`branch_filter` into the Qdrant query itself rather than discarding
+    /// non-matching hits afterward -- the latter silently returns fewer than `k` results whenever
+    /// the raw top-k happens to be full of non-matching chunks.
     pub async fn search_similar(
         &self,
         query_embedding: &[f32],
         k: usize,
+        vector_name: &str,
         repo_filter: Option<&str>,
         branch_filter: Option<&str>,
     ) -> Result<Vec<(Chunk, f32)>> {
         info!(
-            "Starting vector search with k={}, repo_filter={:?}, branch_filter={:?}",
-            k, repo_filter, branch_filter
+            "Starting vector search on '{}' with k={}, repo_filter={:?}, branch_filter={:?}",
+            vector_name, k, repo_filter, branch_filter
         );
 
-        let response = self
-            .client
-            .search_points(
-                SearchPointsBuilder::new(
-                    self.collection_name.clone(),
-                    query_embedding.to_vec(),
-                    k as u64,
-                )
-                .with_payload(SelectorOptions::Enable(true)),
-            )
+        let mut builder = SearchPointsBuilder::new(
+            self.collection_name.clone(),
+            query_embedding.to_vec(),
+            k as u64,
+        )
+        .vector_name(vector_name)
+        .with_payload(SelectorOptions::Enable(true));
+
+        if let Some(filter) = Self::build_filter(repo_filter, branch_filter) {
+            builder = builder.filter(filter);
+        }
+
+        let response = self.client.search_points(builder).await.map_err(|e| {
+            error!("Failed to search in Qdrant: {}", e);
+            anyhow::anyhow!("Failed to search in Qdrant: {}", e)
+        })?;
+
+        info!("Qdrant returned {} results", response.result.len());
+
+        let mut results = Vec::new();
+        for result in &response.result {
+            let point_id = result.id.as_ref().and_then(point_id_to_string);
+            match payload_to_chunk(point_id, result.payload.clone(), result.score) {
+                Ok(chunk) => results.push((chunk, result.score)),
+                Err(e) => warn!("Failed to convert payload to chunk: {}", e),
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Replace every point currently indexed for `(path, branch)` with `chunks` in one operation:
+    /// delete whatever is already there for that path/branch via a filtered
+    /// `DeletePointsBuilder`, then upsert the fresh set. Unlike `update_file`'s hash-diffed
+    /// incremental update, this always re-deletes and re-inserts -- appropriate for callers (e.g.
+    /// a branch rebuild) that want a clean slate rather than a diff against what was indexed
+    /// before.
+    pub async fn upsert_or_replace_file(
+        &mut self,
+        path: &str,
+        branch: &str,
+        chunks: Vec<(Chunk, Embedding, Option<Embedding>, Payload)>,
+    ) -> Result<()> {
+        let filter = Filter::must(vec![
+            Condition::matches("path", path.to_string()),
+            Condition::matches("branch", branch.to_string()),
+        ]);
+
+        self.client
+            .delete_points(DeletePointsBuilder::new(self.collection_name.clone()).points(filter))
             .await
             .map_err(|e| {
-                error!("Failed to search in Qdrant: {}", e);
-                anyhow::anyhow!("Failed to search in Qdrant: {}", e)
+                ForgeIndexerError::vector_db_error_with_source(
+                    "Failed to delete stale points before replacing file",
+                    e,
+                )
             })?;
 
-        info!("Qdrant returned {} raw results", response.result.len());
+        if let Some(stale) = self.file_chunks.remove(path) {
+            for chunk_ref in stale.values() {
+                self.keyword_index.remove(&chunk_ref.chunk_id).await;
+            }
+        }
+        self.index_version.fetch_add(1, Ordering::Relaxed);
 
-        let mut results = Vec::new();
-        for (idx, result) in response.result.iter().enumerate() {
-            let payload = result.payload.clone();
-
-            info!("Processing result {}: score={:.4}", idx, result.score);
-
-            // Apply filters first before converting to chunk to avoid borrow issues
-            let matches_repo = repo_filter.is_none_or(|repo| {
-                // More flexible repo matching - check if the repo name appears anywhere in the path
-                // or if the path contains the repo as a substring
-                if let Some(path_value) = payload.get("path") {
-                    if let Some(qdrant_client::qdrant::value::Kind::StringValue(path)) =
-                        &path_value.kind
-                    {
-                        // Check multiple matching strategies:
-                        // 1. Exact path component match (original logic)
-                        let path_parts: Vec<&str> = path.split('/').collect();
-                        let exact_match = path_parts.contains(&repo);
-
-                        // 2. Substring match (more flexible)
-                        let substring_match = path.contains(repo);
-
-                        // 3. Special case: if repo is "." or empty, match all
-                        let wildcard_match = repo.is_empty() || repo == "." || repo == "*";
-
-                        let matches = exact_match || substring_match || wildcard_match;
-                        info!(
-                            "Repo filter check: path='{}', repo='{}', exact={}, substring={}, wildcard={}, final_match={}",
-                            path, repo, exact_match, substring_match, wildcard_match, matches
-                        );
-                        matches
-                    } else {
-                        warn!("Path value is not a string in payload");
-                        false
-                    }
-                } else {
-                    warn!("No path field found in payload");
-                    false
-                }
-            });
-
-            let matches_branch = branch_filter.is_none_or(|branch| {
-                // Branch filtering requires metadata in payload
-                let matches = payload
-                    .get("branch")
-                    .and_then(|v| v.kind.as_ref())
-                    .and_then(|kind| match kind {
-                        qdrant_client::qdrant::value::Kind::StringValue(s) => Some(s),
-                        _ => None,
-                    })
-                    .is_some_and(|b| b == branch);
-                info!(
-                    "Branch filter check: branch_filter='{}', matches={}",
-                    branch, matches
-                );
-                matches
-            });
-
-            // Only convert to chunk if filters pass
-            if matches_repo && matches_branch {
-                match payload_to_chunk(payload, result.score) {
-                    Ok(chunk) => {
-                        info!(
-                            "Added chunk: path='{}', symbol={:?}",
-                            chunk.path, chunk.symbol
-                        );
-                        results.push((chunk, result.score));
-                    }
-                    Err(e) => {
-                        warn!("Failed to convert payload to chunk: {}", e);
-                    }
+        self.add_embeddings_batch(chunks).await
+    }
+
+    /// Atomically replace every chunk indexed for `path` with `chunks`, all produced for
+    /// `revision`, in a single all-or-nothing commit.
+    ///
+    /// Every point is staged into one Qdrant upsert batch *before* anything about the path's
+    /// previously-indexed revision is touched; only once that batch succeeds does this delete the
+    /// stale points left over from the prior revision and swap in the new bookkeeping. Unlike
+    /// `upsert_or_replace_file`, which deletes the old points up front and so can strand a path
+    /// with neither the old nor the new chunks if the upsert then fails, this leaves the
+    /// previously-indexed revision completely untouched on any embedding/upsert error -- the
+    /// caller gets the error back and the index keeps serving the last good revision. Points whose
+    /// content is unchanged between revisions land on the same deterministic id, so they're
+    /// upserted in place rather than deleted and recreated. Returns `(inserted, deleted)`.
+    pub async fn replace_file_chunks(
+        &mut self,
+        path: &str,
+        revision: &str,
+        chunks: Vec<(Chunk, Embedding, Option<Embedding>, Payload)>,
+    ) -> Result<(usize, usize)> {
+        let mut points = Vec::with_capacity(chunks.len());
+        let mut staged: HashMap<String, IndexedChunkRef> = HashMap::with_capacity(chunks.len());
+        for (chunk, code_embedding, summary_embedding, payload) in &chunks {
+            let hash = Self::chunk_content_hash(chunk);
+            let point_id = Self::deterministic_point_id(chunk);
+            let vectors = Self::build_vectors(code_embedding.clone(), summary_embedding.clone());
+            points.push(PointStruct::new(point_id.clone(), vectors, payload.clone()));
+            staged.insert(hash, IndexedChunkRef { point_id, chunk_id: chunk.id.clone() });
+        }
+
+        if !points.is_empty() {
+            self.client
+                .upsert_points(UpsertPointsBuilder::new(self.collection_name.clone(), points))
+                .await
+                .map_err(|e| {
+                    ForgeIndexerError::vector_db_error_with_source(
+                        "Failed to upsert staged chunks for atomic file replace",
+                        e,
+                    )
+                })?;
+        }
+
+        // Every staged point is now durably in Qdrant for this revision -- safe to commit the
+        // keyword index and bookkeeping, then clean up whatever the previous revision left behind.
+        for (chunk, ..) in &chunks {
+            self.keyword_index.add_chunk(chunk).await;
+        }
+
+        let staged_ids: std::collections::HashSet<&str> =
+            staged.values().map(|r| r.point_id.as_str()).collect();
+        let previous = self.file_chunks.insert(path.to_string(), staged);
+        let inserted = chunks.len();
+
+        let mut deleted = 0;
+        if let Some(previous) = previous {
+            let stale: Vec<&IndexedChunkRef> =
+                previous.values().filter(|r| !staged_ids.contains(r.point_id.as_str())).collect();
+            deleted = stale.len();
+            if !stale.is_empty() {
+                let point_ids: Vec<String> = stale.iter().map(|r| r.point_id.clone()).collect();
+                self.client
+                    .delete_points(
+                        DeletePointsBuilder::new(self.collection_name.clone()).points(point_ids),
+                    )
+                    .await
+                    .map_err(|e| {
+                        ForgeIndexerError::vector_db_error_with_source(
+                            "Failed to delete stale points after atomic file replace",
+                            e,
+                        )
+                    })?;
+                for chunk_ref in &stale {
+                    self.keyword_index.remove(&chunk_ref.chunk_id).await;
                 }
-            } else {
-                info!(
-                    "Result {} filtered out: repo_match={}, branch_match={}",
-                    idx, matches_repo, matches_branch
-                );
             }
         }
 
-        info!("Returning {} filtered results", results.len());
-        Ok(results)
+        if inserted == 0 {
+            self.file_revisions.remove(path);
+        } else {
+            self.file_revisions.insert(path.to_string(), revision.to_string());
+        }
+
+        debug!(
+            "🔁 Atomically replaced {} chunk(s) for {} at revision {}: {} inserted, {} deleted",
+            inserted, path, revision, inserted, deleted
+        );
+        self.index_version.fetch_add(1, Ordering::Relaxed);
+        Ok((inserted, deleted))
+    }
+
+    /// Hybrid search combining the dense vector index with the in-process `KeywordIndex`.
+    ///
+    /// When `semantic_ratio` is `None`, the two ranked lists are merged with reciprocal rank
+    /// fusion (order-only, scale-free). When `semantic_ratio` is `Some(ratio)`, each list's
+    /// scores are min-max normalized to `[0, 1]` and blended as
+    /// `ratio * vector_score + (1 - ratio) * keyword_score`, so a caller can trade off exact-match
+    /// recall against semantic recall explicitly. `1.0` is pure vector search, `0.0` is pure
+    /// keyword search.
+    ///
+    /// Each result carries the raw per-leg scores it was fused from alongside the combined
+    /// `score`, so callers that want to show their work (or debug a surprising ranking) don't
+    /// have to re-run both searches themselves.
+    pub async fn search_hybrid(
+        &self,
+        query_embedding: &[f32],
+        query_text: &str,
+        k: usize,
+        vector_name: &str,
+        semantic_ratio: Option<f32>,
+        repo_filter: Option<&str>,
+        branch_filter: Option<&str>,
+    ) -> Result<Vec<HybridResult>> {
+        // Fuse over a wider candidate pool than `k`, not just the top `k` from each list -- a
+        // chunk that ranks just outside the top `k` on one side but highly on the other would
+        // otherwise never get the chance to be fused in.
+        let candidate_pool = k.saturating_mul(4).max(k);
+
+        let vector_ranked = self
+            .search_similar(query_embedding, candidate_pool, vector_name, repo_filter, branch_filter)
+            .await?;
+        let keyword_ranked = self.keyword_index.search(query_text, candidate_pool).await;
+
+        let vector_scores: HashMap<String, f32> =
+            vector_ranked.iter().map(|(chunk, score)| (chunk.id.clone(), *score)).collect();
+        let keyword_scores: HashMap<String, f32> =
+            keyword_ranked.iter().map(|(chunk, score)| (chunk.id.clone(), *score)).collect();
+
+        let mut fused = match semantic_ratio {
+            Some(ratio) => keyword_index::blend_scores(vector_ranked, keyword_ranked, ratio),
+            None => keyword_index::reciprocal_rank_fusion(vector_ranked, keyword_ranked),
+        };
+        fused.truncate(k);
+
+        Ok(fused
+            .into_iter()
+            .map(|(chunk, score)| {
+                let vector_score = vector_scores.get(&chunk.id).copied();
+                let keyword_score = keyword_scores.get(&chunk.id).copied();
+                HybridResult { chunk, score, vector_score, keyword_score }
+            })
+            .collect())
+    }
+}
+
+/// One fused result from [`IndexService::search_hybrid`]: the winning `chunk`, its combined
+/// `score`, and the raw per-leg scores it was fused from (each `None` if that leg didn't surface
+/// the chunk at all).
+#[derive(Debug, Clone)]
+pub struct HybridResult {
+    pub chunk: Chunk,
+    pub score: f32,
+    pub vector_score: Option<f32>,
+    pub keyword_score: Option<f32>,
+}
+
+/// Extract the string form of a Qdrant point id, whichever variant it was stored as.
+fn point_id_to_string(id: &qdrant_client::qdrant::PointId) -> Option<String> {
+    use qdrant_client::qdrant::point_id::PointIdOptions;
+    match &id.point_id_options {
+        Some(PointIdOptions::Uuid(s)) => Some(s.clone()),
+        Some(PointIdOptions::Num(n)) => Some(n.to_string()),
+        None => None,
     }
 }
 
-/// Convert Qdrant payload back to Chunk
+/// Convert Qdrant payload back to Chunk, threading the point's own id back in as `Chunk.id` when
+/// one is available -- e.g. so a chunk surfaced by `search_similar` carries its real, stable
+/// Qdrant point id rather than a freshly minted one that no later `delete` call could ever match.
 fn payload_to_chunk(
+    point_id: Option<String>,
     payload: std::collections::HashMap<String, qdrant_client::qdrant::Value>,
     _score: f32,
 ) -> Result<Chunk> {
@@ -401,8 +1089,15 @@ fn payload_to_chunk(
             .ok_or_else(|| ForgeIndexerError::validation_error(key, "Missing or invalid field"))
     };
 
+    let get_optional_usize = |key: &str| -> Option<usize> {
+        payload.get(key).and_then(|v| match v.kind {
+            Some(qdrant_client::qdrant::value::Kind::IntegerValue(n)) => Some(n as usize),
+            _ => None,
+        })
+    };
+
     Ok(Chunk {
-        id: uuid::Uuid::new_v4().to_string(), // Generate new ID for search results
+        id: point_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
         path: get_string("path")?,
         lang: get_string("lang")?,
         symbol: get_optional_string("symbol"),
@@ -410,6 +1105,12 @@ fn payload_to_chunk(
         size: get_usize("size")?,
         code: get_string("code")?,
         summary: get_optional_string("summary"),
+        // Points indexed before span tracking was added won't carry these fields; default to 0
+        // rather than failing the whole conversion over missing location metadata.
+        start_byte: get_optional_usize("start_byte").unwrap_or(0),
+        end_byte: get_optional_usize("end_byte").unwrap_or(0),
+        start_line: get_optional_usize("start_line").unwrap_or(0),
+        end_line: get_optional_usize("end_line").unwrap_or(0),
         embedding: None, // Don't include embedding in search results
     })
 }
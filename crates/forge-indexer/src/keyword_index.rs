@@ -0,0 +1,272 @@
+//! In-memory lexical index used alongside the vector store for hybrid retrieval.
+//!
+//! Populated as chunks are embedded (see `IndexService::add_embedding`), so it only covers
+//! chunks indexed by this process -- it is not persisted or shared across the indexer and
+//! retrieval-api binaries the way the Qdrant-backed vector store is.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::RwLock;
+
+use crate::proto::Chunk;
+
+/// Reciprocal-rank-fusion constant; the standard value from the original RRF paper, chosen so a
+/// handful of rank-1 differences dominate the fused score without letting a single list's tail
+/// overwhelm the other.
+pub const RRF_K: f32 = 60.0;
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+}
+
+#[derive(Default)]
+struct KeywordIndexInner {
+    /// term -> (chunk id -> term frequency in that chunk)
+    postings: HashMap<String, HashMap<String, u32>>,
+    documents: HashMap<String, Chunk>,
+}
+
+/// Inverted index over each indexed chunk's `code` and `symbol`, scored by TF-IDF at query time.
+/// Complements the dense vector index for exact-identifier / rare-token queries that embeddings
+/// retrieve poorly.
+#[derive(Clone, Default)]
+pub struct KeywordIndex {
+    inner: Arc<RwLock<KeywordIndexInner>>,
+}
+
+impl KeywordIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Index (or re-index) a chunk under its `id`. Symbol-name terms are weighted higher than
+    /// body terms since an exact symbol match is a much stronger lexical signal.
+    pub async fn add_chunk(&self, chunk: &Chunk) {
+        const SYMBOL_WEIGHT: u32 = 3;
+
+        let mut terms: HashMap<String, u32> = HashMap::new();
+        for term in tokenize(&chunk.code) {
+            *terms.entry(term).or_insert(0) += 1;
+        }
+        if let Some(symbol) = &chunk.symbol {
+            for term in tokenize(symbol) {
+                *terms.entry(term).or_insert(0) += SYMBOL_WEIGHT;
+            }
+        }
+
+        let mut inner = self.inner.write().await;
+        inner.documents.insert(chunk.id.clone(), chunk.clone());
+        for (term, freq) in terms {
+            inner.postings.entry(term).or_default().insert(chunk.id.clone(), freq);
+        }
+    }
+
+    pub async fn remove(&self, chunk_id: &str) {
+        let mut inner = self.inner.write().await;
+        inner.documents.remove(chunk_id);
+        for postings in inner.postings.values_mut() {
+            postings.remove(chunk_id);
+        }
+    }
+
+    /// Rank indexed chunks lexically against `query`, returning up to `k` `(Chunk, score)` pairs
+    /// in descending TF-IDF order.
+    pub async fn search(&self, query: &str, k: usize) -> Vec<(Chunk, f32)> {
+        let inner = self.inner.read().await;
+        let total_docs = inner.documents.len().max(1) as f32;
+
+        let mut scores: HashMap<String, f32> = HashMap::new();
+        for term in tokenize(query) {
+            let Some(postings) = inner.postings.get(&term) else { continue };
+            let idf = (total_docs / postings.len() as f32).ln().max(0.0) + 1.0;
+            for (chunk_id, freq) in postings {
+                *scores.entry(chunk_id.clone()).or_insert(0.0) += idf * (*freq as f32);
+            }
+        }
+
+        let mut ranked: Vec<(String, f32)> = scores.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(k);
+
+        ranked
+            .into_iter()
+            .filter_map(|(id, score)| inner.documents.get(&id).map(|c| (c.clone(), score)))
+            .collect()
+    }
+}
+
+/// Fuse a vector-search ranking and a keyword-search ranking with reciprocal rank fusion: each
+/// list contributes `1 / (RRF_K + rank)` (1-indexed) per chunk it contains, and a chunk appearing
+/// in both lists sums both contributions. Returns chunks sorted by fused score, descending,
+/// preferring the vector-search copy of a chunk (it carries richer payload metadata) when a
+/// chunk appears in both lists.
+pub fn reciprocal_rank_fusion(
+    vector_ranked: Vec<(Chunk, f32)>,
+    keyword_ranked: Vec<(Chunk, f32)>,
+) -> Vec<(Chunk, f32)> {
+    let mut fused: HashMap<String, (Chunk, f32)> = HashMap::new();
+
+    for (rank, (chunk, _)) in vector_ranked.into_iter().enumerate() {
+        let contribution = 1.0 / (RRF_K + (rank + 1) as f32);
+        fused
+            .entry(chunk.id.clone())
+            .and_modify(|(_, score)| *score += contribution)
+            .or_insert((chunk, contribution));
+    }
+
+    for (rank, (chunk, _)) in keyword_ranked.into_iter().enumerate() {
+        let contribution = 1.0 / (RRF_K + (rank + 1) as f32);
+        fused
+            .entry(chunk.id.clone())
+            .and_modify(|(_, score)| *score += contribution)
+            .or_insert((chunk, contribution));
+    }
+
+    let mut results: Vec<(Chunk, f32)> = fused.into_values().collect();
+    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    results
+}
+
+/// Min-max normalize `scores` to `[0, 1]`. An empty list yields an empty map; a constant-score
+/// list (including a single result) has no spread to normalize against and maps every score to
+/// `1.0`, so its chunks still outrank chunks missing from that list.
+fn normalize(scores: &[(Chunk, f32)]) -> HashMap<String, f32> {
+    let Some(min) = scores.iter().map(|(_, s)| *s).fold(None, |acc, s| {
+        Some(acc.map_or(s, |m: f32| m.min(s)))
+    }) else {
+        return HashMap::new();
+    };
+    let max = scores.iter().map(|(_, s)| *s).fold(min, f32::max);
+    let range = max - min;
+
+    scores
+        .iter()
+        .map(|(chunk, score)| {
+            let normalized = if range > 0.0 { (score - min) / range } else { 1.0 };
+            (chunk.id.clone(), normalized)
+        })
+        .collect()
+}
+
+/// Blend a vector-search ranking and a keyword-search ranking by min-max normalizing each list's
+/// scores and combining them as `ratio * vector + (1 - ratio) * keyword`. A chunk missing from one
+/// list contributes `0.0` for that side rather than being dropped. Returns chunks sorted by
+/// blended score, descending.
+pub fn blend_scores(
+    vector_ranked: Vec<(Chunk, f32)>,
+    keyword_ranked: Vec<(Chunk, f32)>,
+    semantic_ratio: f32,
+) -> Vec<(Chunk, f32)> {
+    let vector_norm = normalize(&vector_ranked);
+    let keyword_norm = normalize(&keyword_ranked);
+
+    let mut chunks: HashMap<String, Chunk> = HashMap::new();
+    for (chunk, _) in vector_ranked.into_iter().chain(keyword_ranked) {
+        chunks.entry(chunk.id.clone()).or_insert(chunk);
+    }
+
+    let mut results: Vec<(Chunk, f32)> = chunks
+        .into_iter()
+        .map(|(id, chunk)| {
+            let vector_score = vector_norm.get(&id).copied().unwrap_or(0.0);
+            let keyword_score = keyword_norm.get(&id).copied().unwrap_or(0.0);
+            let blended = semantic_ratio * vector_score + (1.0 - semantic_ratio) * keyword_score;
+            (chunk, blended)
+        })
+        .collect();
+    results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn chunk(id: &str, code: &str, symbol: Option<&str>) -> Chunk {
+        Chunk {
+            id: id.to_string(),
+            path: "file.rs".to_string(),
+            lang: "rust".to_string(),
+            symbol: symbol.map(str::to_string),
+            rev: "rev".to_string(),
+            size: code.len(),
+            code: code.to_string(),
+            summary: None,
+            start_byte: 0,
+            end_byte: code.len(),
+            start_line: 1,
+            end_line: 1,
+            embedding: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn finds_chunk_by_exact_identifier() {
+        let index = KeywordIndex::new();
+        index.add_chunk(&chunk("a", "fn calculate_total(items: &[Item]) -> u64 {}", Some("calculate_total"))).await;
+        index.add_chunk(&chunk("b", "fn unrelated() {}", Some("unrelated"))).await;
+
+        let results = index.search("calculate_total", 10).await;
+
+        assert_eq!(results.first().map(|(c, _)| c.id.clone()), Some("a".to_string()));
+    }
+
+    #[tokio::test]
+    async fn removed_chunk_is_no_longer_returned() {
+        let index = KeywordIndex::new();
+        index.add_chunk(&chunk("a", "fn needle() {}", Some("needle"))).await;
+        index.remove("a").await;
+
+        let results = index.search("needle", 10).await;
+
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn rrf_boosts_chunks_ranked_highly_in_both_lists() {
+        let shared = chunk("shared", "fn shared() {}", None);
+        let vector_only = chunk("vector_only", "fn vector_only() {}", None);
+        let keyword_only = chunk("keyword_only", "fn keyword_only() {}", None);
+
+        let vector_ranked = vec![(shared.clone(), 0.9), (vector_only.clone(), 0.5)];
+        let keyword_ranked = vec![(shared.clone(), 10.0), (keyword_only.clone(), 2.0)];
+
+        let fused = reciprocal_rank_fusion(vector_ranked, keyword_ranked);
+
+        assert_eq!(fused[0].0.id, "shared");
+    }
+
+    #[test]
+    fn blend_scores_pure_vector_ignores_keyword_only_matches() {
+        let vector_only = chunk("vector_only", "fn vector_only() {}", None);
+        let keyword_only = chunk("keyword_only", "fn keyword_only() {}", None);
+
+        let vector_ranked = vec![(vector_only.clone(), 0.9)];
+        let keyword_ranked = vec![(keyword_only.clone(), 10.0)];
+
+        let blended = blend_scores(vector_ranked, keyword_ranked, 1.0);
+
+        assert_eq!(blended[0].0.id, "vector_only");
+        assert_eq!(blended.iter().find(|(c, _)| c.id == "keyword_only").map(|(_, s)| *s), Some(0.0));
+    }
+
+    #[test]
+    fn blend_scores_weights_by_semantic_ratio() {
+        let a = chunk("a", "fn a() {}", None);
+        let b = chunk("b", "fn b() {}", None);
+
+        let vector_ranked = vec![(a.clone(), 1.0), (b.clone(), 0.0)];
+        let keyword_ranked = vec![(a.clone(), 0.0), (b.clone(), 1.0)];
+
+        let blended = blend_scores(vector_ranked, keyword_ranked, 0.25);
+
+        let score_of = |id: &str| blended.iter().find(|(c, _)| c.id == id).map(|(_, s)| *s).unwrap();
+        assert_eq!(score_of("a"), 0.25);
+        assert_eq!(score_of("b"), 0.75);
+    }
+}
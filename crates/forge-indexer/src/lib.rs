@@ -1,21 +1,45 @@
 //! Forge Indexer - Real-time codebase indexing service
 
+pub mod batch_accumulator;
 pub mod chunker;
+pub mod concurrency;
 pub mod embedder;
+pub mod embedder_fallback;
+pub mod embedder_retry;
+pub mod embedding_batcher;
+pub mod embedding_cache;
+pub mod embedding_template;
+pub mod embeddings_queue;
 pub mod errors;
 pub mod index_svc;
+pub mod keyword_index;
 pub mod logging;
+pub mod observability;
 pub mod pipeline;
 pub mod proto;
+pub mod retry;
 
 pub mod watcher;
+pub mod worker_manager;
 
+pub use batch_accumulator::BatchAccumulator;
 pub use chunker::Chunker;
+pub use concurrency::{AdaptiveConcurrencyLimiter, ConcurrencyLimiterStats};
 pub use embedder::Embedder;
+pub use embedder_fallback::FallbackEmbedder;
+pub use embedder_retry::{RetryConfig, RetryingEmbedder};
+pub use embedding_batcher::EmbeddingBatcher;
+pub use embedding_cache::{CachingEmbedder, EmbeddingCache, InMemoryEmbeddingCache, JsonFileEmbeddingCache};
+pub use embedding_template::{ChunkContext, EmbeddingTemplate, EmbeddingTemplateSet};
+pub use embeddings_queue::EmbeddingsQueue;
 pub use errors::{ForgeIndexerError, Result};
 pub use index_svc::IndexService;
+pub use keyword_index::KeywordIndex;
 pub use logging::{
     LoggingConfig, init_default_logging, init_development_logging, init_production_logging,
 };
-pub use pipeline::{EmbedderType, IndexingPipeline, PipelineConfig};
-pub use watcher::FileWatcher;
+pub use observability::{init_metrics, shutdown_metrics};
+pub use pipeline::{EmbedderType, IndexDiff, IndexingPipeline, PipelineConfig, WatchMode};
+pub use retry::{RetryPolicy, retry_with_backoff};
+pub use watcher::{DebouncedEvent, FileWatcher};
+pub use worker_manager::{ReindexProgress, WorkerInfo, WorkerManager, WorkerProgress, WorkerState};
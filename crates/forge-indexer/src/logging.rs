@@ -98,8 +98,10 @@ impl LoggingConfig {
         }
     }
 
-    /// Initialize the global tracing subscriber
+    /// Initialize the global tracing subscriber, and the OpenTelemetry metrics/tracing subsystem
+    /// if `enable_metrics` is set.
     pub fn init_tracing(&self) -> Result<()> {
+        crate::observability::init_metrics(self)?;
         let env_filter = self.build_env_filter()?;
 
         if self.json_format {
@@ -152,6 +154,7 @@ impl LoggingConfig {
         Registry::default()
             .with(env_filter)
             .with(fmt_layer)
+            .with(crate::observability::tracing_layer(self))
             .try_init()?;
 
         Ok(())
@@ -180,6 +183,7 @@ impl LoggingConfig {
         Registry::default()
             .with(env_filter)
             .with(fmt_layer)
+            .with(crate::observability::tracing_layer(self))
             .try_init()?;
 
         Ok(())
@@ -250,9 +254,14 @@ macro_rules! log_operation_error {
     };
 }
 
+/// Logs a performance metric, and -- if the OpenTelemetry subsystem in [`crate::observability`]
+/// has been installed (see `LoggingConfig::enable_metrics`) -- mirrors it into the matching meter
+/// instrument so it shows up on a real dashboard, not just in the log stream. `$value` must be
+/// numeric (anything `as f64`-castable); tag values are formatted with `Debug` since they don't
+/// share a common trait this macro can bound on.
 #[macro_export]
 macro_rules! log_performance_metric {
-    ($metric:expr, $value:expr, $unit:expr, $($field:ident = $tag_value:expr),*) => {
+    ($metric:expr, $value:expr, $unit:expr, $($field:ident = $tag_value:expr),*) => {{
         tracing::info!(
             metric = $metric,
             value = $value,
@@ -260,5 +269,10 @@ macro_rules! log_performance_metric {
             $($field = $tag_value,)*
             "Performance metric"
         );
-    };
+        $crate::observability::record_metric(
+            $metric,
+            $value as f64,
+            &[$(opentelemetry::KeyValue::new(stringify!($field), format!("{:?}", $tag_value))),*],
+        );
+    }};
 }
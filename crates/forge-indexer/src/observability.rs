@@ -0,0 +1,146 @@
+//! OpenTelemetry metrics/tracing subsystem, gated behind `LoggingConfig::enable_metrics`. When
+//! enabled, installs a global OTLP-exporting meter provider with counters/histograms for the
+//! pipeline's core operations, and a matching tracer provider so `init_tracing` can fold spans
+//! into traces via a `tracing-opentelemetry` layer. When disabled (the common case for a local
+//! dev run), `init_metrics` is a no-op and `log_performance_metric!` only ever logs.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Result;
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::{Counter, Histogram, UpDownCounter};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::trace::TracerProvider;
+use tracing_opentelemetry::OpenTelemetryLayer;
+
+use crate::logging::LoggingConfig;
+
+const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+const DEFAULT_EXPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Process-wide handle to the installed meter's instruments. `None` until `init_metrics` has run
+/// with `LoggingConfig::enable_metrics` set; `log_performance_metric!` silently no-ops against an
+/// uninstalled provider so call sites don't need to care whether metrics are enabled.
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+struct Metrics {
+    provider: SdkMeterProvider,
+    embedding_latency_ms: Histogram<f64>,
+    vector_insert_latency_ms: Histogram<f64>,
+    vector_search_latency_ms: Histogram<f64>,
+    vector_operations_total: Counter<u64>,
+    total_vectors: UpDownCounter<i64>,
+}
+
+fn otlp_endpoint() -> String {
+    std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").unwrap_or_else(|_| DEFAULT_OTLP_ENDPOINT.to_string())
+}
+
+fn export_interval() -> Duration {
+    std::env::var("OTEL_METRIC_EXPORT_INTERVAL_MS")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_EXPORT_INTERVAL)
+}
+
+fn resource() -> Resource {
+    Resource::new(vec![KeyValue::new("service.name", "forge-indexer")])
+}
+
+/// Install the global OTLP meter and tracer providers and their instruments, if
+/// `config.enable_metrics` is set. A no-op if metrics are disabled or already installed.
+pub fn init_metrics(config: &LoggingConfig) -> Result<()> {
+    if !config.enable_metrics || METRICS.get().is_some() {
+        return Ok(());
+    }
+
+    let endpoint = otlp_endpoint();
+
+    let metric_exporter =
+        opentelemetry_otlp::MetricExporter::builder().with_tonic().with_endpoint(&endpoint).build()?;
+    let reader = PeriodicReader::builder(metric_exporter).with_interval(export_interval()).build();
+    let meter_provider = SdkMeterProvider::builder().with_reader(reader).with_resource(resource()).build();
+    opentelemetry::global::set_meter_provider(meter_provider.clone());
+
+    let span_exporter = opentelemetry_otlp::SpanExporter::builder().with_tonic().with_endpoint(&endpoint).build()?;
+    let tracer_provider =
+        TracerProvider::builder().with_batch_exporter(span_exporter, opentelemetry_sdk::runtime::Tokio).with_resource(resource()).build();
+    opentelemetry::global::set_tracer_provider(tracer_provider);
+
+    let meter = meter_provider.meter("forge_indexer");
+    let metrics = Metrics {
+        embedding_latency_ms: meter
+            .f64_histogram("embedding_latency_ms")
+            .with_description("Latency of Embedder::embed_batch calls")
+            .with_unit("ms")
+            .build(),
+        vector_insert_latency_ms: meter
+            .f64_histogram("vector_insert_latency_ms")
+            .with_description("Latency of VectorStore insert operations")
+            .with_unit("ms")
+            .build(),
+        vector_search_latency_ms: meter
+            .f64_histogram("vector_search_latency_ms")
+            .with_description("Latency of VectorStore search operations")
+            .with_unit("ms")
+            .build(),
+        vector_operations_total: meter
+            .u64_counter("vector_operations_total")
+            .with_description("Count of recorded performance-metric events, by metric name")
+            .build(),
+        total_vectors: meter
+            .i64_up_down_counter("vector_store_total_vectors")
+            .with_description("VectorStoreStats::total_vectors, the current size of a collection")
+            .build(),
+        provider: meter_provider,
+    };
+
+    let _ = METRICS.set(metrics);
+    Ok(())
+}
+
+/// The `tracing-opentelemetry` layer bridging spans into the tracer provider `init_metrics`
+/// installed, for `init_tracing` to fold into its subscriber alongside the existing fmt layer.
+/// Returns `None` when metrics aren't enabled, so a disabled subsystem costs nothing at all.
+pub fn tracing_layer<S>(config: &LoggingConfig) -> Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>
+where
+    S: tracing::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    if !config.enable_metrics {
+        return None;
+    }
+    let tracer = opentelemetry::global::tracer("forge_indexer");
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
+
+/// Record a metric reported via `log_performance_metric!` into the matching OpenTelemetry
+/// instrument above, if metrics are enabled. `metric` names not recognized here still increment
+/// `vector_operations_total` but otherwise have nowhere else to go -- this bridges the handful of
+/// core operations the subsystem actually instruments, not an open-ended metric namespace.
+pub fn record_metric(metric: &str, value: f64, tags: &[KeyValue]) {
+    let Some(metrics) = METRICS.get() else { return };
+
+    match metric {
+        "embedding_latency_ms" => metrics.embedding_latency_ms.record(value, tags),
+        "vector_insert_latency_ms" | "vector_store_batch_insert_latency" => {
+            metrics.vector_insert_latency_ms.record(value, tags)
+        }
+        "vector_search_latency_ms" => metrics.vector_search_latency_ms.record(value, tags),
+        "vector_store_total_vectors" => metrics.total_vectors.add(value as i64, tags),
+        _ => {}
+    }
+    metrics.vector_operations_total.add(1, tags);
+}
+
+/// Flush and shut down the installed meter provider, so the process's final batch of metrics
+/// isn't lost to the next periodic export that never happens. A no-op if metrics were never
+/// enabled.
+pub fn shutdown_metrics() {
+    if let Some(metrics) = METRICS.get() {
+        let _ = metrics.provider.shutdown();
+    }
+}
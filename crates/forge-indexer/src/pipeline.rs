@@ -1,19 +1,28 @@
 //! Production-grade indexing pipeline
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use anyhow::Result;
 use forge_walker::Walker;
-use ignore::gitignore::Gitignore;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use tokio::sync::{RwLock, mpsc};
 use tracing::{debug, error, info, warn};
 
+use crate::batch_accumulator::BatchAccumulator;
 use crate::chunker::{self, Chunker};
-use crate::embedder::{Embedder, LocalEmbedder, OpenAIEmbedder};
+use crate::concurrency::AdaptiveConcurrencyLimiter;
+use crate::worker_manager::{self, ReindexProgress, WorkerInfo, WorkerManager, WorkerState};
+use crate::embedder::{Embedder, LocalEmbedder, OllamaEmbedder, OpenAIEmbedder};
+use crate::embedder_fallback::FallbackEmbedder;
+use crate::embedder_retry::RetryingEmbedder;
+use crate::embedding_cache::CachingEmbedder;
+use crate::embedding_template::EmbeddingTemplateSet;
+use crate::embeddings_queue::EmbeddingsQueue;
 use crate::index_svc::IndexService;
 use crate::proto::Chunk;
-use crate::watcher::FileWatcher;
+use crate::watcher::{DebouncedEvent, FileWatcher};
 
 /// Configuration for the indexing pipeline
 #[derive(Debug, Clone)]
@@ -23,8 +32,52 @@ pub struct PipelineConfig {
     pub local_model_path: Option<PathBuf>,
     pub local_tokenizer_path: Option<PathBuf>,
     pub batch_size: usize,
+    /// Approximate per-request token ceiling used by `EmbeddingsQueue` to pack embedding
+    /// sub-batches, in place of the fixed `batch_size` chunk count.
+    pub embedding_token_ceiling: usize,
+    /// On-disk path for the content-hash `CachingEmbedder` store, keyed by chunk text plus
+    /// embedder name/dimension so unchanged chunks skip re-embedding on re-index.
+    pub embedding_cache_path: PathBuf,
+    /// Per-language overrides for the `{{field}}` template rendered into the text handed to the
+    /// embedder (see `EmbeddingTemplateSet`), keyed by `Chunk::lang`. Validated once up front in
+    /// `IndexingPipeline::new`, so a typo'd field name is rejected before indexing starts instead
+    /// of silently embedding literal placeholder text. Languages absent here keep their built-in
+    /// default template.
+    pub embedding_template_overrides: HashMap<String, String>,
+    /// Force indexing of every file the walker returns, bypassing `filter_files`'s extension
+    /// allowlist (gitignore/`.forge`-style exclusions still apply). Off by default so an
+    /// unfiltered crawl of a large monorepo doesn't sweep in binary/asset files nobody searches.
+    pub all_files: bool,
+    /// Memory budget, in MB, for the cumulative size of file contents
+    /// `collect_and_process_with_memory_budget` holds in one batch during the initial directory
+    /// crawl. Once adding the next file would exceed it, the accumulated batch is flushed through
+    /// `process_files` before the crawl reads any more, so indexing a large monorepo can't hold
+    /// every file's content in memory at once.
+    pub max_crawl_memory_mb: usize,
+    /// Token budget for the cross-file `BatchAccumulator`: once the texts queued across every
+    /// concurrently-processing file reach this many estimated tokens, they flush as one request
+    /// to the embedder regardless of `cross_file_batch_linger_ms`.
+    pub cross_file_batch_token_budget: usize,
+    /// How long the `BatchAccumulator` lets a batch sit below its token budget before flushing it
+    /// anyway, in milliseconds, so a trickle of small files isn't held up waiting to fill a batch.
+    pub cross_file_batch_linger_ms: u64,
     pub max_concurrent_files: usize,
+    /// How much CPU/IO a background `process_files` run yields to foreground work: each batch
+    /// boundary sleeps `tranquility * <time the last batch took>`. `0` (the default) disables
+    /// throttling entirely.
+    pub tranquility: u32,
+    /// On-disk path for the `ReindexProgress` persisted by `process_files` after each batch, so a
+    /// cancelled or crashed reindex can resume via `resume_pending_reindex` instead of restarting.
+    pub reindex_progress_path: PathBuf,
     pub supported_extensions: Vec<String>,
+    /// Whether `start_watching` should descend into subdirectories of the watched path or watch
+    /// only its top level.
+    pub watch_mode: WatchMode,
+    /// How long a path must go without a new watcher event before `FileWatcher` emits a debounced
+    /// event for it, in milliseconds. A single editor save or a git checkout fires several
+    /// create/modify/rename events per path in quick succession; debouncing collapses all of them
+    /// into one `DebouncedEvent`.
+    pub debounce_ms: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +85,18 @@ pub enum EmbedderType {
     OpenAI,
     Local,
     Hybrid, // Use local with OpenAI fallback
+    /// A model served locally over Ollama's HTTP API, for fully local semantic indexing.
+    Ollama { model: String, dimension: Option<usize>, url: String },
+}
+
+/// Whether a watched directory is watched recursively or only at its top level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WatchMode {
+    #[default]
+    Recursive,
+    /// Watch only direct children of the root; events for deeper paths are dropped. Lets a caller
+    /// watch a flat config/docs directory cheaply without descending into a large nested tree.
+    NonRecursive,
 }
 
 impl Default for PipelineConfig {
@@ -42,7 +107,18 @@ impl Default for PipelineConfig {
             local_model_path: None,
             local_tokenizer_path: None,
             batch_size: 10,
+            embedding_token_ceiling: 8000,
+            embedding_cache_path: PathBuf::from(".forge/embedding_cache.json"),
+            embedding_template_overrides: HashMap::new(),
+            all_files: false,
+            max_crawl_memory_mb: 512,
+            cross_file_batch_token_budget: 8000,
+            cross_file_batch_linger_ms: 200,
             max_concurrent_files: 5,
+            tranquility: 0,
+            reindex_progress_path: PathBuf::from(".forge/reindex_progress.json"),
+            watch_mode: WatchMode::default(),
+            debounce_ms: 300,
             supported_extensions: vec![
                 "rs".to_string(),
                 "py".to_string(),
@@ -93,6 +169,37 @@ pub struct PipelineStats {
     pub embeddings_generated: u64,
     pub errors_encountered: u64,
     pub bytes_processed: u64,
+    /// Chunks whose `code` was identical to another chunk already embedded in the same batch
+    /// (license headers, vendored boilerplate, generated files), so the embedder was called once
+    /// for the shared text and the resulting vector was fanned out to every duplicate instead of
+    /// re-submitting it.
+    pub duplicate_chunks_deduplicated: u64,
+    /// Chunks dropped by `process_file`'s `Chunker::find_near_duplicate_chunks` pass: near-(but
+    /// not byte-identical) duplicates of another chunk in the same file, per MinHash + LSH
+    /// clustering over their symbol sets confirmed by an exact Jaccard check.
+    pub near_duplicate_chunks_skipped: u64,
+    /// Files whose content hash matched what was already indexed, so `process_file` returned
+    /// early without chunking or embedding.
+    pub files_skipped: u64,
+    /// Times `collect_and_process_with_memory_budget` flushed its accumulated batch through
+    /// `process_files` because the next file would have pushed the batch over
+    /// `config.max_crawl_memory_mb`, rather than because the crawl finished.
+    pub crawl_flush_cycles: u64,
+    /// Current `AdaptiveConcurrencyLimiter` limit and lifetime increase/decrease decision counts,
+    /// refreshed on every `get_stats` call.
+    pub concurrency_limit: usize,
+    pub concurrency_increases: u64,
+    pub concurrency_decreases: u64,
+}
+
+/// Result of [`IndexingPipeline::diff_against_directory`]: paths eligible for indexing but absent
+/// from it, and paths the index still tracks whose file is gone from disk.
+#[derive(Debug, Clone, Default)]
+pub struct IndexDiff {
+    /// On disk under the scanned directory, eligible per `filter_files`, but not in the index.
+    pub missing_from_index: Vec<PathBuf>,
+    /// In the index, but the file no longer exists on disk.
+    pub stale_in_index: Vec<PathBuf>,
 }
 
 /// Production-grade indexing pipeline
@@ -100,13 +207,33 @@ pub struct IndexingPipeline {
     config: PipelineConfig,
     chunker: Arc<RwLock<Chunker>>,
     embedder: Arc<dyn Embedder>,
+    /// Renders a chunk's structured metadata into the text handed to `embedder`, per
+    /// `config.embedding_template_overrides`. Built once in `new` so a bad template is rejected
+    /// at load time rather than mid-run.
+    embedding_template_set: Arc<EmbeddingTemplateSet>,
     index_service: Arc<RwLock<IndexService>>,
     stats: Arc<RwLock<PipelineStats>>,
     file_watcher: Option<FileWatcher>,
 
-    gitignore: Option<Gitignore>,
-    event_receiver: Option<mpsc::Receiver<notify::Event>>,
+    /// Layered `.gitignore`/`.ignore` matchers consulted by `should_ignore_file`: one per
+    /// directory that had a file (ancestors up to the repo root, every nested directory under the
+    /// indexed tree, plus the user's global excludes file), each rooted at its own directory so
+    /// relative patterns match correctly. A path is treated as ignored if *any* layer matches it
+    /// -- an approximation of git's closest-file-wins precedence that doesn't attempt to
+    /// reconcile a `!negation` in one file against an ignore in another.
+    gitignore: Vec<Gitignore>,
+    event_receiver: Option<mpsc::Receiver<DebouncedEvent>>,
     walker: Option<Walker>,
+    /// The root and mode most recently passed to `start_watching`, consulted by
+    /// `should_process_file` to drop events for paths deeper than a `WatchMode::NonRecursive`
+    /// root's direct children.
+    watch_root: Option<(PathBuf, WatchMode)>,
+    /// Governs how many `process_file` calls `process_files` runs concurrently, adapting live
+    /// across calls based on observed batch latency and overload errors instead of staying fixed
+    /// at `config.max_concurrent_files`.
+    concurrency_limiter: Arc<AdaptiveConcurrencyLimiter>,
+    /// Tracks every `process_files` job for pause/resume/cancel and progress reporting.
+    worker_manager: Arc<WorkerManager>,
 }
 
 impl IndexingPipeline {
@@ -117,6 +244,8 @@ impl IndexingPipeline {
         openai_api_key: Option<String>,
         local_model_path: Option<String>,
         local_tokenizer_path: Option<String>,
+        ollama_url: Option<String>,
+        ollama_model: Option<String>,
         batch_size: usize,
         max_concurrent_files: usize,
         supported_extensions: Vec<String>,
@@ -125,6 +254,15 @@ impl IndexingPipeline {
             "openai" => EmbedderType::OpenAI,
             "local" => EmbedderType::Local,
             "hybrid" => EmbedderType::Hybrid,
+            "ollama" => EmbedderType::Ollama {
+                model: ollama_model
+                    .or_else(|| std::env::var("OLLAMA_MODEL").ok())
+                    .unwrap_or_else(|| "nomic-embed-text".to_string()),
+                dimension: None,
+                url: ollama_url
+                    .or_else(|| std::env::var("OLLAMA_HOST").ok())
+                    .unwrap_or_else(|| "http://localhost:11434".to_string()),
+            },
             _ => return Err(anyhow::anyhow!("Invalid embedder type: {}", embedder_type)),
         };
 
@@ -139,6 +277,7 @@ impl IndexingPipeline {
             batch_size,
             max_concurrent_files,
             supported_extensions,
+            ..Default::default()
         };
 
         Self::new(config).await
@@ -205,6 +344,16 @@ impl IndexingPipeline {
             }
         }
 
+        // Consult the layered .gitignore/.ignore stack discovered in `load_gitignore_patterns`:
+        // ignored if any layer matches, since this is the only gitignore check applied to
+        // individually-watched file events (bulk indexing instead relies on the walker).
+        for matcher in &self.gitignore {
+            if matcher.matched(file_path, file_path.is_dir()).is_ignore() {
+                debug!("🚫 Ignoring file matched by layered gitignore: {:?}", file_path);
+                return true;
+            }
+        }
+
         false
     }
 
@@ -222,6 +371,12 @@ impl IndexingPipeline {
                     return false;
                 }
 
+                // `all_files` bypasses the extension allowlist entirely; gitignore exclusions
+                // above still apply.
+                if self.config.all_files {
+                    return true;
+                }
+
                 // Check if extension is supported
                 if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
                     let ext_lower = extension.to_lowercase();
@@ -301,25 +456,132 @@ impl IndexingPipeline {
 
         Ok(filtered_files)
     }
-    /// Load gitignore patterns from the repository (deprecated - using
-    /// forge_walker now)
-    async fn load_gitignore_patterns() -> Option<Gitignore> {
-        // Try to load .gitignore from the current directory
-        let gitignore_path = std::env::current_dir().ok()?.join(".gitignore");
-        if !gitignore_path.exists() {
-            return None;
+
+    /// Like [`Self::collect_files_from_directory`], but for an initial crawl of a directory too
+    /// large to hold every file's content in memory at once: files are grouped into batches whose
+    /// cumulative on-disk size stays under `config.max_crawl_memory_mb`, and each batch is flushed
+    /// through [`Self::process_files`] as soon as the next file would exceed the budget, instead
+    /// of collecting every path up front and processing them all in one call. Returns how many
+    /// flush cycles this triggered (also recorded in `PipelineStats::crawl_flush_cycles`).
+    pub async fn collect_and_process_with_memory_budget(&self, dir_path: &Path) -> Result<u64> {
+        let filtered_files = self.collect_files_from_directory(dir_path).await?;
+        let budget_bytes = (self.config.max_crawl_memory_mb as u64) * 1024 * 1024;
+
+        let mut flush_cycles = 0u64;
+        let mut batch: Vec<PathBuf> = Vec::new();
+        let mut batch_bytes = 0u64;
+
+        for path in filtered_files {
+            let file_size = tokio::fs::metadata(&path).await.map(|m| m.len()).unwrap_or(0);
+
+            if !batch.is_empty() && batch_bytes + file_size > budget_bytes {
+                info!(
+                    "💧 Crawl memory budget ({} MB) reached, flushing batch of {} file(s) before continuing",
+                    self.config.max_crawl_memory_mb,
+                    batch.len()
+                );
+                self.process_files(std::mem::take(&mut batch)).await?;
+                batch_bytes = 0;
+                flush_cycles += 1;
+                let mut stats = self.stats.write().await;
+                stats.crawl_flush_cycles += 1;
+            }
+
+            batch_bytes += file_size;
+            batch.push(path);
         }
 
-        let (gitignore, error) = Gitignore::new(&gitignore_path);
-        if let Some(e) = error {
-            warn!("⚠️  Failed to load gitignore patterns: {}", e);
-            None
-        } else {
-            info!("📄 Loaded gitignore patterns from {:?}", gitignore_path);
-            Some(gitignore)
+        if !batch.is_empty() {
+            self.process_files(batch).await?;
+        }
+
+        Ok(flush_cycles)
+    }
+
+    /// Discover and load every `.gitignore`/`.ignore` layer relevant to `root`: walk from `root`
+    /// up through each ancestor directory (stopping once a `.git` entry marks the repository
+    /// root), walk back down through every subdirectory of `root` for nested ignore files, and
+    /// finally add the user's global excludes file if one is configured. `should_ignore_file`
+    /// consults the full stack rather than the single flat file this used to load from the
+    /// current directory only.
+    async fn load_gitignore_patterns(root: &Path) -> Vec<Gitignore> {
+        let root = tokio::fs::canonicalize(root).await.unwrap_or_else(|_| root.to_path_buf());
+        let mut matchers = Vec::new();
+
+        let mut dir = Some(root.as_path());
+        while let Some(current) = dir {
+            Self::add_dir_gitignore(current, &mut matchers);
+            if current.join(".git").exists() {
+                break;
+            }
+            dir = current.parent();
+        }
+
+        for entry in ignore::WalkBuilder::new(&root)
+            .standard_filters(false)
+            .build()
+            .flatten()
+            .filter(|e| e.file_type().is_some_and(|t| t.is_dir()))
+        {
+            Self::add_dir_gitignore(entry.path(), &mut matchers);
+        }
+
+        if let Some(global) = Self::global_excludes_path()
+            && let Some(dir) = global.parent()
+            && let Some(matcher) = Self::build_gitignore_matcher(dir, &global)
+        {
+            matchers.push(matcher);
+        }
+
+        info!("📄 Loaded {} gitignore/ignore layer(s) rooted at {:?}", matchers.len(), root);
+        matchers
+    }
+
+    /// Load `dir`'s own `.gitignore` and `.ignore`, if present, into `matchers`.
+    fn add_dir_gitignore(dir: &Path, matchers: &mut Vec<Gitignore>) {
+        for name in [".gitignore", ".ignore"] {
+            let candidate = dir.join(name);
+            if candidate.exists()
+                && let Some(matcher) = Self::build_gitignore_matcher(dir, &candidate)
+            {
+                matchers.push(matcher);
+            }
+        }
+    }
+
+    /// Build a single-file `Gitignore` matcher rooted at `dir_root` (so its relative patterns
+    /// match correctly regardless of how deep `file` sits in the tree).
+    fn build_gitignore_matcher(dir_root: &Path, file: &Path) -> Option<Gitignore> {
+        let mut builder = GitignoreBuilder::new(dir_root);
+        if let Some(e) = builder.add(file) {
+            warn!("⚠️  Failed to load gitignore patterns from {:?}: {}", file, e);
+            return None;
+        }
+        match builder.build() {
+            Ok(gitignore) => {
+                debug!("📄 Loaded gitignore patterns from {:?}", file);
+                Some(gitignore)
+            }
+            Err(e) => {
+                warn!("⚠️  Failed to build gitignore matcher for {:?}: {}", file, e);
+                None
+            }
         }
     }
 
+    /// The user's global excludes file (`git config core.excludesFile`'s conventional default),
+    /// i.e. `$XDG_CONFIG_HOME/git/ignore` or `~/.config/git/ignore`. Doesn't read a
+    /// `core.excludesFile` override from `.gitconfig` -- that would need a config-file parser this
+    /// crate doesn't otherwise carry -- only the path git falls back to when no override is set.
+    fn global_excludes_path() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from)?;
+        let base = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.join(".config"));
+        let path = base.join("git").join("ignore");
+        path.exists().then_some(path)
+    }
+
     pub async fn new(config: PipelineConfig) -> Result<Self> {
         info!(
             "🔧 Initializing IndexingPipeline with embedder type: {:?}",
@@ -336,9 +598,16 @@ impl IndexingPipeline {
         info!("📝 Initializing chunker...");
         let chunker = Arc::new(RwLock::new(Chunker::new()));
 
+        // Validate any embedding template overrides up front, so a typo'd field name is rejected
+        // before indexing starts rather than producing garbage embeddings mid-run.
+        let embedding_template_set = Arc::new(EmbeddingTemplateSet::from_overrides(
+            &config.embedding_template_overrides,
+            config.embedding_token_ceiling,
+        )?);
+
         // Initialize embedder based on configuration
         info!("🤖 Initializing embedder: {:?}", config.embedder_type);
-        let embedder: Arc<dyn Embedder> = match config.embedder_type {
+        let embedder: Arc<dyn Embedder> = match &config.embedder_type {
             EmbedderType::OpenAI => {
                 let _api_key = config.openai_api_key.clone().ok_or_else(|| {
                     anyhow::anyhow!("OpenAI API key required for OpenAI embedder")
@@ -360,20 +629,63 @@ impl IndexingPipeline {
                 }
             }
             EmbedderType::Hybrid => {
-                // For now, just use local embedder
-                // In production, this would implement fallback logic
-                warn!("⚠️  Hybrid embedder not fully implemented, using local embedder");
-                Arc::new(LocalEmbedder::new_default()?)
+                // Run OpenAI as the primary provider with the always-available local placeholder
+                // as its fallback, so a hosted-provider outage or an exhausted API key degrades
+                // the run instead of aborting it. Each leg is retried independently (see below)
+                // before `FallbackEmbedder` gives up on it and tries the next.
+                info!("🔀 Using hybrid embedder: OpenAI primary, local fallback");
+                let primary: Arc<dyn Embedder> =
+                    Arc::new(RetryingEmbedder::new(Arc::new(OpenAIEmbedder::new().await?)));
+                let fallback: Arc<dyn Embedder> =
+                    Arc::new(RetryingEmbedder::new(Arc::new(LocalEmbedder::new_default()?)));
+                Arc::new(FallbackEmbedder::new(vec![primary, fallback]))
+            }
+            EmbedderType::Ollama { model, dimension, url } => {
+                info!("🦙 Using Ollama embedder: model={}, url={}", model, url);
+                Arc::new(OllamaEmbedder::new(url.clone(), model.clone(), *dimension))
             }
         };
 
+        // Wrap the raw provider call in retry/backoff so transient rate limiting doesn't abort
+        // an entire indexing run, then layer the content-hash cache on top so repeat calls for
+        // unchanged chunks never even reach the (now-retrying) provider. `Hybrid` already wraps
+        // each leg of its fallback chain individually above, so this outer layer is a no-op retry
+        // around the chain as a whole -- harmless, since `FallbackEmbedder::embed_batch_tagged`
+        // only returns `Err` once every provider in the chain has already exhausted its own retries.
+        info!("🔁 Wrapping embedder with rate-limit-aware retry/backoff");
+        let embedder: Arc<dyn Embedder> = Arc::new(RetryingEmbedder::new(embedder));
+
+        // Wrap the embedder in a content-hash cache so unchanged chunks skip re-embedding on
+        // re-index; the cache is transparent to callers since it implements `Embedder` itself.
+        info!(
+            "💾 Wrapping embedder with content-hash cache at {:?}",
+            config.embedding_cache_path
+        );
+        let embedder: Arc<dyn Embedder> =
+            Arc::new(CachingEmbedder::new(embedder, config.embedding_cache_path.clone()).await?);
+
+        // Wrap the whole stack in the cross-file batch accumulator, which is what `process_file`
+        // calls actually invoke: merging the texts submitted by every file processing concurrently
+        // (post-cache, so only real misses count toward the shared token budget) into fewer,
+        // larger requests than embedding each file in isolation would produce.
+        info!(
+            "📥 Wrapping embedder with cross-file batch accumulator (token budget: {}, linger: {}ms)",
+            config.cross_file_batch_token_budget, config.cross_file_batch_linger_ms
+        );
+        let embedder: Arc<dyn Embedder> = BatchAccumulator::new(
+            embedder,
+            config.cross_file_batch_token_budget,
+            std::time::Duration::from_millis(config.cross_file_batch_linger_ms),
+        );
+
         // Initialize index service
         info!("🗂️  Initializing index service...");
         let vector_dimension = embedder.embedding_dimension();
         let index_service = Arc::new(RwLock::new(IndexService::new(vector_dimension).await?));
 
         // Load gitignore patterns if available
-        let gitignore = Self::load_gitignore_patterns().await;
+        let gitignore_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let gitignore = Self::load_gitignore_patterns(&gitignore_root).await;
 
         // Create a walker instance
         let walker = Some(
@@ -382,39 +694,66 @@ impl IndexingPipeline {
 
         info!("✅ IndexingPipeline initialization complete");
 
+        // Concurrency starts at `max_concurrent_files` and is free to grow up to 4x that (a fast,
+        // unthrottled embedder easily sustains far more in-flight batches) or shrink down to 1 (a
+        // single in-flight batch at a time) as `process_files` observes real latency/overload.
+        let concurrency_limiter = Arc::new(AdaptiveConcurrencyLimiter::new(
+            config.max_concurrent_files,
+            1,
+            config.max_concurrent_files.max(1) * 4,
+        ));
+
         Ok(Self {
             config,
             chunker,
             embedder,
+            embedding_template_set,
             index_service,
             stats: Arc::new(RwLock::new(PipelineStats::default())),
             file_watcher: None,
             event_receiver: None,
             gitignore,
             walker,
+            watch_root: None,
+            concurrency_limiter,
+            worker_manager: Arc::new(WorkerManager::new()),
         })
     }
 
-    /// Start watching a directory for file changes
+    /// Start watching a directory for file changes, recursively or not per `self.config.watch_mode`.
     pub async fn start_watching(&mut self, watch_path: &Path) -> Result<()> {
-        info!("👀 Starting file watcher for path: {:?}", watch_path);
+        self.start_watching_with_mode(watch_path, self.config.watch_mode).await
+    }
+
+    /// Start watching a directory for file changes, overriding `self.config.watch_mode` for this
+    /// call. In `WatchMode::NonRecursive`, only direct children of `watch_path` are processed --
+    /// events for deeper paths are dropped in `should_process_file`.
+    pub async fn start_watching_with_mode(
+        &mut self,
+        watch_path: &Path,
+        mode: WatchMode,
+    ) -> Result<()> {
+        info!("👀 Starting file watcher for path: {:?} ({:?})", watch_path, mode);
 
         if !watch_path.exists() {
             error!("❌ Watch path does not exist: {:?}", watch_path);
             return Err(anyhow::anyhow!("Watch path does not exist"));
         }
 
-        let (mut watcher, receiver) = FileWatcher::new()?;
-        watcher.watch_directory(watch_path)?;
+        let debounce = std::time::Duration::from_millis(self.config.debounce_ms);
+        let (mut watcher, receiver) = FileWatcher::new(debounce, self.gitignore.clone())?;
+        watcher.watch_directory(watch_path, mode)?;
 
         self.file_watcher = Some(watcher);
         self.event_receiver = Some(receiver);
+        self.watch_root = Some((watch_path.to_path_buf(), mode));
 
         info!("✅ File watcher started successfully for {:?}", watch_path);
         Ok(())
     }
 
-    /// Process file change events from the watcher
+    /// Process file change events from the watcher. Debouncing and gitignore filtering already
+    /// happened in `FileWatcher`, so this loop just dispatches each `DebouncedEvent` as it arrives.
     pub async fn process_events(&mut self) -> Result<()> {
         let mut receiver = self
             .event_receiver
@@ -422,23 +761,14 @@ impl IndexingPipeline {
             .ok_or_else(|| anyhow::anyhow!("File watcher not started"))?;
 
         info!("🔄 Starting event processing loop - waiting for file changes...");
-        let mut event_count = 0;
+        let mut event_count = 0u64;
 
         while let Some(event) = receiver.recv().await {
             event_count += 1;
-            debug!("📨 Received file event #{}: {:?}", event_count, event);
+            debug!("📨 Received debounced event #{}: {:?}", event_count, event);
 
-            let start_time = std::time::Instant::now();
-            if let Err(e) = self.handle_file_event(event).await {
-                error!("❌ Error handling file event #{}: {}", event_count, e);
-                let mut stats = self.stats.write().await;
-                stats.errors_encountered += 1;
-            } else {
-                let duration = start_time.elapsed();
-                debug!("✅ File event #{} processed in {:?}", event_count, duration);
-            }
+            self.dispatch_debounced(event).await;
 
-            // Log periodic statistics
             if event_count % 10 == 0 {
                 let stats = self.stats.read().await;
                 info!(
@@ -452,51 +782,67 @@ impl IndexingPipeline {
             }
         }
 
-        info!(
-            "🏁 Event processing loop completed after {} events",
-            event_count
-        );
+        info!("🏁 Event processing loop completed after {} event(s)", event_count);
         Ok(())
     }
 
-    /// Handle a single file change event
-    async fn handle_file_event(&self, event: notify::Event) -> Result<()> {
-        debug!("🔍 Analyzing file event: {:?}", event);
+    /// Dispatch one debounced filesystem change: re-index the path on `Created`/`Modified`, drop
+    /// it from the index on `Removed`, or for `Renamed` remove the old path and re-index the new
+    /// one.
+    async fn dispatch_debounced(&self, event: DebouncedEvent) {
+        match event {
+            DebouncedEvent::Created(path) | DebouncedEvent::Modified(path) => {
+                self.upsert_path(&path).await;
+            }
+            DebouncedEvent::Removed(path) => {
+                self.remove_path(&path).await;
+            }
+            DebouncedEvent::Renamed { from, to } => {
+                self.remove_path(&from).await;
+                self.upsert_path(&to).await;
+            }
+        }
+    }
 
-        let mut processed_files = 0;
-        for path in event.paths {
-            if self.should_process_file(&path) {
-                debug!("✅ File eligible for processing: {:?}", path);
-                let start_time = std::time::Instant::now();
+    /// Re-chunk/embed/index a single path, as dispatched by `dispatch_debounced`.
+    async fn upsert_path(&self, path: &Path) {
+        if !self.should_process_file(path) {
+            debug!("⏭️  Skipping file (not eligible): {:?}", path);
+            return;
+        }
 
-                match self.process_file(&path).await {
-                    Ok(()) => {
-                        processed_files += 1;
-                        let duration = start_time.elapsed();
-                        info!(
-                            "✅ Successfully processed file: {:?} (took {:?})",
-                            path, duration
-                        );
-                    }
-                    Err(e) => {
-                        error!("❌ Error processing file {:?}: {}", path, e);
-                        let mut stats = self.stats.write().await;
-                        stats.errors_encountered += 1;
-                    }
-                }
-            } else {
-                debug!("⏭️  Skipping file (not eligible): {:?}", path);
+        let start_time = std::time::Instant::now();
+        match self.process_file(path).await {
+            Ok(()) => {
+                info!("✅ Successfully processed file: {:?} (took {:?})", path, start_time.elapsed());
+            }
+            Err(e) => {
+                error!("❌ Error processing file {:?}: {}", path, e);
+                let mut stats = self.stats.write().await;
+                stats.errors_encountered += 1;
             }
         }
+    }
 
-        if processed_files > 0 {
-            debug!(
-                "📈 Event processing complete - {} files processed",
-                processed_files
-            );
+    /// Drop a deleted/renamed-away path from the index, as dispatched by `dispatch_debounced`.
+    async fn remove_path(&self, path: &Path) {
+        debug!("🗑️  Removing path from index: {:?}", path);
+        let path_key = path.to_string_lossy().to_string();
+        let result = {
+            let mut index_service = self.index_service.write().await;
+            index_service.update_file(&path_key, Vec::new()).await
+        };
+        match result {
+            Ok((_, deleted)) if deleted > 0 => {
+                info!("✅ Removed {} chunk(s) for deleted file: {:?}", deleted, path);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                error!("❌ Error removing deleted file {:?} from index: {}", path, e);
+                let mut stats = self.stats.write().await;
+                stats.errors_encountered += 1;
+            }
         }
-
-        Ok(())
     }
 
     /// Check if a file should be processed based on extension and other
@@ -512,6 +858,16 @@ impl IndexingPipeline {
             return false;
         }
 
+        // In non-recursive watch mode, the watcher still fires for create/remove inside nested
+        // directories if the OS batches them with a direct-child event; drop anything that isn't
+        // a direct child of the watched root ourselves rather than relying on `notify` alone.
+        if let Some((root, WatchMode::NonRecursive)) = &self.watch_root
+            && path.parent() != Some(root.as_path())
+        {
+            debug!("🚫 Ignoring non-direct-child path under non-recursive watch: {:?}", path);
+            return false;
+        }
+
         // Check extension
         if let Some(extension) = path.extension().and_then(|ext| ext.to_str()) {
             let ext_lower = extension.to_lowercase();
@@ -581,6 +937,26 @@ impl IndexingPipeline {
             file_path
         );
 
+        let path_key = file_path.to_string_lossy().to_string();
+
+        // Skip re-chunking/re-embedding entirely if this path's content hash matches what
+        // `IndexService` last indexed for it -- `file_revision` is already tracked there (see
+        // `replace_file_chunks`), so re-running over an unchanged tree turns into near-instant
+        // no-ops instead of a full re-embed, which matters for watch-mode and CI indexing.
+        {
+            let index_service = self.index_service.read().await;
+            if index_service.file_revision(&path_key) == Some(revision.as_str()) {
+                debug!(
+                    "⏭️  Skipping unchanged file {:?} (revision {} already indexed)",
+                    file_path,
+                    &revision[..8]
+                );
+                let mut stats = self.stats.write().await;
+                stats.files_skipped += 1;
+                return Ok(());
+            }
+        }
+
         // Chunk the file
         let chunks = {
             let chunk_start = std::time::Instant::now();
@@ -623,265 +999,456 @@ impl IndexingPipeline {
             }
         };
 
-        // Process chunks in batches
-        let chunk_batches: Vec<Vec<Chunk>> = chunks
-            .chunks(self.config.batch_size)
-            .map(|chunk_slice| {
-                chunk_slice
-                    .iter()
-                    .map(convert_chunker_to_proto_chunk)
-                    .collect()
-            })
-            .collect();
+        // Drop near-duplicate chunks within this file (boilerplate repeated across near-identical
+        // generated/vendored blocks) before embedding, keeping only the first chunk in each
+        // MinHash/LSH-confirmed cluster.
+        let chunks = {
+            let clusters = Chunker::find_near_duplicate_chunks(&chunks);
+            if clusters.is_empty() {
+                chunks
+            } else {
+                let skip: std::collections::HashSet<usize> =
+                    clusters.iter().flat_map(|cluster| cluster.iter().skip(1).copied()).collect();
+                if !skip.is_empty() {
+                    debug!(
+                        "🪞 Skipping {} near-duplicate chunk(s) in {:?}",
+                        skip.len(),
+                        file_path
+                    );
+                    let mut stats = self.stats.write().await;
+                    stats.near_duplicate_chunks_skipped += skip.len() as u64;
+                }
+                chunks.into_iter().enumerate().filter(|(i, _)| !skip.contains(i)).map(|(_, c)| c).collect()
+            }
+        };
+
+        // Embed every chunk belonging to this file through the token-budgeted queue, which packs
+        // embedder sub-batches by estimated token count (rather than the fixed `batch_size` chunk
+        // count) and only returns once the whole file has embedded successfully.
+        let proto_chunks: Vec<Chunk> = chunks.iter().map(convert_chunker_to_proto_chunk).collect();
+        let embeddings_queue = EmbeddingsQueue::new(
+            self.embedder.clone(),
+            self.config.embedding_token_ceiling,
+            self.embedding_template_set.clone(),
+        );
 
         info!(
-            "📦 Processing {} chunk batches (batch size: {}) for file {:?}",
-            chunk_batches.len(),
-            self.config.batch_size,
-            file_path
+            "📦 Embedding {} chunk(s) for file {:?} (token ceiling: {})",
+            proto_chunks.len(),
+            file_path,
+            self.config.embedding_token_ceiling
         );
 
-        let mut total_embeddings = 0;
-        for (batch_idx, batch) in chunk_batches.iter().enumerate() {
-            let batch_start = std::time::Instant::now();
-            info!(
-                "🔄 Processing batch {}/{} with {} chunks for file {:?}",
-                batch_idx + 1,
-                chunk_batches.len(),
-                batch.len(),
-                file_path
-            );
-            match self.process_chunk_batch(batch.clone()).await {
-                Ok(()) => {
-                    total_embeddings += batch.len();
-                    let batch_duration = batch_start.elapsed();
-                    info!(
-                        "✅ Batch {}/{} processed ({} chunks) in {:?} for file {:?}",
-                        batch_idx + 1,
-                        chunk_batches.len(),
-                        batch.len(),
-                        batch_duration,
-                        file_path
-                    );
+        let (embedded, duplicates_deduplicated) = match embeddings_queue.embed_file(&proto_chunks).await {
+            Ok(result) => result,
+            Err(e) => {
+                error!("❌ Failed to embed file {:?}: {}", file_path, e);
+                let mut stats = self.stats.write().await;
+                stats.errors_encountered += 1;
+                return Err(e.into());
+            }
+        };
+
+        // Stage every chunk's point into a buffer keyed by `revision` and hand the whole file to
+        // `IndexService::replace_file_chunks` as a single all-or-nothing commit, so a batch
+        // failure partway through a file can never leave the index holding embeddings from a mix
+        // of revisions, or chunk ids mis-associated with the wrong file on a later retry.
+        let index_start = std::time::Instant::now();
+        let staged: Vec<_> = embedded
+            .iter()
+            .map(|(chunk, embedding, provider)| {
+                let mut payload = qdrant_client::Payload::new();
+                payload.insert("path", chunk.path.clone());
+                payload.insert("lang", chunk.lang.clone());
+                payload.insert("rev", chunk.rev.clone());
+                payload.insert("size", chunk.size as i64);
+                payload.insert("code", chunk.code.clone());
+                payload.insert("start_byte", chunk.start_byte as i64);
+                payload.insert("end_byte", chunk.end_byte as i64);
+                payload.insert("start_line", chunk.start_line as i64);
+                payload.insert("end_line", chunk.end_line as i64);
+                // Add branch information for better search filtering
+                payload.insert("branch", chunk.rev.clone());
+                // Which provider/model produced this vector, so a mixed-provider index (e.g. after
+                // switching `PipelineConfig.embedder_type` or falling back mid-run) can be filtered.
+                payload.insert("embedder", provider.clone());
+
+                if let Some(symbol) = &chunk.symbol {
+                    payload.insert("symbol", symbol.clone());
                 }
-                Err(e) => {
-                    error!(
-                        "❌ Failed to process batch {}/{} for file {:?}: {}",
-                        batch_idx + 1,
-                        chunk_batches.len(),
-                        file_path,
-                        e
-                    );
-                    return Err(e);
+
+                if let Some(summary) = &chunk.summary {
+                    payload.insert("summary", summary.clone());
                 }
+
+                // The embedder above only produces a code embedding today; `summary_embedding` is
+                // `None` until a summary-embedding pass is wired into the queue.
+                (chunk.clone(), embedding.clone(), None, payload)
+            })
+            .collect();
+
+        {
+            let mut index_service = self.index_service.write().await;
+            if let Err(e) = index_service.replace_file_chunks(&path_key, &revision, staged).await {
+                error!("❌ Failed to atomically commit chunks for file {:?}: {}", file_path, e);
+                let mut stats = self.stats.write().await;
+                stats.errors_encountered += 1;
+                return Err(e.into());
             }
         }
+        debug!(
+            "🗂️  Indexed {} chunks in {:?} for file {:?}",
+            embedded.len(),
+            index_start.elapsed(),
+            file_path
+        );
 
         // Update statistics
         let mut stats = self.stats.write().await;
         stats.files_processed += 1;
         stats.chunks_created += chunks.len() as u64;
+        stats.embeddings_generated += embedded.len() as u64;
         stats.bytes_processed += file_size;
+        stats.duplicate_chunks_deduplicated += duplicates_deduplicated as u64;
 
         let total_duration = start_time.elapsed();
         info!(
             "✅ File processing complete: {:?} - {} chunks, {} embeddings in {:?}",
             file_path,
             chunks.len(),
-            total_embeddings,
+            embedded.len(),
             total_duration
         );
 
         Ok(())
     }
 
-    /// Process a batch of chunks: generate embeddings and index them
-    async fn process_chunk_batch(&self, chunks: Vec<Chunk>) -> Result<()> {
+    /// Incrementally re-index `path` given its latest `content`, keyed by `revision`, instead of
+    /// unconditionally regenerating every chunk the way [`Self::process_file`] does. Chunking and
+    /// embedding still run over the whole file -- re-embedding itself is already cheap thanks to
+    /// the content-hash `CachingEmbedder` -- but [`IndexService::update_file`] diffs the produced
+    /// chunks by content hash against what's already indexed for this path, so only chunks that
+    /// actually changed are upserted and only chunks that vanished are deleted. Intended for
+    /// editor-driven "save triggers re-index", where a small edit to a large file would otherwise
+    /// force a full re-embed of the file's Qdrant points.
+    pub async fn update_file(&self, path: &str, content: &str, revision: &str) -> Result<()> {
         let start_time = std::time::Instant::now();
-        debug!("🔄 Processing batch of {} chunks", chunks.len());
+        info!("📝 Incrementally updating index for file: {}", path);
 
-        // Extract text content for embedding
-        let texts: Vec<String> = chunks.iter().map(|chunk| chunk.code.clone()).collect();
+        let language = self.get_language_from_path(Path::new(path));
 
-        // Generate embeddings
-        let embed_start = std::time::Instant::now();
-        let embeddings = match self.embedder.embed_batch(&texts).await {
-            Ok(embeddings) => {
-                let embed_duration = embed_start.elapsed();
-                debug!(
-                    "🤖 Generated {} embeddings in {:?}",
-                    embeddings.len(),
-                    embed_duration
-                );
-                embeddings
-            }
-            Err(e) => {
-                error!("❌ Failed to generate embeddings for batch: {}", e);
-                return Err(e);
-            }
+        let chunks = {
+            let mut chunker = self.chunker.write().await;
+            chunker.chunk_file(path, content, &language, revision)?
         };
 
-        if embeddings.len() != chunks.len() {
-            let error_msg = format!(
-                "Embedding count mismatch: expected {}, got {}",
-                chunks.len(),
-                embeddings.len()
-            );
-            error!("❌ {}", error_msg);
-            return Err(anyhow::anyhow!(error_msg));
-        }
-
-        // Index each chunk with its embedding
-        let index_start = std::time::Instant::now();
-        let mut index_service = self.index_service.write().await;
-        let mut indexed_count = 0;
-
-        for (chunk, embedding) in chunks.iter().zip(embeddings.iter()) {
-            let mut payload = qdrant_client::Payload::new();
-            payload.insert("path", chunk.path.clone());
-            payload.insert("lang", chunk.lang.clone());
-            payload.insert("rev", chunk.rev.clone());
-            payload.insert("size", chunk.size as i64);
-            payload.insert("code", chunk.code.clone());
-            // Add branch information for better search filtering
-            payload.insert("branch", chunk.rev.clone());
-
-            if let Some(symbol) = &chunk.symbol {
-                payload.insert("symbol", symbol.clone());
+        // Drop near-duplicate chunks within this file before embedding; see `process_file`.
+        let chunks = {
+            let clusters = Chunker::find_near_duplicate_chunks(&chunks);
+            if clusters.is_empty() {
+                chunks
+            } else {
+                let skip: std::collections::HashSet<usize> =
+                    clusters.iter().flat_map(|cluster| cluster.iter().skip(1).copied()).collect();
+                if !skip.is_empty() {
+                    let mut stats = self.stats.write().await;
+                    stats.near_duplicate_chunks_skipped += skip.len() as u64;
+                }
+                chunks.into_iter().enumerate().filter(|(i, _)| !skip.contains(i)).map(|(_, c)| c).collect()
             }
+        };
+
+        let proto_chunks: Vec<Chunk> = chunks.iter().map(convert_chunker_to_proto_chunk).collect();
+        let embeddings_queue = EmbeddingsQueue::new(
+            self.embedder.clone(),
+            self.config.embedding_token_ceiling,
+            self.embedding_template_set.clone(),
+        );
 
-            if let Some(summary) = &chunk.summary {
-                payload.insert("summary", summary.clone());
+        let (embedded, duplicates_deduplicated) = match embeddings_queue.embed_file(&proto_chunks).await {
+            Ok(result) => result,
+            Err(e) => {
+                error!("❌ Failed to embed file {} for incremental update: {}", path, e);
+                let mut stats = self.stats.write().await;
+                stats.errors_encountered += 1;
+                return Err(e.into());
             }
+        };
 
-            match index_service
-                .add_embedding(&chunk.id, embedding.clone(), payload)
-                .await
-            {
-                Ok(()) => {
-                    indexed_count += 1;
+        let produced: Vec<(Chunk, Vec<f32>, Option<Vec<f32>>, qdrant_client::Payload)> = embedded
+            .into_iter()
+            .map(|(chunk, embedding, provider)| {
+                let mut payload = qdrant_client::Payload::new();
+                payload.insert("path", chunk.path.clone());
+                payload.insert("lang", chunk.lang.clone());
+                payload.insert("rev", chunk.rev.clone());
+                payload.insert("size", chunk.size as i64);
+                payload.insert("code", chunk.code.clone());
+                payload.insert("start_byte", chunk.start_byte as i64);
+                payload.insert("end_byte", chunk.end_byte as i64);
+                payload.insert("start_line", chunk.start_line as i64);
+                payload.insert("end_line", chunk.end_line as i64);
+                payload.insert("branch", chunk.rev.clone());
+                payload.insert("embedder", provider);
+
+                if let Some(symbol) = &chunk.symbol {
+                    payload.insert("symbol", symbol.clone());
+                }
+                if let Some(summary) = &chunk.summary {
+                    payload.insert("summary", summary.clone());
                 }
+
+                // No separate summary-embedding pass yet; see `process_file`.
+                (chunk, embedding, None, payload)
+            })
+            .collect();
+
+        let (inserted, deleted) = {
+            let mut index_service = self.index_service.write().await;
+            match index_service.update_file(path, produced).await {
+                Ok(delta) => delta,
                 Err(e) => {
-                    error!("❌ Failed to index chunk {}: {}", chunk.id, e);
+                    error!("❌ Failed to apply incremental update for {}: {}", path, e);
                     let mut stats = self.stats.write().await;
                     stats.errors_encountered += 1;
                     return Err(e.into());
                 }
             }
-        }
-
-        let index_duration = index_start.elapsed();
-        debug!(
-            "🗂️  Indexed {} chunks in {:?}",
-            indexed_count, index_duration
-        );
+        };
 
-        // Update statistics
         let mut stats = self.stats.write().await;
-        stats.embeddings_generated += embeddings.len() as u64;
+        stats.files_processed += 1;
+        stats.chunks_created += chunks.len() as u64;
+        stats.embeddings_generated += inserted as u64;
+        stats.bytes_processed += content.len() as u64;
+        stats.duplicate_chunks_deduplicated += duplicates_deduplicated as u64;
+        drop(stats);
 
-        let total_duration = start_time.elapsed();
-        debug!(
-            "✅ Batch processing complete - {} chunks processed in {:?}",
+        info!(
+            "✅ Incremental update complete for {}: {} inserted, {} deleted, {} chunk(s) total in {:?}",
+            path,
+            inserted,
+            deleted,
             chunks.len(),
-            total_duration
+            start_time.elapsed()
         );
+
         Ok(())
     }
 
-    /// Process multiple files concurrently
+    /// List every path the index currently tracks, with the content revision it was last indexed
+    /// at and how many chunks it holds for that revision. There is otherwise no way to ask the
+    /// pipeline what it has actually indexed, which makes it impossible to diagnose files
+    /// silently dropped by `filter_files`/`should_process_file` or lost to a batch failure -- see
+    /// [`Self::diff_against_directory`] for comparing this against what's actually on disk.
+    pub async fn indexed_paths(&self) -> Vec<(PathBuf, String, usize)> {
+        let index_service = self.index_service.read().await;
+        index_service
+            .indexed_paths()
+            .into_iter()
+            .map(|(path, revision, chunk_count)| (PathBuf::from(path), revision, chunk_count))
+            .collect()
+    }
+
+    /// Compare what's indexed against what's actually on disk under `dir_path`, to answer "why
+    /// isn't this file searchable?". Walks `dir_path` with [`Self::collect_files_from_directory`]
+    /// -- the same gitignore/extension filters bulk indexing applies -- and reports paths that are
+    /// on disk and eligible but missing from the index (dropped by a filter or a batch failure),
+    /// as well as paths still tracked in the index whose file no longer exists on disk (a delete
+    /// event was missed).
+    pub async fn diff_against_directory(&self, dir_path: &Path) -> Result<IndexDiff> {
+        let on_disk: std::collections::HashSet<PathBuf> =
+            self.collect_files_from_directory(dir_path).await?.into_iter().collect();
+        let indexed: std::collections::HashSet<PathBuf> =
+            self.indexed_paths().await.into_iter().map(|(path, ..)| path).collect();
+
+        let missing_from_index =
+            on_disk.iter().filter(|path| !indexed.contains(*path)).cloned().collect();
+        let stale_in_index = indexed.iter().filter(|path| !path.exists()).cloned().collect();
+
+        Ok(IndexDiff { missing_from_index, stale_in_index })
+    }
+
+    /// Process multiple files as a registered, controllable background job: batches of up to
+    /// `self.concurrency_limiter.current_limit()` files run concurrently (same as a plain
+    /// `join_all` would), but between batches the job honors pause/resume/cancel signals
+    /// delivered through `self.worker_manager` (see [`Self::pause_worker`] and friends), sleeps
+    /// `config.tranquility * <last batch's duration>` to yield to foreground work, and persists
+    /// its remaining file list so a cancelled or crashed run can continue via
+    /// [`Self::resume_pending_reindex`] instead of restarting from scratch.
     pub async fn process_files(&self, file_paths: Vec<PathBuf>) -> Result<()> {
         let start_time = std::time::Instant::now();
-        info!(
-            "🚀 Starting concurrent processing of {} files",
-            file_paths.len()
-        );
+        let total_files = file_paths.len();
+        info!("🚀 Starting concurrent processing of {} files", total_files);
+
+        let (worker_id, mut control) = self.worker_manager.register(total_files).await;
+        info!("🧑‍💻 Worker {worker_id} registered for {total_files} file(s)");
+
+        let mut remaining = file_paths;
+        let mut files_done = 0usize;
+        let mut last_path: Option<PathBuf> = None;
+        let mut error_count = 0usize;
+        let mut cancelled = false;
+
+        while !remaining.is_empty() {
+            if worker_manager::wait_while_paused(&mut control).await {
+                cancelled = true;
+                break;
+            }
+            self.worker_manager.set_state(worker_id, WorkerState::Active).await;
 
-        let semaphore = Arc::new(tokio::sync::Semaphore::new(
-            self.config.max_concurrent_files,
-        ));
-        let mut tasks = Vec::new();
+            let batch_size = self.concurrency_limiter.current_limit().min(remaining.len());
+            let batch: Vec<PathBuf> = remaining.drain(..batch_size).collect();
+            debug!(
+                "🔄 Worker {worker_id}: starting batch of {} file(s) (concurrency limit {})",
+                batch.len(),
+                batch_size
+            );
 
-        for (idx, file_path) in file_paths.iter().enumerate() {
-            let semaphore = semaphore.clone();
-            let pipeline = self.clone_for_task();
-            let file_path = file_path.clone();
+            let batch_start = std::time::Instant::now();
+            let mut tasks = Vec::with_capacity(batch.len());
+            for file_path in &batch {
+                let limiter = self.concurrency_limiter.clone();
+                let pipeline = self.clone_for_task();
+                let file_path = file_path.clone();
+
+                tasks.push(tokio::spawn(async move {
+                    let permit = limiter.acquire().await;
+                    let attempt_start = std::time::Instant::now();
+                    let result = pipeline.process_file(&file_path).await;
+                    // Release the permit before recording the outcome: `decrease()` forcibly
+                    // removes permits from the live semaphore, so if every permit in a full batch
+                    // is still held here when every task's outcome lands at once, each task's
+                    // `decrease()` call would wait on a permit none of them can release -- a
+                    // deadlock under exactly the correlated-failure scenario AIMD exists to handle.
+                    drop(permit);
+                    match &result {
+                        Ok(()) => limiter.record_success(attempt_start.elapsed()).await,
+                        Err(e) => limiter.record_error(e).await,
+                    }
+                    (file_path, result)
+                }));
+            }
 
-            let task = tokio::spawn(async move {
-                let _permit = semaphore.acquire().await.unwrap();
-                debug!(
-                    "🔄 Starting processing of file {}: {:?}",
-                    idx + 1,
-                    file_path
-                );
-                let result = pipeline.process_file(&file_path).await;
-                if let Err(ref e) = result {
-                    error!(
-                        "❌ Failed to process file {}: {:?} - {}",
-                        idx + 1,
-                        file_path,
-                        e
-                    );
-                } else {
-                    debug!(
-                        "✅ Completed processing of file {}: {:?}",
-                        idx + 1,
-                        file_path
-                    );
+            for joined in futures::future::join_all(tasks).await {
+                match joined {
+                    Ok((path, Ok(()))) => {
+                        files_done += 1;
+                        last_path = Some(path);
+                    }
+                    Ok((path, Err(e))) => {
+                        error!("❌ Failed to process file {:?}: {}", path, e);
+                        error_count += 1;
+                        files_done += 1;
+                        last_path = Some(path);
+                    }
+                    Err(e) => {
+                        error!("❌ Task join error: {}", e);
+                        error_count += 1;
+                    }
                 }
-                result
-            });
-
-            tasks.push(task);
-        }
+            }
 
-        info!(
-            "⏳ Waiting for {} concurrent tasks to complete...",
-            tasks.len()
-        );
+            self.worker_manager.update_progress(worker_id, files_done, last_path.clone()).await;
+            let progress = ReindexProgress {
+                files_done,
+                total_files,
+                last_path: last_path.clone(),
+                remaining: remaining.clone(),
+            };
+            if let Err(e) = progress.persist(&self.config.reindex_progress_path).await {
+                warn!("⚠️  Failed to persist reindex progress: {}", e);
+            }
 
-        // Wait for all tasks to complete
-        let results = futures::future::join_all(tasks).await;
+            if !remaining.is_empty() && self.config.tranquility > 0 {
+                let sleep_for = batch_start.elapsed() * self.config.tranquility;
+                debug!("😌 Tranquility {}: sleeping {:?} before next batch", self.config.tranquility, sleep_for);
+                tokio::time::sleep(sleep_for).await;
+            }
 
-        // Check for errors
-        let mut error_count = 0;
-        let mut success_count = 0;
-        for (idx, result) in results.iter().enumerate() {
-            match result {
-                Ok(Ok(())) => {
-                    success_count += 1;
-                }
-                Ok(Err(e)) => {
-                    error!("❌ File processing error for task {}: {}", idx + 1, e);
-                    error_count += 1;
-                }
-                Err(e) => {
-                    error!("❌ Task join error for task {}: {}", idx + 1, e);
-                    error_count += 1;
-                }
+            if worker_manager::is_cancelled(&control) {
+                cancelled = true;
+                break;
             }
         }
 
         let total_duration = start_time.elapsed();
-        if error_count > 0 {
+        if cancelled {
             warn!(
-                "⚠️  Concurrent processing completed with {} successes and {} errors in {:?}",
-                success_count, error_count, total_duration
+                "🛑 Worker {worker_id} cancelled after {}/{} file(s) in {:?}",
+                files_done, total_files, total_duration
             );
+            self.worker_manager.finish(worker_id, Some("cancelled".to_string())).await;
         } else {
-            info!(
-                "✅ All {} files processed successfully in {:?}",
-                file_paths.len(),
-                total_duration
-            );
+            // A clean finish (every file attempted) has nothing left to resume.
+            let _ = tokio::fs::remove_file(&self.config.reindex_progress_path).await;
+            if error_count > 0 {
+                warn!(
+                    "⚠️  Worker {worker_id} completed with {} error(s) out of {} file(s) in {:?}",
+                    error_count, total_files, total_duration
+                );
+                self.worker_manager
+                    .finish(worker_id, Some(format!("{error_count} file(s) failed")))
+                    .await;
+            } else {
+                info!(
+                    "✅ Worker {worker_id} processed all {} file(s) successfully in {:?}",
+                    total_files, total_duration
+                );
+                self.worker_manager.finish(worker_id, None).await;
+            }
         }
 
         Ok(())
     }
 
-    /// Get current pipeline statistics
+    /// Resume a reindex left incomplete by a cancelled or crashed `process_files` call, if
+    /// `config.reindex_progress_path` holds a persisted [`ReindexProgress`]. Returns `false` (and
+    /// does nothing) if there's no progress to resume.
+    pub async fn resume_pending_reindex(&self) -> Result<bool> {
+        let Some(progress) = ReindexProgress::load(&self.config.reindex_progress_path).await else {
+            return Ok(false);
+        };
+        info!(
+            "▶️  Resuming reindex: {} file(s) remaining out of {}",
+            progress.remaining.len(),
+            progress.total_files
+        );
+        self.process_files(progress.remaining).await?;
+        Ok(true)
+    }
+
+    /// List every `process_files` job registered since this pipeline was created, most recent
+    /// first, with its current state and progress.
+    pub async fn list_workers(&self) -> Vec<WorkerInfo> {
+        self.worker_manager.list().await
+    }
+
+    /// Pause a running job at its next batch boundary; in-flight files in the current batch still
+    /// finish normally.
+    pub async fn pause_worker(&self, worker_id: u64) -> Result<()> {
+        self.worker_manager.pause(worker_id).await
+    }
+
+    /// Resume a job paused via [`Self::pause_worker`].
+    pub async fn resume_worker(&self, worker_id: u64) -> Result<()> {
+        self.worker_manager.resume(worker_id).await
+    }
+
+    /// Cancel a running or paused job at its next batch boundary; its remaining files stay
+    /// persisted for [`Self::resume_pending_reindex`].
+    pub async fn cancel_worker(&self, worker_id: u64) -> Result<()> {
+        self.worker_manager.cancel(worker_id).await
+    }
+
+    /// Get current pipeline statistics, including a live snapshot of the concurrency limiter.
     pub async fn get_stats(&self) -> PipelineStats {
-        self.stats.read().await.clone()
+        let mut stats = self.stats.read().await.clone();
+        let limiter_stats = self.concurrency_limiter.stats();
+        stats.concurrency_limit = limiter_stats.current_limit;
+        stats.concurrency_increases = limiter_stats.increases;
+        stats.concurrency_decreases = limiter_stats.decreases;
+        stats
     }
 
     /// Reset pipeline statistics
@@ -914,12 +1481,16 @@ impl IndexingPipeline {
             config: self.config.clone(),
             chunker: self.chunker.clone(),
             embedder: self.embedder.clone(),
+            embedding_template_set: self.embedding_template_set.clone(),
             index_service: self.index_service.clone(),
             stats: self.stats.clone(),
             file_watcher: None,
             event_receiver: None,
             gitignore: self.gitignore.clone(),
             walker: self.walker.clone(),
+            watch_root: self.watch_root.clone(),
+            concurrency_limiter: self.concurrency_limiter.clone(),
+            worker_manager: self.worker_manager.clone(),
         }
     }
 }
@@ -935,6 +1506,10 @@ fn convert_chunker_to_proto_chunk(chunk: &chunker::Chunk) -> Chunk {
         size: chunk.size,
         code: chunk.code.clone(),
         summary: chunk.summary.clone(),
+        start_byte: chunk.start_byte,
+        end_byte: chunk.end_byte,
+        start_line: chunk.start_line,
+        end_line: chunk.end_line,
         embedding: None, // Will be filled later by the embedder
     }
 }
@@ -21,6 +21,13 @@ pub struct Chunk {
     pub size: usize,
     pub code: String,
     pub summary: Option<String>,
+    /// Byte offset of the chunk's first/last byte in the source file, so a search result can be
+    /// re-anchored to its exact region instead of just the file path.
+    pub start_byte: usize,
+    pub end_byte: usize,
+    /// 1-based line numbers spanning the chunk, for editor-style "jump to range" navigation.
+    pub start_line: usize,
+    pub end_line: usize,
     pub embedding: Option<Vec<f32>>,
 }
 
@@ -33,6 +40,10 @@ pub struct RetrievalRequest {
     pub user_id: String,
     pub file_hashes: std::collections::HashMap<String, String>, // path -> sha256
     pub k: usize,
+    /// Hybrid search weighting passed through to `IndexService::search_hybrid`: `Some(1.0)` is
+    /// pure vector search, `Some(0.0)` is pure keyword search, and `None` uses reciprocal rank
+    /// fusion instead of a weighted blend. See `search_hybrid`'s doc comment for the full story.
+    pub semantic_ratio: Option<f32>,
 }
 
 /// RetrievalResponse represents the response to a retrieval request
@@ -48,4 +59,11 @@ pub struct RetrievedChunk {
     pub path: String,
     pub score: f32,
     pub chunk_hash: String,
+    /// This chunk's raw score from the vector search leg of a hybrid query, if it was found
+    /// there; a pure vector search reports its score here too. `None` when only the keyword leg
+    /// matched this chunk.
+    pub vector_score: Option<f32>,
+    /// This chunk's raw score from the keyword search leg of a hybrid query, if it was found
+    /// there. `None` when only the vector leg matched this chunk.
+    pub keyword_score: Option<f32>,
 }
@@ -0,0 +1,230 @@
+//! Generic retry-with-backoff for fallible async operations, driven purely by
+//! [`ForgeIndexerError::is_retryable`] -- callers no longer need to hand-roll their own retry
+//! loop around embedding, vector-DB, or external-service calls (see `RetryingEmbedder` in
+//! [`crate::embedder_retry`] for the analogous wrapper this module does *not* replace, since it
+//! retries against `anyhow::Error` rather than a structured `ForgeIndexerError`).
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, warn};
+
+use crate::errors::ForgeIndexerError;
+
+/// Backoff policy for [`retry_with_backoff`].
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first (non-retry) one.
+    pub max_attempts: u32,
+    /// Backoff before the first retry.
+    pub base_delay: Duration,
+    /// Factor the backoff grows by on each subsequent retry.
+    pub multiplier: f64,
+    /// Upper bound on the (pre-jitter) backoff delay.
+    pub max_delay: Duration,
+    /// Stop retrying once this much total time has elapsed, even if attempts remain.
+    pub max_elapsed: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(500),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(30),
+            max_elapsed: Duration::from_secs(120),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff from `base_delay`, capped at `max_delay`, with full jitter (a uniform
+    /// random delay between 0 and the capped exponential value) so retrying callers don't all
+    /// wake up in lockstep and re-trigger the same rate limit.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exp = self.base_delay.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        let capped = exp.min(self.max_delay.as_secs_f64());
+        Duration::from_secs_f64(capped * pseudo_random_fraction())
+    }
+}
+
+/// Retry `operation` until it succeeds, its error isn't retryable, attempts are exhausted, or
+/// `policy.max_elapsed` has passed -- whichever comes first. The final error is returned
+/// unchanged when retries are exhausted, so callers can match on it exactly as they would a
+/// non-retried failure. For a [`ForgeIndexerError::RateLimitError`], the wait before the next
+/// attempt is the larger of the usual backoff and the delay implied by the error's `window`
+/// field, so a provider's own rate-limit window is respected rather than retried into blindly.
+pub async fn retry_with_backoff<T, F, Fut>(
+    policy: &RetryPolicy,
+    mut operation: F,
+) -> Result<T, ForgeIndexerError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, ForgeIndexerError>>,
+{
+    let start = Instant::now();
+    let mut attempt = 0;
+
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                let elapsed = start.elapsed();
+                let exhausted = attempt + 1 >= policy.max_attempts || elapsed >= policy.max_elapsed;
+
+                if !err.is_retryable() || exhausted {
+                    warn!(
+                        attempt = attempt + 1,
+                        retryable = err.is_retryable(),
+                        elapsed_ms = elapsed.as_millis() as u64,
+                        error = %err,
+                        "giving up on operation"
+                    );
+                    return Err(err);
+                }
+
+                let delay = rate_limit_wait(&err)
+                    .map(|wait| wait.max(policy.backoff_delay(attempt)))
+                    .unwrap_or_else(|| policy.backoff_delay(attempt));
+
+                debug!(
+                    attempt = attempt + 1,
+                    max_attempts = policy.max_attempts,
+                    delay_ms = delay.as_millis() as u64,
+                    error = %err,
+                    "retrying after transient error"
+                );
+
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Minimum wait implied by a [`ForgeIndexerError::RateLimitError`]'s `window`, or `None` for
+/// every other variant (or an unparsable window), in which case the caller falls back to the
+/// normal backoff curve.
+fn rate_limit_wait(err: &ForgeIndexerError) -> Option<Duration> {
+    match err {
+        ForgeIndexerError::RateLimitError { window, .. } => parse_window(window),
+        _ => None,
+    }
+}
+
+/// Parse a rate-limit window such as `"60s"`, `"1m"`, `"2 minutes"`, or a bare number of seconds
+/// into a `Duration`. Returns `None` for anything that doesn't parse as `<number><unit>`.
+fn parse_window(window: &str) -> Option<Duration> {
+    let window = window.trim().to_lowercase();
+    let split_at = window.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(window.len());
+    let (number, unit) = window.split_at(split_at);
+    let value: f64 = number.parse().ok()?;
+
+    let seconds = match unit.trim() {
+        "" | "s" | "sec" | "secs" | "second" | "seconds" => value,
+        "m" | "min" | "mins" | "minute" | "minutes" => value * 60.0,
+        "h" | "hr" | "hrs" | "hour" | "hours" => value * 3_600.0,
+        "d" | "day" | "days" => value * 86_400.0,
+        _ => return None,
+    };
+
+    Some(Duration::from_secs_f64(seconds))
+}
+
+/// A dependency-free uniform value in `[0.0, 1.0)`, seeded from the system clock. Only used for
+/// retry jitter, where cryptographic quality randomness isn't needed -- just enough spread to
+/// avoid synchronized retries.
+fn pseudo_random_fraction() -> f64 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos())
+        .unwrap_or(0);
+    (nanos as f64) / 1_000_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 5,
+            base_delay: Duration::from_millis(1),
+            multiplier: 2.0,
+            max_delay: Duration::from_millis(5),
+            max_elapsed: Duration::from_secs(5),
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_retryable_errors_until_success() {
+        let calls = AtomicUsize::new(0);
+
+        let result = retry_with_backoff(&fast_policy(), || {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if call < 2 {
+                    Err(ForgeIndexerError::vector_db_error("transient qdrant failure"))
+                } else {
+                    Ok(42)
+                }
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let calls = AtomicUsize::new(0);
+
+        let result: Result<(), ForgeIndexerError> = retry_with_backoff(&fast_policy(), || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(ForgeIndexerError::external_service_error("still down")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), fast_policy().max_attempts as usize);
+    }
+
+    #[tokio::test]
+    async fn non_retryable_error_fails_on_first_attempt() {
+        let calls = AtomicUsize::new(0);
+
+        let result: Result<(), ForgeIndexerError> = retry_with_backoff(&fast_policy(), || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(ForgeIndexerError::validation_error("query", "empty")) }
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn parses_seconds_minutes_and_bare_numbers() {
+        assert_eq!(parse_window("60s"), Some(Duration::from_secs(60)));
+        assert_eq!(parse_window("1m"), Some(Duration::from_secs(60)));
+        assert_eq!(parse_window("2 minutes"), Some(Duration::from_secs(120)));
+        assert_eq!(parse_window("30"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_window("nonsense"), None);
+    }
+
+    #[test]
+    fn rate_limit_wait_overrides_short_backoff() {
+        let err = ForgeIndexerError::rate_limit_error(10, "1m");
+        assert_eq!(rate_limit_wait(&err), Some(Duration::from_secs(60)));
+
+        let err = ForgeIndexerError::vector_db_error("unrelated");
+        assert_eq!(rate_limit_wait(&err), None);
+    }
+}
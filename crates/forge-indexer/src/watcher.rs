@@ -1,25 +1,73 @@
-//! File watcher service using the notify crate
+//! File watcher service using the notify crate.
+//!
+//! Raw `notify` events are noisy: a single editor save commonly fires a create, a few modifies,
+//! and sometimes a rename in quick succession, and a git checkout can fire hundreds of events at
+//! once. `FileWatcher` coalesces that noise into a small stream of [`DebouncedEvent`]s that
+//! `IndexingPipeline::process_events` can act on directly: events for a path only emit once no
+//! further event for it has arrived within `debounce`, paths matched by the repository's
+//! gitignore stack are dropped before they ever reach the debounce buffer, and a rename `notify`
+//! reports as a single `RenameMode::Both` event is emitted as one `Renamed` event rather than a
+//! separate remove/create pair.
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::Result;
-use notify::{Event, RecursiveMode, Watcher, recommended_watcher};
+use ignore::gitignore::Gitignore;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecursiveMode, Watcher, recommended_watcher};
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
 use tracing::{debug, error, info};
 
+use crate::pipeline::WatchMode;
+
+/// A filesystem change coalesced from one or more raw `notify` events for the same path, emitted
+/// once `FileWatcher`'s debounce window has elapsed with no further activity for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DebouncedEvent {
+    Created(PathBuf),
+    Modified(PathBuf),
+    Removed(PathBuf),
+    /// A rename `notify` reported as a single `RenameMode::Both` event. Platforms that only ever
+    /// report the `From` or `To` half individually surface those as a plain `Removed`/`Created`
+    /// instead, since there's nothing to pair them with.
+    Renamed { from: PathBuf, to: PathBuf },
+}
+
+/// Coalesced intent for a path while its debounce window is still open. A later raw event always
+/// overwrites an earlier one for the same path, since whatever state the filesystem is in once
+/// the window closes is what `IndexingPipeline` should act on.
+#[derive(Debug, Clone)]
+enum PendingAction {
+    Created,
+    Modified,
+    Removed,
+    /// Keyed by the rename's destination path; the field is the path it was renamed from.
+    RenamedFrom(PathBuf),
+}
+
 pub struct FileWatcher {
     watcher: notify::RecommendedWatcher,
+    /// Coalesces raw events from `watcher` into debounced, gitignore-filtered `DebouncedEvent`s.
+    /// Aborted on drop so it doesn't keep running once the watcher itself is gone.
+    debounce_task: JoinHandle<()>,
 }
 
 impl FileWatcher {
-    pub fn new() -> Result<(Self, mpsc::Receiver<Event>)> {
+    /// `debounce` is how long a path must go without a new raw event before it's emitted;
+    /// `gitignore` is the layered matcher stack (see `IndexingPipeline::load_gitignore_patterns`)
+    /// consulted to drop ignored paths before they ever enter the debounce buffer.
+    pub fn new(debounce: Duration, gitignore: Vec<Gitignore>) -> Result<(Self, mpsc::Receiver<DebouncedEvent>)> {
         info!("🔧 Initializing file watcher...");
 
-        let (tx, rx) = mpsc::channel(1024);
+        let (raw_tx, raw_rx) = mpsc::channel(1024);
         let watcher = recommended_watcher(move |res| match res {
             Ok(event) => {
                 debug!("📨 File system event detected: {:?}", event);
-                if let Err(e) = tx.blocking_send(event) {
+                if let Err(e) = raw_tx.blocking_send(event) {
                     error!("❌ Failed to send file event: {}", e);
                 }
             }
@@ -28,12 +76,16 @@ impl FileWatcher {
             }
         })?;
 
+        let (debounced_tx, debounced_rx) = mpsc::channel(1024);
+        let debounce_task =
+            tokio::spawn(Self::run_debounce_loop(raw_rx, debounced_tx, debounce, gitignore));
+
         info!("✅ File watcher initialized successfully");
-        Ok((Self { watcher }, rx))
+        Ok((Self { watcher, debounce_task }, debounced_rx))
     }
 
-    pub fn watch_directory(&mut self, path: &Path) -> Result<()> {
-        info!("👀 Setting up directory watch for: {:?}", path);
+    pub fn watch_directory(&mut self, path: &Path, mode: WatchMode) -> Result<()> {
+        info!("👀 Setting up directory watch for: {:?} ({:?})", path, mode);
 
         if !path.exists() {
             error!("❌ Watch path does not exist: {:?}", path);
@@ -45,12 +97,135 @@ impl FileWatcher {
             return Err(anyhow::anyhow!("Watch path is not a directory: {:?}", path));
         }
 
-        // Watch the directory recursively
+        let recursive_mode = match mode {
+            WatchMode::Recursive => RecursiveMode::Recursive,
+            WatchMode::NonRecursive => RecursiveMode::NonRecursive,
+        };
+
         self.watcher
-            .watch(path, RecursiveMode::Recursive)
+            .watch(path, recursive_mode)
             .map_err(|e| anyhow::anyhow!("Failed to watch directory: {}", e))?;
 
         info!("✅ Successfully watching directory: {:?}", path);
         Ok(())
     }
+
+    /// A path is dropped before it ever reaches the debounce buffer if any layer of `gitignore`
+    /// matches it, mirroring `IndexingPipeline::should_ignore_file`'s layered-gitignore check.
+    fn is_ignored(gitignore: &[Gitignore], path: &Path) -> bool {
+        gitignore.iter().any(|matcher| matcher.matched(path, path.is_dir()).is_ignore())
+    }
+
+    /// Coalesce raw `notify` events into debounced, gitignore-filtered `DebouncedEvent`s. Runs
+    /// until `raw_rx` closes (the watcher was dropped), flushing whatever is still pending before
+    /// returning.
+    async fn run_debounce_loop(
+        mut raw_rx: mpsc::Receiver<Event>,
+        debounced_tx: mpsc::Sender<DebouncedEvent>,
+        debounce: Duration,
+        gitignore: Vec<Gitignore>,
+    ) {
+        let mut pending: HashMap<PathBuf, (PendingAction, Instant)> = HashMap::new();
+
+        loop {
+            let next_deadline = pending.values().map(|(_, deadline)| *deadline).min();
+            let sleep = match next_deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline),
+                None => tokio::time::sleep(Duration::from_secs(3600)),
+            };
+            tokio::pin!(sleep);
+
+            tokio::select! {
+                maybe_event = raw_rx.recv() => {
+                    let Some(event) = maybe_event else { break };
+                    Self::record_event(&mut pending, &gitignore, event, debounce);
+                }
+                _ = &mut sleep, if next_deadline.is_some() => {
+                    Self::flush_ready(&mut pending, &debounced_tx).await;
+                }
+            }
+        }
+
+        Self::flush_all(pending, &debounced_tx).await;
+    }
+
+    /// Fold one raw `notify` event into `pending`, dropping any path matched by `gitignore`.
+    fn record_event(
+        pending: &mut HashMap<PathBuf, (PendingAction, Instant)>,
+        gitignore: &[Gitignore],
+        event: Event,
+        debounce: Duration,
+    ) {
+        let deadline = Instant::now() + debounce;
+
+        if let EventKind::Modify(ModifyKind::Name(RenameMode::Both)) = event.kind
+            && let [from, to] = event.paths.as_slice()
+        {
+            pending.remove(from);
+            if !Self::is_ignored(gitignore, to) {
+                pending.insert(to.clone(), (PendingAction::RenamedFrom(from.clone()), deadline));
+            }
+            return;
+        }
+
+        let action = match event.kind {
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                PendingAction::Created
+            }
+            EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+                PendingAction::Removed
+            }
+            _ => PendingAction::Modified,
+        };
+        for path in event.paths {
+            if Self::is_ignored(gitignore, &path) {
+                continue;
+            }
+            pending.insert(path, (action.clone(), deadline));
+        }
+    }
+
+    /// Emit every path whose debounce window has closed.
+    async fn flush_ready(
+        pending: &mut HashMap<PathBuf, (PendingAction, Instant)>,
+        debounced_tx: &mpsc::Sender<DebouncedEvent>,
+    ) {
+        let now = Instant::now();
+        let ready: Vec<PathBuf> =
+            pending.iter().filter(|(_, (_, deadline))| *deadline <= now).map(|(path, _)| path.clone()).collect();
+
+        for path in ready {
+            let (action, _) = pending.remove(&path).expect("path just collected from pending");
+            Self::emit(debounced_tx, path, action).await;
+        }
+    }
+
+    /// Emit everything still pending, regardless of deadline, used once the raw event channel
+    /// closes.
+    async fn flush_all(
+        pending: HashMap<PathBuf, (PendingAction, Instant)>,
+        debounced_tx: &mpsc::Sender<DebouncedEvent>,
+    ) {
+        for (path, (action, _)) in pending {
+            Self::emit(debounced_tx, path, action).await;
+        }
+    }
+
+    async fn emit(debounced_tx: &mpsc::Sender<DebouncedEvent>, path: PathBuf, action: PendingAction) {
+        let event = match action {
+            PendingAction::Created => DebouncedEvent::Created(path),
+            PendingAction::Modified => DebouncedEvent::Modified(path),
+            PendingAction::Removed => DebouncedEvent::Removed(path),
+            PendingAction::RenamedFrom(from) => DebouncedEvent::Renamed { from, to: path },
+        };
+        if debounced_tx.send(event).await.is_err() {
+            debug!("📭 Debounced event receiver dropped; discarding further events");
+        }
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        self.debounce_task.abort();
+    }
 }
@@ -0,0 +1,274 @@
+//! Tracks and controls the background jobs spawned by `IndexingPipeline::process_files`, so an
+//! operator has a control surface over a long-running reindex instead of an all-or-nothing
+//! `join_all`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{RwLock, watch};
+
+/// A worker's lifecycle state, as reported by [`WorkerManager::list`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkerState {
+    /// Currently processing a batch of files.
+    Active,
+    /// Paused via [`WorkerManager::pause`], waiting on [`WorkerManager::resume`] or
+    /// [`WorkerManager::cancel`].
+    Idle,
+    /// Finished, whether by completing every file, erroring, or being cancelled -- see
+    /// `WorkerInfo.last_error`.
+    Dead,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorkerProgress {
+    pub files_done: usize,
+    pub total_files: usize,
+    pub last_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkerInfo {
+    pub id: u64,
+    pub state: WorkerState,
+    pub progress: WorkerProgress,
+    /// Set once the worker reaches `WorkerState::Dead`, if it stopped due to a cancellation or an
+    /// unrecoverable error rather than completing normally.
+    pub last_error: Option<String>,
+}
+
+/// Minimal on-disk record of an in-progress `process_files` run, so a cancelled or crashed reindex
+/// can pick up where it left off via `IndexingPipeline::resume_pending_reindex` instead of
+/// restarting from the full file list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReindexProgress {
+    pub files_done: usize,
+    pub total_files: usize,
+    pub last_path: Option<PathBuf>,
+    pub remaining: Vec<PathBuf>,
+}
+
+impl ReindexProgress {
+    pub async fn persist(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let bytes = serde_json::to_vec(self)?;
+        tokio::fs::write(path, bytes).await?;
+        Ok(())
+    }
+
+    /// `None` if no progress file exists or it fails to parse (e.g. left over from an older
+    /// version) -- treated as "nothing to resume" rather than an error.
+    pub async fn load(path: &Path) -> Option<Self> {
+        let bytes = tokio::fs::read(path).await.ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+/// Signals delivered to a running job via a `tokio::sync::watch` channel, checked at each batch
+/// boundary rather than per-file so a pause/cancel takes effect promptly without interrupting
+/// in-flight work.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct WorkerControl {
+    paused: bool,
+    cancelled: bool,
+}
+
+struct WorkerSlot {
+    info: WorkerInfo,
+    control: watch::Sender<WorkerControl>,
+}
+
+/// Registry of every `process_files` job that has run since the pipeline was created. Entries for
+/// finished (`WorkerState::Dead`) jobs are kept so their outcome remains visible to `list`.
+#[derive(Default)]
+pub struct WorkerManager {
+    next_id: AtomicU64,
+    workers: RwLock<HashMap<u64, WorkerSlot>>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a new job and return its id alongside the `watch::Receiver` its loop should poll
+    /// at each batch boundary for a pause or cancel request.
+    pub(crate) async fn register(&self, total_files: usize) -> (u64, watch::Receiver<WorkerControl>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = watch::channel(WorkerControl::default());
+        let info = WorkerInfo {
+            id,
+            state: WorkerState::Active,
+            progress: WorkerProgress { files_done: 0, total_files, last_path: None },
+            last_error: None,
+        };
+        self.workers.write().await.insert(id, WorkerSlot { info, control: tx });
+        (id, rx)
+    }
+
+    pub(crate) async fn set_state(&self, id: u64, state: WorkerState) {
+        if let Some(slot) = self.workers.write().await.get_mut(&id) {
+            slot.info.state = state;
+        }
+    }
+
+    pub(crate) async fn update_progress(&self, id: u64, files_done: usize, last_path: Option<PathBuf>) {
+        if let Some(slot) = self.workers.write().await.get_mut(&id) {
+            slot.info.progress.files_done = files_done;
+            slot.info.progress.last_path = last_path;
+        }
+    }
+
+    pub(crate) async fn finish(&self, id: u64, error: Option<String>) {
+        if let Some(slot) = self.workers.write().await.get_mut(&id) {
+            slot.info.state = WorkerState::Dead;
+            slot.info.last_error = error;
+        }
+    }
+
+    /// List every job this manager has ever registered, most recently started first.
+    pub async fn list(&self) -> Vec<WorkerInfo> {
+        let mut workers: Vec<WorkerInfo> =
+            self.workers.read().await.values().map(|slot| slot.info.clone()).collect();
+        workers.sort_by(|a, b| b.id.cmp(&a.id));
+        workers
+    }
+
+    pub async fn pause(&self, id: u64) -> Result<()> {
+        let workers = self.workers.read().await;
+        let slot = workers.get(&id).ok_or_else(|| anyhow::anyhow!("no such worker: {id}"))?;
+        slot.control.send_modify(|control| control.paused = true);
+        Ok(())
+    }
+
+    pub async fn resume(&self, id: u64) -> Result<()> {
+        let workers = self.workers.read().await;
+        let slot = workers.get(&id).ok_or_else(|| anyhow::anyhow!("no such worker: {id}"))?;
+        slot.control.send_modify(|control| control.paused = false);
+        Ok(())
+    }
+
+    pub async fn cancel(&self, id: u64) -> Result<()> {
+        let workers = self.workers.read().await;
+        let slot = workers.get(&id).ok_or_else(|| anyhow::anyhow!("no such worker: {id}"))?;
+        slot.control.send_modify(|control| control.cancelled = true);
+        Ok(())
+    }
+}
+
+impl WorkerControl {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled
+    }
+
+    fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
+
+/// Blocks a running job's loop while `control` reports paused, waking on every change. Returns
+/// `true` if the job should stop (cancelled while active or while paused).
+pub(crate) async fn wait_while_paused(control: &mut watch::Receiver<WorkerControl>) -> bool {
+    loop {
+        let current = *control.borrow();
+        if current.is_cancelled() {
+            return true;
+        }
+        if !current.is_paused() {
+            return false;
+        }
+        if control.changed().await.is_err() {
+            return true;
+        }
+    }
+}
+
+pub(crate) fn is_cancelled(control: &watch::Receiver<WorkerControl>) -> bool {
+    control.borrow().is_cancelled()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn lists_registered_workers_with_progress() {
+        let manager = WorkerManager::new();
+        let (id, _control) = manager.register(3).await;
+
+        manager.update_progress(id, 1, Some(PathBuf::from("a.rs"))).await;
+
+        let workers = manager.list().await;
+        assert_eq!(workers.len(), 1);
+        assert_eq!(workers[0].progress.files_done, 1);
+        assert_eq!(workers[0].progress.total_files, 3);
+    }
+
+    #[tokio::test]
+    async fn pause_blocks_until_resume() {
+        let manager = WorkerManager::new();
+        let (id, mut control) = manager.register(1).await;
+
+        manager.pause(id).await.unwrap();
+        assert!(control.borrow().is_paused());
+
+        let waiter = tokio::spawn(async move { wait_while_paused(&mut control).await });
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        manager.resume(id).await.unwrap();
+        let cancelled = waiter.await.unwrap();
+        assert!(!cancelled);
+    }
+
+    #[tokio::test]
+    async fn cancel_unblocks_a_paused_worker() {
+        let manager = WorkerManager::new();
+        let (id, mut control) = manager.register(1).await;
+
+        manager.pause(id).await.unwrap();
+        let waiter = tokio::spawn(async move { wait_while_paused(&mut control).await });
+
+        manager.cancel(id).await.unwrap();
+        let cancelled = waiter.await.unwrap();
+        assert!(cancelled);
+    }
+
+    #[tokio::test]
+    async fn finish_marks_worker_dead_with_error() {
+        let manager = WorkerManager::new();
+        let (id, _control) = manager.register(1).await;
+
+        manager.finish(id, Some("cancelled".to_string())).await;
+
+        let workers = manager.list().await;
+        assert_eq!(workers[0].state, WorkerState::Dead);
+        assert_eq!(workers[0].last_error.as_deref(), Some("cancelled"));
+    }
+
+    #[tokio::test]
+    async fn persists_and_loads_progress() {
+        let path = std::env::temp_dir()
+            .join(format!("forge-indexer-worker-progress-test-{}.json", std::process::id()));
+        let progress = ReindexProgress {
+            files_done: 2,
+            total_files: 5,
+            last_path: Some(PathBuf::from("b.rs")),
+            remaining: vec![PathBuf::from("c.rs"), PathBuf::from("d.rs")],
+        };
+
+        progress.persist(&path).await.unwrap();
+        let loaded = ReindexProgress::load(&path).await.unwrap();
+
+        assert_eq!(loaded.files_done, 2);
+        assert_eq!(loaded.remaining.len(), 2);
+        let _ = std::fs::remove_file(&path);
+    }
+}
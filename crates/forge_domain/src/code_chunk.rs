@@ -5,6 +5,8 @@ use std::collections::HashMap;
 use derive_setters::Setters;
 use serde::{Deserialize, Serialize};
 
+use crate::prompt_template::PromptTemplate;
+
 /// Represents a piece of code with metadata for indexing and search
 #[derive(Debug, Clone, Serialize, Deserialize, Setters, PartialEq)]
 #[setters(strip_option, into)]
@@ -72,6 +74,9 @@ pub struct ChunkingConfig {
     pub strategy: ChunkingStrategy,
     /// Languages to enable semantic chunking for
     pub semantic_languages: Vec<String>,
+    /// Template rendered from a chunk's fields into the text an `Embedder` sees, instead of
+    /// embedding raw `content` alone
+    pub prompt_template: PromptTemplate,
 }
 
 /// Available chunking strategies
@@ -102,6 +107,7 @@ impl Default for ChunkingConfig {
                 "cpp".to_string(),
                 "c".to_string(),
             ],
+            prompt_template: PromptTemplate::default(),
         }
     }
 }
@@ -130,6 +136,9 @@ pub struct IndexedCodebase {
     pub total_size_bytes: u64,
     /// Status of the indexing process
     pub status: IndexingStatus,
+    /// Whether stored embeddings are L2-normalized unit vectors, so the query side knows to score
+    /// with a plain dot product rather than a full cosine computation
+    pub normalized_embeddings: bool,
 }
 
 /// Status of an indexing operation
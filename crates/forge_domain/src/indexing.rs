@@ -12,7 +12,6 @@ use crate::embedding::EmbeddingConfig;
 /// Configuration for indexing operations
 #[derive(Debug, Clone, Serialize, Deserialize, Setters)]
 #[setters(strip_option, into)]
-#[derive(Default)]
 pub struct IndexingConfig {
     /// Chunking configuration
     pub chunking: ChunkingConfig,
@@ -24,6 +23,10 @@ pub struct IndexingConfig {
     pub processing: ProcessingConfig,
     /// File filtering configuration
     pub filtering: FilterConfig,
+    /// Whether to L2-normalize embeddings to unit vectors at insert time, so retrieval can score
+    /// with a plain dot product instead of a full cosine computation. Disable for a provider that
+    /// already emits unit vectors.
+    pub normalize_embeddings: bool,
 }
 
 /// Configuration for vector storage
@@ -38,6 +41,18 @@ pub struct VectorStoreConfig {
     pub distance_metric: DistanceMetric,
     /// Whether to enable compression
     pub enable_compression: bool,
+    /// Whether to maintain an HNSW approximate-nearest-neighbor index over each collection
+    /// instead of scanning every vector at query time. Trades a small amount of recall for
+    /// sub-linear query time on large collections.
+    pub use_ann_index: bool,
+    /// Max neighbors kept per node per layer above layer 0 (layer 0 keeps `2 * hnsw_m`). Higher
+    /// values improve recall at the cost of memory and insertion time.
+    pub hnsw_m: usize,
+    /// Candidate list size explored while inserting a node; higher values build a
+    /// higher-quality graph at the cost of slower insertion.
+    pub hnsw_ef_construction: usize,
+    /// Candidate list size explored while searching; `search` uses `max(hnsw_ef_search, limit)`.
+    pub hnsw_ef_search: usize,
 }
 
 /// Available vector store types
@@ -71,6 +86,11 @@ pub enum VectorStoreType {
         /// Optional authentication token
         auth_token: Option<String>,
     },
+    /// Postgres with the `pgvector` extension
+    Postgres {
+        /// `tokio_postgres`-style connection string
+        connection_string: String,
+    },
 }
 
 /// Distance metrics for vector similarity
@@ -159,6 +179,26 @@ pub struct IndexingRequest {
     pub include_patterns: Vec<String>,
     /// Specific files to index (takes precedence over directory scanning)
     pub specific_files: Vec<PathBuf>,
+    /// Whether to reprocess every discovered file or skip ones unchanged since the last run
+    /// against this collection
+    pub reindex_mode: ReindexMode,
+}
+
+/// Whether `index_codebase` reprocesses every discovered file or consults the collection's
+/// content manifest to skip files that haven't changed since the last run.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ReindexMode {
+    /// Chunk and embed every discovered file, ignoring any existing manifest.
+    Full,
+    /// Skip files whose content hash matches the manifest; reprocess new, changed, and (by
+    /// deleting their stale vectors) removed files only.
+    Incremental,
+}
+
+impl Default for ReindexMode {
+    fn default() -> Self {
+        Self::Incremental
+    }
 }
 
 /// Response from an indexing operation
@@ -176,6 +216,37 @@ pub struct IndexingResponse {
     pub processing_time_ms: u64,
 }
 
+/// Why a file `discover_files` would select isn't represented in the index, as far as
+/// `IndexingService::coverage_report` can tell from the manifest alone.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum CoverageGap {
+    /// Chunking produced zero chunks for the file's current content (e.g. it's empty, or every
+    /// chunk fell below `min_chunk_size`).
+    NoChunks,
+    /// No manifest entry exists for the file at all -- it was never processed, or failed to read
+    /// during the attempt that would have indexed it.
+    Unindexed,
+}
+
+/// One discovered-but-missing file, with a best-effort guess at why.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoverageGapEntry {
+    /// Path relative to `root_path`, matching `FileManifestEntry` keys.
+    pub path: String,
+    /// Why the file isn't represented in the index.
+    pub reason: CoverageGap,
+}
+
+/// Audit of what's actually present in a collection's index versus what `discover_files` would
+/// currently select for it, without running a full (re-)index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageReport {
+    /// Indexed file path -> number of chunks recorded for it in the manifest.
+    pub indexed_files: HashMap<String, usize>,
+    /// Files `discover_files` selects that the manifest doesn't account for.
+    pub missing_files: Vec<CoverageGapEntry>,
+}
+
 /// Detailed statistics from an indexing operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexingStatistics {
@@ -260,6 +331,19 @@ pub enum IndexingStage {
     Failed(String),
 }
 
+impl Default for IndexingConfig {
+    fn default() -> Self {
+        Self {
+            chunking: ChunkingConfig::default(),
+            embedding: EmbeddingConfig::default(),
+            vector_store: VectorStoreConfig::default(),
+            processing: ProcessingConfig::default(),
+            filtering: FilterConfig::default(),
+            normalize_embeddings: true,
+        }
+    }
+}
+
 impl Default for VectorStoreConfig {
     fn default() -> Self {
         Self {
@@ -267,6 +351,10 @@ impl Default for VectorStoreConfig {
             collection_name: "codebase".to_string(),
             distance_metric: DistanceMetric::Cosine,
             enable_compression: false,
+            use_ann_index: false,
+            hnsw_m: 16,
+            hnsw_ef_construction: 200,
+            hnsw_ef_search: 50,
         }
     }
 }
@@ -370,6 +458,7 @@ mod tests {
             reset_existing: false,
             include_patterns: vec![],
             specific_files: vec![],
+            reindex_mode: ReindexMode::default(),
         };
 
         let actual_repo = fixture.repository.clone();
@@ -404,4 +493,12 @@ mod tests {
         assert_eq!(actual_attempts, expected_attempts);
         assert_eq!(actual_delay, expected_delay);
     }
+
+    #[test]
+    fn test_reindex_mode_defaults_to_incremental() {
+        let actual = ReindexMode::default();
+        let expected = ReindexMode::Incremental;
+
+        assert_eq!(actual, expected);
+    }
 }
@@ -0,0 +1,186 @@
+//! Prompt templating applied to a `CodeChunk` before its text reaches an `Embedder`, so
+//! context like path and symbol can influence the vector instead of just the raw code body.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::CodeChunk;
+
+/// Placeholders a template is allowed to reference
+const KNOWN_FIELDS: &[&str] = &["path", "lang", "symbol", "summary", "content"];
+
+/// A `{{field}}` interpolation template rendered into the text handed to an `Embedder`.
+/// Validated at construction time so a typo in a field name is rejected before indexing starts
+/// rather than silently embedding the literal placeholder text.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PromptTemplate {
+    template: String,
+    /// Maximum length of the rendered prompt before truncation
+    max_length: usize,
+}
+
+impl PromptTemplate {
+    /// Build a template, rejecting unknown `{{field}}` placeholders up front
+    pub fn new(template: impl Into<String>, max_length: usize) -> Result<Self> {
+        let template = template.into();
+
+        for field in extract_placeholders(&template) {
+            if !KNOWN_FIELDS.contains(&field.as_str()) {
+                bail!(
+                    "Unknown prompt template field '{{{{{field}}}}}'; expected one of {:?}",
+                    KNOWN_FIELDS
+                );
+            }
+        }
+
+        Ok(Self { template, max_length })
+    }
+
+    /// Render the template against a chunk's fields, truncating to `max_length` if needed
+    pub fn render(&self, chunk: &CodeChunk) -> String {
+        let mut rendered = self.template.clone();
+        rendered = rendered.replace("{{path}}", &chunk.path);
+        rendered = rendered.replace("{{lang}}", &chunk.language);
+        rendered = rendered.replace("{{symbol}}", chunk.symbol.as_deref().unwrap_or(""));
+        rendered = rendered.replace("{{summary}}", chunk.summary.as_deref().unwrap_or(""));
+        rendered = rendered.replace("{{content}}", &chunk.content);
+
+        if rendered.len() > self.max_length {
+            rendered.truncate(self.max_length);
+        }
+
+        rendered
+    }
+
+    /// Render the template for a search query rather than an indexed chunk, so the query
+    /// embedding lands in the same representation space `render` puts document embeddings in.
+    /// `query` fills `{{content}}`; every other placeholder (`path`, `lang`, `symbol`, `summary`)
+    /// has no query-side equivalent and renders as an empty string.
+    pub fn render_query(&self, query: &str) -> String {
+        let mut rendered = self.template.clone();
+        rendered = rendered.replace("{{path}}", "");
+        rendered = rendered.replace("{{lang}}", "");
+        rendered = rendered.replace("{{symbol}}", "");
+        rendered = rendered.replace("{{summary}}", "");
+        rendered = rendered.replace("{{content}}", query);
+
+        if rendered.len() > self.max_length {
+            rendered.truncate(self.max_length);
+        }
+
+        rendered
+    }
+}
+
+impl Default for PromptTemplate {
+    fn default() -> Self {
+        Self::new("{{content}}", 8000).expect("default template references only known fields")
+    }
+}
+
+/// Extract the names of `{{field}}` placeholders in a template string
+fn extract_placeholders(template: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let after_open = &rest[start + 2..];
+        let Some(end) = after_open.find("}}") else {
+            break;
+        };
+
+        fields.push(after_open[..end].to_string());
+        rest = &after_open[end + 2..];
+    }
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn chunk_with(path: &str, symbol: Option<&str>, content: &str) -> CodeChunk {
+        let chunk = CodeChunk::new(
+            "id".to_string(),
+            path.to_string(),
+            "rust".to_string(),
+            "rev".to_string(),
+            content.to_string(),
+            1,
+            10,
+        );
+
+        match symbol {
+            Some(symbol) => chunk.symbol(symbol.to_string()),
+            None => chunk,
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_field_at_construction() {
+        let result = PromptTemplate::new("{{path}}: {{nonsense}}", 1000);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn accepts_known_fields() {
+        let result = PromptTemplate::new("File: {{path}}\nSymbol: {{symbol}}\n{{content}}", 1000);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn renders_path_and_symbol_context_around_content() {
+        let template =
+            PromptTemplate::new("File: {{path}}\nSymbol: {{symbol}}\n{{content}}", 1000).unwrap();
+        let chunk = chunk_with("src/lib.rs", Some("parse"), "fn parse() {}");
+
+        let actual = template.render(&chunk);
+
+        assert_eq!(actual, "File: src/lib.rs\nSymbol: parse\nfn parse() {}");
+    }
+
+    #[test]
+    fn missing_optional_fields_render_as_empty_string() {
+        let template = PromptTemplate::new("Symbol: {{symbol}}|{{content}}", 1000).unwrap();
+        let chunk = chunk_with("src/lib.rs", None, "code");
+
+        let actual = template.render(&chunk);
+
+        assert_eq!(actual, "Symbol: |code");
+    }
+
+    #[test]
+    fn truncates_rendered_output_to_max_length() {
+        let template = PromptTemplate::new("{{content}}", 5).unwrap();
+        let chunk = chunk_with("src/lib.rs", None, "0123456789");
+
+        let actual = template.render(&chunk);
+
+        assert_eq!(actual, "01234");
+    }
+
+    #[test]
+    fn render_query_fills_content_and_blanks_other_placeholders() {
+        let template =
+            PromptTemplate::new("File: {{path}}\nSymbol: {{symbol}}\n{{content}}", 1000).unwrap();
+
+        let actual = template.render_query("how does retry backoff work");
+
+        assert_eq!(actual, "File: \nSymbol: \nhow does retry backoff work");
+    }
+
+    #[test]
+    fn default_template_embeds_bare_content() {
+        let template = PromptTemplate::default();
+        let chunk = chunk_with("src/lib.rs", Some("parse"), "fn parse() {}");
+
+        let actual = template.render(&chunk);
+
+        assert_eq!(actual, "fn parse() {}");
+    }
+}
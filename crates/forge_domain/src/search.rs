@@ -37,9 +37,30 @@ pub enum SearchMode {
     Hybrid {
         semantic_weight: f32,
         keyword_weight: f32,
+        /// How the two result sets are combined into one ranked list
+        #[serde(default)]
+        fusion: FusionMethod,
     },
 }
 
+/// Strategy for fusing the semantic and keyword result sets in `SearchMode::Hybrid`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum FusionMethod {
+    /// Blend each arm's raw score directly as `score * weight`. Only sound when both arms'
+    /// scores already live on comparable scales.
+    WeightedScore,
+    /// Reciprocal Rank Fusion: fuse each arm's *rank* rather than its raw score, so the scale
+    /// mismatch between cosine similarity and keyword scores never has to be reconciled. `k`
+    /// dampens the influence of low ranks.
+    Rrf { k: f32 },
+}
+
+impl Default for FusionMethod {
+    fn default() -> Self {
+        FusionMethod::Rrf { k: 60.0 }
+    }
+}
+
 /// Filters that can be applied to search results
 #[derive(Debug, Clone, Serialize, Deserialize, Setters, Default)]
 #[setters(strip_option, into)]
@@ -50,8 +71,12 @@ pub struct SearchFilters {
     pub branch: Option<String>,
     /// Filter by programming languages
     pub languages: Vec<String>,
-    /// Filter by file paths (glob patterns supported)
+    /// Filter by file paths. Supports gitignore-style glob patterns (`*`, `**`, `?`, `[...]`
+    /// character classes) and a leading `!` to exclude a path that would otherwise match; an
+    /// empty list matches every path. Exclusion patterns are applied after inclusion patterns.
     pub paths: Vec<String>,
+    /// Full regex match against the file path, applied in addition to `paths`.
+    pub path_regex: Option<String>,
     /// Filter by symbols/functions
     pub symbols: Vec<String>,
     /// Filter by modification date range
@@ -113,6 +138,14 @@ pub struct SearchOptions {
     pub highlight_matches: bool,
     /// Maximum content length to return
     pub max_content_length: Option<usize>,
+    /// Maximum wall-clock time, in milliseconds, the search may spend across retrieval and
+    /// post-processing. Once exceeded, later stages stop accumulating/scoring further chunks
+    /// and return whatever was already collected rather than block past the caller's SLA.
+    pub time_budget_ms: Option<u64>,
+    /// Minimum keyword score a `Hybrid` search requires, for at least `limit` hits, before it
+    /// will skip the embedding round-trip and return the keyword arm alone. `None` always runs
+    /// both arms.
+    pub lazy_embed_threshold: Option<f32>,
 }
 
 /// Grouping options for search results
@@ -160,6 +193,9 @@ pub struct SearchResults {
     pub stats: SearchStats,
     /// Suggestions for query improvement
     pub suggestions: Vec<String>,
+    /// `true` if `options.time_budget_ms` was exceeded and one or more stages were cut short,
+    /// meaning these results are partial rather than the full ranked answer.
+    pub degraded: bool,
 }
 
 /// Individual search result with score and context
@@ -177,6 +213,30 @@ pub struct SearchResult {
     pub context: Option<SearchContext>,
     /// Explanation of why this result matched
     pub explanation: Option<String>,
+    /// For a `MatchType::Hybrid` result, whether the semantic arm contributed more to the fused
+    /// score than the keyword arm. `None` for match types that only ever have one source.
+    pub semantic_dominant: Option<bool>,
+    /// Which collection this result was retrieved from. Only populated by
+    /// `SearchService::search_federated`; `None` for a single-collection search.
+    pub collection: Option<String>,
+    /// Per-arm detail behind a `SearchMode::Hybrid` result's fused `score`, so a caller can
+    /// debug why one chunk outranked another. `None` for single-arm match types.
+    pub score_breakdown: Option<ScoreBreakdown>,
+}
+
+/// Per-retriever detail behind a hybrid result's fused `score`. Ranks are 1-based, matching the
+/// ranks already logged in `SearchResult::explanation`; a `None` rank means the chunk didn't
+/// appear in that arm's result list at all.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct ScoreBreakdown {
+    /// This chunk's 1-based rank within the semantic arm's own ranking, if it appeared there
+    pub semantic_rank: Option<usize>,
+    /// This chunk's 1-based rank within the keyword arm's own ranking, if it appeared there
+    pub keyword_rank: Option<usize>,
+    /// The semantic arm's weighted contribution to the fused `score`
+    pub semantic_contribution: f32,
+    /// The keyword arm's weighted contribution to the fused `score`
+    pub keyword_contribution: f32,
 }
 
 /// Type of match found in search
@@ -218,12 +278,22 @@ pub struct SearchStats {
     pub semantic_matches: usize,
     /// Number of keyword matches
     pub keyword_matches: usize,
+    /// Number of final results whose top-contributing signal was semantic: every
+    /// `MatchType::Semantic` result, plus `MatchType::Hybrid` results where the semantic arm's
+    /// weighted score outweighed the keyword arm's.
+    pub semantic_hit_count: usize,
+    /// Number of pipeline stages (a hybrid search arm, post-processing) cut short because
+    /// `options.time_budget_ms` was exceeded
+    pub degraded_stages: usize,
     /// Number of filters applied
     pub filters_applied: usize,
     /// Breakdown by match type
     pub match_type_breakdown: HashMap<MatchType, usize>,
     /// Breakdown by language
     pub language_breakdown: HashMap<String, usize>,
+    /// Breakdown by source collection, populated by `SearchService::search_federated`; empty
+    /// for a single-collection search.
+    pub collection_breakdown: HashMap<String, usize>,
 }
 
 impl Default for SearchQuery {
@@ -232,7 +302,11 @@ impl Default for SearchQuery {
             query: String::new(),
             limit: 20,
             similarity_threshold: 0.7,
-            mode: SearchMode::Hybrid { semantic_weight: 0.7, keyword_weight: 0.3 },
+            mode: SearchMode::Hybrid {
+                semantic_weight: 0.7,
+                keyword_weight: 0.3,
+                fusion: FusionMethod::default(),
+            },
             filters: SearchFilters::default(),
             options: SearchOptions::default(),
         }
@@ -250,6 +324,8 @@ impl Default for SearchOptions {
             sort_by: SortBy::Relevance,
             highlight_matches: true,
             max_content_length: Some(2000),
+            time_budget_ms: None,
+            lazy_embed_threshold: None,
         }
     }
 }
@@ -273,15 +349,43 @@ impl SearchQuery {
         }
     }
 
-    /// Create a hybrid search query with custom weights
+    /// Create a hybrid search query with custom weights, fused via Reciprocal Rank Fusion
     pub fn hybrid(query: impl Into<String>, semantic_weight: f32, keyword_weight: f32) -> Self {
         Self {
             query: query.into(),
-            mode: SearchMode::Hybrid { semantic_weight, keyword_weight },
+            mode: SearchMode::Hybrid {
+                semantic_weight,
+                keyword_weight,
+                fusion: FusionMethod::default(),
+            },
             ..Default::default()
         }
     }
 
+    /// Create a hybrid search query with custom weights and an explicit fusion strategy
+    pub fn hybrid_with_fusion(
+        query: impl Into<String>,
+        semantic_weight: f32,
+        keyword_weight: f32,
+        fusion: FusionMethod,
+    ) -> Self {
+        Self {
+            query: query.into(),
+            mode: SearchMode::Hybrid { semantic_weight, keyword_weight, fusion },
+            ..Default::default()
+        }
+    }
+
+    /// Create a hybrid search query from a single MeiliSearch-style `semantic_ratio` in
+    /// `[0.0, 1.0]` rather than two independent weights: `0.0` is pure keyword, `1.0` is pure
+    /// semantic, and anything in between splits `keyword_weight` as `1.0 - semantic_ratio`. The
+    /// ratio is clamped into range so an out-of-bounds caller value degrades gracefully instead
+    /// of producing a negative weight.
+    pub fn hybrid_with_ratio(query: impl Into<String>, semantic_ratio: f32) -> Self {
+        let semantic_ratio = semantic_ratio.clamp(0.0, 1.0);
+        Self::hybrid(query, semantic_ratio, 1.0 - semantic_ratio)
+    }
+
     /// Add a repository filter
     pub fn repository(mut self, repo: impl Into<String>) -> Self {
         self.filters.repository = Some(repo.into());
@@ -395,11 +499,15 @@ mod tests {
                 chunks_searched: 0,
                 semantic_matches: 0,
                 keyword_matches: 0,
+                semantic_hit_count: 0,
+                degraded_stages: 0,
                 filters_applied: 0,
                 match_type_breakdown: HashMap::new(),
                 language_breakdown: HashMap::new(),
+                collection_breakdown: HashMap::new(),
             },
             suggestions: vec![],
+            degraded: false,
         };
 
         let actual = fixture.is_empty();
@@ -417,4 +525,61 @@ mod tests {
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_hybrid_defaults_to_rrf_fusion() {
+        let fixture = SearchQuery::hybrid("rust function", 0.8, 0.2);
+
+        let actual = fixture.mode;
+        let expected = SearchMode::Hybrid {
+            semantic_weight: 0.8,
+            keyword_weight: 0.2,
+            fusion: FusionMethod::Rrf { k: 60.0 },
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_hybrid_with_fusion_uses_requested_method() {
+        let fixture =
+            SearchQuery::hybrid_with_fusion("rust function", 0.8, 0.2, FusionMethod::WeightedScore);
+
+        let actual = fixture.mode;
+        let expected = SearchMode::Hybrid {
+            semantic_weight: 0.8,
+            keyword_weight: 0.2,
+            fusion: FusionMethod::WeightedScore,
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_hybrid_with_ratio_splits_weights() {
+        let fixture = SearchQuery::hybrid_with_ratio("rust function", 0.8);
+
+        let actual = fixture.mode;
+        let expected = SearchMode::Hybrid {
+            semantic_weight: 0.8,
+            keyword_weight: 1.0 - 0.8_f32,
+            fusion: FusionMethod::Rrf { k: 60.0 },
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_hybrid_with_ratio_clamps_out_of_range() {
+        let fixture = SearchQuery::hybrid_with_ratio("rust function", 1.5);
+
+        let actual = fixture.mode;
+        let expected = SearchMode::Hybrid {
+            semantic_weight: 1.0,
+            keyword_weight: 0.0,
+            fusion: FusionMethod::Rrf { k: 60.0 },
+        };
+
+        assert_eq!(actual, expected);
+    }
 }
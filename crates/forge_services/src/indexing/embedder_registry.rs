@@ -0,0 +1,144 @@
+//! Named, lazily-constructed embedder registry
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use forge_domain::{EmbeddingConfig, EmbeddingProvider, Embedder};
+use tokio::sync::RwLock;
+
+use super::mock_embedder::MockEmbedder;
+
+/// Registry of user-named embedder configurations, following MeiliSearch's multi-embedder model:
+/// each name (e.g. `"openai-large"`, `"local-codebert"`, `"ollama-nomic"`) maps to an
+/// [`EmbeddingConfig`] that is only turned into a live embedder the first time it's looked up,
+/// then cached for subsequent calls. Vectors produced by different embedders are not comparable
+/// and must never be mixed in one collection, so a caller resolving an embedder for an *existing*
+/// index should go through [`EmbedderRegistry::get_for_dimension`] rather than `get`, to catch a
+/// name/dimension mismatch before it silently corrupts search results.
+#[derive(Default)]
+pub struct EmbedderRegistry {
+    configs: HashMap<String, EmbeddingConfig>,
+    cache: RwLock<HashMap<String, Arc<dyn Embedder>>>,
+}
+
+impl EmbedderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a named embedder configuration. Construction is deferred until `name` is first
+    /// resolved via `get`.
+    pub fn register(&mut self, name: impl Into<String>, config: EmbeddingConfig) {
+        self.configs.insert(name.into(), config);
+    }
+
+    /// Names of every registered embedder, in no particular order.
+    pub fn names(&self) -> Vec<&str> {
+        self.configs.keys().map(String::as_str).collect()
+    }
+
+    /// Resolve `name` to a live embedder, constructing and caching it on first use.
+    pub async fn get(&self, name: &str) -> Result<Arc<dyn Embedder>> {
+        if let Some(embedder) = self.cache.read().await.get(name) {
+            return Ok(embedder.clone());
+        }
+
+        let config = self
+            .configs
+            .get(name)
+            .with_context(|| format!("No embedder registered under the name '{name}'"))?;
+        let embedder =
+            Self::build(config).with_context(|| format!("Failed to construct embedder '{name}'"))?;
+
+        self.cache.write().await.insert(name.to_string(), embedder.clone());
+        Ok(embedder)
+    }
+
+    /// Like `get`, but additionally rejects a lookup whose embedder produces a different
+    /// dimension than `expected_dimension` -- the check a caller querying an already-indexed
+    /// collection must make, since vectors from a mismatched dimension aren't just lower
+    /// quality, they're structurally incompatible with what's already stored.
+    pub async fn get_for_dimension(&self, name: &str, expected_dimension: usize) -> Result<Arc<dyn Embedder>> {
+        let embedder = self.get(name).await?;
+        let actual = embedder.embedding_dimension();
+        if actual != expected_dimension {
+            bail!(
+                "Embedder '{name}' produces {actual}-dimensional vectors, but this index was built with {expected_dimension}-dimensional vectors; pick a matching embedder or reindex"
+            );
+        }
+        Ok(embedder)
+    }
+
+    fn build(config: &EmbeddingConfig) -> Result<Arc<dyn Embedder>> {
+        match &config.provider {
+            EmbeddingProvider::Mock { dimension } => Ok(Arc::new(MockEmbedder::new(*dimension))),
+            EmbeddingProvider::OpenAI { .. } | EmbeddingProvider::Local { .. } => Err(anyhow::anyhow!(
+                "Embedding provider {:?} is not constructible from forge_services; register it directly as a boxed Embedder instead of through EmbedderRegistry",
+                config.provider
+            )),
+        }
+    }
+}
+
+/// Thin adapter so a registry-resolved `Arc<dyn Embedder>` can be handed to APIs built around
+/// `Box<dyn Embedder>`, e.g. `IndexingService::new`, without cloning the underlying embedder.
+pub(crate) struct SharedEmbedder(pub Arc<dyn Embedder>);
+
+#[async_trait]
+impl Embedder for SharedEmbedder {
+    async fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+        self.0.embed_text(text).await
+    }
+
+    async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        self.0.embed_batch(texts).await
+    }
+
+    fn embedding_dimension(&self) -> usize {
+        self.0.embedding_dimension()
+    }
+
+    fn provider(&self) -> &EmbeddingProvider {
+        self.0.provider()
+    }
+
+    fn name(&self) -> &str {
+        self.0.name()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_get_constructs_and_caches() {
+        let mut registry = EmbedderRegistry::new();
+        registry.register("local-mock", EmbeddingConfig::default());
+
+        let first = registry.get("local-mock").await.unwrap();
+        let second = registry.get("local-mock").await.unwrap();
+
+        assert_eq!(first.embedding_dimension(), 384);
+        assert!(Arc::ptr_eq(&first, &second), "expected the cached instance to be reused");
+    }
+
+    #[tokio::test]
+    async fn test_get_unknown_name_errors() {
+        let registry = EmbedderRegistry::new();
+        assert!(registry.get("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_for_dimension_rejects_mismatch() {
+        let mut registry = EmbedderRegistry::new();
+        registry.register("small", EmbeddingConfig { provider: EmbeddingProvider::Mock { dimension: 64 }, ..EmbeddingConfig::default() });
+
+        assert!(registry.get_for_dimension("small", 64).await.is_ok());
+        assert!(registry.get_for_dimension("small", 128).await.is_err());
+    }
+}
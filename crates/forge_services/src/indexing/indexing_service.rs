@@ -1,38 +1,74 @@
 //! Indexing service implementation following established patterns
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::Instant;
 
 use anyhow::{Context, Result};
 use forge_domain::{
-    ChunkingConfig, CodeChunk, Embedder, Chunker, EmbeddingProvider, IndexedCodebase, 
-    IndexingConfig, IndexingProgress, IndexingRequest, IndexingResponse, IndexingStage, 
-    IndexingStatistics, IndexingStatus, ProcessingTimeBreakdown,
+    ChunkingConfig, CodeChunk, Embedder, Chunker, CoverageGap, CoverageGapEntry, CoverageReport,
+    EmbeddingProvider, IndexedCodebase, IndexingConfig, IndexingProgress, IndexingRequest,
+    IndexingResponse, IndexingStage, IndexingStatistics, IndexingStatus, ProcessingTimeBreakdown,
+    ReindexMode,
 };
 use forge_walker::Walker;
-use tokio::sync::{RwLock, mpsc};
+use tokio::sync::{RwLock, Semaphore, mpsc};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
-use crate::vector_store::{SharedVectorStore, VectorStoreFactory};
+use forge_indexer::{log_operation_start, log_operation_success};
+
+use super::manifest::{self, FileManifestEntry, IndexManifest};
+use crate::search::SharedKeywordIndex;
+use crate::vector_store::{IndexStatus, SharedVectorStore, VectorStoreFactory};
+
+/// What processing a single file produced, so the caller can record it in the index manifest for
+/// the next incremental run.
+struct ProcessOutcome {
+    content_hash: String,
+    size: u64,
+    chunk_ids: Vec<String>,
+}
+
+/// One file's contribution to [`IndexingStatistics`], returned by `process_single_file` instead
+/// of an in-place mutation so concurrent file tasks don't race on a shared `&mut`.
+#[derive(Default)]
+struct FileStatsDelta {
+    language: String,
+    bytes_processed: u64,
+    chunks_created: usize,
+    embeddings_generated: usize,
+    chunking_ms: u64,
+    embedding_ms: u64,
+    storage_ms: u64,
+    error_summary: HashMap<String, usize>,
+}
 
 /// Service for indexing codebases with configurable chunking and embedding
-/// strategies
+/// strategies. Cheap to clone -- every field is an `Arc` (directly, or via `SharedVectorStore`
+/// /`SharedKeywordIndex`) or itself `Clone`, so a clone can be moved into a spawned task without
+/// cloning the underlying chunker/embedder/store.
+#[derive(Clone)]
 pub struct IndexingService {
     vector_store: SharedVectorStore,
     chunker: Arc<RwLock<Box<dyn Chunker>>>,
     embedder: Arc<RwLock<Box<dyn Embedder>>>,
+    keyword_index: SharedKeywordIndex,
     config: IndexingConfig,
     progress_sender: Option<mpsc::UnboundedSender<IndexingProgress>>,
 }
 
 impl IndexingService {
-    /// Create a new indexing service with the given configuration
+    /// Create a new indexing service with the given configuration. `keyword_index` is fed each
+    /// file's chunks in one batch as that file is processed, so the BM25 lexical index backing
+    /// `SearchMode::Keyword` stays in sync with the vector store.
     pub async fn new(
         config: IndexingConfig,
         chunker: Box<dyn Chunker>,
         embedder: Box<dyn Embedder>,
+        keyword_index: SharedKeywordIndex,
     ) -> Result<Self> {
         info!("Initializing IndexingService");
 
@@ -61,6 +97,7 @@ impl IndexingService {
             vector_store: shared_store,
             chunker: Arc::new(RwLock::new(chunker)),
             embedder: Arc::new(RwLock::new(embedder)),
+            keyword_index,
             config,
             progress_sender: None,
         })
@@ -78,7 +115,28 @@ impl IndexingService {
 
     /// Index a codebase according to the request
     pub async fn index_codebase(&self, request: IndexingRequest) -> Result<IndexingResponse> {
+        self.run_index_codebase(request, None).await
+    }
+
+    /// Index a codebase, stopping early if `cancel_token` is cancelled. Cancellation is checked
+    /// cooperatively between files -- a file already dispatched to a task still runs to
+    /// completion, but no new ones are started once it fires, so an aborted run's manifest and
+    /// vector store stay consistent with whatever finished.
+    pub async fn index_codebase_cancellable(
+        &self,
+        request: IndexingRequest,
+        cancel_token: CancellationToken,
+    ) -> Result<IndexingResponse> {
+        self.run_index_codebase(request, Some(cancel_token)).await
+    }
+
+    async fn run_index_codebase(
+        &self,
+        request: IndexingRequest,
+        cancel_token: Option<CancellationToken>,
+    ) -> Result<IndexingResponse> {
         let start_time = Instant::now();
+        let request = Arc::new(request);
         let mut stats = IndexingStatistics {
             files_discovered: 0,
             files_processed: 0,
@@ -99,7 +157,7 @@ impl IndexingService {
             language_distribution: HashMap::new(),
         };
 
-        let warnings = Vec::new();
+        let mut warnings = Vec::new();
 
         // Reset existing index if requested
         if request.reset_existing {
@@ -128,6 +186,8 @@ impl IndexingService {
             0,
             0,
             0,
+            0.0,
+            None,
         )
         .await;
         let discovery_start = Instant::now();
@@ -144,7 +204,66 @@ impl IndexingService {
 
         info!("Discovered {} files for indexing", stats.files_discovered);
 
-        // Stage 2: Process files (Chunking, Embedding, Storage)
+        // Incremental re-indexing: consult the collection's content manifest so a run against an
+        // already-indexed codebase only touches files that are new, changed, or gone since the
+        // manifest was last persisted. A `reset_existing` run wipes the collection above, so the
+        // previous manifest no longer describes what's in it and is ignored. A `Full` run always
+        // reprocesses everything it discovers, matching indexing's behavior before manifests
+        // existed, but still seeds the manifest for the next `Incremental` run.
+        let manifest_path = manifest::manifest_path(
+            &request.root_path,
+            &request.config.vector_store.collection_name,
+        );
+        let mut index_manifest = if request.reset_existing {
+            IndexManifest::default()
+        } else {
+            IndexManifest::load(&manifest_path).await
+        };
+
+        let files_to_process = if request.reindex_mode == ReindexMode::Incremental
+            && !request.reset_existing
+        {
+            let current_paths: HashSet<String> = files_to_process
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect();
+
+            for vanished in index_manifest.vanished_paths(&current_paths) {
+                if let Some(entry) = index_manifest.remove(&vanished) {
+                    self.delete_stale_chunks(&request, &entry).await?;
+                }
+            }
+
+            let mut changed_files = Vec::with_capacity(files_to_process.len());
+            for file_path in files_to_process {
+                let path_key = file_path.to_string_lossy().to_string();
+                let content = tokio::fs::read(&file_path)
+                    .await
+                    .context("Failed to read file for manifest comparison")?;
+                let content_hash = manifest::hash_content(&content);
+
+                match index_manifest.entry(&path_key) {
+                    Some(entry) if entry.content_hash == content_hash => {
+                        stats.files_skipped += 1;
+                    }
+                    Some(entry) => {
+                        let entry = entry.clone();
+                        self.delete_stale_chunks(&request, &entry).await?;
+                        changed_files.push(file_path);
+                    }
+                    None => changed_files.push(file_path),
+                }
+            }
+            changed_files
+        } else {
+            files_to_process
+        };
+
+        // Stage 2: Process files (Chunking, Embedding, Storage). Each file runs as its own task,
+        // bounded by `max_concurrent_files`, so a slow embedding call for one file doesn't stall
+        // the others behind it; a second, independently-sized semaphore caps how many
+        // `embed_batch` calls are in flight at once regardless of how many files are mid-read or
+        // mid-chunk.
         self.send_progress(
             &request.request_id,
             IndexingStage::Chunking,
@@ -153,18 +272,109 @@ impl IndexingService {
             0,
             stats.files_discovered,
             0,
+            0.0,
+            None,
         )
         .await;
 
-        for (file_index, file_path) in files_to_process.iter().enumerate() {
-            let file_start = Instant::now();
-
-            match self
-                .process_single_file(file_path, &request, &mut stats)
+        let total_to_process = files_to_process.len();
+        let file_semaphore =
+            Arc::new(Semaphore::new(request.config.processing.max_concurrent_files.max(1)));
+        let embed_semaphore =
+            Arc::new(Semaphore::new(request.config.processing.max_concurrent_chunks.max(1)));
+        let files_completed = Arc::new(AtomicUsize::new(0));
+        let chunks_completed = Arc::new(AtomicUsize::new(0));
+        let stage_start = Instant::now();
+
+        let mut tasks = Vec::with_capacity(total_to_process);
+        for file_path in files_to_process {
+            // The file permit is taken here rather than inside the task, so cancellation is
+            // checked each time a slot frees up instead of once for every file up front.
+            let permit = file_semaphore
+                .clone()
+                .acquire_owned()
                 .await
-            {
-                Ok(()) => {
+                .expect("file semaphore closed while tasks are still pending");
+            if cancel_token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                info!("Indexing run {} cancelled; not dispatching remaining files", request.request_id);
+                break;
+            }
+
+            let embed_semaphore = embed_semaphore.clone();
+            let files_completed = files_completed.clone();
+            let chunks_completed = chunks_completed.clone();
+            let service = self.clone();
+            let request = request.clone();
+
+            tasks.push(tokio::spawn(async move {
+                let _permit = permit;
+                let result = service
+                    .process_single_file(&file_path, &request, &embed_semaphore)
+                    .await;
+
+                let chunks_this_file =
+                    result.as_ref().map(|(_, delta)| delta.chunks_created).unwrap_or(0);
+                let total_chunks = chunks_completed.fetch_add(chunks_this_file, Ordering::SeqCst)
+                    + chunks_this_file;
+                let completed = files_completed.fetch_add(1, Ordering::SeqCst) + 1;
+
+                let elapsed_secs = stage_start.elapsed().as_secs_f32();
+                let throughput_fps =
+                    if elapsed_secs > 0.0 { completed as f32 / elapsed_secs } else { 0.0 };
+                let remaining = total_to_process.saturating_sub(completed);
+                let estimated_remaining_seconds = if throughput_fps > 0.0 {
+                    Some((remaining as f32 / throughput_fps).round() as u64)
+                } else {
+                    None
+                };
+
+                let progress = completed as f32 / total_to_process.max(1) as f32;
+                let stage = if progress < 0.6 {
+                    IndexingStage::Chunking
+                } else if progress < 0.9 {
+                    IndexingStage::Embedding
+                } else {
+                    IndexingStage::Storage
+                };
+
+                service
+                    .send_progress(
+                        &request.request_id,
+                        stage,
+                        0.1 + progress * 0.8,
+                        Some(file_path.to_string_lossy().to_string()),
+                        completed,
+                        total_to_process,
+                        total_chunks,
+                        throughput_fps,
+                        estimated_remaining_seconds,
+                    )
+                    .await;
+
+                (file_path, result)
+            }));
+        }
+
+        // Join and merge sequentially -- tasks ran concurrently, but folding their results into
+        // `stats`/`index_manifest` happens one at a time so there's no contention over either.
+        let mut files_with_no_chunks = 0usize;
+        for task in tasks {
+            let (file_path, result) = task.await.context("Indexing task panicked")?;
+            match result {
+                Ok((outcome, delta)) => {
                     stats.files_processed += 1;
+                    if outcome.chunk_ids.is_empty() {
+                        files_with_no_chunks += 1;
+                    }
+                    Self::merge_file_stats(&mut stats, delta);
+                    index_manifest.record(
+                        file_path.to_string_lossy().to_string(),
+                        FileManifestEntry {
+                            content_hash: outcome.content_hash,
+                            size: outcome.size,
+                            chunk_ids: outcome.chunk_ids,
+                        },
+                    );
                 }
                 Err(e) => {
                     stats.files_failed += 1;
@@ -173,32 +383,28 @@ impl IndexingService {
                     warn!("Failed to process file {:?}: {}", file_path, e);
                 }
             }
+        }
 
-            // Update progress
-            let progress = (file_index + 1) as f32 / stats.files_discovered as f32;
-            let stage = if progress < 0.6 {
-                IndexingStage::Chunking
-            } else if progress < 0.9 {
-                IndexingStage::Embedding
-            } else {
-                IndexingStage::Storage
-            };
+        // Surface the common "why are so many paths missing from the index?" causes directly in
+        // the response instead of making a caller cross-reference statistics themselves.
+        if files_with_no_chunks > 0 {
+            warnings.push(format!(
+                "{files_with_no_chunks} discovered file(s) produced no chunks"
+            ));
+        }
+        if stats.files_failed > 0 {
+            warnings.push(format!(
+                "{} discovered file(s) failed to process and were skipped",
+                stats.files_failed
+            ));
+        }
 
-            self.send_progress(
-                &request.request_id,
-                stage,
-                0.1 + progress * 0.8,
-                Some(file_path.to_string_lossy().to_string()),
-                stats.files_processed,
-                stats.files_discovered,
-                stats.chunks_created,
-            )
-            .await;
+        stats.time_breakdown.file_reading_ms += stage_start.elapsed().as_millis() as u64;
 
-            // Add processing time
-            let file_time = file_start.elapsed().as_millis() as u64;
-            stats.time_breakdown.file_reading_ms += file_time;
-        }
+        index_manifest
+            .persist(&manifest_path)
+            .await
+            .context("Failed to persist index manifest")?;
 
         // Stage 3: Finalization
         self.send_progress(
@@ -209,6 +415,8 @@ impl IndexingService {
             stats.files_processed,
             stats.files_discovered,
             stats.chunks_created,
+            0.0,
+            None,
         )
         .await;
 
@@ -230,6 +438,7 @@ impl IndexingService {
             } else {
                 IndexingStatus::Failed(format!("{} files failed to process", stats.files_failed))
             },
+            normalized_embeddings: request.config.normalize_embeddings,
         };
 
         // Send completion
@@ -241,6 +450,8 @@ impl IndexingService {
             stats.files_processed,
             stats.files_discovered,
             stats.chunks_created,
+            0.0,
+            None,
         )
         .await;
 
@@ -250,7 +461,7 @@ impl IndexingService {
         );
 
         Ok(IndexingResponse {
-            request_id: request.request_id,
+            request_id: request.request_id.clone(),
             codebase,
             statistics: stats,
             warnings,
@@ -258,13 +469,16 @@ impl IndexingService {
         })
     }
 
-    /// Process a single file through the indexing pipeline
+    /// Process a single file through the indexing pipeline. Runs concurrently with other files
+    /// (bounded by the caller's file semaphore), so it returns its statistics as a
+    /// [`FileStatsDelta`] instead of mutating a shared `&mut IndexingStatistics` -- the caller
+    /// merges deltas from completed tasks one at a time once they're joined.
     async fn process_single_file(
         &self,
         file_path: &Path,
         request: &IndexingRequest,
-        stats: &mut IndexingStatistics,
-    ) -> Result<()> {
+        embed_semaphore: &Semaphore,
+    ) -> Result<(ProcessOutcome, FileStatsDelta)> {
         debug!("Processing file: {:?}", file_path);
 
         // Read file content
@@ -272,19 +486,15 @@ impl IndexingService {
             .await
             .context("Failed to read file")?;
 
-        stats.bytes_processed += content.len() as u64;
+        let content_hash = manifest::hash_content(content.as_bytes());
+        let mut delta = FileStatsDelta { bytes_processed: content.len() as u64, ..Default::default() };
 
         // Detect language
         let chunker = self.chunker.read().await;
         let language = chunker
             .detect_language(file_path)
             .unwrap_or_else(|| "text".to_string());
-
-        // Update language distribution
-        *stats
-            .language_distribution
-            .entry(language.clone())
-            .or_insert(0) += 1;
+        delta.language = language.clone();
 
         // Chunk the file
         let chunking_start = Instant::now();
@@ -299,48 +509,251 @@ impl IndexingService {
             .await
             .context("Failed to chunk file")?;
 
-        stats.time_breakdown.chunking_ms += chunking_start.elapsed().as_millis() as u64;
-        stats.chunks_created += chunks.len();
+        delta.chunking_ms = chunking_start.elapsed().as_millis() as u64;
+        delta.chunks_created = chunks.len();
 
         if chunks.is_empty() {
             debug!("No chunks created for file: {:?}", file_path);
-            return Ok(());
+            let outcome = ProcessOutcome { content_hash, size: content.len() as u64, chunk_ids: Vec::new() };
+            return Ok((outcome, delta));
         }
 
-        // Generate embeddings
+        // Generate embeddings, deduplicating identical rendered texts first -- a repo can contain
+        // many chunks with the same content (license headers, generated boilerplate), and
+        // embedding each copy separately is wasted work that also makes a later positional zip
+        // against `chunks` fragile to the embedder returning results out of order.
         let embedding_start = Instant::now();
         let embedder = self.embedder.read().await;
-        let texts: Vec<String> = chunks.iter().map(|c| c.content.clone()).collect();
-        let embeddings = embedder
-            .embed_batch(&texts)
-            .await
-            .context("Failed to generate embeddings")?;
+        let texts: Vec<String> = chunks
+            .iter()
+            .map(|c| request.config.chunking.prompt_template.render(c))
+            .collect();
+
+        let mut text_to_slot: HashMap<&str, usize> = HashMap::new();
+        let mut unique_texts: Vec<String> = Vec::new();
+        for text in &texts {
+            if !text_to_slot.contains_key(text.as_str()) {
+                text_to_slot.insert(text.as_str(), unique_texts.len());
+                unique_texts.push(text.clone());
+            }
+        }
+
+        // Capped separately from `max_concurrent_files` -- an embedding provider often has its
+        // own request-rate ceiling that's much lower than how many files can be read/chunked at
+        // once.
+        let mut unique_embeddings = {
+            let _permit = embed_semaphore.acquire().await.context("Embedding semaphore closed")?;
+            embedder
+                .embed_batch(&unique_texts)
+                .await
+                .context("Failed to generate embeddings")?
+        };
+
+        // L2-normalize to unit vectors so retrieval can score with a plain dot product instead of
+        // a full cosine computation. A zero-norm vector (an all-zero embedding) has no direction
+        // to normalize to, so it's left untouched and counted instead of divided by zero.
+        if request.config.normalize_embeddings {
+            let mut zero_norm_count = 0;
+            for embedding in &mut unique_embeddings {
+                let norm = embedding.iter().map(|v| v * v).sum::<f32>().sqrt();
+                if norm > 0.0 {
+                    for value in embedding.iter_mut() {
+                        *value /= norm;
+                    }
+                } else {
+                    zero_norm_count += 1;
+                }
+            }
+            if zero_norm_count > 0 {
+                warn!(
+                    "{} embedding(s) for file {:?} had a zero norm and were left un-normalized",
+                    zero_norm_count, file_path
+                );
+                *delta.error_summary.entry("zero_norm_embedding".to_string()).or_insert(0) +=
+                    zero_norm_count;
+            }
+        }
 
-        stats.time_breakdown.embedding_ms += embedding_start.elapsed().as_millis() as u64;
-        stats.embeddings_generated += embeddings.len();
+        delta.embedding_ms = embedding_start.elapsed().as_millis() as u64;
+        delta.embeddings_generated = unique_embeddings.len();
+
+        // Pair each chunk with its embedding by looking its own text's slot back up, rather than
+        // zipping `chunks` against `unique_embeddings` positionally -- the two are different
+        // lengths whenever duplicates were collapsed above. A chunk whose slot the embedder
+        // didn't return a vector for (a partially failed batch) is recorded and skipped instead
+        // of aborting the rest of the file.
+        let mut chunk_embedding_pairs: Vec<(CodeChunk, Vec<f32>)> = Vec::with_capacity(chunks.len());
+        let mut failed_indices: Vec<usize> = Vec::new();
+        for (index, (chunk, text)) in chunks.into_iter().zip(texts.iter()).enumerate() {
+            match unique_embeddings.get(text_to_slot[text.as_str()]) {
+                Some(embedding) => chunk_embedding_pairs.push((chunk, embedding.clone())),
+                None => failed_indices.push(index),
+            }
+        }
+
+        if !failed_indices.is_empty() {
+            warn!(
+                "Embedder returned {} vector(s) for {} unique text(s); chunk indices {:?} in file {:?} have no embedding and were skipped",
+                unique_embeddings.len(),
+                unique_texts.len(),
+                failed_indices,
+                file_path
+            );
+            *delta.error_summary.entry("embedding_incomplete".to_string()).or_insert(0) +=
+                failed_indices.len();
+        }
 
         // Store in vector database
         let storage_start = Instant::now();
-        let chunk_embedding_pairs: Vec<(CodeChunk, Vec<f32>)> =
-            chunks.into_iter().zip(embeddings.into_iter()).collect();
-
         let mut store = self.vector_store.write().await;
-        store
+        let chunk_ids = store
             .insert_chunks(
                 &request.config.vector_store.collection_name,
                 &chunk_embedding_pairs,
             )
             .await
             .context("Failed to store chunks in vector database")?;
+        drop(store);
 
-        stats.time_breakdown.storage_ms += storage_start.elapsed().as_millis() as u64;
+        // Keep the BM25 lexical index in sync with what was just stored in the vector store.
+        // Batched so this file's chunks persist once, not once per chunk -- `add_chunk` in a loop
+        // here made indexing a whole repo O(total chunks squared) in disk I/O.
+        let file_chunks: Vec<CodeChunk> = chunk_embedding_pairs.iter().map(|(chunk, _)| chunk.clone()).collect();
+        self.keyword_index.add_chunks(&file_chunks).await.context("Failed to update keyword index")?;
+
+        delta.storage_ms = storage_start.elapsed().as_millis() as u64;
 
         debug!(
             "Successfully processed file: {:?} ({} chunks)",
             file_path,
             chunk_embedding_pairs.len()
         );
-        Ok(())
+        let outcome = ProcessOutcome { content_hash, size: content.len() as u64, chunk_ids };
+        Ok((outcome, delta))
+    }
+
+    /// Fold one file's [`FileStatsDelta`] into the run's shared statistics. Called from the
+    /// (sequential) task-joining loop, never concurrently, so plain `+=` is safe here even though
+    /// the deltas themselves were produced by tasks running in parallel.
+    fn merge_file_stats(stats: &mut IndexingStatistics, delta: FileStatsDelta) {
+        stats.bytes_processed += delta.bytes_processed;
+        *stats.language_distribution.entry(delta.language).or_insert(0) += 1;
+        stats.chunks_created += delta.chunks_created;
+        stats.embeddings_generated += delta.embeddings_generated;
+        stats.time_breakdown.chunking_ms += delta.chunking_ms;
+        stats.time_breakdown.embedding_ms += delta.embedding_ms;
+        stats.time_breakdown.storage_ms += delta.storage_ms;
+        for (key, count) in delta.error_summary {
+            *stats.error_summary.entry(key).or_insert(0) += count;
+        }
+    }
+
+    /// Delete the vectors recorded for a file that changed or disappeared since the manifest was
+    /// last persisted, so a stale copy doesn't linger in search results alongside (or instead of)
+    /// the reprocessed version.
+    async fn delete_stale_chunks(
+        &self,
+        request: &IndexingRequest,
+        entry: &FileManifestEntry,
+    ) -> Result<()> {
+        if entry.chunk_ids.is_empty() {
+            return Ok(());
+        }
+        self.vector_store
+            .write()
+            .await
+            .delete_chunks(&request.config.vector_store.collection_name, &entry.chunk_ids)
+            .await
+            .context("Failed to delete stale chunks for a changed or removed file")
+    }
+
+    /// Diff-based alternative to `reset_existing: true`, which wipes and rebuilds `request`'s
+    /// collection unconditionally. `repair` first checks whether the collection can even be
+    /// caught up incrementally: if the embedder's `embedding_dimension()` no longer matches what's
+    /// stored, or the vector store itself reports an errored index, a full reset is the only way
+    /// forward and `repair` reports that instead of touching anything. Otherwise it runs an
+    /// `Incremental` reindex, which (via the collection's manifest) re-embeds only files whose
+    /// content changed since the last run and deletes the stale vectors for the rest -- avoiding a
+    /// multi-hour full rebuild after a small change or a same-dimension model swap.
+    pub async fn repair(&self, request: &IndexingRequest) -> Result<RepairOutcome> {
+        let collection = request.config.vector_store.collection_name.clone();
+        log_operation_start!("vector_store_repair", collection = collection.as_str());
+        let start = Instant::now();
+
+        let stats = self
+            .vector_store
+            .read()
+            .await
+            .get_stats(&collection)
+            .await
+            .context("Failed to read vector store stats for repair")?;
+
+        if let IndexStatus::Error(reason) = stats.index_status {
+            warn!("Vector store reported index '{collection}' as errored; skipping repair: {reason}");
+            return Ok(RepairOutcome::IndexErrored(reason));
+        }
+
+        let expected_dimension = self.embedder.read().await.embedding_dimension();
+        if stats.vector_dimension != expected_dimension {
+            warn!(
+                "Collection '{collection}' has vector dimension {}, embedder produces {}; a diff-based repair can't reconcile this, a full reset is required",
+                stats.vector_dimension, expected_dimension
+            );
+            return Ok(RepairOutcome::DimensionMismatch {
+                expected: expected_dimension,
+                found: stats.vector_dimension,
+            });
+        }
+
+        let mut repair_request = request.clone();
+        repair_request.reset_existing = false;
+        repair_request.reindex_mode = ReindexMode::Incremental;
+
+        let response = self.run_index_codebase(repair_request, None).await?;
+
+        log_operation_success!(
+            "vector_store_repair",
+            start.elapsed(),
+            collection = collection.as_str(),
+            files_processed = response.statistics.files_processed
+        );
+
+        Ok(RepairOutcome::Repaired(Box::new(response)))
+    }
+
+    /// Audit what's actually present in `request`'s collection against what `discover_files`
+    /// would currently select for it, without running a (re-)index. Relies on the collection's
+    /// manifest (the same one incremental re-indexing consults) rather than querying the vector
+    /// store directly, since the manifest is what already tracks a path's chunk ids.
+    pub async fn coverage_report(&self, request: &IndexingRequest) -> Result<CoverageReport> {
+        let manifest_path = manifest::manifest_path(
+            &request.root_path,
+            &request.config.vector_store.collection_name,
+        );
+        let index_manifest = IndexManifest::load(&manifest_path).await;
+
+        let discovered = if !request.specific_files.is_empty() {
+            request.specific_files.clone()
+        } else {
+            self.discover_files(&request.root_path, &request.config.filtering).await?
+        };
+
+        let mut indexed_files = HashMap::new();
+        let mut missing_files = Vec::new();
+        for file_path in discovered {
+            let path_key = file_path.to_string_lossy().to_string();
+            match index_manifest.entry(&path_key) {
+                Some(entry) if !entry.chunk_ids.is_empty() => {
+                    indexed_files.insert(path_key, entry.chunk_ids.len());
+                }
+                Some(_) => missing_files
+                    .push(CoverageGapEntry { path: path_key, reason: CoverageGap::NoChunks }),
+                None => missing_files
+                    .push(CoverageGapEntry { path: path_key, reason: CoverageGap::Unindexed }),
+            }
+        }
+
+        Ok(CoverageReport { indexed_files, missing_files })
     }
 
     /// Discover files to index based on configuration
@@ -367,44 +780,61 @@ impl IndexingService {
 
     /// Check if a file should be included based on filter configuration
     fn should_include_file(&self, path: &Path, config: &forge_domain::FilterConfig) -> bool {
-        // Check file size
+        self.exclusion_reason(path, config).is_none()
+    }
+
+    /// Why `should_include_file` would reject `path`, or `None` if it passes every filter --
+    /// surfaced so a caller can debug why a file is missing from the index instead of it being
+    /// silently absent. `.gitignore` files found during the walk itself are honored by
+    /// `forge_walker::Walker` before `discover_files` ever sees these paths; this only judges
+    /// `FilterConfig`'s own size/extension/pattern rules.
+    pub fn exclusion_reason(
+        &self,
+        path: &Path,
+        config: &forge_domain::FilterConfig,
+    ) -> Option<ExclusionReason> {
         if let Ok(metadata) = std::fs::metadata(path) {
             let size = metadata.len();
             if size < config.min_file_size_bytes || size > config.max_file_size_bytes {
-                return false;
+                return Some(ExclusionReason::FileSize);
             }
         }
 
-        // Check extension
         if let Some(extension) = path.extension().and_then(|ext| ext.to_str())
             && !config
                 .supported_extensions
                 .contains(&extension.to_lowercase())
             {
-                return false;
+                return Some(ExclusionReason::UnsupportedExtension);
             }
 
-        // Check ignore patterns
-        let path_str = path.to_string_lossy();
-        for pattern in &config.ignore_patterns {
-            if glob_match(pattern, &path_str) {
-                return false;
+        // Normalize to `/` so patterns and directory names match the same way on every platform.
+        let path_str = path.to_string_lossy().replace('\\', "/");
+
+        for ignore_dir in &config.ignore_directories {
+            if path_str.split('/').any(|segment| segment == ignore_dir.as_str()) {
+                return Some(ExclusionReason::IgnoredDirectory(ignore_dir.clone()));
             }
         }
 
-        // Check ignore directories
-        for ignore_dir in &config.ignore_directories {
-            if path_str.contains(&format!("/{ignore_dir}/"))
-                || path_str.contains(&format!("\\{ignore_dir}\\"))
-            {
-                return false;
+        // Later patterns win, matching gitignore semantics: a `!pattern` re-includes a path an
+        // earlier, broader pattern excluded.
+        let mut matched: Option<&str> = None;
+        for pattern in &config.ignore_patterns {
+            if let Some(negated) = pattern.strip_prefix('!') {
+                if glob_match(negated, &path_str) {
+                    matched = None;
+                }
+            } else if glob_match(pattern, &path_str) {
+                matched = Some(pattern);
             }
         }
 
-        true
+        matched.map(|pattern| ExclusionReason::IgnorePattern(pattern.to_string()))
     }
 
     /// Send progress update if callback is configured
+    #[allow(clippy::too_many_arguments)]
     async fn send_progress(
         &self,
         request_id: &str,
@@ -414,6 +844,8 @@ impl IndexingService {
         files_processed: usize,
         total_files: usize,
         chunks_created: usize,
+        throughput_fps: f32,
+        estimated_remaining_seconds: Option<u64>,
     ) {
         if let Some(sender) = &self.progress_sender {
             let progress = IndexingProgress {
@@ -424,8 +856,8 @@ impl IndexingService {
                 files_processed,
                 total_files,
                 chunks_created,
-                estimated_remaining_seconds: None, // Could be calculated based on throughput
-                throughput_fps: 0.0,               // Could be calculated
+                estimated_remaining_seconds,
+                throughput_fps,
             };
 
             if let Err(e) = sender.send(progress) {
@@ -435,38 +867,126 @@ impl IndexingService {
     }
 }
 
-/// Simple glob pattern matching (basic implementation)
+/// Result of `IndexingService::repair`.
+#[derive(Debug)]
+pub enum RepairOutcome {
+    /// The embedder's current `embedding_dimension()` doesn't match what's stored in the
+    /// collection; repair left the collection untouched and a caller should fall back to an
+    /// `index_codebase` run with `reset_existing: true` instead.
+    DimensionMismatch { expected: usize, found: usize },
+    /// The vector store reported `IndexStatus::Error` for the collection; repair left it
+    /// untouched rather than layering an incremental reindex on top of a broken index.
+    IndexErrored(String),
+    /// Dimensions matched and the index wasn't errored; `response` is the incremental reindex
+    /// that ran to catch the collection up.
+    Repaired(Box<IndexingResponse>),
+}
+
+/// Why `IndexingService::exclusion_reason` rejected a path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExclusionReason {
+    /// Outside `min_file_size_bytes`..`max_file_size_bytes`.
+    FileSize,
+    /// Extension not in `supported_extensions`.
+    UnsupportedExtension,
+    /// Matched an `ignore_patterns` glob; holds the pattern that matched.
+    IgnorePattern(String),
+    /// A path segment matched an `ignore_directories` entry; holds that entry.
+    IgnoredDirectory(String),
+}
+
+/// Gitignore-style glob matching against a `/`-separated path: `pattern` is anchored to the start
+/// of `text` if it contains a `/` (other than a trailing one), and otherwise may match any
+/// segment. Supports `**` (any number of segments, including none), `*` and `?` within a segment,
+/// and `[...]` character classes.
 fn glob_match(pattern: &str, text: &str) -> bool {
-    // Very basic glob matching - just check for wildcards
-    if pattern.contains("**") {
-        // Recursive wildcard - check if any part matches
-        let parts: Vec<&str> = pattern.split("**").collect();
-        if parts.len() == 2 {
-            let prefix = parts[0].trim_end_matches('/');
-            let suffix = parts[1].trim_start_matches('/');
-            return text.starts_with(prefix) && text.ends_with(suffix);
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let pattern_segments: Vec<&str> = pattern.trim_end_matches('/').split('/').collect();
+    let text_segments: Vec<&str> = text.split('/').collect();
+
+    if anchored {
+        return match_segments(&pattern_segments, &text_segments);
+    }
+
+    // Unanchored: the pattern may match starting at any segment of the text.
+    for start in 0..text_segments.len() {
+        if match_segments(&pattern_segments, &text_segments[start..]) {
+            return true;
         }
-    } else if pattern.contains('*') {
-        // Simple wildcard matching
-        let parts: Vec<&str> = pattern.split('*').collect();
-        let mut pos = 0;
-        for (i, part) in parts.iter().enumerate() {
-            if i == 0 {
-                if !text[pos..].starts_with(part) {
-                    return false;
+    }
+    false
+}
+
+/// Match a sequence of pattern segments (some of which may be `**`) against a sequence of text
+/// segments.
+fn match_segments(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(&"**") => {
+            // `**` matches zero or more whole segments.
+            (0..=text.len()).any(|skip| match_segments(&pattern[1..], &text[skip..]))
+        }
+        Some(&head) => match text.first() {
+            Some(&first) => match_segment(head, first) && match_segments(&pattern[1..], &text[1..]),
+            None => false,
+        },
+    }
+}
+
+/// Match a single path segment against a single pattern segment containing `*`, `?`, and
+/// `[...]` character classes (no `/`).
+fn match_segment(pattern: &str, text: &str) -> bool {
+    fn go(pattern: &[char], text: &[char]) -> bool {
+        match pattern.first() {
+            None => text.is_empty(),
+            Some('*') => {
+                (0..=text.len()).any(|skip| go(&pattern[1..], &text[skip..]))
+            }
+            Some('?') => !text.is_empty() && go(&pattern[1..], &text[1..]),
+            Some('[') => {
+                let Some(class_end) = pattern.iter().position(|&c| c == ']').filter(|&i| i > 0) else {
+                    // No closing bracket: treat '[' as a literal.
+                    return !text.is_empty() && text[0] == '[' && go(&pattern[1..], &text[1..]);
+                };
+                match text.first() {
+                    Some(&c) if char_class_matches(&pattern[1..class_end], c) => {
+                        go(&pattern[class_end + 1..], &text[1..])
+                    }
+                    _ => false,
                 }
-                pos += part.len();
-            } else if i == parts.len() - 1 {
-                return text[pos..].ends_with(part);
-            } else if let Some(found) = text[pos..].find(part) {
-                pos += found + part.len();
-            } else {
-                return false;
             }
+            Some(&p) => !text.is_empty() && text[0] == p && go(&pattern[1..], &text[1..]),
+        }
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    go(&pattern, &text)
+}
+
+/// Test `c` against a `[...]` character class body (without the brackets), honoring `!`/`^`
+/// negation and `a-z`-style ranges.
+fn char_class_matches(class: &[char], c: char) -> bool {
+    let (negate, class) = match class.first() {
+        Some('!') | Some('^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+
+    let mut matched = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            if c >= class[i] && c <= class[i + 2] {
+                matched = true;
+            }
+            i += 3;
+        } else {
+            if class[i] == c {
+                matched = true;
+            }
+            i += 1;
         }
-        return true;
     }
 
-    // Exact match
-    text.contains(pattern)
+    matched != negate
 }
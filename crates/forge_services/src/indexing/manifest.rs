@@ -0,0 +1,82 @@
+//! Per-file content manifest backing incremental re-indexing: `IndexingService` consults it to
+//! skip files whose content hasn't changed since the last run against a collection, and to find
+//! the chunk ids to delete for files that changed or disappeared.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// What was indexed for one file the last time a run touched it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileManifestEntry {
+    pub content_hash: String,
+    pub size: u64,
+    pub chunk_ids: Vec<String>,
+}
+
+/// Per-collection manifest, persisted as JSON alongside the collection so a later run can diff a
+/// re-scan against what was indexed last time instead of reprocessing every file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct IndexManifest {
+    files: HashMap<String, FileManifestEntry>,
+}
+
+impl IndexManifest {
+    /// Load the manifest at `path`, defaulting to empty if it doesn't exist yet or fails to
+    /// parse (matching `embedding_cache`'s load-or-default behavior for this kind of sidecar
+    /// file).
+    pub async fn load(path: &Path) -> Self {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub async fn persist(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let bytes = serde_json::to_vec(self)?;
+        tokio::fs::write(path, bytes).await?;
+        Ok(())
+    }
+
+    pub fn entry(&self, path: &str) -> Option<&FileManifestEntry> {
+        self.files.get(path)
+    }
+
+    pub fn record(&mut self, path: String, entry: FileManifestEntry) {
+        self.files.insert(path, entry);
+    }
+
+    /// Paths recorded in the manifest that are absent from `current_paths` -- files indexed last
+    /// run that no longer exist (or were excluded by filters) this run.
+    pub fn vanished_paths(&self, current_paths: &HashSet<String>) -> Vec<String> {
+        self.files
+            .keys()
+            .filter(|path| !current_paths.contains(path.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    /// Remove and return the entry for `path`, e.g. after deleting its stale vectors.
+    pub fn remove(&mut self, path: &str) -> Option<FileManifestEntry> {
+        self.files.remove(path)
+    }
+}
+
+/// Hash the raw bytes of a file's content (not its chunks -- this is a whole-file change
+/// detector, run before any chunking happens).
+pub fn hash_content(content: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Where the manifest for `collection` lives, rooted under the codebase being indexed.
+pub fn manifest_path(root_path: &Path, collection: &str) -> PathBuf {
+    root_path.join(".forge").join(format!("index_manifest_{collection}.json"))
+}
@@ -1,18 +1,34 @@
 //! Indexing services and utilities
 
+mod embedder_registry;
 mod indexing_service;
+mod manifest;
 mod mock_embedder;
 mod simple_chunker;
+mod task_store;
+mod tree_sitter_chunker;
 
-use anyhow::Result;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
 use forge_domain::{IndexingConfig, Chunker, Embedder};
-pub use indexing_service::IndexingService;
+pub use embedder_registry::EmbedderRegistry;
+use embedder_registry::SharedEmbedder;
+pub use indexing_service::{ExclusionReason, IndexingService, RepairOutcome};
 pub use mock_embedder::MockEmbedder;
 pub use simple_chunker::SimpleChunker;
+pub use task_store::{IndexingTask, IndexingTaskStore, TaskStatus};
+pub use tree_sitter_chunker::TreeSitterChunker;
+
+use crate::search::{KeywordIndex, SharedKeywordIndex};
 
 // Re-export utility functions
 pub use utils::{supports_semantic_chunking, recommended_chunk_size};
 
+/// Default on-disk location for the BM25 keyword index populated during indexing.
+const DEFAULT_KEYWORD_INDEX_PATH: &str = ".forge/keyword_index.json";
+
 /// Factory for creating indexing service instances
 pub struct IndexingServiceFactory;
 
@@ -20,11 +36,12 @@ impl IndexingServiceFactory {
     /// Create an indexing service with default implementations
     pub async fn create_default(_config: IndexingConfig) -> Result<IndexingService> {
         // Create default implementations
-        let chunker = Box::new(SimpleChunker::new());
+        let chunker = Box::new(TreeSitterChunker::new());
         let embedder = Box::new(MockEmbedder::new(384));
+        let keyword_index = Arc::new(KeywordIndex::new(PathBuf::from(DEFAULT_KEYWORD_INDEX_PATH)).await);
 
         // Create the service
-        IndexingService::new(_config, chunker, embedder).await
+        IndexingService::new(_config, chunker, embedder, keyword_index).await
     }
 
     /// Create an indexing service with custom implementations
@@ -32,8 +49,27 @@ impl IndexingServiceFactory {
         config: IndexingConfig,
         chunker: Box<dyn Chunker>,
         embedder: Box<dyn Embedder>,
+        keyword_index: SharedKeywordIndex,
     ) -> Result<IndexingService> {
-        IndexingService::new(config, chunker, embedder).await
+        IndexingService::new(config, chunker, embedder, keyword_index).await
+    }
+
+    /// Create an indexing service against a named embedder resolved from `registry` with
+    /// `EmbedderRegistry::get`. The embedder's dimension is not compared against an existing
+    /// collection's; `EmbedderRegistry::get_for_dimension` is the lookup that makes that check.
+    pub async fn create_with_registry(
+        config: IndexingConfig,
+        registry: &EmbedderRegistry,
+        embedder_name: &str,
+        chunker: Box<dyn Chunker>,
+    ) -> Result<IndexingService> {
+        let embedder = registry
+            .get(embedder_name)
+            .await
+            .with_context(|| format!("Failed to resolve embedder '{embedder_name}' from the registry"))?;
+        let keyword_index = Arc::new(KeywordIndex::new(PathBuf::from(DEFAULT_KEYWORD_INDEX_PATH)).await);
+
+        IndexingService::new(config, chunker, Box::new(SharedEmbedder(embedder)), keyword_index).await
     }
 }
 
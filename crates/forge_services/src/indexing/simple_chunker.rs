@@ -141,6 +141,7 @@ mod tests {
             overlap_size: 10,
             strategy: ChunkingStrategy::SizeBased,
             semantic_languages: vec![],
+            prompt_template: Default::default(),
         };
 
         let actual = chunker
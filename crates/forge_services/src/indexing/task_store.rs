@@ -0,0 +1,145 @@
+//! Background task store for indexing runs: submitting an `IndexingRequest` returns a task id
+//! immediately, the run proceeds on a spawned task, and its lifecycle and latest progress are
+//! queryable by id instead of requiring the caller to await the whole run.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use forge_domain::{IndexingProgress, IndexingRequest, IndexingStatistics};
+use tokio::sync::{RwLock, mpsc};
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+use uuid::Uuid;
+
+use super::IndexingService;
+
+/// Lifecycle of a submitted indexing run.
+#[derive(Debug, Clone)]
+pub enum TaskStatus {
+    /// Submitted but not yet picked up by the background worker.
+    Enqueued,
+    /// Currently indexing.
+    Processing,
+    /// Finished; holds the run's final statistics.
+    Succeeded(IndexingStatistics),
+    /// Finished with an error; holds the error message.
+    Failed(String),
+    /// Stopped early by a cancellation request.
+    Cancelled,
+}
+
+/// Everything the store knows about one submitted run.
+#[derive(Debug, Clone)]
+pub struct IndexingTask {
+    pub task_id: String,
+    pub request_id: String,
+    pub status: TaskStatus,
+    /// Latest progress reported by the run, if any has arrived yet.
+    pub progress: Option<IndexingProgress>,
+    #[allow(dead_code)]
+    cancel_token: CancellationToken,
+}
+
+/// Store of indexing runs executing in the background. Submitting a request returns a task id
+/// immediately; the run itself executes on a spawned task and updates this store as it
+/// progresses, so a caller (e.g. a server front-end) can poll status rather than awaiting the
+/// whole operation inline.
+#[derive(Clone)]
+pub struct IndexingTaskStore {
+    tasks: Arc<RwLock<HashMap<String, IndexingTask>>>,
+}
+
+impl Default for IndexingTaskStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IndexingTaskStore {
+    pub fn new() -> Self {
+        Self { tasks: Arc::new(RwLock::new(HashMap::new())) }
+    }
+
+    /// Submit a request for background processing against `service`, returning the new task's
+    /// id immediately. `service` is cloned so the run gets its own progress channel -- cheap,
+    /// since every field behind `IndexingService` is an `Arc`.
+    pub async fn submit(&self, service: &IndexingService, request: IndexingRequest) -> String {
+        let task_id = Uuid::new_v4().to_string();
+        let request_id = request.request_id.clone();
+        let cancel_token = CancellationToken::new();
+
+        self.tasks.write().await.insert(
+            task_id.clone(),
+            IndexingTask {
+                task_id: task_id.clone(),
+                request_id,
+                status: TaskStatus::Enqueued,
+                progress: None,
+                cancel_token: cancel_token.clone(),
+            },
+        );
+
+        let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+        let mut service = service.clone();
+        service.set_progress_callback(progress_tx);
+
+        let tasks = self.tasks.clone();
+        let progress_task_id = task_id.clone();
+        tokio::spawn(async move {
+            while let Some(progress) = progress_rx.recv().await {
+                if let Some(task) = tasks.write().await.get_mut(&progress_task_id) {
+                    task.progress = Some(progress);
+                }
+            }
+        });
+
+        let tasks = self.tasks.clone();
+        let run_task_id = task_id.clone();
+        let run_cancel_token = cancel_token.clone();
+        tokio::spawn(async move {
+            if let Some(task) = tasks.write().await.get_mut(&run_task_id) {
+                task.status = TaskStatus::Processing;
+            }
+
+            let result = service.index_codebase_cancellable(request, run_cancel_token.clone()).await;
+
+            let status = match result {
+                Ok(_) if run_cancel_token.is_cancelled() => TaskStatus::Cancelled,
+                Ok(response) => TaskStatus::Succeeded(response.statistics),
+                Err(e) => {
+                    error!("Indexing task {} failed: {}", run_task_id, e);
+                    TaskStatus::Failed(e.to_string())
+                }
+            };
+
+            if let Some(task) = tasks.write().await.get_mut(&run_task_id) {
+                task.status = status;
+            }
+        });
+
+        info!("Submitted indexing task {}", task_id);
+        task_id
+    }
+
+    /// Fetch a task's current status and latest progress.
+    pub async fn get_task(&self, task_id: &str) -> Option<IndexingTask> {
+        self.tasks.read().await.get(task_id).cloned()
+    }
+
+    /// List every task the store knows about, newest submissions included.
+    pub async fn list_tasks(&self) -> Vec<IndexingTask> {
+        self.tasks.read().await.values().cloned().collect()
+    }
+
+    /// Request cancellation of a running (or not-yet-started) task. Cancellation is cooperative
+    /// -- the task's current file finishes, but no further files are dispatched.
+    pub async fn cancel(&self, task_id: &str) -> Result<()> {
+        let tasks = self.tasks.read().await;
+        let task = tasks
+            .get(task_id)
+            .ok_or_else(|| anyhow!("No indexing task with id {task_id}"))?;
+        task.cancel_token.cancel();
+        Ok(())
+    }
+}
@@ -0,0 +1,446 @@
+//! AST-aware chunker implementation backed by tree-sitter grammars
+
+use std::path::Path;
+
+use anyhow::Result;
+use forge_domain::{ChunkingConfig, CodeChunk, Chunker, detect_language_from_extension};
+use tree_sitter::{Node, Parser};
+
+use crate::indexing::simple_chunker::SimpleChunker;
+
+/// Top-level node kinds that represent a semantic unit worth its own chunk, per language.
+fn semantic_node_kinds(language: &str) -> &'static [&'static str] {
+    match language {
+        "rust" => &[
+            "function_item",
+            "impl_item",
+            "mod_item",
+            "struct_item",
+            "enum_item",
+            "trait_item",
+        ],
+        "python" => &["function_definition", "class_definition", "decorated_definition"],
+        "javascript" | "typescript" => &[
+            "function_declaration",
+            "class_declaration",
+            "method_definition",
+            "interface_declaration",
+            "export_statement",
+        ],
+        "go" => &["function_declaration", "method_declaration", "type_declaration"],
+        "java" => &[
+            "class_declaration",
+            "interface_declaration",
+            "enum_declaration",
+            "method_declaration",
+        ],
+        "c" | "cpp" => &["function_definition", "struct_specifier", "class_specifier", "enum_specifier"],
+        _ => &[],
+    }
+}
+
+/// Build a tree-sitter parser for `language`, returning `None` when no grammar is registered.
+fn parser_for(language: &str) -> Option<Parser> {
+    let mut parser = Parser::new();
+    let set_ok = match language {
+        "rust" => parser.set_language(&tree_sitter_rust::LANGUAGE.into()).is_ok(),
+        "python" => parser.set_language(&tree_sitter_python::LANGUAGE.into()).is_ok(),
+        "javascript" => parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+            .is_ok(),
+        "typescript" => parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+            .is_ok(),
+        "go" => parser.set_language(&tree_sitter_go::LANGUAGE.into()).is_ok(),
+        "java" => parser.set_language(&tree_sitter_java::LANGUAGE.into()).is_ok(),
+        "c" | "cpp" => parser.set_language(&tree_sitter_cpp::LANGUAGE.into()).is_ok(),
+        _ => false,
+    };
+    set_ok.then_some(parser)
+}
+
+/// A raw semantic unit extracted from the AST before merge/split is applied. Keeps its own
+/// `node` around so an oversized unit can be recursed into (e.g. splitting an `impl` block into
+/// its individual methods) -- `Unit` and its borrowed `node` never outlive the synchronous
+/// `plan_units` call tree they're built in, so tree-sitter's non-`Send` `Node` never has to cross
+/// an `await` point.
+struct Unit<'a> {
+    start_byte: usize,
+    end_byte: usize,
+    start_line: usize,
+    end_line: usize,
+    symbol: Option<String>,
+    node: Node<'a>,
+}
+
+/// One piece of a file's chunk plan, produced by `plan_units`.
+enum PlanItem {
+    /// One or more adjacent small sibling nodes, already coalesced into a single chunk's range.
+    Merged { start_byte: usize, end_byte: usize, start_line: usize, end_line: usize, symbol: Option<String> },
+    /// A declaration that stayed oversized with no matching child node kinds to recurse into;
+    /// the caller falls back to line-based splitting for this byte range.
+    OversizedLeaf { start_byte: usize, end_byte: usize, start_line: usize, symbol: Option<String> },
+}
+
+/// Chunker that aligns chunks to AST structure (function, method, class/impl block, module)
+/// instead of arbitrary line windows, falling back to [`SimpleChunker`]'s line-window splitter
+/// for unsupported languages and for units that alone exceed `max_chunk_size`.
+pub struct TreeSitterChunker {
+    supported_languages: Vec<String>,
+    fallback: SimpleChunker,
+}
+
+impl Default for TreeSitterChunker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TreeSitterChunker {
+    pub fn new() -> Self {
+        Self {
+            supported_languages: vec![
+                "rust".to_string(),
+                "python".to_string(),
+                "javascript".to_string(),
+                "typescript".to_string(),
+                "go".to_string(),
+                "java".to_string(),
+                "c".to_string(),
+                "cpp".to_string(),
+            ],
+            fallback: SimpleChunker::new(),
+        }
+    }
+
+    /// Extract a human-readable symbol name for `node` by scanning its immediate children for an
+    /// identifier-like token. Mirrors the shallow, per-language-agnostic scan already used for
+    /// symbol extraction elsewhere in this codebase.
+    fn extract_symbol_name(node: Node, content: &str) -> Option<String> {
+        let mut cursor = node.walk();
+        if cursor.goto_first_child() {
+            loop {
+                let child = cursor.node();
+                if matches!(
+                    child.kind(),
+                    "identifier" | "type_identifier" | "field_identifier" | "property_identifier"
+                ) {
+                    return content.get(child.byte_range()).map(|s| s.to_string());
+                }
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+        None
+    }
+
+    /// First line of `text`, used as a signature prefix when a unit must be split further.
+    fn signature_line(text: &str) -> &str {
+        text.lines().next().unwrap_or(text).trim()
+    }
+
+    fn line_of_byte(content: &str, byte: usize) -> usize {
+        content[..byte.min(content.len())].lines().count()
+    }
+
+    /// Walk `root`'s direct children and collect one [`Unit`] per recognized semantic node kind
+    /// for `language`.
+    fn collect_units<'a>(root: Node<'a>, content: &str, language: &str) -> Vec<Unit<'a>> {
+        let kinds = semantic_node_kinds(language);
+        let mut units = Vec::new();
+        let mut cursor = root.walk();
+
+        if cursor.goto_first_child() {
+            loop {
+                let node = cursor.node();
+                if kinds.contains(&node.kind()) {
+                    let symbol = Self::extract_symbol_name(node, content);
+                    units.push(Unit {
+                        start_byte: node.start_byte(),
+                        end_byte: node.end_byte(),
+                        start_line: Self::line_of_byte(content, node.start_byte()) + 1,
+                        end_line: Self::line_of_byte(content, node.end_byte()),
+                        symbol,
+                        node,
+                    });
+                }
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+        }
+
+        units
+    }
+
+    /// Build a chunk plan for `root`'s direct children: greedily merge adjacent small units, and
+    /// for any unit exceeding `max_chunk_size` recurse into its own children (e.g. splitting an
+    /// `impl` block into its individual methods) before giving up and emitting an
+    /// [`PlanItem::OversizedLeaf`] for line-based fallback splitting. Stays entirely synchronous
+    /// so the non-`Send` [`Node`] never has to cross an `.await` point.
+    fn plan_units(root: Node, content: &str, language: &str, config: &ChunkingConfig) -> Vec<PlanItem> {
+        let units = Self::collect_units(root, content, language);
+        let mut plan = Vec::new();
+        let mut pending: Vec<&Unit> = Vec::new();
+
+        let flush_pending = |pending: &mut Vec<&Unit>, plan: &mut Vec<PlanItem>| {
+            if pending.is_empty() {
+                return;
+            }
+            let first = pending[0];
+            let last = pending[pending.len() - 1];
+            let symbol = pending.iter().find_map(|u| u.symbol.clone());
+            plan.push(PlanItem::Merged {
+                start_byte: first.start_byte,
+                end_byte: last.end_byte,
+                start_line: first.start_line,
+                end_line: last.end_line,
+                symbol,
+            });
+            pending.clear();
+        };
+
+        let mut pending_size = 0usize;
+        for unit in &units {
+            let unit_size = unit.end_byte - unit.start_byte;
+
+            if unit_size > config.max_chunk_size {
+                flush_pending(&mut pending, &mut plan);
+                pending_size = 0;
+
+                let nested = Self::plan_units(
+                unit.node.child_by_field_name("body").unwrap_or(unit.node),
+                content,
+                language,
+                config,
+            );
+                if nested.is_empty() {
+                    plan.push(PlanItem::OversizedLeaf {
+                        start_byte: unit.start_byte,
+                        end_byte: unit.end_byte,
+                        start_line: unit.start_line,
+                        symbol: unit.symbol.clone(),
+                    });
+                } else {
+                    plan.extend(nested);
+                }
+                continue;
+            }
+
+            pending.push(unit);
+            pending_size += unit_size;
+
+            if pending_size >= config.min_chunk_size {
+                flush_pending(&mut pending, &mut plan);
+                pending_size = 0;
+            }
+        }
+        flush_pending(&mut pending, &mut plan);
+
+        plan
+    }
+
+    /// Split an oversized leaf's text with the fallback line-window splitter, prepending its
+    /// signature line to every resulting sub-chunk so context survives the split.
+    #[allow(clippy::too_many_arguments)]
+    async fn split_oversized_leaf(
+        &self,
+        path: &str,
+        content: &str,
+        language: &str,
+        revision: &str,
+        config: &ChunkingConfig,
+        start_byte: usize,
+        end_byte: usize,
+        start_line: usize,
+        symbol: Option<String>,
+        chunk_id: &mut usize,
+    ) -> Result<Vec<CodeChunk>> {
+        let unit_text = &content[start_byte..end_byte];
+        let signature = Self::signature_line(unit_text);
+
+        let sub_chunks = self
+            .fallback
+            .chunk_file(path, unit_text, language, revision, config)
+            .await?;
+
+        Ok(sub_chunks
+            .into_iter()
+            .map(|mut sub| {
+                let body = if sub.content.starts_with(signature) {
+                    sub.content.clone()
+                } else {
+                    format!("{signature}\n{}", sub.content)
+                };
+                sub.id = format!("{path}:{chunk_id}");
+                *chunk_id += 1;
+                sub.content = body.clone();
+                sub.size = body.len();
+                sub.start_line = start_line + sub.start_line - 1;
+                sub.end_line = start_line + sub.end_line - 1;
+                if let Some(symbol) = &symbol {
+                    sub.symbol = Some(symbol.clone());
+                }
+                sub
+            })
+            .collect())
+    }
+}
+
+#[async_trait::async_trait]
+impl Chunker for TreeSitterChunker {
+    async fn chunk_file(
+        &self,
+        path: &str,
+        content: &str,
+        language: &str,
+        revision: &str,
+        config: &ChunkingConfig,
+    ) -> Result<Vec<CodeChunk>> {
+        if content.is_empty() || !config.semantic_languages.iter().any(|l| l == language) {
+            return self.fallback.chunk_file(path, content, language, revision, config).await;
+        }
+
+        let Some(mut parser) = parser_for(language) else {
+            return self.fallback.chunk_file(path, content, language, revision, config).await;
+        };
+
+        let Some(tree) = parser.parse(content, None) else {
+            return self.fallback.chunk_file(path, content, language, revision, config).await;
+        };
+
+        let plan = Self::plan_units(tree.root_node(), content, language, config);
+        if plan.is_empty() {
+            return self.fallback.chunk_file(path, content, language, revision, config).await;
+        }
+
+        let mut chunks = Vec::new();
+        let mut chunk_id = 0usize;
+
+        for item in plan {
+            match item {
+                PlanItem::Merged { start_byte, end_byte, start_line, end_line, symbol } => {
+                    let merged_content = content[start_byte..end_byte].to_string();
+                    let mut chunk = CodeChunk::new(
+                        format!("{path}:{chunk_id}"),
+                        path.to_string(),
+                        language.to_string(),
+                        revision.to_string(),
+                        merged_content,
+                        start_line,
+                        end_line,
+                    );
+                    if let Some(symbol) = symbol {
+                        chunk = chunk.symbol(symbol);
+                    }
+                    chunks.push(chunk);
+                    chunk_id += 1;
+                }
+                PlanItem::OversizedLeaf { start_byte, end_byte, start_line, symbol } => {
+                    let split = self
+                        .split_oversized_leaf(
+                            path, content, language, revision, config, start_byte, end_byte, start_line, symbol,
+                            &mut chunk_id,
+                        )
+                        .await?;
+                    chunks.extend(split);
+                }
+            }
+        }
+
+        Ok(chunks)
+    }
+
+    fn supported_languages(&self) -> &[String] {
+        &self.supported_languages
+    }
+
+    fn detect_language(&self, path: &Path) -> Option<String> {
+        detect_language_from_extension(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use forge_domain::ChunkingStrategy;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn config() -> ChunkingConfig {
+        ChunkingConfig {
+            max_chunk_size: 1000,
+            min_chunk_size: 10,
+            overlap_size: 0,
+            strategy: ChunkingStrategy::Semantic,
+            semantic_languages: vec!["rust".to_string()],
+            prompt_template: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chunks_align_to_functions() {
+        let chunker = TreeSitterChunker::new();
+        let content = "fn one() {\n    1\n}\n\nfn two() {\n    2\n}\n";
+
+        let chunks = chunker
+            .chunk_file("test.rs", content, "rust", "rev1", &config())
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty());
+        assert!(chunks.iter().any(|c| c.symbol.as_deref() == Some("one")));
+        assert!(chunks.iter().any(|c| c.symbol.as_deref() == Some("two")));
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_for_unsupported_language() {
+        let chunker = TreeSitterChunker::new();
+        let content = "line 1\nline 2\nline 3";
+        let mut cfg = config();
+        cfg.semantic_languages = vec![];
+
+        let chunks = chunker
+            .chunk_file("test.txt", content, "text", "rev1", &cfg)
+            .await
+            .unwrap();
+
+        assert!(!chunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_small_functions_are_merged() {
+        let chunker = TreeSitterChunker::new();
+        let content = "fn a() {}\nfn b() {}\nfn c() {}\n";
+        let mut cfg = config();
+        cfg.min_chunk_size = 25;
+
+        let chunks = chunker
+            .chunk_file("test.rs", content, "rust", "rev1", &cfg)
+            .await
+            .unwrap();
+
+        assert!(chunks.len() < 3, "expected merging to reduce chunk count, got {}", chunks.len());
+    }
+
+    #[tokio::test]
+    async fn test_oversized_impl_block_recurses_into_methods() {
+        let chunker = TreeSitterChunker::new();
+        let method_body = "x".repeat(40);
+        let content = format!(
+            "impl Widget {{\n    fn one() {{\n        \"{method_body}\";\n    }}\n\n    fn two() {{\n        \"{method_body}\";\n    }}\n}}\n"
+        );
+        let mut cfg = config();
+        cfg.max_chunk_size = 60;
+        cfg.min_chunk_size = 1;
+
+        let chunks = chunker
+            .chunk_file("test.rs", &content, "rust", "rev1", &cfg)
+            .await
+            .unwrap();
+
+        assert!(chunks.iter().any(|c| c.symbol.as_deref() == Some("one")));
+        assert!(chunks.iter().any(|c| c.symbol.as_deref() == Some("two")));
+    }
+}
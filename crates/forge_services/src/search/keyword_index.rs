@@ -0,0 +1,524 @@
+//! On-disk inverted index providing BM25-ranked lexical retrieval for `SearchMode::Keyword`.
+//!
+//! Persisted as a small JSON store (mirroring the content-hash embedding cache) so the lexical
+//! side of hybrid search survives process restarts instead of only living for one process's
+//! lifetime.
+//!
+//! Replaces the earlier approach of embedding a zero vector and linear-scanning the whole vector
+//! store to rescore every chunk: `search` below costs proportional to the number of query terms
+//! times their posting-list sizes, not the size of the collection.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use forge_domain::{CodeChunk, SearchFilters};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use super::symbol_matcher::fuzzy_symbol_score;
+
+/// BM25 term-frequency saturation constant: higher rewards repeated term occurrences more before
+/// the score plateaus.
+const BM25_K1: f32 = 1.2;
+/// BM25 document-length normalization constant: 0 disables length normalization entirely, 1
+/// fully normalizes to the average document length.
+const BM25_B: f32 = 0.75;
+
+/// A vocabulary term at or above this document frequency is considered "known" and is left
+/// alone by spelling correction.
+const MIN_KNOWN_TERM_FREQUENCY: usize = 2;
+/// Maximum Damerau-Levenshtein distance accepted for a did-you-mean rewrite.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+/// Only the top-N vocabulary terms by shared trigram count are edit-distance checked, since
+/// scanning the full vocabulary at that cost would not scale.
+const TRIGRAM_CANDIDATE_LIMIT: usize = 20;
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+        .map(|t| stem(&t.to_lowercase()))
+}
+
+/// Minimal suffix-stripping stemmer so "indexes"/"indexing"/"indexed" fold onto a shared "index"
+/// term; not a full Porter stemmer, just enough to reduce the most common English inflections.
+fn stem(term: &str) -> String {
+    for suffix in ["ing", "edly", "ed", "es", "s"] {
+        if term.len() > suffix.len() + 2 && term.ends_with(suffix) {
+            return term[..term.len() - suffix.len()].to_string();
+        }
+    }
+    term.to_string()
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InvertedIndexStore {
+    /// term -> (chunk id -> term frequency in that chunk)
+    postings: HashMap<String, HashMap<String, u32>>,
+    /// chunk id -> token length, used for BM25 length normalization
+    doc_lengths: HashMap<String, usize>,
+    documents: HashMap<String, CodeChunk>,
+}
+
+impl InvertedIndexStore {
+    fn average_doc_length(&self) -> f32 {
+        if self.doc_lengths.is_empty() {
+            return 0.0;
+        }
+        self.doc_lengths.values().sum::<usize>() as f32 / self.doc_lengths.len() as f32
+    }
+}
+
+/// BM25-scored inverted index over indexed chunk content/symbol. Complements the dense vector
+/// index for exact-identifier and rare-token queries that embeddings retrieve poorly.
+pub struct KeywordIndex {
+    store_path: PathBuf,
+    store: RwLock<InvertedIndexStore>,
+}
+
+/// Shared keyword index handle, cloneable across the indexing and search services.
+pub type SharedKeywordIndex = Arc<KeywordIndex>;
+
+impl KeywordIndex {
+    /// Load (or start) an index backed by `store_path`.
+    pub async fn new(store_path: impl Into<PathBuf>) -> Self {
+        let store_path = store_path.into();
+        let store = Self::load(&store_path).await;
+        Self { store_path, store: RwLock::new(store) }
+    }
+
+    async fn load(path: &Path) -> InvertedIndexStore {
+        match tokio::fs::read(path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_default(),
+            Err(_) => InvertedIndexStore::default(),
+        }
+    }
+
+    async fn persist(&self) -> Result<()> {
+        if let Some(parent) = self.store_path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let bytes = serde_json::to_vec(&*self.store.read().await)?;
+        tokio::fs::write(&self.store_path, bytes).await?;
+        Ok(())
+    }
+
+    /// Index (or re-index) a chunk under its id. Symbol terms are weighted higher than body
+    /// terms since an exact symbol match is a much stronger lexical signal than a body mention.
+    ///
+    /// Persists after every call, so prefer `add_chunks` when indexing more than one chunk at a
+    /// time -- each call here rewrites the entire on-disk store.
+    pub async fn add_chunk(&self, chunk: &CodeChunk) -> Result<()> {
+        self.insert_chunk(chunk).await;
+        if let Err(e) = self.persist().await {
+            warn!("KeywordIndex: failed to persist index to {:?}: {}", self.store_path, e);
+        }
+        Ok(())
+    }
+
+    /// Index (or re-index) `chunks`, persisting once at the end instead of once per chunk. This
+    /// is the entry point indexing a file's chunks should use: `add_chunk` in a loop rewrites the
+    /// entire on-disk store per chunk, making indexing a whole repo O(total chunks squared).
+    pub async fn add_chunks(&self, chunks: &[CodeChunk]) -> Result<()> {
+        for chunk in chunks {
+            self.insert_chunk(chunk).await;
+        }
+        if let Err(e) = self.persist().await {
+            warn!("KeywordIndex: failed to persist index to {:?}: {}", self.store_path, e);
+        }
+        Ok(())
+    }
+
+    async fn insert_chunk(&self, chunk: &CodeChunk) {
+        const SYMBOL_WEIGHT: u32 = 3;
+
+        let mut terms: HashMap<String, u32> = HashMap::new();
+        let mut token_count = 0usize;
+        for term in tokenize(&chunk.content) {
+            *terms.entry(term).or_insert(0) += 1;
+            token_count += 1;
+        }
+        if let Some(symbol) = &chunk.symbol {
+            for term in tokenize(symbol) {
+                *terms.entry(term).or_insert(0) += SYMBOL_WEIGHT;
+            }
+        }
+
+        let mut store = self.store.write().await;
+        store.documents.insert(chunk.id.clone(), chunk.clone());
+        store.doc_lengths.insert(chunk.id.clone(), token_count.max(1));
+        for (term, freq) in terms {
+            store.postings.entry(term).or_default().insert(chunk.id.clone(), freq);
+        }
+    }
+
+    /// Remove a previously indexed chunk, e.g. when a file is deleted or re-indexed.
+    pub async fn remove(&self, chunk_id: &str) -> Result<()> {
+        {
+            let mut store = self.store.write().await;
+            store.documents.remove(chunk_id);
+            store.doc_lengths.remove(chunk_id);
+            for postings in store.postings.values_mut() {
+                postings.remove(chunk_id);
+            }
+        }
+        self.persist().await
+    }
+
+    /// Rank indexed chunks by BM25 against `query`, applying `filters` as post-filters, and
+    /// return up to `limit` `(CodeChunk, score)` pairs in descending score order.
+    pub async fn search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        limit: usize,
+    ) -> Vec<(CodeChunk, f32)> {
+        let store = self.store.read().await;
+        let total_docs = store.documents.len();
+        if total_docs == 0 {
+            return Vec::new();
+        }
+        let avgdl = store.average_doc_length().max(1.0);
+
+        let mut scores: HashMap<String, f32> = HashMap::new();
+        for term in tokenize(query) {
+            let Some(postings) = store.postings.get(&term) else { continue };
+            let df = postings.len();
+            let idf = ((total_docs as f32 - df as f32 + 0.5) / (df as f32 + 0.5) + 1.0).ln();
+
+            for (chunk_id, &tf) in postings {
+                let doc_len = *store.doc_lengths.get(chunk_id).unwrap_or(&1) as f32;
+                let tf = tf as f32;
+                let denom = tf + BM25_K1 * (1.0 - BM25_B + BM25_B * doc_len / avgdl);
+                *scores.entry(chunk_id.clone()).or_insert(0.0) += idf * (tf * (BM25_K1 + 1.0)) / denom;
+            }
+        }
+
+        debug!("KeywordIndex: BM25 matched {} chunk(s) for query '{}'", scores.len(), query);
+
+        let mut ranked: Vec<(String, f32)> = scores
+            .into_iter()
+            .filter(|(id, _)| {
+                store
+                    .documents
+                    .get(id)
+                    .map(|chunk| Self::passes_filters(chunk, filters))
+                    .unwrap_or(false)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(limit);
+
+        ranked
+            .into_iter()
+            .filter_map(|(id, score)| store.documents.get(&id).map(|c| (c.clone(), score)))
+            .collect()
+    }
+
+    /// Fuzzy-match `query` against every indexed chunk's symbol name (IDE-style "type `gsq` to
+    /// find `get_search_query`"), applying `filters` and `similarity_threshold` as post-filters,
+    /// and return up to `limit` `(CodeChunk, score)` pairs in descending score order.
+    pub async fn fuzzy_symbol_search(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        similarity_threshold: f32,
+        limit: usize,
+    ) -> Vec<(CodeChunk, f32)> {
+        let store = self.store.read().await;
+
+        let mut scored: Vec<(CodeChunk, f32)> = store
+            .documents
+            .values()
+            .filter(|chunk| Self::passes_filters(chunk, filters))
+            .filter_map(|chunk| {
+                let symbol = chunk.symbol.as_deref()?;
+                let score = fuzzy_symbol_score(query, symbol)?;
+                (score >= similarity_threshold).then(|| (chunk.clone(), score))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        scored.truncate(limit);
+        scored
+    }
+
+    /// Rewrite `query` by replacing each out-of-vocabulary or rare term with its closest
+    /// vocabulary match (by shared trigrams, then Damerau-Levenshtein distance), returning
+    /// `None` once every term is already well represented in the index.
+    pub async fn suggest_query(&self, query: &str) -> Option<String> {
+        let store = self.store.read().await;
+        if store.postings.is_empty() {
+            return None;
+        }
+
+        let mut rewrote_any = false;
+        let rewritten: Vec<String> = query
+            .split_whitespace()
+            .map(|word| {
+                let term = stem(&word.to_lowercase());
+                let frequency = store.postings.get(&term).map(|p| p.len()).unwrap_or(0);
+                if frequency >= MIN_KNOWN_TERM_FREQUENCY {
+                    return word.to_string();
+                }
+
+                match Self::suggest_term(&store, &term) {
+                    Some(suggestion) if suggestion != term => {
+                        rewrote_any = true;
+                        suggestion
+                    }
+                    _ => word.to_string(),
+                }
+            })
+            .collect();
+
+        rewrote_any.then(|| rewritten.join(" "))
+    }
+
+    /// Find the best vocabulary replacement for `term`: the candidate sharing the most trigrams
+    /// whose Damerau-Levenshtein distance is within `MAX_SUGGESTION_DISTANCE`, breaking ties by
+    /// document (corpus) frequency.
+    fn suggest_term(store: &InvertedIndexStore, term: &str) -> Option<String> {
+        let term_trigrams = trigrams(term);
+
+        let mut by_shared_trigrams: Vec<(&String, usize)> = store
+            .postings
+            .keys()
+            .filter(|candidate| candidate.as_str() != term)
+            .map(|candidate| (candidate, trigrams(candidate).intersection(&term_trigrams).count()))
+            .filter(|(_, shared)| *shared > 0)
+            .collect();
+        by_shared_trigrams.sort_by(|a, b| b.1.cmp(&a.1));
+        by_shared_trigrams.truncate(TRIGRAM_CANDIDATE_LIMIT);
+
+        by_shared_trigrams
+            .into_iter()
+            .filter_map(|(candidate, _)| {
+                let distance = damerau_levenshtein(term, candidate);
+                (distance <= MAX_SUGGESTION_DISTANCE).then(|| {
+                    let frequency = store.postings.get(candidate).map(|p| p.len()).unwrap_or(0);
+                    (candidate.clone(), distance, frequency)
+                })
+            })
+            .min_by(|a, b| a.1.cmp(&b.1).then_with(|| b.2.cmp(&a.2)))
+            .map(|(candidate, _, _)| candidate)
+    }
+
+    fn passes_filters(chunk: &CodeChunk, filters: &SearchFilters) -> bool {
+        if !filters.languages.is_empty()
+            && !filters.languages.iter().any(|l| l.eq_ignore_ascii_case(&chunk.language))
+        {
+            return false;
+        }
+
+        if !filters.symbols.is_empty() {
+            let matches_symbol = chunk
+                .symbol
+                .as_deref()
+                .map(|symbol| filters.symbols.iter().any(|f| f == symbol))
+                .unwrap_or(false);
+            if !matches_symbol {
+                return false;
+            }
+        }
+
+        // `paths` entries go through the single-`*` `glob_match` below; `path_regex` is not
+        // checked by the keyword index.
+        if !filters.paths.is_empty() && !filters.paths.iter().any(|pattern| glob_match(pattern, &chunk.path)) {
+            return false;
+        }
+
+        true
+    }
+}
+
+/// Path matching for `SearchFilters::paths` in the keyword index. A pattern containing `*`
+/// matches any path that starts with the text before its first `*` and ends with the text after
+/// it (so that `*` may span `/`); a pattern without `*` must equal the path. Only the first `*`
+/// is a wildcard: `**`, `?`, `[...]` and a leading `!` are not interpreted and are compared as
+/// literal text.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => text.starts_with(prefix) && text.ends_with(suffix),
+        None => text == pattern,
+    }
+}
+
+/// Character trigrams for `term`, bracketed with a boundary marker so short words still yield at
+/// least one trigram and prefix/suffix differences are distinguished from interior ones.
+fn trigrams(term: &str) -> std::collections::HashSet<String> {
+    let padded: Vec<char> = format!("\u{1}{term}\u{1}").chars().collect();
+    if padded.len() < 3 {
+        return std::collections::HashSet::from([padded.into_iter().collect()]);
+    }
+    padded.windows(3).map(|w| w.iter().collect()).collect()
+}
+
+/// Restricted Damerau-Levenshtein edit distance (insertion, deletion, substitution, and
+/// transposition of adjacent characters).
+fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (len_a, len_b) = (a.len(), b.len());
+
+    let mut distance = vec![vec![0usize; len_b + 1]; len_a + 1];
+    for (i, row) in distance.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=len_b {
+        distance[0][j] = j;
+    }
+
+    for i in 1..=len_a {
+        for j in 1..=len_b {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            distance[i][j] = (distance[i - 1][j] + 1)
+                .min(distance[i][j - 1] + 1)
+                .min(distance[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance[i][j] = distance[i][j].min(distance[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    distance[len_a][len_b]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_store_path() -> PathBuf {
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("forge-services-keyword-index-test-{}-{id}.json", std::process::id()))
+    }
+
+    fn chunk(id: &str, path: &str, language: &str, content: &str, symbol: Option<&str>) -> CodeChunk {
+        let mut c = CodeChunk::new(
+            id.to_string(),
+            path.to_string(),
+            language.to_string(),
+            "rev".to_string(),
+            content.to_string(),
+            1,
+            1,
+        );
+        c.symbol = symbol.map(str::to_string);
+        c
+    }
+
+    #[tokio::test]
+    async fn finds_chunk_by_exact_identifier() {
+        let store_path = temp_store_path();
+        let index = KeywordIndex::new(&store_path).await;
+        index
+            .add_chunk(&chunk("a", "a.rs", "rust", "fn calculate_total(items: &[Item]) -> u64 {}", Some("calculate_total")))
+            .await
+            .unwrap();
+        index
+            .add_chunk(&chunk("b", "b.rs", "rust", "fn unrelated() {}", Some("unrelated")))
+            .await
+            .unwrap();
+
+        let results = index.search("calculate_total", &SearchFilters::default(), 10).await;
+
+        assert_eq!(results.first().map(|(c, _)| c.id.clone()), Some("a".to_string()));
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn removed_chunk_is_no_longer_returned() {
+        let store_path = temp_store_path();
+        let index = KeywordIndex::new(&store_path).await;
+        index.add_chunk(&chunk("a", "a.rs", "rust", "fn needle() {}", Some("needle"))).await.unwrap();
+        index.remove("a").await.unwrap();
+
+        let results = index.search("needle", &SearchFilters::default(), 10).await;
+
+        assert!(results.is_empty());
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn language_filter_excludes_non_matching_chunks() {
+        let store_path = temp_store_path();
+        let index = KeywordIndex::new(&store_path).await;
+        index.add_chunk(&chunk("a", "a.rs", "rust", "fn needle() {}", None)).await.unwrap();
+        index.add_chunk(&chunk("b", "b.py", "python", "def needle(): pass", None)).await.unwrap();
+
+        let filters = SearchFilters { languages: vec!["python".to_string()], ..Default::default() };
+        let results = index.search("needle", &filters, 10).await;
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.id, "b");
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn index_survives_reload_from_disk() {
+        let store_path = temp_store_path();
+
+        let index = KeywordIndex::new(&store_path).await;
+        index.add_chunk(&chunk("a", "a.rs", "rust", "fn needle() {}", Some("needle"))).await.unwrap();
+        drop(index);
+
+        let reloaded = KeywordIndex::new(&store_path).await;
+        let results = reloaded.search("needle", &SearchFilters::default(), 10).await;
+
+        assert_eq!(results.first().map(|(c, _)| c.id.clone()), Some("a".to_string()));
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn ranks_higher_term_frequency_first() {
+        let store_path = temp_store_path();
+        let index = KeywordIndex::new(&store_path).await;
+        index.add_chunk(&chunk("low", "low.rs", "rust", "fn parse() { parse_inner(); }", None)).await.unwrap();
+        index
+            .add_chunk(&chunk("high", "high.rs", "rust", "fn parse() { parse(); parse(); parse(); }", None))
+            .await
+            .unwrap();
+
+        let results = index.search("parse", &SearchFilters::default(), 10).await;
+
+        assert_eq!(results.first().map(|(c, _)| c.id.clone()), Some("high".to_string()));
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn suggests_rewrite_for_misspelled_term() {
+        let store_path = temp_store_path();
+        let index = KeywordIndex::new(&store_path).await;
+        index.add_chunk(&chunk("a", "a.rs", "rust", "fn calculate(x: u64) -> u64 { x }", None)).await.unwrap();
+        index.add_chunk(&chunk("b", "b.rs", "rust", "fn calculate(y: u64) -> u64 { y }", None)).await.unwrap();
+
+        let suggestion = index.suggest_query("calculat").await;
+
+        assert_eq!(suggestion, Some("calculate".to_string()));
+        let _ = std::fs::remove_file(&store_path);
+    }
+
+    #[tokio::test]
+    async fn leaves_well_known_terms_untouched() {
+        let store_path = temp_store_path();
+        let index = KeywordIndex::new(&store_path).await;
+        index.add_chunk(&chunk("a", "a.rs", "rust", "fn calculate(x: u64) -> u64 { x }", None)).await.unwrap();
+        index.add_chunk(&chunk("b", "b.rs", "rust", "fn calculate(y: u64) -> u64 { y }", None)).await.unwrap();
+
+        let suggestion = index.suggest_query("calculate").await;
+
+        assert_eq!(suggestion, None);
+        let _ = std::fs::remove_file(&store_path);
+    }
+}
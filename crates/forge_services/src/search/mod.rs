@@ -1,12 +1,20 @@
 //! Search services and utilities
 
+mod keyword_index;
 mod search_service;
+mod symbol_extractor;
+mod symbol_matcher;
 
+use std::path::PathBuf;
+use std::sync::Arc;
+
+pub use keyword_index::{KeywordIndex, SharedKeywordIndex};
 pub use search_service::SearchService;
+pub use symbol_extractor::{ExtractedSymbol, SymbolExtractor, SymbolKind};
 
 // Re-export utility functions
 pub use utils::{
-    quick_semantic_search, quick_keyword_search, quick_hybrid_search,
+    quick_semantic_search, quick_keyword_search, quick_hybrid_search, quick_hybrid_with_ratio,
     extract_function_names, calculate_relevance_score
 };
 
@@ -14,18 +22,35 @@ pub use utils::{
 pub struct SearchServiceFactory;
 
 impl SearchServiceFactory {
-    /// Create a search service with the given vector store and embedder
-    pub fn create(
+    /// Create a search service with the given vector store, embedder, and an on-disk BM25
+    /// keyword index backed by `keyword_index_path`. `prompt_template` must be the same template
+    /// the collection was indexed with, so query embeddings land in the same representation
+    /// space as the document embeddings they're compared against.
+    pub async fn create(
+        vector_store: crate::vector_store::SharedVectorStore,
+        embedder: Box<dyn crate::indexing::Embedder>,
+        keyword_index_path: impl Into<PathBuf>,
+        prompt_template: forge_domain::PromptTemplate,
+    ) -> SearchService {
+        let keyword_index = Arc::new(KeywordIndex::new(keyword_index_path).await);
+        SearchService::new(vector_store, embedder, keyword_index, prompt_template)
+    }
+
+    /// Create a search service that shares an already-constructed keyword index, e.g. the same
+    /// index populated by an `IndexingService` during indexing.
+    pub fn create_with_keyword_index(
         vector_store: crate::vector_store::SharedVectorStore,
         embedder: Box<dyn crate::indexing::Embedder>,
+        keyword_index: SharedKeywordIndex,
+        prompt_template: forge_domain::PromptTemplate,
     ) -> SearchService {
-        SearchService::new(vector_store, embedder)
+        SearchService::new(vector_store, embedder, keyword_index, prompt_template)
     }
 }
 
 /// Utility functions for search operations
 pub mod utils {
-    use forge_domain::{SearchMode, SearchOptions, SearchQuery, SortBy};
+    use forge_domain::{FusionMethod, SearchMode, SearchOptions, SearchQuery, SortBy};
 
     /// Create a quick semantic search query
     pub fn quick_semantic_search(query: impl Into<String>, limit: usize) -> SearchQuery {
@@ -44,6 +69,8 @@ pub mod utils {
                 sort_by: SortBy::Relevance,
                 highlight_matches: false,
                 max_content_length: Some(500),
+                time_budget_ms: None,
+                lazy_embed_threshold: None,
             },
         }
     }
@@ -65,6 +92,8 @@ pub mod utils {
                 sort_by: SortBy::Relevance,
                 highlight_matches: true,
                 max_content_length: Some(1000),
+                time_budget_ms: None,
+                lazy_embed_threshold: None,
             },
         }
     }
@@ -75,7 +104,11 @@ pub mod utils {
             query: query.into(),
             limit,
             similarity_threshold: 0.6,
-            mode: SearchMode::Hybrid { semantic_weight: 0.7, keyword_weight: 0.3 },
+            mode: SearchMode::Hybrid {
+                semantic_weight: 0.7,
+                keyword_weight: 0.3,
+                fusion: FusionMethod::default(),
+            },
             filters: Default::default(),
             options: SearchOptions {
                 include_content: true,
@@ -86,112 +119,32 @@ pub mod utils {
                 sort_by: SortBy::Relevance,
                 highlight_matches: true,
                 max_content_length: Some(800),
+                time_budget_ms: None,
+                lazy_embed_threshold: None,
             },
         }
     }
 
-    /// Extract function names from code content
-    pub fn extract_function_names(content: &str, language: &str) -> Vec<String> {
-        match language {
-            "rust" => extract_rust_functions(content),
-            "python" => extract_python_functions(content),
-            "javascript" | "typescript" => extract_js_functions(content),
-            "java" => extract_java_functions(content),
-            _ => Vec::new(),
-        }
-    }
-
-    /// Extract Rust function names
-    fn extract_rust_functions(content: &str) -> Vec<String> {
-        let mut functions = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if (trimmed.starts_with("fn ") || trimmed.starts_with("pub fn "))
-                && let Some(name) = extract_function_name_from_signature(trimmed, "fn ") {
-                    functions.push(name);
-                }
-        }
-        functions
-    }
-
-    /// Extract Python function names
-    fn extract_python_functions(content: &str) -> Vec<String> {
-        let mut functions = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.starts_with("def ")
-                && let Some(name) = extract_function_name_from_signature(trimmed, "def ") {
-                    functions.push(name);
-                }
-        }
-        functions
+    /// Create a hybrid search query from a single MeiliSearch-style `semantic_ratio` in
+    /// `[0.0, 1.0]` (`0.0` = pure keyword, `1.0` = pure semantic) rather than `quick_hybrid_search`'s
+    /// fixed 0.7/0.3 weight split.
+    pub fn quick_hybrid_with_ratio(query: impl Into<String>, semantic_ratio: f32, limit: usize) -> SearchQuery {
+        SearchQuery { limit, ..SearchQuery::hybrid_with_ratio(query, semantic_ratio) }
     }
 
-    /// Extract JavaScript/TypeScript function names
-    fn extract_js_functions(content: &str) -> Vec<String> {
-        let mut functions = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
-            if trimmed.starts_with("function ") {
-                if let Some(name) = extract_function_name_from_signature(trimmed, "function ") {
-                    functions.push(name);
-                }
-            } else if trimmed.contains(" = function") || trimmed.contains(" => ") {
-                // Arrow functions and function expressions
-                if let Some(start) = trimmed
-                    .find("const ")
-                    .or_else(|| trimmed.find("let ").or_else(|| trimmed.find("var ")))
-                    && let Some(end) = trimmed[start..].find(' ') {
-                        let name = &trimmed[start..start + end];
-                        if !name.is_empty() {
-                            functions.push(name.to_string());
-                        }
-                    }
-            }
-        }
-        functions
-    }
+    /// Extract function/method names from code content. Thin compatibility wrapper over
+    /// [`crate::search::SymbolExtractor`], filtering its structured symbols down to
+    /// function-like kinds; returns an empty list for languages without a registered grammar
+    /// (previously, callers fell back to brittle line-prefix scanning for those too).
+    pub fn extract_function_names(content: &str, language: &str) -> Vec<String> {
+        use super::{SymbolExtractor, SymbolKind};
 
-    /// Extract Java function names
-    fn extract_java_functions(content: &str) -> Vec<String> {
-        let mut functions = Vec::new();
-        for line in content.lines() {
-            let trimmed = line.trim();
-            // Look for method signatures (simplified)
-            if (trimmed.contains("public ")
-                || trimmed.contains("private ")
-                || trimmed.contains("protected "))
-                && trimmed.contains('(')
-                && trimmed.contains('{')
-            {
-                // Extract method name (very simplified)
-                if let Some(paren_pos) = trimmed.find('(') {
-                    let before_paren = &trimmed[..paren_pos];
-                    if let Some(last_space) = before_paren.rfind(' ') {
-                        let name = &before_paren[last_space + 1..];
-                        if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
-                        {
-                            functions.push(name.to_string());
-                        }
-                    }
-                }
-            }
-        }
-        functions
-    }
-
-    /// Extract function name from a function signature
-    fn extract_function_name_from_signature(signature: &str, prefix: &str) -> Option<String> {
-        if let Some(start) = signature.find(prefix) {
-            let after_prefix = &signature[start + prefix.len()..];
-            if let Some(end) = after_prefix.find('(').or_else(|| after_prefix.find(' ')) {
-                let name = &after_prefix[..end];
-                if !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') {
-                    return Some(name.to_string());
-                }
-            }
-        }
-        None
+        SymbolExtractor::new()
+            .extract(content, language)
+            .into_iter()
+            .filter(|symbol| matches!(symbol.kind, SymbolKind::Function | SymbolKind::Method))
+            .map(|symbol| symbol.name)
+            .collect()
     }
 
     /// Calculate search relevance score combining multiple factors
@@ -267,6 +220,20 @@ mod tests {
         assert!(matches!(hybrid_query.mode, SearchMode::Hybrid { .. }));
     }
 
+    #[test]
+    fn test_quick_hybrid_with_ratio_maps_ratio_to_weights() {
+        let fixture = utils::quick_hybrid_with_ratio("test", 0.9, 10);
+
+        assert_eq!(fixture.limit, 10);
+        match fixture.mode {
+            SearchMode::Hybrid { semantic_weight, keyword_weight, .. } => {
+                assert_eq!(semantic_weight, 0.9);
+                assert_eq!(keyword_weight, 1.0 - 0.9_f32);
+            }
+            other => panic!("expected Hybrid mode, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_calculate_relevance_score() {
         let fixtures = vec![
@@ -6,27 +6,49 @@ use std::time::Instant;
 
 use anyhow::{Context, Result};
 use forge_domain::{
-    CodeChunk, MatchType, SearchContext, SearchMode, SearchOptions, SearchQuery, SearchResult,
-    SearchResults, SearchStats, SortBy,
+    CodeChunk, FusionMethod, MatchType, PromptTemplate, ScoreBreakdown, SearchContext, SearchMode,
+    SearchOptions, SearchQuery, SearchResult, SearchResults, SearchStats, SortBy,
 };
+use futures::future;
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::indexing::Embedder;
-use crate::search::{quick_semantic_search, quick_keyword_search, quick_hybrid_search, calculate_relevance_score, extract_function_names};
+use crate::search::{
+    quick_semantic_search, quick_keyword_search, quick_hybrid_search, quick_hybrid_with_ratio,
+    extract_function_names,
+};
+use crate::search::SharedKeywordIndex;
 use crate::vector_store::SharedVectorStore;
 
+/// Vector store collection a plain (non-federated) `search` queries
+const DEFAULT_COLLECTION: &str = "codebase";
+/// Reciprocal Rank Fusion constant used when combining per-collection ranked lists in
+/// `search_federated`
+const FEDERATION_RRF_K: f32 = 60.0;
+
 /// Service for searching indexed codebases with semantic and keyword
 /// capabilities
 pub struct SearchService {
     vector_store: SharedVectorStore,
     embedder: Arc<RwLock<Box<dyn Embedder>>>,
+    keyword_index: SharedKeywordIndex,
+    /// Same template `IndexingService` renders indexed chunks through, so a query embedding and
+    /// the document embeddings it's compared against land in the same representation space.
+    prompt_template: PromptTemplate,
 }
 
 impl SearchService {
-    /// Create a new search service
-    pub fn new(vector_store: SharedVectorStore, embedder: Box<dyn Embedder>) -> Self {
-        Self { vector_store, embedder: Arc::new(RwLock::new(embedder)) }
+    /// Create a new search service backed by a BM25 keyword index for the lexical side of
+    /// search. `prompt_template` must match the template used to index `vector_store`'s
+    /// collections -- otherwise query and document embeddings are rendered inconsistently.
+    pub fn new(
+        vector_store: SharedVectorStore,
+        embedder: Box<dyn Embedder>,
+        keyword_index: SharedKeywordIndex,
+        prompt_template: PromptTemplate,
+    ) -> Self {
+        Self { vector_store, embedder: Arc::new(RwLock::new(embedder)), keyword_index, prompt_template }
     }
 
     /// Quick semantic search with default settings
@@ -47,6 +69,69 @@ impl SearchService {
         self.search(search_query).await
     }
 
+    /// Quick hybrid search with a single `semantic_ratio` in `[0.0, 1.0]` (`0.0` = pure keyword,
+    /// `1.0` = pure semantic) standing in for `quick_hybrid`'s fixed weight split.
+    pub async fn quick_hybrid_with_ratio(
+        &self,
+        query: impl Into<String>,
+        semantic_ratio: f32,
+        limit: usize,
+    ) -> Result<SearchResults> {
+        let search_query = quick_hybrid_with_ratio(query, semantic_ratio, limit);
+        self.search(search_query).await
+    }
+
+    /// Fuzzy symbol search: rank indexed chunks by how well their symbol name matches `query`
+    /// as a character-bag-filtered, boundary-aware subsequence (IDE-style "type `gsq` to find
+    /// `get_search_query`"), rather than the exact match `SearchFilters::symbols` implies.
+    pub async fn fuzzy_symbol_search(&self, query: SearchQuery) -> Result<SearchResults> {
+        let start_time = Instant::now();
+        info!("Executing fuzzy symbol search: '{}'", query.query);
+
+        let matches = self
+            .keyword_index
+            .fuzzy_symbol_search(&query.query, &query.filters, query.similarity_threshold, query.limit)
+            .await;
+
+        let results: Vec<SearchResult> = matches
+            .into_iter()
+            .map(|(chunk, score)| SearchResult {
+                explanation: Some(format!(
+                    "Fuzzy symbol match: '{}' ~ '{}' ({:.3})",
+                    query.query,
+                    chunk.symbol.as_deref().unwrap_or(""),
+                    score
+                )),
+                chunk,
+                score,
+                match_type: MatchType::Symbol,
+                highlighted_content: None,
+                context: None,
+                semantic_dominant: None,
+                collection: None,
+                score_breakdown: None,
+            })
+            .collect();
+
+        let execution_time = start_time.elapsed().as_millis() as u64;
+        let (processed_results, post_process_degraded) = self
+            .post_process_results(results, &query.options, start_time)
+            .await?;
+        let degraded_stages = usize::from(post_process_degraded);
+        let stats = self.generate_stats(&processed_results, degraded_stages);
+        let total_matches = processed_results.len();
+
+        Ok(SearchResults {
+            query: query.clone(),
+            chunks: processed_results,
+            total_matches,
+            execution_time_ms: execution_time,
+            stats,
+            suggestions: Vec::new(),
+            degraded: post_process_degraded,
+        })
+    }
+
     /// Search for functions in the codebase
     pub async fn search_functions(&self, query: impl Into<String>, language: &str, limit: usize) -> Result<SearchResults> {
         let query_str = query.into();
@@ -72,61 +157,150 @@ impl SearchService {
         Ok(results)
     }
 
-    /// Execute a search query
+    /// Execute a search query against the default `"codebase"` collection
     pub async fn search(&self, query: SearchQuery) -> Result<SearchResults> {
         let start_time = Instant::now();
         info!("Executing search query: '{}'", query.query);
 
-        let results = match query.mode {
-            SearchMode::Semantic => self.semantic_search(&query).await?,
-            SearchMode::Keyword => self.keyword_search(&query).await?,
-            SearchMode::Hybrid { semantic_weight, keyword_weight } => {
-                self.hybrid_search(&query, semantic_weight, keyword_weight)
-                    .await?
-            }
-        };
-
-        let execution_time = start_time.elapsed().as_millis() as u64;
-
-        // Sort results
-        let mut sorted_results = results;
-        self.sort_results(&mut sorted_results, &query.options.sort_by);
-
-        // Apply limit
-        sorted_results.truncate(query.limit);
-
-        // Post-process results
-        let processed_results = self
-            .post_process_results(sorted_results, &query.options)
+        let (processed_results, degraded_stages) = self
+            .execute_query(&query, DEFAULT_COLLECTION, start_time)
             .await?;
 
-        // Generate statistics
-        let stats = self.generate_stats(&processed_results);
-
-        // Generate suggestions (simple implementation)
-        let suggestions = self.generate_suggestions(&query, &processed_results);
-
-        // Store the length before moving processed_results
+        let stats = self.generate_stats(&processed_results, degraded_stages);
+        let suggestions = self.generate_suggestions(&query, &processed_results).await;
         let total_matches = processed_results.len();
 
         Ok(SearchResults {
             query: query.clone(),
             chunks: processed_results,
-            total_matches, // Use the stored value
-            execution_time_ms: execution_time,
+            total_matches,
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
             stats,
             suggestions,
+            degraded: degraded_stages > 0,
+        })
+    }
+
+    /// Search multiple named collections concurrently and fuse each collection's own ranked
+    /// list into one combined ranking via Reciprocal Rank Fusion, labeling every hit with the
+    /// collection it came from. Lets a monorepo split into several indexed sub-projects be
+    /// searched in a single call.
+    pub async fn search_federated(&self, queries: Vec<(String, SearchQuery)>) -> Result<SearchResults> {
+        let start_time = Instant::now();
+        let query_labels: Vec<String> = queries.iter().map(|(c, q)| format!("{c}:{}", q.query)).collect();
+        info!("Executing federated search across {} collection(s): {}", queries.len(), query_labels.join(", "));
+
+        let per_collection: Vec<(String, Vec<SearchResult>, usize)> = future::join_all(queries.into_iter().map(
+            |(collection, query)| {
+                let collection_start = Instant::now();
+                async move {
+                    let (results, degraded_stages) =
+                        self.execute_query(&query, &collection, collection_start).await?;
+                    Ok::<_, anyhow::Error>((collection, results, degraded_stages))
+                }
+            },
+        ))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+
+        let mut fused: HashMap<String, SearchResult> = HashMap::new();
+        let mut fused_score: HashMap<String, f32> = HashMap::new();
+        let mut collection_breakdown: HashMap<String, usize> = HashMap::new();
+        let mut degraded_stages = 0;
+
+        for (collection, mut results, collection_degraded_stages) in per_collection {
+            degraded_stages += collection_degraded_stages;
+            results.sort_by(|a, b| {
+                b.score
+                    .partial_cmp(&a.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+            *collection_breakdown.entry(collection.clone()).or_insert(0) += results.len();
+
+            for (rank, mut result) in results.into_iter().enumerate() {
+                result.collection = Some(collection.clone());
+                let chunk_id = result.chunk.id.clone();
+                *fused_score.entry(chunk_id.clone()).or_insert(0.0) +=
+                    1.0 / (FEDERATION_RRF_K + rank as f32 + 1.0);
+                fused.entry(chunk_id).or_insert(result);
+            }
+        }
+
+        let mut chunks: Vec<SearchResult> = fused
+            .into_iter()
+            .map(|(chunk_id, mut result)| {
+                result.score = fused_score[&chunk_id];
+                result.explanation = Some(format!(
+                    "Federated RRF score: {:.4} (collection={:?})",
+                    result.score, result.collection
+                ));
+                result
+            })
+            .collect();
+        chunks.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let total_matches = chunks.len();
+        let mut stats = self.generate_stats(&chunks, degraded_stages);
+        stats.collection_breakdown = collection_breakdown;
+
+        Ok(SearchResults {
+            query: SearchQuery { query: query_labels.join(", "), ..Default::default() },
+            chunks,
+            total_matches,
+            execution_time_ms: start_time.elapsed().as_millis() as u64,
+            stats,
+            suggestions: Vec::new(),
+            degraded: degraded_stages > 0,
         })
     }
 
+    /// Run `query`'s mode against `collection` and return the sorted, limited, post-processed
+    /// hits. Shared by `search` (which always targets `DEFAULT_COLLECTION`) and
+    /// `search_federated` (one call per named collection), so a federated sub-query gets the
+    /// exact same pipeline as a standalone search.
+    async fn execute_query(
+        &self,
+        query: &SearchQuery,
+        collection: &str,
+        start_time: Instant,
+    ) -> Result<(Vec<SearchResult>, usize)> {
+        let (results, mut degraded_stages) = match query.mode {
+            SearchMode::Semantic => (self.semantic_search(query, collection).await?, 0),
+            SearchMode::Keyword => (self.keyword_search(query).await?, 0),
+            SearchMode::Hybrid { semantic_weight, keyword_weight, ref fusion } => {
+                self.hybrid_search(query, semantic_weight, keyword_weight, fusion, start_time, collection)
+                    .await?
+            }
+        };
+
+        let mut sorted_results = results;
+        self.sort_results(&mut sorted_results, &query.options.sort_by);
+        sorted_results.truncate(query.limit);
+
+        let (processed_results, post_process_degraded) = self
+            .post_process_results(sorted_results, &query.options, start_time)
+            .await?;
+        if post_process_degraded {
+            degraded_stages += 1;
+        }
+
+        Ok((processed_results, degraded_stages))
+    }
+
     /// Perform semantic search using vector embeddings
-    async fn semantic_search(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
-        debug!("Performing semantic search");
+    async fn semantic_search(&self, query: &SearchQuery, collection: &str) -> Result<Vec<SearchResult>> {
+        debug!("Performing semantic search against collection '{collection}'");
 
         // Generate embedding for query
         let embedder = self.embedder.read().await;
+        let rendered_query = self.prompt_template.render_query(&query.query);
         let query_embedding = embedder
-            .embed_text(&query.query)
+            .embed_text(&rendered_query)
             .await
             .context("Failed to generate query embedding")?;
 
@@ -134,7 +308,7 @@ impl SearchService {
         let store = self.vector_store.read().await;
         let vector_results = store
             .search(
-                "codebase", // TODO: Make collection name configurable
+                collection,
                 &query_embedding,
                 query.limit * 2, // Get more results to allow for filtering
                 Some(&query.filters),
@@ -153,6 +327,9 @@ impl SearchService {
                 highlighted_content: None, // Will be added in post-processing
                 context: None,             // Will be added in post-processing
                 explanation: Some(format!("Semantic similarity: {:.3}", result.score)),
+                semantic_dominant: None,
+                collection: None,
+                score_breakdown: None,
             })
             .collect();
 
@@ -160,109 +337,200 @@ impl SearchService {
         Ok(results)
     }
 
-    /// Perform keyword search using text matching
+    /// Perform keyword search by ranking chunks against the on-disk BM25 inverted index. The
+    /// index is shared across every vector-store collection, so (unlike `semantic_search`) this
+    /// has no `collection` parameter to scope against in `search_federated`.
     async fn keyword_search(&self, query: &SearchQuery) -> Result<Vec<SearchResult>> {
-        debug!("Performing keyword search");
-
-        // For now, we'll implement a simple approach by searching through all chunks
-        // In a production system, this would use a dedicated text index like
-        // Elasticsearch
-
-        let store = self.vector_store.read().await;
-
-        // Get all chunks (this is inefficient but works for demo)
-        // In practice, we'd use a text search index
-        let all_results = store
-            .search(
-                "codebase",
-                &vec![0.0; 1536], // Dummy embedding - we'll filter by content
-                10000,            // Large limit to get all
-                Some(&query.filters),
-            )
-            .await?;
+        debug!("Performing BM25 keyword search");
 
-        let query_terms = self.extract_keywords(&query.query);
-        let mut results = Vec::new();
+        let ranked = self
+            .keyword_index
+            .search(&query.query, &query.filters, query.limit * 2)
+            .await;
 
-        for vector_result in all_results {
-            let score = self.calculate_keyword_score(&vector_result.chunk.content, &query_terms);
-            if score > 0.0 {
-                let has_exact_match =
-                    self.has_exact_match(&vector_result.chunk.content, &query.query);
-                results.push(SearchResult {
-                    chunk: vector_result.chunk,
-                    score,
+        let results = ranked
+            .into_iter()
+            .map(|(chunk, score)| {
+                let has_exact_match = self.has_exact_match(&chunk.content, &query.query);
+                SearchResult {
                     match_type: if has_exact_match {
                         MatchType::ExactKeyword
                     } else {
                         MatchType::PartialKeyword
                     },
+                    explanation: Some(format!("BM25 score: {score:.3}")),
+                    chunk,
+                    score,
                     highlighted_content: None,
                     context: None,
-                    explanation: Some(format!("Keyword match score: {score:.3}")),
-                });
-            }
-        }
+                    semantic_dominant: None,
+                    collection: None,
+                    score_breakdown: None,
+                }
+            })
+            .collect::<Vec<_>>();
 
         debug!("Keyword search found {} results", results.len());
         Ok(results)
     }
 
-    /// Perform hybrid search combining semantic and keyword approaches
+    /// Perform hybrid search combining semantic and keyword approaches, fused via either a
+    /// plain weighted sum of raw scores or rank-based Reciprocal Rank Fusion (see
+    /// `FusionMethod`). RRF is the recommended default since it's invariant to the scale
+    /// mismatch between the semantic retriever's cosine similarities and the keyword
+    /// retriever's term-frequency scores.
     async fn hybrid_search(
         &self,
         query: &SearchQuery,
         semantic_weight: f32,
         keyword_weight: f32,
-    ) -> Result<Vec<SearchResult>> {
+        fusion: &FusionMethod,
+        start: Instant,
+        collection: &str,
+    ) -> Result<(Vec<SearchResult>, usize)> {
         debug!(
-            "Performing hybrid search with weights: semantic={}, keyword={}",
-            semantic_weight, keyword_weight
+            "Performing hybrid search against collection '{collection}' with weights: semantic={}, keyword={}, fusion={:?}",
+            semantic_weight, keyword_weight, fusion
         );
+        let mut degraded_stages = 0;
 
-        // Get semantic results
-        let semantic_results = self.semantic_search(query).await?;
+        // Run the keyword arm first: it's needed either way, both to fuse against the semantic
+        // arm and to decide whether the semantic arm is worth running at all.
+        let mut keyword_results = self.keyword_search(query).await?;
+        keyword_results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
 
-        // Get keyword results
-        let keyword_results = self.keyword_search(query).await?;
+        // Lazy embedding: if the keyword arm alone already clears the caller's confidence bar
+        // for at least `limit` hits, the query is "obviously keyword-satisfiable" and the
+        // embedding round-trip would just add latency/cost for no ranking benefit -- skip it and
+        // return the keyword hits as-is.
+        if let Some(threshold) = query.options.lazy_embed_threshold {
+            let confident = keyword_results.len() >= query.limit
+                && keyword_results[..query.limit].iter().all(|r| r.score >= threshold);
+            if confident {
+                debug!(
+                    "Keyword arm cleared lazy_embed_threshold={threshold}; skipping semantic arm of hybrid search"
+                );
+                return Ok((keyword_results, degraded_stages));
+            }
+        }
 
-        // Combine and re-score results
-        let mut combined_results = HashMap::new();
+        // Get each retriever's own ranking (best match first). A flaky/rate-limited embedder
+        // shouldn't fail the whole request when the caller also asked for keyword signal -- only
+        // degrade to keyword-only silently when there's a keyword arm to fall back to; a
+        // semantic-only query (keyword_weight == 0.0) still surfaces the embedding error. Also
+        // skip it outright once the keyword arm has already burned the caller's time budget.
+        let mut semantic_results = if Self::budget_exceeded(start, query.options.time_budget_ms) {
+            debug!("Time budget exceeded after keyword arm, skipping semantic arm of hybrid search");
+            degraded_stages += 1;
+            Vec::new()
+        } else {
+            match self.semantic_search(query, collection).await {
+                Ok(results) => results,
+                Err(err) if semantic_weight > 0.0 && keyword_weight > 0.0 => {
+                    warn!("Query embedding failed, degrading hybrid search to keyword-only: {err:#}");
+                    Vec::new()
+                }
+                Err(err) => return Err(err),
+            }
+        };
+        semantic_results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
 
-        // Add semantic results
+        // 1-based rank and raw score of each chunk within its own retriever's list
+        let semantic_ranks: HashMap<String, usize> = semantic_results
+            .iter()
+            .enumerate()
+            .map(|(rank, result)| (result.chunk.id.clone(), rank + 1))
+            .collect();
+        let keyword_ranks: HashMap<String, usize> = keyword_results
+            .iter()
+            .enumerate()
+            .map(|(rank, result)| (result.chunk.id.clone(), rank + 1))
+            .collect();
+        let semantic_scores: HashMap<String, f32> = semantic_results
+            .iter()
+            .map(|result| (result.chunk.id.clone(), result.score))
+            .collect();
+        let keyword_scores: HashMap<String, f32> = keyword_results
+            .iter()
+            .map(|result| (result.chunk.id.clone(), result.score))
+            .collect();
+
+        // Merge the two result sets, marking a chunk `Hybrid` when it appears in both
+        let mut fused: HashMap<String, SearchResult> = HashMap::new();
         for result in semantic_results {
-            let chunk_id = result.chunk.id.clone();
-            let weighted_score = result.score * semantic_weight;
-            combined_results.insert(
-                chunk_id,
-                (result, weighted_score, vec![MatchType::Semantic]),
-            );
+            fused.insert(result.chunk.id.clone(), result);
         }
-
-        // Add keyword results (combining scores if chunk already exists)
         for result in keyword_results {
-            let chunk_id = result.chunk.id.clone();
-            let weighted_score = result.score * keyword_weight;
-
-            if let Some((existing_result, existing_score, match_types)) =
-                combined_results.get_mut(&chunk_id)
-            {
-                // Combine scores and match types
-                let combined_score = *existing_score + weighted_score;
-                *existing_score = combined_score;
-                match_types.push(result.match_type.clone());
-                existing_result.match_type = MatchType::Hybrid;
-                existing_result.score = combined_score;
-            } else {
-                let match_type = result.match_type.clone();
-                combined_results.insert(chunk_id, (result, weighted_score, vec![match_type]));
-            }
+            fused
+                .entry(result.chunk.id.clone())
+                .and_modify(|existing| existing.match_type = MatchType::Hybrid)
+                .or_insert(result);
         }
 
-        // Convert back to vector and sort by combined score
-        let mut results: Vec<SearchResult> = combined_results
+        let mut results: Vec<SearchResult> = fused
             .into_values()
-            .map(|(result, _, _)| result)
+            .map(|mut result| {
+                let semantic_rank = semantic_ranks.get(&result.chunk.id).copied();
+                let keyword_rank = keyword_ranks.get(&result.chunk.id).copied();
+
+                let (semantic_term, keyword_term, explanation) = match *fusion {
+                    FusionMethod::Rrf { k } => {
+                        let semantic_term = semantic_rank
+                            .map(|rank| semantic_weight / (k + rank as f32))
+                            .unwrap_or(0.0);
+                        let keyword_term = keyword_rank
+                            .map(|rank| keyword_weight / (k + rank as f32))
+                            .unwrap_or(0.0);
+
+                        let explanation = format!(
+                            "RRF fused score: {:.4} (semantic_rank={:?}, keyword_rank={:?})",
+                            semantic_term + keyword_term,
+                            semantic_rank,
+                            keyword_rank
+                        );
+                        (semantic_term, keyword_term, explanation)
+                    }
+                    FusionMethod::WeightedScore => {
+                        let semantic_score = semantic_scores.get(&result.chunk.id).copied();
+                        let keyword_score = keyword_scores.get(&result.chunk.id).copied();
+
+                        let semantic_term =
+                            semantic_score.map(|score| score * semantic_weight).unwrap_or(0.0);
+                        let keyword_term =
+                            keyword_score.map(|score| score * keyword_weight).unwrap_or(0.0);
+
+                        let explanation = format!(
+                            "Weighted score: {:.4} (semantic={:?}, keyword={:?})",
+                            semantic_term + keyword_term,
+                            semantic_score,
+                            keyword_score
+                        );
+                        (semantic_term, keyword_term, explanation)
+                    }
+                };
+
+                result.score = semantic_term + keyword_term;
+                result.semantic_dominant = match result.match_type {
+                    MatchType::Hybrid => Some(semantic_term >= keyword_term),
+                    _ => None,
+                };
+                result.explanation = Some(explanation);
+                result.score_breakdown = Some(ScoreBreakdown {
+                    semantic_rank,
+                    keyword_rank,
+                    semantic_contribution: semantic_term,
+                    keyword_contribution: keyword_term,
+                });
+                result
+            })
             .collect();
 
         results.sort_by(|a, b| {
@@ -271,61 +539,8 @@ impl SearchService {
                 .unwrap_or(std::cmp::Ordering::Equal)
         });
 
-        debug!("Hybrid search found {} combined results", results.len());
-        Ok(results)
-    }
-
-    /// Extract keywords from query text
-    fn extract_keywords(&self, query: &str) -> Vec<String> {
-        query
-            .split_whitespace()
-            .map(|word| {
-                word.to_lowercase()
-                    .trim_matches(|c: char| !c.is_alphanumeric())
-                    .to_string()
-            })
-            .filter(|word| !word.is_empty() && word.len() > 2) // Filter out very short words
-            .collect()
-    }
-
-    /// Calculate enhanced relevance score using semantic and keyword signals
-    fn calculate_enhanced_score(
-        &self,
-        semantic_score: f32,
-        keyword_score: f32,
-        path_match: bool,
-        symbol_match: bool,
-    ) -> f32 {
-        calculate_relevance_score(semantic_score, keyword_score, path_match, symbol_match)
-    }
-
-    /// Calculate keyword matching score
-    fn calculate_keyword_score(&self, content: &str, keywords: &[String]) -> f32 {
-        if keywords.is_empty() {
-            return 0.0;
-        }
-
-        let content_lower = content.to_lowercase();
-        let mut score = 0.0;
-        let mut matches = 0;
-
-        for keyword in keywords {
-            let keyword_count = content_lower.matches(keyword).count();
-            if keyword_count > 0 {
-                matches += 1;
-                // Score based on frequency and keyword length
-                score += (keyword_count as f32) * (keyword.len() as f32 / 10.0);
-            }
-        }
-
-        // Normalize by number of keywords and content length
-        if matches > 0 {
-            let coverage = matches as f32 / keywords.len() as f32;
-            let density = score / (content.len() as f32 / 1000.0); // Per 1000 chars
-            coverage * density.min(1.0) // Cap density at 1.0
-        } else {
-            0.0
-        }
+        debug!("Hybrid search found {} fused results", results.len());
+        Ok((results, degraded_stages))
     }
 
     /// Check if content has exact match for query
@@ -333,6 +548,11 @@ impl SearchService {
         content.to_lowercase().contains(&query.to_lowercase())
     }
 
+    /// Returns `true` once `start.elapsed()` has consumed the caller's `time_budget_ms`, if any
+    fn budget_exceeded(start: Instant, budget_ms: Option<u64>) -> bool {
+        budget_ms.is_some_and(|budget| start.elapsed().as_millis() as u64 >= budget)
+    }
+
     /// Sort results according to sort criteria
     fn sort_results(&self, results: &mut [SearchResult], sort_by: &SortBy) {
         match sort_by {
@@ -379,13 +599,23 @@ impl SearchService {
         }
     }
 
-    /// Post-process results (add highlighting, context, etc.)
+    /// Post-process results (add highlighting, context, etc.), stopping early once
+    /// `options.time_budget_ms` is exceeded. The second return value is `true` if enrichment
+    /// was cut short, leaving trailing results un-enriched (but still present).
     async fn post_process_results(
         &self,
         mut results: Vec<SearchResult>,
         options: &SearchOptions,
-    ) -> Result<Vec<SearchResult>> {
+        start: Instant,
+    ) -> Result<(Vec<SearchResult>, bool)> {
+        let mut degraded = false;
         for result in &mut results {
+            if Self::budget_exceeded(start, options.time_budget_ms) {
+                debug!("Time budget exceeded during post-processing, returning partially enriched results");
+                degraded = true;
+                break;
+            }
+
             // Add content highlighting if requested
             if options.highlight_matches {
                 result.highlighted_content =
@@ -419,7 +649,7 @@ impl SearchService {
             }
         }
 
-        Ok(results)
+        Ok((results, degraded))
     }
 
     /// Generate context around a chunk
@@ -441,11 +671,12 @@ impl SearchService {
     }
 
     /// Generate search statistics
-    fn generate_stats(&self, results: &[SearchResult]) -> SearchStats {
+    fn generate_stats(&self, results: &[SearchResult], degraded_stages: usize) -> SearchStats {
         let mut match_type_breakdown = HashMap::new();
         let mut language_breakdown = HashMap::new();
         let mut semantic_matches = 0;
         let mut keyword_matches = 0;
+        let mut semantic_hit_count = 0;
 
         for result in results {
             // Count match types
@@ -460,11 +691,17 @@ impl SearchService {
 
             // Count specific match types
             match result.match_type {
-                MatchType::Semantic => semantic_matches += 1,
+                MatchType::Semantic => {
+                    semantic_matches += 1;
+                    semantic_hit_count += 1;
+                }
                 MatchType::ExactKeyword | MatchType::PartialKeyword => keyword_matches += 1,
                 MatchType::Hybrid => {
                     semantic_matches += 1;
                     keyword_matches += 1;
+                    if result.semantic_dominant.unwrap_or(false) {
+                        semantic_hit_count += 1;
+                    }
                 }
                 _ => {}
             }
@@ -475,14 +712,17 @@ impl SearchService {
                                              * results */
             semantic_matches,
             keyword_matches,
+            semantic_hit_count,
+            degraded_stages,
             filters_applied: 0, // TODO: Count actual filters applied
             match_type_breakdown,
             language_breakdown,
+            collection_breakdown: HashMap::new(),
         }
     }
 
     /// Generate search suggestions
-    fn generate_suggestions(&self, query: &SearchQuery, results: &[SearchResult]) -> Vec<String> {
+    async fn generate_suggestions(&self, query: &SearchQuery, results: &[SearchResult]) -> Vec<String> {
         let mut suggestions = Vec::new();
 
         // If no results, suggest broader search
@@ -492,8 +732,13 @@ impl SearchService {
             suggestions.push("Try using different keywords".to_string());
         }
 
-        // If very few results, suggest related terms
+        // If very few results, offer a "did you mean" rewrite based on the indexed vocabulary
         if results.len() < 3 {
+            if let Some(rewrite) = self.keyword_index.suggest_query(&query.query).await
+                && rewrite != query.query {
+                    suggestions.push(format!("Did you mean '{rewrite}'?"));
+                }
+
             suggestions.push("Try searching for related terms".to_string());
             if !query.filters.languages.is_empty() {
                 suggestions.push("Try removing language filters".to_string());
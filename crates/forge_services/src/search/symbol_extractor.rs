@@ -0,0 +1,264 @@
+//! AST-backed symbol extraction, replacing the old `trim().starts_with("fn ")`-style line
+//! scanning: multi-line signatures, methods nested in `impl`/`class` blocks, async/generic
+//! functions, and anything not anchored at line start all parse correctly because this walks the
+//! real tree-sitter tree instead of scanning source lines as text.
+
+use tree_sitter::{Node, Parser};
+
+/// The structural role of an extracted symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolKind {
+    Function,
+    Method,
+    Class,
+    Struct,
+    Trait,
+    Enum,
+}
+
+/// A symbol found in a parsed source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub start_byte: usize,
+    pub end_byte: usize,
+    /// Name of the nearest enclosing class/struct/impl block, if any -- e.g. `Some("Parser")`
+    /// for a method extracted from `impl Parser { .. }`.
+    pub enclosing_scope: Option<String>,
+}
+
+/// Extracts structured symbols (name, kind, byte range, enclosing scope) from source text via
+/// the same tree-sitter grammars [`crate::indexing::TreeSitterChunker`] parses with, so new
+/// language support is a matter of adding a grammar rather than a new regex-like branch.
+#[derive(Default)]
+pub struct SymbolExtractor;
+
+impl SymbolExtractor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Extract every recognized symbol from `content`, parsed as `language`. Returns an empty
+    /// list for languages without a registered grammar or that fail to parse, rather than
+    /// erroring -- callers already treat "no symbols found" as a valid outcome.
+    pub fn extract(&self, content: &str, language: &str) -> Vec<ExtractedSymbol> {
+        let Some(mut parser) = parser_for(language) else {
+            return Vec::new();
+        };
+        let Some(tree) = parser.parse(content, None) else {
+            return Vec::new();
+        };
+
+        let mut symbols = Vec::new();
+        walk(tree.root_node(), content, language, false, None, &mut symbols);
+        symbols
+    }
+}
+
+/// Build a tree-sitter parser for `language`, returning `None` when no grammar is registered.
+fn parser_for(language: &str) -> Option<Parser> {
+    let mut parser = Parser::new();
+    let set_ok = match language {
+        "rust" => parser.set_language(&tree_sitter_rust::LANGUAGE.into()).is_ok(),
+        "python" => parser.set_language(&tree_sitter_python::LANGUAGE.into()).is_ok(),
+        "javascript" | "typescript" => parser
+            .set_language(&tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into())
+            .is_ok(),
+        "go" => parser.set_language(&tree_sitter_go::LANGUAGE.into()).is_ok(),
+        "java" => parser.set_language(&tree_sitter_java::LANGUAGE.into()).is_ok(),
+        "c" | "cpp" => parser.set_language(&tree_sitter_cpp::LANGUAGE.into()).is_ok(),
+        _ => false,
+    };
+    set_ok.then_some(parser)
+}
+
+/// Map a grammar node kind to a [`SymbolKind`], given whether it was found as a direct-or-nested
+/// member of a class/impl body (`in_container`) -- the same node kind (e.g. Rust's
+/// `function_item`) is a free function at module scope but a method inside an `impl` block.
+fn node_kind_to_symbol_kind(language: &str, node_kind: &str, in_container: bool) -> Option<SymbolKind> {
+    let method_or_function = |in_container: bool| if in_container { SymbolKind::Method } else { SymbolKind::Function };
+
+    match (language, node_kind) {
+        ("rust", "function_item") => Some(method_or_function(in_container)),
+        ("rust", "struct_item") => Some(SymbolKind::Struct),
+        ("rust", "enum_item") => Some(SymbolKind::Enum),
+        ("rust", "trait_item") => Some(SymbolKind::Trait),
+
+        ("python", "function_definition") => Some(method_or_function(in_container)),
+        ("python", "class_definition") => Some(SymbolKind::Class),
+
+        ("javascript" | "typescript", "function_declaration") => Some(SymbolKind::Function),
+        ("javascript" | "typescript", "method_definition") => Some(SymbolKind::Method),
+        ("javascript" | "typescript", "class_declaration") => Some(SymbolKind::Class),
+        ("javascript" | "typescript", "interface_declaration") => Some(SymbolKind::Trait),
+
+        ("go", "function_declaration") => Some(SymbolKind::Function),
+        ("go", "method_declaration") => Some(SymbolKind::Method),
+        ("go", "type_declaration") => Some(SymbolKind::Struct),
+
+        ("java", "class_declaration") => Some(SymbolKind::Class),
+        ("java", "interface_declaration") => Some(SymbolKind::Trait),
+        ("java", "enum_declaration") => Some(SymbolKind::Enum),
+        ("java", "method_declaration") => Some(SymbolKind::Method),
+
+        ("c" | "cpp", "function_definition") => Some(SymbolKind::Function),
+        ("c" | "cpp", "struct_specifier") => Some(SymbolKind::Struct),
+        ("c" | "cpp", "class_specifier") => Some(SymbolKind::Class),
+        ("c" | "cpp", "enum_specifier") => Some(SymbolKind::Enum),
+
+        _ => None,
+    }
+}
+
+/// Whether `node_kind` opens a new method/field scope for its children, e.g. a Rust `impl_item`
+/// or a Python/JS `class` body.
+fn is_container_kind(language: &str, node_kind: &str) -> bool {
+    matches!(
+        (language, node_kind),
+        ("rust", "impl_item")
+            | ("python", "class_definition")
+            | ("javascript" | "typescript", "class_declaration")
+            | ("java", "class_declaration" | "interface_declaration" | "enum_declaration")
+            | ("c" | "cpp", "struct_specifier" | "class_specifier")
+    )
+}
+
+/// Name of the scope a container node establishes for its children -- the `Self` type for a Rust
+/// `impl` block, or the symbol's own name for everything else.
+fn container_scope_name(language: &str, node: Node, content: &str) -> Option<String> {
+    if language == "rust" && node.kind() == "impl_item" {
+        return node
+            .child_by_field_name("type")
+            .and_then(|n| content.get(n.byte_range()))
+            .map(str::to_string);
+    }
+    extract_symbol_name(node, content)
+}
+
+/// Extract a human-readable name for `node`, preferring the grammar's `name` field and falling
+/// back to the first identifier-like immediate child for node kinds that don't expose one.
+fn extract_symbol_name(node: Node, content: &str) -> Option<String> {
+    if let Some(name_node) = node.child_by_field_name("name") {
+        return content.get(name_node.byte_range()).map(str::to_string);
+    }
+
+    let mut cursor = node.walk();
+    if cursor.goto_first_child() {
+        loop {
+            let child = cursor.node();
+            if matches!(
+                child.kind(),
+                "identifier" | "type_identifier" | "field_identifier" | "property_identifier"
+            ) {
+                return content.get(child.byte_range()).map(str::to_string);
+            }
+            if !cursor.goto_next_sibling() {
+                break;
+            }
+        }
+    }
+    None
+}
+
+/// Recursively walk `node`'s children, collecting a symbol for every recognized node kind and
+/// descending into every child regardless so nested methods/classes are found too.
+fn walk(
+    node: Node,
+    content: &str,
+    language: &str,
+    in_container: bool,
+    scope: Option<String>,
+    out: &mut Vec<ExtractedSymbol>,
+) {
+    let mut cursor = node.walk();
+    if !cursor.goto_first_child() {
+        return;
+    }
+
+    loop {
+        let child = cursor.node();
+        let kind = child.kind();
+
+        if let Some(symbol_kind) = node_kind_to_symbol_kind(language, kind, in_container) {
+            let name = extract_symbol_name(child, content).unwrap_or_else(|| "<anonymous>".to_string());
+            out.push(ExtractedSymbol {
+                name,
+                kind: symbol_kind,
+                start_byte: child.start_byte(),
+                end_byte: child.end_byte(),
+                enclosing_scope: scope.clone(),
+            });
+        }
+
+        if is_container_kind(language, kind) {
+            let child_scope = container_scope_name(language, child, content).or_else(|| scope.clone());
+            walk(child, content, language, true, child_scope, out);
+        } else {
+            walk(child, content, language, in_container, scope.clone(), out);
+        }
+
+        if !cursor.goto_next_sibling() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn extracts_top_level_rust_functions() {
+        let content = "fn one() {}\n\nfn two() {}\n";
+        let symbols = SymbolExtractor::new().extract(content, "rust");
+
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["one", "two"]);
+        assert!(symbols.iter().all(|s| s.kind == SymbolKind::Function));
+    }
+
+    #[test]
+    fn classifies_methods_inside_impl_block_with_enclosing_scope() {
+        let content = "struct Parser;\n\nimpl Parser {\n    fn parse(&self) {}\n}\n";
+        let symbols = SymbolExtractor::new().extract(content, "rust");
+
+        let parse_method = symbols.iter().find(|s| s.name == "parse").unwrap();
+        assert_eq!(parse_method.kind, SymbolKind::Method);
+        assert_eq!(parse_method.enclosing_scope.as_deref(), Some("Parser"));
+
+        let struct_symbol = symbols.iter().find(|s| s.name == "Parser").unwrap();
+        assert_eq!(struct_symbol.kind, SymbolKind::Struct);
+    }
+
+    #[test]
+    fn extracts_python_class_and_methods() {
+        let content = "class Greeter:\n    def greet(self):\n        pass\n";
+        let symbols = SymbolExtractor::new().extract(content, "python");
+
+        let class_symbol = symbols.iter().find(|s| s.name == "Greeter").unwrap();
+        assert_eq!(class_symbol.kind, SymbolKind::Class);
+
+        let method_symbol = symbols.iter().find(|s| s.name == "greet").unwrap();
+        assert_eq!(method_symbol.kind, SymbolKind::Method);
+        assert_eq!(method_symbol.enclosing_scope.as_deref(), Some("Greeter"));
+    }
+
+    #[test]
+    fn handles_multiline_async_generic_signature() {
+        let content = "pub async fn fetch<T: Send>(\n    client: &Client,\n) -> Result<T> {\n    todo!()\n}\n";
+        let symbols = SymbolExtractor::new().extract(content, "rust");
+
+        assert_eq!(symbols.len(), 1);
+        assert_eq!(symbols[0].name, "fetch");
+        assert_eq!(symbols[0].kind, SymbolKind::Function);
+    }
+
+    #[test]
+    fn unsupported_language_returns_no_symbols() {
+        let symbols = SymbolExtractor::new().extract("anything", "cobol");
+        assert!(symbols.is_empty());
+    }
+}
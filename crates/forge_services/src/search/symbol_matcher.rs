@@ -0,0 +1,167 @@
+//! Fuzzy symbol name matching, e.g. typing `gsq` to find `get_search_query`.
+//!
+//! Each candidate is scored in two stages: an O(1) character-bag prefilter rules out
+//! candidates missing a query character entirely, then a greedy subsequence scan over survivors
+//! rewards consecutive runs and matches that land on word boundaries (camelCase humps, `_`,
+//! `/`, `.`, `-`).
+
+/// Score `query` against `candidate`, normalized to `[0, 1]`. Returns `None` if `query` is not a
+/// subsequence of `candidate` at all.
+pub fn fuzzy_symbol_score(query: &str, candidate: &str) -> Option<f32> {
+    if query.is_empty() || candidate.is_empty() {
+        return None;
+    }
+
+    let query_chars: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate_chars: Vec<char> = candidate.chars().collect();
+    let candidate_lower: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    // Lowercasing can change a string's character count for some non-ASCII scripts; bail out
+    // rather than risk boundary flags misaligning with the lowercased comparison chars.
+    if candidate_lower.len() != candidate_chars.len() {
+        return None;
+    }
+
+    if !char_bag_subset(&query_chars, &candidate_lower) {
+        return None;
+    }
+
+    let boundaries = boundary_flags(&candidate_chars);
+    subsequence_score(&query_chars, &candidate_lower, &boundaries)
+}
+
+/// Every distinct character class in `query` must appear somewhere in `candidate`, else no
+/// subsequence match is possible; checked as a single bitmask intersection.
+fn char_bag_subset(query: &[char], candidate: &[char]) -> bool {
+    let query_bag = char_bitmask(query);
+    let candidate_bag = char_bitmask(candidate);
+    query_bag & candidate_bag == query_bag
+}
+
+/// Map each character to a bit: `a`-`z` get their own bit, `0`-`9` get the next ten, everything
+/// else shares one catch-all bit.
+fn char_bitmask(chars: &[char]) -> u64 {
+    let mut mask = 0u64;
+    for &ch in chars {
+        let bit = match ch {
+            'a'..='z' => ch as u32 - 'a' as u32,
+            '0'..='9' => 26 + (ch as u32 - '0' as u32),
+            _ => 36,
+        };
+        mask |= 1u64 << bit;
+    }
+    mask
+}
+
+/// A position is a word boundary if it's the first character, follows a separator, or follows
+/// a lowercase-to-uppercase transition (a camelCase hump).
+fn boundary_flags(candidate: &[char]) -> Vec<bool> {
+    candidate
+        .iter()
+        .enumerate()
+        .map(|(i, &current)| {
+            if i == 0 {
+                return true;
+            }
+            let prev = candidate[i - 1];
+            matches!(prev, '_' | '/' | '.' | '-') || (prev.is_lowercase() && current.is_uppercase())
+        })
+        .collect()
+}
+
+/// Greedily match `query` as a subsequence of `candidate` (earliest-occurrence), scoring each
+/// matched character as: 1.0 base, +1.0 if it lands on a word boundary, +0.5 if it immediately
+/// follows the previous matched character. Returns `None` if `query` isn't a full subsequence.
+fn subsequence_score(query: &[char], candidate: &[char], boundaries: &[bool]) -> Option<f32> {
+    let mut qi = 0;
+    let mut score = 0.0f32;
+    let mut last_match_index: Option<usize> = None;
+
+    for (ci, &c) in candidate.iter().enumerate() {
+        if qi >= query.len() {
+            break;
+        }
+        if c != query[qi] {
+            continue;
+        }
+
+        let mut char_score = 1.0;
+        if boundaries[ci] {
+            char_score += 1.0;
+        }
+        if last_match_index == Some(ci.wrapping_sub(1)) {
+            char_score += 0.5;
+        }
+        score += char_score;
+        last_match_index = Some(ci);
+        qi += 1;
+    }
+
+    if qi < query.len() {
+        return None;
+    }
+
+    let max_possible = query.len() as f32 * 2.5;
+    Some((score / max_possible).clamp(0.0, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn matches_initialism_against_snake_case_symbol() {
+        let score = fuzzy_symbol_score("gsq", "get_search_query");
+
+        assert!(score.is_some());
+        assert!(score.unwrap() > 0.5);
+    }
+
+    #[test]
+    fn matches_initialism_against_camel_case_symbol() {
+        let score = fuzzy_symbol_score("gsq", "getSearchQuery");
+
+        assert!(score.is_some());
+    }
+
+    #[test]
+    fn rejects_candidate_missing_a_query_character() {
+        let score = fuzzy_symbol_score("gsq", "parse_file");
+
+        assert_eq!(score, None);
+    }
+
+    #[test]
+    fn rejects_out_of_order_subsequence() {
+        // every character of "tesg" is present in the candidate, but not in that order
+        let score = fuzzy_symbol_score("tesg", "get_search");
+
+        assert_eq!(score, None);
+    }
+
+    #[test]
+    fn consecutive_run_scores_higher_than_scattered_match() {
+        let consecutive = fuzzy_symbol_score("bcd", "abcdefghij").unwrap();
+        let scattered = fuzzy_symbol_score("bdf", "abcdefghij").unwrap();
+
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn boundary_match_scores_higher_than_midword_match_of_equal_length() {
+        // 'q' lands on a word boundary in "my_query" (right after '_') but mid-word in
+        // "mysteryq"
+        let boundary = fuzzy_symbol_score("q", "my_query").unwrap();
+        let midword = fuzzy_symbol_score("q", "mysteryq").unwrap();
+
+        assert!(boundary > midword);
+    }
+
+    #[test]
+    fn empty_query_or_candidate_has_no_score() {
+        assert_eq!(fuzzy_symbol_score("", "anything"), None);
+        assert_eq!(fuzzy_symbol_score("x", ""), None);
+    }
+}
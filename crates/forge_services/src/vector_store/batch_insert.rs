@@ -0,0 +1,165 @@
+//! Debounced, size-batched front end for `VectorStore::insert_chunks`, so indexing a large repo
+//! doesn't pay for one embedder round-trip per chunk. Callers `submit` a `(CodeChunk, text)` pair
+//! and return as soon as it's queued; a background task flushes the accumulated batch to the
+//! embedder and then the store once `batch_size` items have queued or `debounce` has elapsed
+//! since the oldest queued item, whichever comes first.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use forge_domain::{CodeChunk, Embedder};
+use forge_indexer::log_performance_metric;
+use tokio::sync::mpsc;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::error;
+
+use super::SharedVectorStore;
+
+/// Flush once this many items have queued, absent an earlier debounce-timeout flush.
+const DEFAULT_BATCH_SIZE: usize = 100;
+/// Flush a non-empty, under-`DEFAULT_BATCH_SIZE` queue after this much time has passed since its
+/// oldest item, so a trickle of chunks at the tail of an index run isn't held back indefinitely.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(200);
+
+struct PendingChunk {
+    chunk: CodeChunk,
+    text: String,
+}
+
+/// Batches `(CodeChunk, text)` pairs ahead of `VectorStore::insert_chunks`, flushing a batch
+/// either at `batch_size` items or after `debounce` has elapsed since the oldest queued item --
+/// whichever comes first -- using `ReceiverStream::chunks_timeout` as the batching primitive so
+/// the bounded channel's back-pressure is the only thing that ever blocks a caller. `submit` never
+/// waits for its chunk to actually be embedded or inserted; embed/insert failures are logged and
+/// drop that batch rather than propagated back to the (long-since-returned) caller.
+pub struct BatchedInserter {
+    sender: mpsc::Sender<PendingChunk>,
+    flushes: Arc<AtomicU64>,
+    total_latency_ms: Arc<std::sync::Mutex<f64>>,
+}
+
+impl BatchedInserter {
+    /// Spawn the background flush task against `store`/`collection`. `channel_capacity` bounds
+    /// how many submitted items can be queued before `submit` starts exerting back-pressure.
+    pub fn new(
+        store: SharedVectorStore,
+        collection: String,
+        embedder: Arc<dyn Embedder>,
+        batch_size: usize,
+        debounce: Duration,
+        channel_capacity: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel(channel_capacity);
+        let flushes = Arc::new(AtomicU64::new(0));
+        let total_latency_ms = Arc::new(std::sync::Mutex::new(0.0));
+
+        let task_flushes = flushes.clone();
+        let task_total_latency_ms = total_latency_ms.clone();
+        tokio::spawn(async move {
+            let mut batches = ReceiverStream::new(receiver).chunks_timeout(batch_size, debounce);
+            while let Some(batch) = batches.next().await {
+                Self::flush(&store, &collection, embedder.as_ref(), &task_flushes, &task_total_latency_ms, batch)
+                    .await;
+            }
+        });
+
+        Self { sender, flushes, total_latency_ms }
+    }
+
+    /// `new` with the repo's default batch size (100) and debounce window (200ms), and a channel
+    /// capacity generous enough to absorb a few batches' worth of back-pressure.
+    pub fn with_defaults(store: SharedVectorStore, collection: String, embedder: Arc<dyn Embedder>) -> Self {
+        Self::new(
+            store,
+            collection,
+            embedder,
+            DEFAULT_BATCH_SIZE,
+            DEFAULT_DEBOUNCE,
+            DEFAULT_BATCH_SIZE * 4,
+        )
+    }
+
+    /// Queue `chunk` for insertion, embedding `text` rather than `chunk.content` -- the two may
+    /// differ once a `PromptTemplate` renders additional context into the text actually sent to
+    /// the embedder. Only blocks if the channel is at `channel_capacity`.
+    pub async fn submit(&self, chunk: CodeChunk, text: String) -> Result<()> {
+        self.sender
+            .send(PendingChunk { chunk, text })
+            .await
+            .map_err(|_| anyhow::anyhow!("batched inserter's background flush task has stopped"))
+    }
+
+    /// Total number of batches successfully inserted into the store so far (a batch whose embed or
+    /// insert failed is not counted), for callers that
+    /// want to report progress alongside the per-flush `log_performance_metric!` emissions.
+    pub fn flush_count(&self) -> u64 {
+        self.flushes.load(Ordering::Relaxed)
+    }
+
+    async fn flush(
+        store: &SharedVectorStore,
+        collection: &str,
+        embedder: &dyn Embedder,
+        flushes: &AtomicU64,
+        total_latency_ms: &std::sync::Mutex<f64>,
+        batch: Vec<PendingChunk>,
+    ) {
+        if batch.is_empty() {
+            return;
+        }
+
+        let started = Instant::now();
+        let texts: Vec<String> = batch.iter().map(|item| item.text.clone()).collect();
+        let embeddings = match embedder.embed_batch(&texts).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                error!("BatchedInserter: failed to embed batch of {} chunk(s): {e}", batch.len());
+                return;
+            }
+        };
+
+        if embeddings.len() != batch.len() {
+            error!(
+                "BatchedInserter: embedder returned {} vector(s) for {} queued chunk(s)",
+                embeddings.len(),
+                batch.len()
+            );
+            return;
+        }
+
+        let pairs: Vec<(CodeChunk, Vec<f32>)> =
+            batch.into_iter().map(|item| item.chunk).zip(embeddings).collect();
+        let batch_len = pairs.len();
+
+        if let Err(e) = store.write().await.insert_chunks(collection, &pairs).await {
+            error!("BatchedInserter: failed to insert batch of {} chunk(s): {e}", batch_len);
+            return;
+        }
+
+        let flush_count = flushes.fetch_add(1, Ordering::Relaxed) + 1;
+        let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+        let avg_latency_ms = {
+            let mut total = total_latency_ms.lock().unwrap();
+            *total += latency_ms;
+            *total / flush_count as f64
+        };
+
+        log_performance_metric!(
+            "vector_store_batch_insert_latency",
+            latency_ms,
+            "ms",
+            batch_size = batch_len,
+            collection = collection
+        );
+        log_performance_metric!(
+            "vector_store_batch_insert_flush_count",
+            flush_count,
+            "count",
+            avg_latency_ms = avg_latency_ms,
+            collection = collection
+        );
+    }
+}
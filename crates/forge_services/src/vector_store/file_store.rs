@@ -0,0 +1,462 @@
+//! Disk-persistent `VectorStore` backend for `VectorStoreType::FileStore`. Each collection is
+//! kept in memory (mirroring `InMemoryVectorStore`'s layout) and mirrored to a single JSON file
+//! per collection under the configured storage directory, so an indexed repo survives a restart
+//! without needing to re-embed. Writes are crash-safe via the standard write-temp-then-rename
+//! trick: a torn write lands in the `.tmp` file, never the path `create_collection` loads from.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use forge_domain::{CodeChunk, DistanceMetric, SearchFilters, VectorStoreConfig, VectorStoreType};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use super::path_filter::CompiledPathFilter;
+use super::store_trait::{IndexStatus, SearchResult, VectorStore, VectorStoreStats};
+
+/// On-disk representation of one collection; serialized whole to `{storage_path}/{name}.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedCollection {
+    dimension: usize,
+    distance_metric: DistanceMetric,
+    vectors: HashMap<String, VectorEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VectorEntry {
+    chunk: CodeChunk,
+    embedding: Vec<f32>,
+    metadata: HashMap<String, String>,
+}
+
+/// File-backed vector store: durable, ACID-by-whole-file-replacement persistence for `VectorStore`
+/// collections, as a drop-in alternative to `InMemoryVectorStore` via `VectorStoreType::FileStore`.
+pub struct FileVectorStore {
+    config: VectorStoreConfig,
+    storage_path: PathBuf,
+    collections: RwLock<HashMap<String, PersistedCollection>>,
+}
+
+impl FileVectorStore {
+    pub fn new(storage_path: PathBuf) -> Self {
+        Self {
+            config: VectorStoreConfig { store_type: VectorStoreType::FileStore { storage_path: storage_path.clone() }, ..Default::default() },
+            storage_path,
+            collections: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn collection_path(&self, name: &str) -> PathBuf {
+        self.storage_path.join(format!("{name}.json"))
+    }
+
+    /// Write `collection`'s current in-memory state to disk, via a temp file and rename so a
+    /// process crash mid-write never corrupts the file a subsequent `create_collection` would load.
+    async fn persist_collection(&self, name: &str) -> Result<()> {
+        let bytes = {
+            let collections = self.collections.read().unwrap();
+            let Some(collection) = collections.get(name) else { return Ok(()) };
+            serde_json::to_vec(collection)?
+        };
+
+        tokio::fs::create_dir_all(&self.storage_path).await?;
+        let path = self.collection_path(name);
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, bytes).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    /// Load a previously persisted collection from disk into memory, if present. No-op if the
+    /// file doesn't exist or fails to parse (matching the manifest's load-or-default behavior for
+    /// this kind of sidecar file).
+    async fn load_collection(&self, name: &str) -> Option<PersistedCollection> {
+        let bytes = tokio::fs::read(self.collection_path(name)).await.ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+
+    fn calculate_similarity(metric: &DistanceMetric, a: &[f32], b: &[f32]) -> f32 {
+        match metric {
+            DistanceMetric::Cosine => {
+                let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+                let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 { 0.0 } else { dot / (norm_a * norm_b) }
+            }
+            DistanceMetric::Euclidean => {
+                let distance = a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt();
+                1.0 / (1.0 + distance)
+            }
+            DistanceMetric::DotProduct => a.iter().zip(b).map(|(x, y)| x * y).sum(),
+            DistanceMetric::Manhattan => {
+                let distance = a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum::<f32>();
+                1.0 / (1.0 + distance)
+            }
+        }
+    }
+
+    /// Check if a chunk matches the given filters (mirrors `InMemoryVectorStore::matches_filters`).
+    fn matches_filters(chunk: &CodeChunk, filters: &SearchFilters) -> bool {
+        if let Some(ref repo) = filters.repository
+            && chunk.metadata.repository.as_deref() != Some(repo.as_str())
+        {
+            return false;
+        }
+        if let Some(ref branch) = filters.branch
+            && chunk.metadata.branch.as_deref() != Some(branch.as_str())
+        {
+            return false;
+        }
+        if !filters.languages.is_empty() && !filters.languages.contains(&chunk.language) {
+            return false;
+        }
+        // Path filtering (glob/regex) is handled separately by `CompiledPathFilter`, compiled
+        // once per search call instead of re-parsed for every chunk here.
+        if !filters.symbols.is_empty() && !chunk.symbol.as_ref().is_some_and(|s| filters.symbols.contains(s)) {
+            return false;
+        }
+        if !filters.tags.is_empty() && !filters.tags.iter().any(|tag| chunk.metadata.tags.contains(tag)) {
+            return false;
+        }
+        true
+    }
+}
+
+#[async_trait]
+impl VectorStore for FileVectorStore {
+    async fn initialize(&mut self, config: &VectorStoreConfig) -> Result<()> {
+        if let VectorStoreType::FileStore { storage_path } = &config.store_type {
+            self.storage_path = storage_path.clone();
+        }
+        info!("Initializing file-backed vector store at {:?}", self.storage_path);
+        self.config = config.clone();
+        tokio::fs::create_dir_all(&self.storage_path).await?;
+        Ok(())
+    }
+
+    async fn create_collection(&mut self, name: &str, dimension: usize) -> Result<()> {
+        info!("Creating collection '{}' with dimension {}", name, dimension);
+
+        let collection = match self.load_collection(name).await {
+            Some(existing) => existing,
+            None => PersistedCollection {
+                dimension,
+                distance_metric: self.config.distance_metric.clone(),
+                vectors: HashMap::new(),
+            },
+        };
+
+        self.collections.write().unwrap().insert(name.to_string(), collection);
+        self.persist_collection(name).await?;
+
+        debug!("Created collection '{}' successfully", name);
+        Ok(())
+    }
+
+    async fn delete_collection(&mut self, name: &str) -> Result<()> {
+        info!("Deleting collection '{}'", name);
+
+        self.collections.write().unwrap().remove(name);
+        let path = self.collection_path(name);
+        if path.exists() {
+            tokio::fs::remove_file(&path).await?;
+            debug!("Deleted collection '{}' successfully", name);
+        } else {
+            warn!("Collection '{}' not found for deletion", name);
+        }
+
+        Ok(())
+    }
+
+    async fn collection_exists(&self, name: &str) -> Result<bool> {
+        if self.collections.read().unwrap().contains_key(name) {
+            return Ok(true);
+        }
+        Ok(self.collection_path(name).exists())
+    }
+
+    async fn insert_chunk(&mut self, collection: &str, chunk: &CodeChunk, embedding: &[f32]) -> Result<String> {
+        self.insert_chunks(collection, std::slice::from_ref(&(chunk.clone(), embedding.to_vec())))
+            .await?;
+        Ok(chunk.id.clone())
+    }
+
+    async fn insert_chunks(&mut self, collection: &str, chunks: &[(CodeChunk, Vec<f32>)]) -> Result<Vec<String>> {
+        info!("Inserting {} chunks into collection '{}'", chunks.len(), collection);
+
+        let ids = {
+            let mut collections = self.collections.write().unwrap();
+            let coll = collections
+                .get_mut(collection)
+                .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+
+            let mut ids = Vec::with_capacity(chunks.len());
+            for (chunk, embedding) in chunks {
+                if embedding.len() != coll.dimension {
+                    return Err(anyhow::anyhow!(
+                        "Embedding dimension {} does not match collection dimension {}",
+                        embedding.len(),
+                        coll.dimension
+                    ));
+                }
+                coll.vectors.insert(
+                    chunk.id.clone(),
+                    VectorEntry { chunk: chunk.clone(), embedding: embedding.clone(), metadata: HashMap::new() },
+                );
+                ids.push(chunk.id.clone());
+            }
+            ids
+        };
+
+        // Batch every chunk in this call into a single on-disk commit rather than one write per
+        // chunk.
+        self.persist_collection(collection).await?;
+
+        debug!("Inserted {} chunks successfully", chunks.len());
+        Ok(ids)
+    }
+
+    async fn update_chunk(
+        &mut self,
+        collection: &str,
+        chunk_id: &str,
+        chunk: &CodeChunk,
+        embedding: &[f32],
+    ) -> Result<()> {
+        {
+            let mut collections = self.collections.write().unwrap();
+            let coll = collections
+                .get_mut(collection)
+                .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+
+            if embedding.len() != coll.dimension {
+                return Err(anyhow::anyhow!(
+                    "Embedding dimension {} does not match collection dimension {}",
+                    embedding.len(),
+                    coll.dimension
+                ));
+            }
+
+            coll.vectors.insert(
+                chunk_id.to_string(),
+                VectorEntry { chunk: chunk.clone(), embedding: embedding.to_vec(), metadata: HashMap::new() },
+            );
+        }
+        self.persist_collection(collection).await?;
+
+        debug!("Updated chunk '{}' successfully", chunk_id);
+        Ok(())
+    }
+
+    async fn delete_chunk(&mut self, collection: &str, chunk_id: &str) -> Result<()> {
+        let removed = {
+            let mut collections = self.collections.write().unwrap();
+            let coll = collections
+                .get_mut(collection)
+                .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+            coll.vectors.remove(chunk_id).is_some()
+        };
+
+        if removed {
+            self.persist_collection(collection).await?;
+            debug!("Deleted chunk '{}' successfully", chunk_id);
+        } else {
+            warn!("Chunk '{}' not found for deletion", chunk_id);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_chunks(&mut self, collection: &str, chunk_ids: &[String]) -> Result<()> {
+        info!("Deleting {} chunks from collection '{}'", chunk_ids.len(), collection);
+
+        {
+            let mut collections = self.collections.write().unwrap();
+            let coll = collections
+                .get_mut(collection)
+                .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+            for chunk_id in chunk_ids {
+                coll.vectors.remove(chunk_id);
+            }
+        }
+        self.persist_collection(collection).await?;
+
+        debug!("Deleted {} chunks successfully", chunk_ids.len());
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        collection: &str,
+        query_embedding: &[f32],
+        limit: usize,
+        filters: Option<&SearchFilters>,
+    ) -> Result<Vec<SearchResult>> {
+        debug!("Searching collection '{}' with limit {}", collection, limit);
+
+        let collections = self.collections.read().unwrap();
+        let coll = collections
+            .get(collection)
+            .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+
+        if query_embedding.len() != coll.dimension {
+            return Err(anyhow::anyhow!(
+                "Query embedding dimension {} does not match collection dimension {}",
+                query_embedding.len(),
+                coll.dimension
+            ));
+        }
+
+        let path_filter = filters.map(CompiledPathFilter::compile).transpose()?;
+
+        let mut results: Vec<SearchResult> = coll
+            .vectors
+            .iter()
+            .filter(|(_, entry)| {
+                filters.is_none_or(|f| Self::matches_filters(&entry.chunk, f))
+                    && path_filter.as_ref().is_none_or(|pf| pf.matches(&entry.chunk.path))
+            })
+            .map(|(chunk_id, entry)| SearchResult {
+                chunk_id: chunk_id.clone(),
+                chunk: entry.chunk.clone(),
+                score: Self::calculate_similarity(&coll.distance_metric, query_embedding, &entry.embedding),
+                metadata: entry.metadata.clone(),
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(limit);
+
+        debug!("Found {} results", results.len());
+        Ok(results)
+    }
+
+    async fn hybrid_search(
+        &self,
+        _collection: &str,
+        _query_embedding: &[f32],
+        _query_text: &str,
+        _limit: usize,
+        _filters: Option<&SearchFilters>,
+        _semantic_ratio: Option<f32>,
+    ) -> Result<Vec<SearchResult>> {
+        Err(anyhow::anyhow!("hybrid_search is not yet implemented for FileVectorStore"))
+    }
+
+    async fn flush(&self, collection: &str) -> Result<()> {
+        self.persist_collection(collection).await
+    }
+
+    async fn get_stats(&self, collection: &str) -> Result<VectorStoreStats> {
+        let (total_vectors, vector_dimension, distance_metric) = {
+            let collections = self.collections.read().unwrap();
+            let coll = collections
+                .get(collection)
+                .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+            (coll.vectors.len(), coll.dimension, coll.distance_metric.clone())
+        };
+
+        let storage_size_bytes = tokio::fs::metadata(self.collection_path(collection))
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        Ok(VectorStoreStats {
+            total_vectors,
+            vector_dimension,
+            storage_size_bytes,
+            index_status: IndexStatus::Ready,
+            distance_metric,
+            additional_metrics: HashMap::new(),
+        })
+    }
+
+    fn get_config(&self) -> &VectorStoreConfig {
+        &self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use forge_domain::CodeChunk;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fn temp_storage_dir() -> PathBuf {
+        let id = COUNTER.fetch_add(1, Ordering::SeqCst);
+        std::env::temp_dir().join(format!("forge-services-file-vector-store-test-{}-{id}", std::process::id()))
+    }
+
+    async fn fixture_store(storage_path: &Path) -> FileVectorStore {
+        let mut store = FileVectorStore::new(storage_path.to_path_buf());
+        store
+            .initialize(&VectorStoreConfig {
+                store_type: VectorStoreType::FileStore { storage_path: storage_path.to_path_buf() },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn test_insert_and_search_survives_reload() {
+        let storage_path = temp_storage_dir();
+        let mut fixture = fixture_store(&storage_path).await;
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let chunk = CodeChunk::new(
+            "test-1".to_string(),
+            "test.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn test() {}".to_string(),
+            1,
+            1,
+        );
+        fixture.insert_chunk("test", &chunk, &[0.1, 0.2, 0.3]).await.unwrap();
+
+        let mut reloaded = fixture_store(&storage_path).await;
+        reloaded.create_collection("test", 3).await.unwrap();
+
+        let actual = reloaded.search("test", &[0.1, 0.2, 0.3], 10, None).await.unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].chunk_id, "test-1");
+
+        tokio::fs::remove_dir_all(&storage_path).await.ok();
+    }
+
+    #[tokio::test]
+    async fn test_get_stats_reports_real_file_size() {
+        let storage_path = temp_storage_dir();
+        let mut fixture = fixture_store(&storage_path).await;
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let chunk = CodeChunk::new(
+            "test-1".to_string(),
+            "test.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn test() {}".to_string(),
+            1,
+            1,
+        );
+        fixture.insert_chunk("test", &chunk, &[0.1, 0.2, 0.3]).await.unwrap();
+
+        let actual = fixture.get_stats("test").await.unwrap();
+
+        assert_eq!(actual.total_vectors, 1);
+        assert!(actual.storage_size_bytes > 0);
+
+        tokio::fs::remove_dir_all(&storage_path).await.ok();
+    }
+}
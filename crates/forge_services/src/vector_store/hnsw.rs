@@ -0,0 +1,364 @@
+//! Hierarchical Navigable Small World approximate-nearest-neighbor index, used by
+//! `InMemoryVectorStore` in place of a brute-force scan when
+//! `VectorStoreConfig::use_ann_index` is set. Stores only graph topology (ids and per-layer
+//! neighbor lists) -- the embeddings themselves stay in `Collection::vectors`, and callers supply
+//! similarity as closures so this module never has to know the distance metric or vector layout.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use anyhow::{Result, bail};
+
+/// A node's per-layer neighbor lists; `neighbors[l]` holds its links at layer `l`.
+struct HnswNode {
+    layer: usize,
+    neighbors: Vec<Vec<String>>,
+}
+
+/// Minimal splitmix64 PRNG, avoiding a dependency on the `rand` crate for the small amount of
+/// randomness layer assignment needs.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform `f64` in `(0, 1]`, never exactly `0.0` so a caller can safely take its `ln()`.
+    fn next_f64(&mut self) -> f64 {
+        ((self.next_u64() >> 11) as f64 + 1.0) / ((1u64 << 53) as f64 + 1.0)
+    }
+}
+
+/// A candidate scored by similarity to some target, ordered so a max-heap pops the closest match
+/// first.
+struct Scored(f32, String);
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl Eq for Scored {}
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// HNSW graph over a collection's chunk ids. Insertion draws each new node's top layer as
+/// `floor(-ln(U(0,1)) * mL)` with `mL = 1 / ln(M)`, greedily descends from the single entry point
+/// to find a good starting node, then at each layer runs a bounded best-first search to collect
+/// candidates and keeps up to `M` of them (`2 * M` on layer 0) per the "prefer diverse neighbors" heuristic: a
+/// candidate is kept only if it's closer to the new node than to any neighbor already selected.
+/// Deletions tombstone their node instead of repairing links immediately -- a tombstoned node
+/// still serves as a waypoint during traversal, it's just never returned from a search.
+pub struct HnswIndex {
+    m: usize,
+    m_max0: usize,
+    ef_construction: usize,
+    ml: f64,
+    entry_point: Option<String>,
+    nodes: HashMap<String, HnswNode>,
+    tombstones: HashSet<String>,
+    rng: SplitMix64,
+}
+
+impl HnswIndex {
+    /// `m < 2` is rejected rather than silently clamped: `ml = 1 / ln(m)` is only finite and
+    /// positive for `m >= 2` (`m == 1` gives `ln(1) == 0`, so `ml` is infinite and `random_layer`
+    /// saturates to `usize::MAX`, overflowing the `layer + 1`-sized neighbor list `insert`
+    /// allocates for it).
+    pub fn new(m: usize, ef_construction: usize) -> Result<Self> {
+        if m < 2 {
+            bail!("hnsw_m must be at least 2, got {m}");
+        }
+        Ok(Self {
+            m,
+            m_max0: m * 2,
+            ef_construction: ef_construction.max(1),
+            ml: 1.0 / (m as f64).ln(),
+            entry_point: None,
+            nodes: HashMap::new(),
+            tombstones: HashSet::new(),
+            rng: SplitMix64(0x9E37_79B9_7F4A_7C15),
+        })
+    }
+
+    fn random_layer(&mut self) -> usize {
+        let u = self.rng.next_f64();
+        (-u.ln() * self.ml).floor() as usize
+    }
+
+    /// Insert `id` into the graph. `sim_to_target` scores any existing node's similarity to the
+    /// node being inserted; `sim_between` scores similarity between two existing nodes, used only
+    /// by the neighbor-selection heuristic.
+    pub fn insert(
+        &mut self,
+        id: String,
+        sim_to_target: impl Fn(&str) -> f32,
+        sim_between: impl Fn(&str, &str) -> f32,
+    ) {
+        let layer = self.random_layer();
+
+        let Some(entry_point) = self.entry_point.clone() else {
+            self.nodes.insert(id.clone(), HnswNode { layer, neighbors: vec![Vec::new(); layer + 1] });
+            self.entry_point = Some(id);
+            return;
+        };
+
+        let entry_layer = self.nodes[&entry_point].layer;
+        let mut cur = entry_point;
+        for lc in (layer + 1..=entry_layer).rev() {
+            cur = self.greedy_closest(&cur, lc, &sim_to_target);
+        }
+
+        let mut node = HnswNode { layer, neighbors: vec![Vec::new(); layer + 1] };
+        let mut entry_points = vec![cur];
+
+        for lc in (0..=layer.min(entry_layer)).rev() {
+            let candidates = self.search_layer(&entry_points, lc, self.ef_construction, &sim_to_target);
+            let m_target = if lc == 0 { self.m_max0 } else { self.m };
+            let selected = Self::select_neighbors_heuristic(&candidates, m_target, &sim_between);
+
+            node.neighbors[lc] = selected.clone();
+            entry_points = candidates.into_iter().map(|(candidate_id, _)| candidate_id).collect();
+
+            for neighbor in &selected {
+                let cap = if lc == 0 { self.m_max0 } else { self.m };
+                if let Some(neighbor_node) = self.nodes.get_mut(neighbor)
+                    && lc < neighbor_node.neighbors.len()
+                {
+                    neighbor_node.neighbors[lc].push(id.clone());
+                    if neighbor_node.neighbors[lc].len() > cap {
+                        let mut ranked: Vec<(String, f32)> = neighbor_node.neighbors[lc]
+                            .iter()
+                            .map(|n| (n.clone(), sim_between(neighbor, n)))
+                            .collect();
+                        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+                        neighbor_node.neighbors[lc] = Self::select_neighbors_heuristic(&ranked, cap, &sim_between)
+                            .into_iter()
+                            .collect();
+                    }
+                }
+            }
+        }
+
+        self.tombstones.remove(&id);
+        self.nodes.insert(id.clone(), node);
+        if layer > entry_layer {
+            self.entry_point = Some(id);
+        }
+    }
+
+    /// Tombstone `id` so it's skipped by future searches. The node's links stay in the graph and
+    /// keep serving as traversal waypoints until it's re-inserted or the graph is rebuilt.
+    pub fn remove(&mut self, id: &str) {
+        self.tombstones.insert(id.to_string());
+    }
+
+    /// Return up to `limit` non-tombstoned node ids ranked by `sim_to_target`, descending.
+    /// Descends greedily from the entry point through upper layers, then runs a best-first search
+    /// bounded by `ef` on layer 0.
+    pub fn search(&self, ef: usize, limit: usize, sim_to_target: impl Fn(&str) -> f32) -> Vec<String> {
+        let Some(entry_point) = &self.entry_point else { return Vec::new() };
+
+        let top_layer = self.nodes[entry_point].layer;
+        let mut cur = entry_point.clone();
+        for lc in (1..=top_layer).rev() {
+            cur = self.greedy_closest(&cur, lc, &sim_to_target);
+        }
+
+        let ef = ef.max(limit);
+        let mut results = self.search_layer(&[cur], 0, ef, &sim_to_target);
+        results.truncate(limit);
+        results.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// Single-best greedy descent at `layer`, starting from `from`: repeatedly hop to whichever
+    /// neighbor improves similarity to the target, stopping at a local optimum.
+    fn greedy_closest(&self, from: &str, layer: usize, sim_to_target: &impl Fn(&str) -> f32) -> String {
+        let mut current = from.to_string();
+        let mut current_sim = sim_to_target(&current);
+        loop {
+            let Some(node) = self.nodes.get(&current) else { break };
+            if layer >= node.neighbors.len() {
+                break;
+            }
+            let mut improved = false;
+            for neighbor in &node.neighbors[layer] {
+                let sim = sim_to_target(neighbor);
+                if sim > current_sim {
+                    current_sim = sim;
+                    current = neighbor.clone();
+                    improved = true;
+                }
+            }
+            if !improved {
+                break;
+            }
+        }
+        current
+    }
+
+    /// Best-first search at `layer` bounded to `ef` candidates. Tombstoned nodes are still
+    /// traversed (they keep the graph connected) but are never added to the returned set.
+    fn search_layer(
+        &self,
+        entry_points: &[String],
+        layer: usize,
+        ef: usize,
+        sim_to_target: &impl Fn(&str) -> f32,
+    ) -> Vec<(String, f32)> {
+        let mut visited: HashSet<String> = entry_points.iter().cloned().collect();
+        let mut candidates: BinaryHeap<Scored> = BinaryHeap::new();
+        let mut found: BinaryHeap<std::cmp::Reverse<Scored>> = BinaryHeap::new();
+
+        for ep in entry_points {
+            let sim = sim_to_target(ep);
+            candidates.push(Scored(sim, ep.clone()));
+            if !self.tombstones.contains(ep) {
+                found.push(std::cmp::Reverse(Scored(sim, ep.clone())));
+            }
+        }
+
+        while let Some(Scored(sim, current)) = candidates.pop() {
+            if let Some(std::cmp::Reverse(Scored(worst_sim, _))) = found.peek()
+                && found.len() >= ef
+                && sim < *worst_sim
+            {
+                break;
+            }
+
+            let Some(node) = self.nodes.get(&current) else { continue };
+            if layer >= node.neighbors.len() {
+                continue;
+            }
+            for neighbor in &node.neighbors[layer] {
+                if !visited.insert(neighbor.clone()) {
+                    continue;
+                }
+                let n_sim = sim_to_target(neighbor);
+                candidates.push(Scored(n_sim, neighbor.clone()));
+
+                if self.tombstones.contains(neighbor) {
+                    continue;
+                }
+                let should_add = found.len() < ef
+                    || found
+                        .peek()
+                        .map(|std::cmp::Reverse(Scored(worst, _))| n_sim > *worst)
+                        .unwrap_or(true);
+                if should_add {
+                    found.push(std::cmp::Reverse(Scored(n_sim, neighbor.clone())));
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        let mut results: Vec<(String, f32)> =
+            found.into_iter().map(|std::cmp::Reverse(Scored(s, id))| (id, s)).collect();
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        results
+    }
+
+    /// Keep up to `m` of `candidates` (already sorted by similarity to the target, descending):
+    /// a candidate is kept only if it's closer to the target than to any neighbor already
+    /// selected, preferring a diverse neighbor set over the naive top-`m` by raw similarity.
+    /// Remaining slots (if the heuristic filtered out more than it should have) are back-filled
+    /// from the discarded candidates in rank order.
+    fn select_neighbors_heuristic(
+        candidates: &[(String, f32)],
+        m: usize,
+        sim_between: &impl Fn(&str, &str) -> f32,
+    ) -> Vec<String> {
+        let mut selected: Vec<String> = Vec::with_capacity(m);
+        let mut discarded: Vec<String> = Vec::new();
+
+        for (candidate, sim_to_target) in candidates {
+            if selected.len() >= m {
+                discarded.push(candidate.clone());
+                continue;
+            }
+            let dominated = selected.iter().any(|s| sim_between(s, candidate) >= *sim_to_target);
+            if dominated {
+                discarded.push(candidate.clone());
+            } else {
+                selected.push(candidate.clone());
+            }
+        }
+
+        for candidate in discarded {
+            if selected.len() >= m {
+                break;
+            }
+            selected.push(candidate);
+        }
+
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    /// Embeddings laid out so "a"/"b" are close to each other and far from "c", and similarity is
+    /// just negative distance on a 1-D line.
+    fn sim(points: &HashMap<&str, f32>, a: &str, b: &str) -> f32 {
+        -((points[a] - points[b]).abs())
+    }
+
+    #[test]
+    fn rejects_m_below_2() {
+        // `m == 1` makes `ml = 1 / ln(1) = infinity`, which would saturate `random_layer` to
+        // `usize::MAX` and overflow the neighbor list `insert` allocates for it.
+        assert!(HnswIndex::new(1, 20).is_err());
+        assert!(HnswIndex::new(0, 20).is_err());
+        assert!(HnswIndex::new(2, 20).is_ok());
+    }
+
+    #[test]
+    fn test_insert_and_search_finds_nearest() {
+        let points: HashMap<&str, f32> = [("a", 0.0), ("b", 1.0), ("c", 100.0)].into_iter().collect();
+
+        let mut fixture = HnswIndex::new(4, 20).unwrap();
+        for id in ["a", "b", "c"] {
+            fixture.insert(id.to_string(), |other| sim(&points, id, other), |x, y| sim(&points, x, y));
+        }
+
+        let actual = fixture.search(10, 2, |other| -(points[other] - 0.5).abs());
+
+        assert_eq!(actual.len(), 2);
+        assert!(actual.contains(&"a".to_string()));
+        assert!(actual.contains(&"b".to_string()));
+    }
+
+    #[test]
+    fn test_removed_node_is_excluded_from_search() {
+        let points: HashMap<&str, f32> = [("a", 0.0), ("b", 1.0)].into_iter().collect();
+
+        let mut fixture = HnswIndex::new(4, 20).unwrap();
+        for id in ["a", "b"] {
+            fixture.insert(id.to_string(), |other| sim(&points, id, other), |x, y| sim(&points, x, y));
+        }
+        fixture.remove("a");
+
+        let actual = fixture.search(10, 2, |other| -(points[other]).abs());
+
+        assert_eq!(actual, vec!["b".to_string()]);
+    }
+}
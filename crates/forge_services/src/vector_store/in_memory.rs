@@ -1,6 +1,6 @@
 //! In-memory vector store implementation for development and testing
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::RwLock;
 
 use anyhow::Result;
@@ -8,8 +8,20 @@ use async_trait::async_trait;
 use forge_domain::{CodeChunk, DistanceMetric, SearchFilters, VectorStoreConfig, VectorStoreType};
 use tracing::{debug, info, warn};
 
+use super::hnsw::HnswIndex;
+use super::path_filter::CompiledPathFilter;
 use super::store_trait::{IndexStatus, SearchResult, VectorStore, VectorStoreStats};
 
+/// Reciprocal Rank Fusion constant for `hybrid_search`; keeps a single low-ranked list from
+/// dominating the fused score while still rewarding a chunk for ranking highly in either list.
+const RRF_K: f32 = 60.0;
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_lowercase())
+}
+
 /// In-memory vector store implementation
 pub struct InMemoryVectorStore {
     config: VectorStoreConfig,
@@ -17,12 +29,15 @@ pub struct InMemoryVectorStore {
 }
 
 /// A collection of vectors and their metadata
-#[derive(Debug, Clone)]
 struct Collection {
     name: String,
     dimension: usize,
     vectors: HashMap<String, VectorEntry>,
     distance_metric: DistanceMetric,
+    /// Present when `VectorStoreConfig::use_ann_index` is set; kept in sync by `insert_chunk`,
+    /// `update_chunk` and `delete_chunk` and queried by `search` instead of the brute-force scan.
+    ann_index: Option<HnswIndex>,
+    ann_ef_search: usize,
 }
 
 /// An entry in the vector collection
@@ -55,6 +70,7 @@ impl InMemoryVectorStore {
                 collection_name: "default".to_string(),
                 distance_metric: DistanceMetric::Cosine,
                 enable_compression: false,
+                ..Default::default()
             },
             collections: RwLock::new(HashMap::new()),
         }
@@ -109,7 +125,7 @@ impl InMemoryVectorStore {
     }
 
     /// Calculate similarity score based on distance metric
-    fn calculate_similarity(&self, metric: &DistanceMetric, a: &[f32], b: &[f32]) -> f32 {
+    fn calculate_similarity(metric: &DistanceMetric, a: &[f32], b: &[f32]) -> f32 {
         match metric {
             DistanceMetric::Cosine => Self::cosine_similarity(a, b),
             DistanceMetric::Euclidean => {
@@ -126,6 +142,30 @@ impl InMemoryVectorStore {
         }
     }
 
+    /// (Re-)insert `id` into `coll`'s HNSW graph, if one is enabled, comparing it against the
+    /// other vectors already stored in the collection. No-op when the collection has no ANN
+    /// index.
+    fn index_in_ann(coll: &mut Collection, id: &str) {
+        let Collection { vectors, ann_index, distance_metric, .. } = coll;
+        let Some(ann_index) = ann_index else { return };
+        let Some(target) = vectors.get(id) else { return };
+        let target_embedding = target.embedding.clone();
+
+        ann_index.insert(
+            id.to_string(),
+            |other| {
+                vectors
+                    .get(other)
+                    .map(|entry| Self::calculate_similarity(distance_metric, &target_embedding, &entry.embedding))
+                    .unwrap_or(0.0)
+            },
+            |a, b| match (vectors.get(a), vectors.get(b)) {
+                (Some(a), Some(b)) => Self::calculate_similarity(distance_metric, &a.embedding, &b.embedding),
+                _ => 0.0,
+            },
+        );
+    }
+
     /// Check if a chunk matches the given filters
     fn matches_filters(chunk: &CodeChunk, filters: &SearchFilters) -> bool {
         // Check repository filter
@@ -156,16 +196,8 @@ impl InMemoryVectorStore {
                 return false;
             }
 
-        // Check path filters (simple glob matching)
-        if !filters.paths.is_empty() {
-            let path_matches = filters.paths.iter().any(|pattern| {
-                // Simple glob matching - exact match or ends with pattern
-                chunk.path.contains(pattern) || chunk.path.ends_with(pattern)
-            });
-            if !path_matches {
-                return false;
-            }
-        }
+        // Path filtering (glob/regex) is handled separately by `CompiledPathFilter`, compiled
+        // once per search call instead of re-parsed for every chunk here.
 
         // Check symbol filters
         if !filters.symbols.is_empty() {
@@ -191,6 +223,117 @@ impl InMemoryVectorStore {
 
         true
     }
+
+    /// Term-frequency lexical score for `query_terms` against one chunk's content, with
+    /// symbol-name occurrences weighted higher since an exact symbol match is a much stronger
+    /// lexical signal than a body mention.
+    fn lexical_score(query_terms: &HashSet<String>, chunk: &CodeChunk) -> f32 {
+        const SYMBOL_WEIGHT: f32 = 3.0;
+
+        let mut score = tokenize(&chunk.content).filter(|term| query_terms.contains(term)).count() as f32;
+        if let Some(symbol) = &chunk.symbol {
+            score += SYMBOL_WEIGHT
+                * tokenize(symbol).filter(|term| query_terms.contains(term)).count() as f32;
+        }
+        score
+    }
+
+    /// Fuse two ranked `SearchResult` lists with Reciprocal Rank Fusion: each list contributes
+    /// `1 / (RRF_K + rank)` (1-indexed) per chunk it contains, and a chunk appearing in both
+    /// lists sums both contributions. Each fused result's `metadata` records the 1-based
+    /// `vector_rank`/`keyword_rank` it contributed from (absent from a list it didn't appear in),
+    /// so a caller can see why a result surfaced. Returns results sorted by fused score,
+    /// descending, with the score min-max normalized to `[0, 1]`.
+    fn reciprocal_rank_fusion(
+        vector_ranked: Vec<SearchResult>,
+        keyword_ranked: Vec<SearchResult>,
+    ) -> Vec<SearchResult> {
+        let mut fused: HashMap<String, SearchResult> = HashMap::new();
+        let mut scores: HashMap<String, f32> = HashMap::new();
+
+        for (rank, result) in vector_ranked.into_iter().enumerate() {
+            *scores.entry(result.chunk_id.clone()).or_insert(0.0) +=
+                1.0 / (RRF_K + (rank + 1) as f32);
+            let entry = fused.entry(result.chunk_id.clone()).or_insert(result);
+            entry.metadata.insert("vector_rank".to_string(), (rank + 1).to_string());
+        }
+        for (rank, result) in keyword_ranked.into_iter().enumerate() {
+            *scores.entry(result.chunk_id.clone()).or_insert(0.0) +=
+                1.0 / (RRF_K + (rank + 1) as f32);
+            let entry = fused.entry(result.chunk_id.clone()).or_insert(result);
+            entry.metadata.insert("keyword_rank".to_string(), (rank + 1).to_string());
+        }
+
+        let mut results: Vec<SearchResult> = fused
+            .into_iter()
+            .map(|(id, mut result)| {
+                result.score = scores[&id];
+                result
+            })
+            .collect();
+
+        let min = results.iter().map(|r| r.score).fold(f32::INFINITY, f32::min);
+        let max = results.iter().map(|r| r.score).fold(f32::NEG_INFINITY, f32::max);
+        let range = max - min;
+        for result in &mut results {
+            result.score = if range > 0.0 { (result.score - min) / range } else { 0.0 };
+        }
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results
+    }
+
+    /// Min-max normalize a ranked `SearchResult` list's scores to `[0, 1]`, keyed by chunk id; an
+    /// empty or constant-score list maps every score to `0.0` since there is no spread to
+    /// normalize against.
+    fn normalize_scores(results: &[SearchResult]) -> HashMap<String, f32> {
+        let Some(min) = results
+            .iter()
+            .map(|r| r.score)
+            .fold(None, |acc, s| Some(acc.map_or(s, |m: f32| m.min(s))))
+        else {
+            return HashMap::new();
+        };
+        let max = results.iter().map(|r| r.score).fold(min, f32::max);
+        let range = max - min;
+
+        results
+            .iter()
+            .map(|r| {
+                let normalized = if range > 0.0 { (r.score - min) / range } else { 0.0 };
+                (r.chunk_id.clone(), normalized)
+            })
+            .collect()
+    }
+
+    /// Blend two ranked `SearchResult` lists by min-max normalizing each side's scores and
+    /// weighting them `semantic_ratio` / `1.0 - semantic_ratio`. Returns results sorted by
+    /// blended score, descending.
+    fn blend_scores(
+        vector_ranked: Vec<SearchResult>,
+        keyword_ranked: Vec<SearchResult>,
+        semantic_ratio: f32,
+    ) -> Vec<SearchResult> {
+        let vector_norm = Self::normalize_scores(&vector_ranked);
+        let keyword_norm = Self::normalize_scores(&keyword_ranked);
+
+        let mut fused: HashMap<String, SearchResult> = HashMap::new();
+        for result in vector_ranked.into_iter().chain(keyword_ranked) {
+            fused.entry(result.chunk_id.clone()).or_insert(result);
+        }
+
+        let mut results: Vec<SearchResult> = fused
+            .into_values()
+            .map(|mut result| {
+                let vector_score = vector_norm.get(&result.chunk_id).copied().unwrap_or(0.0);
+                let keyword_score = keyword_norm.get(&result.chunk_id).copied().unwrap_or(0.0);
+                result.score = semantic_ratio * vector_score + (1.0 - semantic_ratio) * keyword_score;
+                result
+            })
+            .collect();
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        results
+    }
 }
 
 impl Default for InMemoryVectorStore {
@@ -213,11 +356,19 @@ impl VectorStore for InMemoryVectorStore {
             name, dimension
         );
 
+        let ann_index = self
+            .config
+            .use_ann_index
+            .then(|| HnswIndex::new(self.config.hnsw_m, self.config.hnsw_ef_construction))
+            .transpose()?;
+
         let collection = Collection {
             name: name.to_string(),
             dimension,
             vectors: HashMap::new(),
             distance_metric: self.config.distance_metric.clone(),
+            ann_index,
+            ann_ef_search: self.config.hnsw_ef_search,
         };
 
         let mut collections = self.collections.write().unwrap();
@@ -276,6 +427,7 @@ impl VectorStore for InMemoryVectorStore {
         };
 
         coll.vectors.insert(chunk.id.clone(), entry);
+        Self::index_in_ann(coll, &chunk.id);
 
         debug!("Inserted chunk '{}' successfully", chunk.id);
         Ok(chunk.id.clone())
@@ -334,6 +486,10 @@ impl VectorStore for InMemoryVectorStore {
         };
 
         coll.vectors.insert(chunk_id.to_string(), entry);
+        if let Some(ann_index) = &mut coll.ann_index {
+            ann_index.remove(chunk_id);
+        }
+        Self::index_in_ann(coll, chunk_id);
 
         debug!("Updated chunk '{}' successfully", chunk_id);
         Ok(())
@@ -351,6 +507,9 @@ impl VectorStore for InMemoryVectorStore {
             .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
 
         if coll.vectors.remove(chunk_id).is_some() {
+            if let Some(ann_index) = &mut coll.ann_index {
+                ann_index.remove(chunk_id);
+            }
             debug!("Deleted chunk '{}' successfully", chunk_id);
         } else {
             warn!("Chunk '{}' not found for deletion", chunk_id);
@@ -396,25 +555,55 @@ impl VectorStore for InMemoryVectorStore {
             ));
         }
 
-        let mut results: Vec<SearchResult> = Vec::new();
-
-        for (chunk_id, entry) in &coll.vectors {
-            // Apply filters if provided
-            if let Some(filters) = filters
-                && !Self::matches_filters(&entry.chunk, filters) {
-                    continue;
-                }
-
-            let score =
-                self.calculate_similarity(&coll.distance_metric, query_embedding, &entry.embedding);
+        // Compile path globs/regex once for this call instead of re-parsing them per chunk below.
+        let path_filter = filters.map(CompiledPathFilter::compile).transpose()?;
+        let passes_filters = |chunk: &CodeChunk| {
+            filters.is_none_or(|f| Self::matches_filters(chunk, f))
+                && path_filter.as_ref().is_none_or(|pf| pf.matches(&chunk.path))
+        };
 
-            results.push(SearchResult {
-                chunk_id: chunk_id.clone(),
-                chunk: entry.chunk.clone(),
-                score,
-                metadata: entry.metadata.clone(),
-            });
-        }
+        let mut results: Vec<SearchResult> = if let Some(ann_index) = &coll.ann_index {
+            // Over-fetch from the graph so post-hoc filtering doesn't starve the final result set.
+            let ef = coll.ann_ef_search.max(limit).saturating_mul(4).max(limit);
+            ann_index
+                .search(ef, ef, |id| {
+                    coll.vectors
+                        .get(id)
+                        .map(|entry| {
+                            Self::calculate_similarity(&coll.distance_metric, query_embedding, &entry.embedding)
+                        })
+                        .unwrap_or(0.0)
+                })
+                .into_iter()
+                .filter_map(|chunk_id| {
+                    let entry = coll.vectors.get(&chunk_id)?;
+                    if !passes_filters(&entry.chunk) {
+                        return None;
+                    }
+                    Some(SearchResult {
+                        score: Self::calculate_similarity(
+                            &coll.distance_metric,
+                            query_embedding,
+                            &entry.embedding,
+                        ),
+                        chunk_id,
+                        chunk: entry.chunk.clone(),
+                        metadata: entry.metadata.clone(),
+                    })
+                })
+                .collect()
+        } else {
+            coll.vectors
+                .iter()
+                .filter(|(_, entry)| passes_filters(&entry.chunk))
+                .map(|(chunk_id, entry)| SearchResult {
+                    chunk_id: chunk_id.clone(),
+                    chunk: entry.chunk.clone(),
+                    score: Self::calculate_similarity(&coll.distance_metric, query_embedding, &entry.embedding),
+                    metadata: entry.metadata.clone(),
+                })
+                .collect()
+        };
 
         // Sort by score (highest first) and limit results
         results.sort_by(|a, b| {
@@ -428,6 +617,59 @@ impl VectorStore for InMemoryVectorStore {
         Ok(results)
     }
 
+    async fn hybrid_search(
+        &self,
+        collection: &str,
+        query_embedding: &[f32],
+        query_text: &str,
+        limit: usize,
+        filters: Option<&SearchFilters>,
+        semantic_ratio: Option<f32>,
+    ) -> Result<Vec<SearchResult>> {
+        debug!("Hybrid searching collection '{}' with limit {}", collection, limit);
+
+        // Oversample both ranked lists before fusing -- a chunk that just misses the top `limit`
+        // on one side but ranks highly on the other would otherwise never get the chance to be
+        // fused in.
+        let candidate_pool = limit.saturating_mul(4).max(limit);
+        let vector_ranked = self.search(collection, query_embedding, candidate_pool, filters).await?;
+
+        let query_terms: HashSet<String> = tokenize(query_text).collect();
+        let path_filter = filters.map(CompiledPathFilter::compile).transpose()?;
+        let mut keyword_ranked: Vec<SearchResult> = {
+            let collections = self.collections.read().unwrap();
+            let coll = collections
+                .get(collection)
+                .ok_or_else(|| anyhow::anyhow!("Collection '{}' not found", collection))?;
+
+            coll.vectors
+                .iter()
+                .filter(|(_, entry)| {
+                    filters.is_none_or(|f| Self::matches_filters(&entry.chunk, f))
+                        && path_filter.as_ref().is_none_or(|pf| pf.matches(&entry.chunk.path))
+                })
+                .map(|(chunk_id, entry)| SearchResult {
+                    chunk_id: chunk_id.clone(),
+                    chunk: entry.chunk.clone(),
+                    score: Self::lexical_score(&query_terms, &entry.chunk),
+                    metadata: entry.metadata.clone(),
+                })
+                .filter(|result| result.score > 0.0)
+                .collect()
+        };
+        keyword_ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        keyword_ranked.truncate(candidate_pool);
+
+        let mut fused = match semantic_ratio {
+            Some(ratio) => Self::blend_scores(vector_ranked, keyword_ranked, ratio),
+            None => Self::reciprocal_rank_fusion(vector_ranked, keyword_ranked),
+        };
+        fused.truncate(limit);
+
+        debug!("Hybrid search found {} fused results", fused.len());
+        Ok(fused)
+    }
+
     async fn get_stats(&self, collection: &str) -> Result<VectorStoreStats> {
         let collections = self.collections.read().unwrap();
         let coll = collections
@@ -553,6 +795,216 @@ mod tests {
         assert_eq!(actual_results[0].chunk_id, "test-1");
     }
 
+    #[tokio::test]
+    async fn test_search_with_glob_path_filter() {
+        let mut fixture = InMemoryVectorStore::new();
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let chunk1 = CodeChunk::new(
+            "test-1".to_string(),
+            "src/main.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn main() {}".to_string(),
+            1,
+            1,
+        );
+        let chunk2 = CodeChunk::new(
+            "test-2".to_string(),
+            "tests/main.rs".to_string(),
+            "rust".to_string(),
+            "def456".to_string(),
+            "fn test() {}".to_string(),
+            1,
+            1,
+        );
+
+        let embedding = vec![0.1, 0.2, 0.3];
+        fixture.insert_chunk("test", &chunk1, &embedding).await.unwrap();
+        fixture.insert_chunk("test", &chunk2, &embedding).await.unwrap();
+
+        let filters = SearchFilters { paths: vec!["src/**/*.rs".to_string()], ..Default::default() };
+
+        let actual = fixture.search("test", &embedding, 10, Some(&filters)).await.unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].chunk_id, "test-1");
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_favors_lexical_match_over_dissimilar_embedding() {
+        let mut fixture = InMemoryVectorStore::new();
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let mut chunk1 = CodeChunk::new(
+            "test-1".to_string(),
+            "auth.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn authenticate_user() {}".to_string(),
+            1,
+            1,
+        );
+        chunk1.symbol = Some("authenticate_user".to_string());
+
+        let chunk2 = CodeChunk::new(
+            "test-2".to_string(),
+            "unrelated.rs".to_string(),
+            "rust".to_string(),
+            "def456".to_string(),
+            "fn something_else() {}".to_string(),
+            1,
+            1,
+        );
+
+        // chunk2's embedding is closer to the query vector, but chunk1 is the only lexical match.
+        fixture.insert_chunk("test", &chunk1, &[0.0, 1.0, 0.0]).await.unwrap();
+        fixture.insert_chunk("test", &chunk2, &[1.0, 0.0, 0.0]).await.unwrap();
+
+        let actual = fixture
+            .hybrid_search("test", &[1.0, 0.0, 0.0], "authenticate_user", 10, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(actual.len(), 2);
+        assert_eq!(actual[0].chunk_id, "test-1");
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_pure_vector_ratio_ignores_lexical_score() {
+        let mut fixture = InMemoryVectorStore::new();
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let chunk1 = CodeChunk::new(
+            "test-1".to_string(),
+            "auth.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn authenticate_user() {}".to_string(),
+            1,
+            1,
+        );
+        let chunk2 = CodeChunk::new(
+            "test-2".to_string(),
+            "unrelated.rs".to_string(),
+            "rust".to_string(),
+            "def456".to_string(),
+            "fn something_else() {}".to_string(),
+            1,
+            1,
+        );
+
+        fixture.insert_chunk("test", &chunk1, &[0.0, 1.0, 0.0]).await.unwrap();
+        fixture.insert_chunk("test", &chunk2, &[1.0, 0.0, 0.0]).await.unwrap();
+
+        let actual = fixture
+            .hybrid_search("test", &[1.0, 0.0, 0.0], "authenticate_user", 10, None, Some(1.0))
+            .await
+            .unwrap();
+
+        assert_eq!(actual[0].chunk_id, "test-2");
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_rrf_records_contributing_ranks_and_normalizes_score() {
+        let mut fixture = InMemoryVectorStore::new();
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let mut chunk1 = CodeChunk::new(
+            "test-1".to_string(),
+            "auth.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn authenticate_user() {}".to_string(),
+            1,
+            1,
+        );
+        chunk1.symbol = Some("authenticate_user".to_string());
+
+        let chunk2 = CodeChunk::new(
+            "test-2".to_string(),
+            "unrelated.rs".to_string(),
+            "rust".to_string(),
+            "def456".to_string(),
+            "fn something_else() {}".to_string(),
+            1,
+            1,
+        );
+
+        fixture.insert_chunk("test", &chunk1, &[1.0, 0.0, 0.0]).await.unwrap();
+        fixture.insert_chunk("test", &chunk2, &[0.0, 1.0, 0.0]).await.unwrap();
+
+        let actual = fixture
+            .hybrid_search("test", &[1.0, 0.0, 0.0], "authenticate_user", 10, None, None)
+            .await
+            .unwrap();
+
+        // chunk1 ranks first in both the vector and keyword lists, so it carries both ranks and
+        // the top normalized score of 1.0.
+        let top = actual.iter().find(|r| r.chunk_id == "test-1").unwrap();
+        assert_eq!(top.metadata.get("vector_rank").map(String::as_str), Some("1"));
+        assert_eq!(top.metadata.get("keyword_rank").map(String::as_str), Some("1"));
+        assert_eq!(top.score, 1.0);
+
+        // chunk2 only ever matches the vector list, so it has no keyword_rank.
+        let other = actual.iter().find(|r| r.chunk_id == "test-2").unwrap();
+        assert_eq!(other.metadata.get("vector_rank").map(String::as_str), Some("2"));
+        assert!(other.metadata.get("keyword_rank").is_none());
+        assert!((0.0..=1.0).contains(&other.score));
+    }
+
+    #[tokio::test]
+    async fn test_search_uses_ann_index_when_enabled() {
+        let mut fixture = InMemoryVectorStore::new();
+        fixture.config.use_ann_index = true;
+        // The embeddings below all lie on one axis, so cosine similarity would tie every nonzero
+        // one at 1.0; Euclidean distance makes test-5 the unique nearest neighbor of the query.
+        fixture.config.distance_metric = DistanceMetric::Euclidean;
+        fixture.create_collection("test", 3).await.unwrap();
+
+        for i in 0..20 {
+            let chunk = CodeChunk::new(
+                format!("test-{i}"),
+                "test.rs".to_string(),
+                "rust".to_string(),
+                "abc123".to_string(),
+                "fn test() {}".to_string(),
+                1,
+                1,
+            );
+            let embedding = vec![i as f32, 0.0, 0.0];
+            fixture.insert_chunk("test", &chunk, &embedding).await.unwrap();
+        }
+
+        let actual = fixture.search("test", &[5.0, 0.0, 0.0], 3, None).await.unwrap();
+
+        assert_eq!(actual.len(), 3);
+        assert_eq!(actual[0].chunk_id, "test-5");
+    }
+
+    #[tokio::test]
+    async fn test_deleted_chunk_excluded_from_ann_search() {
+        let mut fixture = InMemoryVectorStore::new();
+        fixture.config.use_ann_index = true;
+        fixture.create_collection("test", 3).await.unwrap();
+
+        let chunk = CodeChunk::new(
+            "test-1".to_string(),
+            "test.rs".to_string(),
+            "rust".to_string(),
+            "abc123".to_string(),
+            "fn test() {}".to_string(),
+            1,
+            1,
+        );
+        fixture.insert_chunk("test", &chunk, &[1.0, 0.0, 0.0]).await.unwrap();
+        fixture.delete_chunk("test", "test-1").await.unwrap();
+
+        let actual = fixture.search("test", &[1.0, 0.0, 0.0], 10, None).await.unwrap();
+
+        assert!(actual.is_empty());
+    }
+
     #[test]
     fn test_cosine_similarity() {
         let a = vec![1.0, 0.0, 0.0];
@@ -1,13 +1,21 @@
 //! Vector store implementations and utilities
 
+mod batch_insert;
+mod file_store;
+mod hnsw;
 mod in_memory;
+mod path_filter;
+mod pg_store;
 mod store_trait;
 
 use std::sync::Arc;
 
 use anyhow::Result;
 use forge_domain::{VectorStoreConfig, VectorStoreType};
+pub use batch_insert::BatchedInserter;
+pub use file_store::FileVectorStore;
 pub use in_memory::InMemoryVectorStore;
+pub use pg_store::PgVectorStore;
 pub use store_trait::{IndexStatus, SearchResult, VectorStore, VectorStoreStats};
 
 // Re-export utility functions from domain
@@ -21,11 +29,8 @@ impl VectorStoreFactory {
     pub async fn create(config: &VectorStoreConfig) -> Result<Box<dyn VectorStore>> {
         let mut store: Box<dyn VectorStore> = match &config.store_type {
             VectorStoreType::InMemory => Box::new(InMemoryVectorStore::new()),
-            VectorStoreType::FileStore { storage_path: _ } => {
-                // TODO: Implement file-based vector store
-                return Err(anyhow::anyhow!(
-                    "File-based vector store not yet implemented"
-                ));
+            VectorStoreType::FileStore { storage_path } => {
+                Box::new(FileVectorStore::new(storage_path.clone()))
             }
             VectorStoreType::Qdrant { url: _, api_key: _ } => {
                 // TODO: Implement Qdrant vector store
@@ -39,6 +44,7 @@ impl VectorStoreFactory {
                 // TODO: Implement Chroma vector store
                 return Err(anyhow::anyhow!("Chroma vector store not yet implemented"));
             }
+            VectorStoreType::Postgres { connection_string: _ } => Box::new(PgVectorStore::new()),
         };
 
         store.initialize(config).await?;
@@ -68,6 +74,7 @@ mod tests {
             collection_name: "test".to_string(),
             distance_metric: DistanceMetric::Cosine,
             enable_compression: false,
+            ..Default::default()
         };
 
         let actual = VectorStoreFactory::create(&config).await;
@@ -75,6 +82,21 @@ mod tests {
         assert!(actual.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_vector_store_factory_file_store() {
+        let storage_path = std::env::temp_dir()
+            .join(format!("forge-services-vector-store-factory-test-{}", std::process::id()));
+        let config = VectorStoreConfig {
+            store_type: VectorStoreType::FileStore { storage_path: storage_path.clone() },
+            ..Default::default()
+        };
+
+        let actual = VectorStoreFactory::create(&config).await;
+
+        assert!(actual.is_ok());
+        std::fs::remove_dir_all(&storage_path).ok();
+    }
+
     #[test]
     fn test_normalize_vector() {
         let mut fixture = vec![3.0, 4.0];
@@ -0,0 +1,184 @@
+//! Precompiled path filtering for `SearchFilters::paths`/`path_regex`, shared by
+//! `InMemoryVectorStore` and `FileVectorStore`. Patterns are parsed once per search call into a
+//! `CompiledPathFilter` rather than being re-parsed for every chunk in the scan loop.
+
+use anyhow::Result;
+use forge_domain::SearchFilters;
+use regex::Regex;
+
+/// A single glob pattern, pre-split into `/`-separated segments so repeated matches against many
+/// paths don't re-split the pattern string every time. Supports `**` (any number of whole
+/// segments, including none), `*`/`?` within a segment, and `[...]` character classes.
+struct CompiledGlob {
+    segments: Vec<String>,
+    anchored: bool,
+}
+
+impl CompiledGlob {
+    fn compile(pattern: &str) -> Self {
+        let trimmed = pattern.trim_end_matches('/');
+        Self { segments: trimmed.split('/').map(str::to_string).collect(), anchored: trimmed.contains('/') }
+    }
+
+    fn matches(&self, path: &str) -> bool {
+        let pattern_segments: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        let text_segments: Vec<&str> = path.split('/').collect();
+
+        if self.anchored {
+            return match_segments(&pattern_segments, &text_segments);
+        }
+
+        // Unanchored: the pattern may match starting at any segment of the path.
+        (0..text_segments.len()).any(|start| match_segments(&pattern_segments, &text_segments[start..]))
+    }
+}
+
+fn match_segments(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(&"**") => (0..=text.len()).any(|skip| match_segments(&pattern[1..], &text[skip..])),
+        Some(&head) => match text.first() {
+            Some(&first) => match_segment(head, first) && match_segments(&pattern[1..], &text[1..]),
+            None => false,
+        },
+    }
+}
+
+fn match_segment(pattern: &str, text: &str) -> bool {
+    fn go(pattern: &[char], text: &[char]) -> bool {
+        match pattern.first() {
+            None => text.is_empty(),
+            Some('*') => (0..=text.len()).any(|skip| go(&pattern[1..], &text[skip..])),
+            Some('?') => !text.is_empty() && go(&pattern[1..], &text[1..]),
+            Some('[') => {
+                let Some(class_end) = pattern.iter().position(|&c| c == ']').filter(|&i| i > 0) else {
+                    return !text.is_empty() && text[0] == '[' && go(&pattern[1..], &text[1..]);
+                };
+                match text.first() {
+                    Some(&c) if char_class_matches(&pattern[1..class_end], c) => {
+                        go(&pattern[class_end + 1..], &text[1..])
+                    }
+                    _ => false,
+                }
+            }
+            Some(&p) => !text.is_empty() && text[0] == p && go(&pattern[1..], &text[1..]),
+        }
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    go(&pattern, &text)
+}
+
+fn char_class_matches(class: &[char], c: char) -> bool {
+    let (negate, class) = match class.first() {
+        Some('!') | Some('^') => (true, &class[1..]),
+        _ => (false, class),
+    };
+
+    let mut matched = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            if c >= class[i] && c <= class[i + 2] {
+                matched = true;
+            }
+            i += 3;
+        } else {
+            if class[i] == c {
+                matched = true;
+            }
+            i += 1;
+        }
+    }
+
+    matched != negate
+}
+
+/// Precompiled `paths`/`path_regex` filter state, built once per search call (not per chunk).
+/// `paths` entries without a leading `!` are inclusion globs (a path must match at least one, if
+/// any are given); entries with a leading `!` are exclusion globs, checked after the inclusion
+/// globs. `path_regex`, if set, must additionally match.
+pub(super) struct CompiledPathFilter {
+    include: Vec<CompiledGlob>,
+    exclude: Vec<CompiledGlob>,
+    regex: Option<Regex>,
+}
+
+impl CompiledPathFilter {
+    pub(super) fn compile(filters: &SearchFilters) -> Result<Self> {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+        for pattern in &filters.paths {
+            match pattern.strip_prefix('!') {
+                Some(negated) => exclude.push(CompiledGlob::compile(negated)),
+                None => include.push(CompiledGlob::compile(pattern)),
+            }
+        }
+
+        let regex = filters.path_regex.as_deref().map(Regex::new).transpose()?;
+
+        Ok(Self { include, exclude, regex })
+    }
+
+    pub(super) fn matches(&self, path: &str) -> bool {
+        if !self.include.is_empty() && !self.include.iter().any(|glob| glob.matches(path)) {
+            return false;
+        }
+        if self.exclude.iter().any(|glob| glob.matches(path)) {
+            return false;
+        }
+        if let Some(regex) = &self.regex
+            && !regex.is_match(path)
+        {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_recursive_glob_matches_nested_path() {
+        let filters = SearchFilters { paths: vec!["src/**/*.rs".to_string()], ..Default::default() };
+        let fixture = CompiledPathFilter::compile(&filters).unwrap();
+
+        assert!(fixture.matches("src/a/b/c.rs"));
+        assert!(fixture.matches("src/c.rs"));
+        assert!(!fixture.matches("tests/c.rs"));
+    }
+
+    #[test]
+    fn test_negated_pattern_excludes_after_inclusion_match() {
+        let filters = SearchFilters {
+            paths: vec!["src/**/*.rs".to_string(), "!src/generated/*.rs".to_string()],
+            ..Default::default()
+        };
+        let fixture = CompiledPathFilter::compile(&filters).unwrap();
+
+        assert!(fixture.matches("src/main.rs"));
+        assert!(!fixture.matches("src/generated/schema.rs"));
+    }
+
+    #[test]
+    fn test_path_regex_filter() {
+        let filters = SearchFilters { path_regex: Some(r"^src/.*\.rs$".to_string()), ..Default::default() };
+        let fixture = CompiledPathFilter::compile(&filters).unwrap();
+
+        assert!(fixture.matches("src/main.rs"));
+        assert!(!fixture.matches("src/main.py"));
+    }
+
+    #[test]
+    fn test_empty_paths_matches_everything() {
+        let filters = SearchFilters::default();
+        let fixture = CompiledPathFilter::compile(&filters).unwrap();
+
+        assert!(fixture.matches("anything/at/all.rs"));
+    }
+}
@@ -0,0 +1,375 @@
+//! Postgres-backed `VectorStore` for `VectorStoreType::Postgres`, using the `pgvector` extension
+//! so a deployment that already runs Postgres can avoid standing up a dedicated Qdrant instance.
+//! Each collection is its own table with a `vector(dimension)` column; connections are pooled via
+//! `deadpool-postgres` rather than opened per call.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+use forge_domain::{CodeChunk, DistanceMetric, SearchFilters, VectorStoreConfig, VectorStoreType};
+use pgvector::Vector as PgVector;
+use tokio_postgres::NoTls;
+use tracing::{debug, info};
+
+use super::path_filter::CompiledPathFilter;
+use super::store_trait::{IndexStatus, SearchResult, VectorStore, VectorStoreStats};
+
+/// Postgres/`pgvector`-backed vector store, as a drop-in alternative to `InMemoryVectorStore` and
+/// `FileVectorStore` via `VectorStoreType::Postgres`.
+pub struct PgVectorStore {
+    config: VectorStoreConfig,
+    pool: Option<Pool>,
+}
+
+impl PgVectorStore {
+    pub fn new() -> Self {
+        Self { config: VectorStoreConfig::default(), pool: None }
+    }
+
+    async fn client(&self) -> Result<deadpool_postgres::Client> {
+        let pool = self.pool.as_ref().context("PgVectorStore has not been initialized")?;
+        pool.get().await.context("failed to get a connection from the postgres pool")
+    }
+
+    /// Collection names become part of a SQL identifier (table/index names can't be bound as
+    /// query parameters), so reject anything that isn't a safe Rust-style identifier rather than
+    /// interpolating arbitrary input into a `CREATE TABLE`/`DROP TABLE` statement.
+    fn validate_identifier(name: &str) -> Result<()> {
+        let valid = !name.is_empty()
+            && name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(anyhow::anyhow!(
+                "collection name '{name}' is not a valid identifier (expected ascii letters, digits, underscore)"
+            ));
+        }
+        Ok(())
+    }
+
+    fn table_name(collection: &str) -> String {
+        format!("forge_vectors_{collection}")
+    }
+
+    /// `pgvector` operator class for the HNSW/IVFFlat index matching `metric`; `DistanceMetric`
+    /// has no Postgres analogue for Manhattan distance, so that case falls back to the L2 class
+    /// (the closest built-in operator class `pgvector` ships).
+    fn operator_class(metric: &DistanceMetric) -> &'static str {
+        match metric {
+            DistanceMetric::Cosine => "vector_cosine_ops",
+            DistanceMetric::Euclidean | DistanceMetric::Manhattan => "vector_l2_ops",
+            DistanceMetric::DotProduct => "vector_ip_ops",
+        }
+    }
+
+    /// SQL distance operator matching `metric`, paired with `operator_class`.
+    fn distance_operator(metric: &DistanceMetric) -> &'static str {
+        match metric {
+            DistanceMetric::Cosine => "<=>",
+            DistanceMetric::Euclidean | DistanceMetric::Manhattan => "<->",
+            DistanceMetric::DotProduct => "<#>",
+        }
+    }
+
+    /// Convert the raw SQL distance into the same `[0, 1]`-ish "higher is more similar" scale the
+    /// other backends' `calculate_similarity` returns, so callers can't tell which backend served
+    /// a given `SearchResult`.
+    fn distance_to_score(metric: &DistanceMetric, distance: f32) -> f32 {
+        match metric {
+            DistanceMetric::Cosine => 1.0 - distance,
+            DistanceMetric::Euclidean | DistanceMetric::Manhattan => 1.0 / (1.0 + distance),
+            // pgvector's `<#>` returns the *negative* inner product so that ascending order is
+            // still "closest first"; negate it back to a plain similarity score.
+            DistanceMetric::DotProduct => -distance,
+        }
+    }
+
+    /// Mirrors `FileVectorStore::matches_filters` / `InMemoryVectorStore::matches_filters`: the
+    /// filters SQL can't cheaply express (symbol/tag membership, path globs) are applied here
+    /// against the over-fetched candidate rows instead of being pushed into the query.
+    fn matches_filters(chunk: &CodeChunk, filters: &SearchFilters) -> bool {
+        if let Some(ref repo) = filters.repository
+            && chunk.metadata.repository.as_deref() != Some(repo.as_str())
+        {
+            return false;
+        }
+        if let Some(ref branch) = filters.branch
+            && chunk.metadata.branch.as_deref() != Some(branch.as_str())
+        {
+            return false;
+        }
+        if !filters.languages.is_empty() && !filters.languages.contains(&chunk.language) {
+            return false;
+        }
+        if !filters.symbols.is_empty() && !chunk.symbol.as_ref().is_some_and(|s| filters.symbols.contains(s)) {
+            return false;
+        }
+        if !filters.tags.is_empty() && !filters.tags.iter().any(|tag| chunk.metadata.tags.contains(tag)) {
+            return false;
+        }
+        true
+    }
+
+    fn row_to_result(row: &tokio_postgres::Row, metric: &DistanceMetric) -> Result<SearchResult> {
+        let chunk: CodeChunk = serde_json::from_value(row.get::<_, serde_json::Value>("chunk"))?;
+        let metadata: HashMap<String, String> =
+            serde_json::from_value(row.get::<_, serde_json::Value>("metadata"))?;
+        let distance: f32 = row.get("distance");
+        Ok(SearchResult {
+            chunk_id: row.get("id"),
+            chunk,
+            score: Self::distance_to_score(metric, distance),
+            metadata,
+        })
+    }
+}
+
+impl Default for PgVectorStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VectorStore for PgVectorStore {
+    async fn initialize(&mut self, config: &VectorStoreConfig) -> Result<()> {
+        let VectorStoreType::Postgres { connection_string } = &config.store_type else {
+            return Err(anyhow::anyhow!("PgVectorStore requires VectorStoreType::Postgres"));
+        };
+
+        info!("Initializing postgres-backed vector store");
+        let pg_config: tokio_postgres::Config =
+            connection_string.parse().context("invalid postgres connection string")?;
+        let manager = Manager::from_config(
+            pg_config,
+            NoTls,
+            ManagerConfig { recycling_method: RecyclingMethod::Fast },
+        );
+        let pool = Pool::builder(manager).build().context("failed to build postgres connection pool")?;
+
+        self.config = config.clone();
+        self.pool = Some(pool);
+        Ok(())
+    }
+
+    async fn create_collection(&mut self, name: &str, dimension: usize) -> Result<()> {
+        Self::validate_identifier(name)?;
+        info!("Creating collection '{}' with dimension {}", name, dimension);
+
+        let table = Self::table_name(name);
+        let client = self.client().await?;
+        client.execute("CREATE EXTENSION IF NOT EXISTS vector", &[]).await?;
+        client
+            .execute(
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {table} (
+                        id TEXT PRIMARY KEY,
+                        chunk JSONB NOT NULL,
+                        metadata JSONB NOT NULL DEFAULT '{{}}',
+                        embedding vector({dimension}) NOT NULL
+                    )"
+                ),
+                &[],
+            )
+            .await?;
+
+        if self.config.use_ann_index {
+            let op_class = Self::operator_class(&self.config.distance_metric);
+            client
+                .execute(
+                    &format!(
+                        "CREATE INDEX IF NOT EXISTS {table}_embedding_hnsw_idx ON {table}
+                         USING hnsw (embedding {op_class})
+                         WITH (m = {}, ef_construction = {})",
+                        self.config.hnsw_m, self.config.hnsw_ef_construction
+                    ),
+                    &[],
+                )
+                .await?;
+        }
+
+        debug!("Created collection '{}' successfully", name);
+        Ok(())
+    }
+
+    async fn delete_collection(&mut self, name: &str) -> Result<()> {
+        Self::validate_identifier(name)?;
+        info!("Deleting collection '{}'", name);
+
+        let table = Self::table_name(name);
+        self.client().await?.execute(&format!("DROP TABLE IF EXISTS {table}"), &[]).await?;
+        Ok(())
+    }
+
+    async fn collection_exists(&self, name: &str) -> Result<bool> {
+        Self::validate_identifier(name)?;
+        let table = Self::table_name(name);
+        let row = self
+            .client()
+            .await?
+            .query_one("SELECT to_regclass($1) IS NOT NULL AS exists", &[&table])
+            .await?;
+        Ok(row.get("exists"))
+    }
+
+    async fn insert_chunk(&mut self, collection: &str, chunk: &CodeChunk, embedding: &[f32]) -> Result<String> {
+        self.insert_chunks(collection, std::slice::from_ref(&(chunk.clone(), embedding.to_vec())))
+            .await?;
+        Ok(chunk.id.clone())
+    }
+
+    async fn insert_chunks(&mut self, collection: &str, chunks: &[(CodeChunk, Vec<f32>)]) -> Result<Vec<String>> {
+        Self::validate_identifier(collection)?;
+        info!("Inserting {} chunks into collection '{}'", chunks.len(), collection);
+
+        let table = Self::table_name(collection);
+        let mut client = self.client().await?;
+        let transaction = client.transaction().await?;
+        let statement = transaction
+            .prepare(&format!(
+                "INSERT INTO {table} (id, chunk, embedding) VALUES ($1, $2, $3)
+                 ON CONFLICT (id) DO UPDATE SET chunk = EXCLUDED.chunk, embedding = EXCLUDED.embedding"
+            ))
+            .await?;
+
+        let mut ids = Vec::with_capacity(chunks.len());
+        for (chunk, embedding) in chunks {
+            let chunk_json = serde_json::to_value(chunk)?;
+            transaction
+                .execute(&statement, &[&chunk.id, &chunk_json, &PgVector::from(embedding.clone())])
+                .await?;
+            ids.push(chunk.id.clone());
+        }
+        transaction.commit().await?;
+
+        debug!("Inserted {} chunks successfully", chunks.len());
+        Ok(ids)
+    }
+
+    async fn update_chunk(
+        &mut self,
+        collection: &str,
+        chunk_id: &str,
+        chunk: &CodeChunk,
+        embedding: &[f32],
+    ) -> Result<()> {
+        let mut chunk = chunk.clone();
+        chunk.id = chunk_id.to_string();
+        self.insert_chunk(collection, &chunk, embedding).await?;
+        debug!("Updated chunk '{}' successfully", chunk_id);
+        Ok(())
+    }
+
+    async fn delete_chunk(&mut self, collection: &str, chunk_id: &str) -> Result<()> {
+        self.delete_chunks(collection, std::slice::from_ref(&chunk_id.to_string())).await
+    }
+
+    async fn delete_chunks(&mut self, collection: &str, chunk_ids: &[String]) -> Result<()> {
+        Self::validate_identifier(collection)?;
+        info!("Deleting {} chunks from collection '{}'", chunk_ids.len(), collection);
+
+        let table = Self::table_name(collection);
+        self.client()
+            .await?
+            .execute(&format!("DELETE FROM {table} WHERE id = ANY($1)"), &[&chunk_ids])
+            .await?;
+        Ok(())
+    }
+
+    async fn search(
+        &self,
+        collection: &str,
+        query_embedding: &[f32],
+        limit: usize,
+        filters: Option<&SearchFilters>,
+    ) -> Result<Vec<SearchResult>> {
+        Self::validate_identifier(collection)?;
+        debug!("Searching collection '{}' with limit {}", collection, limit);
+
+        let table = Self::table_name(collection);
+        let metric = self.config.distance_metric.clone();
+        let operator = Self::distance_operator(&metric);
+
+        // The filters SQL can't express cheaply (symbols, tags, path globs) are applied in Rust
+        // below, so over-fetch candidates rather than letting the DB-side LIMIT starve them --
+        // the same oversampling idiom `InMemoryVectorStore::search` uses for its ANN index.
+        let candidate_pool =
+            if filters.is_some() { limit.saturating_mul(4).max(limit) } else { limit };
+
+        let client = self.client().await?;
+        let rows = client
+            .query(
+                &format!(
+                    "SELECT id, chunk, metadata, embedding {operator} $1 AS distance
+                     FROM {table}
+                     ORDER BY embedding {operator} $1
+                     LIMIT $2"
+                ),
+                &[&PgVector::from(query_embedding.to_vec()), &(candidate_pool as i64)],
+            )
+            .await?;
+
+        let path_filter = filters.map(CompiledPathFilter::compile).transpose()?;
+        let mut results = Vec::with_capacity(rows.len());
+        for row in &rows {
+            let result = Self::row_to_result(row, &metric)?;
+            let passes = filters.is_none_or(|f| Self::matches_filters(&result.chunk, f))
+                && path_filter.as_ref().is_none_or(|pf| pf.matches(&result.chunk.path));
+            if passes {
+                results.push(result);
+            }
+        }
+        results.truncate(limit);
+
+        debug!("Found {} results", results.len());
+        Ok(results)
+    }
+
+    async fn hybrid_search(
+        &self,
+        _collection: &str,
+        _query_embedding: &[f32],
+        _query_text: &str,
+        _limit: usize,
+        _filters: Option<&SearchFilters>,
+        _semantic_ratio: Option<f32>,
+    ) -> Result<Vec<SearchResult>> {
+        Err(anyhow::anyhow!("hybrid_search is not yet implemented for PgVectorStore"))
+    }
+
+    async fn get_stats(&self, collection: &str) -> Result<VectorStoreStats> {
+        Self::validate_identifier(collection)?;
+        let table = Self::table_name(collection);
+        let client = self.client().await?;
+
+        let row = client
+            .query_one(&format!("SELECT count(*) AS total, pg_total_relation_size('{table}') AS size FROM {table}"), &[])
+            .await?;
+        let total_vectors: i64 = row.get("total");
+        let storage_size_bytes: i64 = row.get("size");
+
+        // pgvector stores the declared dimension of a `vector(n)` column as the column's typmod.
+        let dimension_row = client
+            .query_one(
+                "SELECT atttypmod AS dimension FROM pg_attribute
+                 WHERE attrelid = $1::regclass AND attname = 'embedding'",
+                &[&table],
+            )
+            .await?;
+        let vector_dimension: i32 = dimension_row.get("dimension");
+
+        Ok(VectorStoreStats {
+            total_vectors: total_vectors as usize,
+            vector_dimension: vector_dimension.max(0) as usize,
+            storage_size_bytes: storage_size_bytes as u64,
+            index_status: IndexStatus::Ready,
+            distance_metric: self.config.distance_metric.clone(),
+            additional_metrics: HashMap::new(),
+        })
+    }
+
+    fn get_config(&self) -> &VectorStoreConfig {
+        &self.config
+    }
+}
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use anyhow::Result;
 use async_trait::async_trait;
-use forge_domain::{CodeChunk, DistanceMetric, SearchFilters, VectorStoreConfig};
+use forge_domain::{CodeChunk, DistanceMetric, Embedder, SearchFilters, VectorStoreConfig};
 use serde::{Deserialize, Serialize};
 
 /// Trait for vector store implementations
@@ -61,6 +61,62 @@ pub trait VectorStore: Send + Sync {
         filters: Option<&SearchFilters>,
     ) -> Result<Vec<SearchResult>>;
 
+    /// Hybrid search combining vector similarity with a lexical score over `chunk.content`/
+    /// `chunk.symbol`. When `semantic_ratio` is `None`, the vector and lexical ranked lists are
+    /// fused with Reciprocal Rank Fusion (order-only, scale-free). When `semantic_ratio` is
+    /// `Some(ratio)`, each list's scores are min-max normalized to `[0, 1]` and blended as
+    /// `ratio * vector_score + (1 - ratio) * lexical_score`; `1.0` is pure vector search, `0.0`
+    /// is pure lexical search.
+    async fn hybrid_search(
+        &self,
+        collection: &str,
+        query_embedding: &[f32],
+        query_text: &str,
+        limit: usize,
+        filters: Option<&SearchFilters>,
+        semantic_ratio: Option<f32>,
+    ) -> Result<Vec<SearchResult>>;
+
+    /// Embed `chunks` with `embedder` and insert them, so a caller can index plain source text
+    /// without generating embeddings itself. Default implementation delegates to `insert_chunks`
+    /// -- reusing the existing `Embedder` abstraction instead of duplicating embedding logic in
+    /// every call site.
+    async fn insert_texts(
+        &mut self,
+        collection: &str,
+        chunks: &[CodeChunk],
+        embedder: &(dyn Embedder),
+    ) -> Result<Vec<String>> {
+        let texts: Vec<String> = chunks.iter().map(|chunk| chunk.content.clone()).collect();
+        let embeddings = embedder.embed_batch(&texts).await?;
+        let pairs: Vec<(CodeChunk, Vec<f32>)> =
+            chunks.iter().cloned().zip(embeddings).collect();
+        self.insert_chunks(collection, &pairs).await
+    }
+
+    /// Embed `query` with `embedder` and search, so a caller can query with plain text instead of
+    /// a pre-computed embedding. Default implementation delegates to `search`.
+    async fn search_text(
+        &self,
+        collection: &str,
+        query: &str,
+        limit: usize,
+        filters: Option<&SearchFilters>,
+        embedder: &(dyn Embedder),
+    ) -> Result<Vec<SearchResult>> {
+        let embedding = embedder.embed_text(query).await?;
+        self.search(collection, &embedding, limit, filters).await
+    }
+
+    /// Force any buffered writes for `collection` to durable storage. In-memory backends have
+    /// nothing to flush, so the default is a no-op; persistent backends override this to batch
+    /// writes (e.g. across `insert_chunks`) and still expose an explicit "make sure it's on disk"
+    /// hook for callers like `compact`-style maintenance jobs.
+    async fn flush(&self, collection: &str) -> Result<()> {
+        let _ = collection;
+        Ok(())
+    }
+
     /// Get statistics about the vector store
     async fn get_stats(&self, collection: &str) -> Result<VectorStoreStats>;
 
@@ -111,10 +167,80 @@ pub enum IndexStatus {
 
 #[cfg(test)]
 mod tests {
-    use forge_domain::{ChunkMetadata, CodeChunk};
+    use forge_domain::{ChunkMetadata, CodeChunk, EmbeddingProvider, preprocessing::generate_hash_embedding};
     use pretty_assertions::assert_eq;
 
     use super::*;
+    use super::super::InMemoryVectorStore;
+
+    struct HashEmbedder {
+        dimension: usize,
+        provider: EmbeddingProvider,
+    }
+
+    impl HashEmbedder {
+        fn new(dimension: usize) -> Self {
+            Self { dimension, provider: EmbeddingProvider::Mock { dimension } }
+        }
+    }
+
+    #[async_trait]
+    impl Embedder for HashEmbedder {
+        async fn embed_text(&self, text: &str) -> Result<Vec<f32>> {
+            Ok(generate_hash_embedding(text, self.dimension, None))
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+            let mut embeddings = Vec::with_capacity(texts.len());
+            for text in texts {
+                embeddings.push(self.embed_text(text).await?);
+            }
+            Ok(embeddings)
+        }
+
+        fn embedding_dimension(&self) -> usize {
+            self.dimension
+        }
+
+        fn provider(&self) -> &EmbeddingProvider {
+            &self.provider
+        }
+
+        fn name(&self) -> &str {
+            "hash"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_insert_texts_and_search_text_round_trip() {
+        let embedder = HashEmbedder::new(8);
+        let mut store = InMemoryVectorStore::new();
+        store.create_collection("test", 8).await.unwrap();
+
+        let chunk = CodeChunk {
+            id: "test-chunk".to_string(),
+            path: "test.rs".to_string(),
+            language: "rust".to_string(),
+            symbol: None,
+            revision: "abc123".to_string(),
+            size: 100,
+            content: "fn authenticate_user() {}".to_string(),
+            summary: None,
+            embedding: None,
+            start_line: 1,
+            end_line: 3,
+            metadata: ChunkMetadata::default(),
+        };
+
+        store.insert_texts("test", std::slice::from_ref(&chunk), &embedder).await.unwrap();
+        let actual = store
+            .search_text("test", "fn authenticate_user() {}", 10, None, &embedder)
+            .await
+            .unwrap();
+
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].chunk_id, "test-chunk");
+    }
 
     #[test]
     fn test_search_result_creation() {